@@ -0,0 +1,127 @@
+use chrono::{Datelike, Duration, Local, NaiveDate, TimeZone, Weekday};
+use criterion::{Criterion, criterion_group, criterion_main};
+use std::collections::HashMap;
+use std::hint::black_box;
+
+use tutor_mgr::domain::{
+    Clock, Domain, PaymentData, PaymentType, PersonalName, SessionData, SessionRecord, Student,
+    Tutor, TutorSubject,
+};
+use tutor_mgr::money::MoneyFormat;
+
+const STUDENTS: usize = 500;
+/// 2024 and 2025, so 24 calendar months of history.
+const DAYS: i64 = 731;
+
+/// Two sessions a week per student, attended on most scheduled days
+/// over roughly two years.
+fn synthetic_domain() -> Domain {
+    let start = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap();
+    let weekdays = [
+        Weekday::Mon,
+        Weekday::Tue,
+        Weekday::Wed,
+        Weekday::Thu,
+        Weekday::Fri,
+        Weekday::Sat,
+        Weekday::Sun,
+    ];
+
+    let students = (0..STUDENTS)
+        .map(|i| {
+            let days = [weekdays[i % 7], weekdays[(i + 3) % 7]];
+            let sessions = (0..DAYS)
+                .map(|offset| start + Duration::days(offset))
+                .filter(|date| days.contains(&date.weekday()))
+                .filter(|date| (date.ordinal() as usize + i) % 5 != 0)
+                .map(|date| {
+                    SessionRecord::completed(
+                        Local
+                            .with_ymd_and_hms(date.year(), date.month(), date.day(), 17, 0, 0)
+                            .unwrap(),
+                    )
+                })
+                .collect();
+
+            Student {
+                id: format!("student-{}", i),
+                name: PersonalName {
+                    first: format!("Student{}", i),
+                    last: String::from("Bench"),
+                    other: None,
+                },
+                subject: TutorSubject::new("Extended Mathematics"),
+                tags: Vec::new(),
+                phone: None,
+                email: None,
+                tabled_sessions: days
+                    .iter()
+                    .map(|&day| SessionData {
+                        day,
+                        time: String::from("5:00 PM"),
+                        duration_minutes: 60,
+                    })
+                    .collect(),
+                sessions,
+                other_subjects: Vec::new(),
+                reschedules: Vec::new(),
+                cancelled_sessions: Vec::new(),
+                location: None,
+                session_locations: Vec::new(),
+                travel_km: None,
+                session_distances: Vec::new(),
+                session_ratings: Vec::new(),
+                payment_data: PaymentData {
+                    payment_type: if i % 4 == 0 {
+                        PaymentType::Monthly
+                    } else {
+                        PaymentType::PerSession
+                    },
+                    amount: 100.0,
+                    min_sessions: None,
+                },
+                rate_changes: Vec::new(),
+                tution_start_date: Local.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap(),
+                tution_end_date: None,
+            }
+        })
+        .collect();
+
+    Domain {
+        tutor: Tutor {
+            id: String::from("tutor"),
+            name: PersonalName {
+                first: String::from("Bench"),
+                last: String::from("Tutor"),
+                other: None,
+            },
+            subjects: vec![TutorSubject::new("Extended Mathematics")],
+            tutoring_days: weekdays.to_vec(),
+            available_times: HashMap::new(),
+        },
+        students,
+        money_format: MoneyFormat::default(),
+        payment_instructions: String::new(),
+        blackout_dates: Vec::new(),
+        locations: Vec::new(),
+        rates: Vec::new(),
+        waitlist: Vec::new(),
+        monthly_snapshots: Vec::new(),
+        trash: Vec::new(),
+        clock: Clock::Fixed(NaiveDate::from_ymd_opt(2025, 12, 15).unwrap()),
+    }
+}
+
+fn domain_benchmarks(c: &mut Criterion) {
+    let domain = synthetic_domain();
+
+    c.bench_function("compute_income_data 500 students x 24 months", |b| {
+        b.iter(|| black_box(&domain).compute_income_data())
+    });
+    c.bench_function("compute_attendance_data 500 students x 24 months", |b| {
+        b.iter(|| black_box(&domain).compute_attendance_data())
+    });
+}
+
+criterion_group!(benches, domain_benchmarks);
+criterion_main!(benches);
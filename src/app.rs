@@ -1,69 +1,1123 @@
+use std::path::PathBuf;
 use std::rc::Rc;
+use std::time::{Duration, SystemTime};
 
-use crate::domain::Domain;
+use crate::billing::{self, Ledger, Payment};
+use crate::calendar::{self, CalendarState};
+use crate::compare::{self, CompareState};
+use crate::config::{self, ConfigError, UserConfig};
+use crate::data_dirs::DataDirs;
+use crate::domain::{self, Domain, DomainError, MonthlySummary, SaveQueue, SessionStatus};
+use crate::export::ExportError;
+use crate::ical;
+use crate::interchange::Interchange;
+use crate::invoices::{self, InvoicesState};
+use crate::money::MoneyFormat;
+use crate::notifications::StudentNotices;
 
 use crate::dashboard::{self, DashboardState};
-use crate::shell::{self, Screen, ShellState};
+use crate::reconciliation::{self, ReconciliationState};
+use crate::reports::{self, ReportsState};
+use crate::routes::{self, Route, RouteError};
+use crate::search::{self, SearchResult};
+use crate::settings::{self, SettingsState};
+use crate::shell::{self, QuickStats, Screen, ShellState};
+use crate::store::{DomainEdit, DomainStore};
+use crate::student_detail::{self, StudentDetailState};
 use crate::students::{self, StudentManagerState};
+use crate::ui_components::not_implemented;
+use crate::usage::{self, UsageError, UsageStats};
 
-use iced::{Element, Subscription, Task};
+use chrono::{DateTime, Local, NaiveDate};
+use iced::widget::{center, text};
+use iced::{Element, Subscription, Task, Theme};
+
+/// How often `config.toml` is checked for changes.
+const CONFIG_CHECK_INTERVAL: Duration = Duration::from_secs(2);
+/// How often countdowns move on and the date is checked for a new day.
+const CLOCK_INTERVAL: Duration = Duration::from_secs(60);
 
 pub struct App {
-    pub domain: Option<Rc<Domain>>,
+    pub store: Option<DomainStore>,
     pub shell: ShellState,
     pub dashboard: DashboardState,
-    pub students: StudentManagerState,
+    /// Built the first time their screen is opened and dropped again on
+    /// logout. The dashboard holds the ledger and settings the reminders,
+    /// so those two always exist.
+    pub students: Option<StudentManagerState>,
+    pub student_detail: Option<StudentDetailState>,
+    pub compare: Option<CompareState>,
+    pub calendar: Option<CalendarState>,
+    pub reconciliation: Option<ReconciliationState>,
+    pub invoices: Option<InvoicesState>,
+    pub reports: Option<ReportsState>,
+    pub settings: SettingsState,
+    /// Where the domain is saved after every edit.
+    database: Option<PathBuf>,
+    /// Shared by every save of the domain, so they land in order.
+    domain_saves: SaveQueue,
+    /// The same for the ledger beside it.
+    ledger_saves: SaveQueue,
+    /// Where the open route is kept between runs.
+    route_dir: Option<PathBuf>,
+    plugin_dir: Option<PathBuf>,
+    load_error: Option<String>,
+    config_path: Option<PathBuf>,
+    config: UserConfig,
+    /// When the config file was last read from, to spot edits.
+    config_modified: Option<SystemTime>,
+    /// The day the screens last worked out "today" for.
+    today: Option<NaiveDate>,
+    /// Kept beside the open route and never sent anywhere.
+    usage: UsageStats,
 }
+
+/// Something shared that changed, routed by `App::update` to every screen
+/// showing it so none is left with a stale copy.
+#[derive(Clone, Debug)]
+pub enum DomainChanged {
+    /// The store moved to a new revision. `logged_at` is set when only that
+    /// one session changed.
+    Edited { logged_at: Option<DateTime<Local>> },
+    /// A payment went into the ledger.
+    PaymentRecorded(Payment),
+    /// Midnight passed, so today's sessions, the next session and the
+    /// month so far all have to be worked out again.
+    DayChanged,
+}
+
 #[derive(Clone, Debug)]
 pub enum AppMsg {
     Shell(shell::Msg),
     Dashboard(dashboard::Msg),
     StudentManager(students::Msg),
+    StudentDetail(student_detail::Msg),
+    Compare(compare::Msg),
+    Calendar(calendar::Msg),
+    Reconciliation(reconciliation::Msg),
+    Invoices(invoices::Msg),
+    Reports(reports::Msg),
+    Settings(settings::Msg),
 
-    DomainLoaded(Domain),
+    /// The domain and the ledger kept beside it.
+    DomainLoaded(Result<(Box<Domain>, Ledger), DomainError>),
+    DomainSaved(Result<(), DomainError>),
+    RouteSaved(Result<(), RouteError>),
+    UsageSaved(Result<(), UsageError>),
+    CalendarFeedWritten(Result<(), ExportError>),
+    /// Summaries of months that ended since they were last recorded.
+    MonthsSnapshotted(Vec<MonthlySummary>),
+    CheckConfig,
+    /// Keeps everything that depends on the time of day current while
+    /// the app is left open.
+    ClockTicked,
+    ConfigLoaded(Result<UserConfig, ConfigError>),
+    ConfigSaved(Result<(), ConfigError>),
 }
 
 impl App {
     pub fn new() -> (Self, Task<AppMsg>) {
-        // let domain = Rc::new(Domain::load_state_from_db());
+        let config_path = config::config_path();
+        let config_modified = config_path.as_deref().and_then(config::modified);
+        let config = config_path
+            .as_deref()
+            .map(config::load_config)
+            .unwrap_or(Ok(UserConfig::default()));
+        let data_path = config
+            .as_ref()
+            .ok()
+            .and_then(|config| config.data_path.clone());
 
-        let app = Self {
-            domain: None,
+        let data_dirs = match data_path {
+            Some(root) => Ok(DataDirs::rooted_at(&root)),
+            None => DataDirs::locate(),
+        }
+        .and_then(|data_dirs| {
+            data_dirs.ensure_created()?;
+            Ok(data_dirs)
+        });
+
+        let database = data_dirs
+            .as_ref()
+            .ok()
+            .map(|data_dirs| data_dirs.database.clone());
+        let route_dir = data_dirs
+            .as_ref()
+            .ok()
+            .map(|data_dirs| data_dirs.root.clone());
+        let plugin_dir = data_dirs
+            .as_ref()
+            .ok()
+            .map(|data_dirs| data_dirs.plugins.clone());
+        let route = routes::route_from_args(std::env::args())
+            .or_else(|| route_dir.as_deref().and_then(routes::load_last_route));
+        let usage = route_dir
+            .as_deref()
+            .map(usage::load_usage)
+            .unwrap_or_default();
+
+        let mut app = Self {
+            store: None,
             shell: ShellState::default(),
             dashboard: DashboardState::empty(),
-            students: StudentManagerState::empty(),
+            students: None,
+            student_detail: None,
+            compare: None,
+            calendar: None,
+            reconciliation: None,
+            invoices: None,
+            reports: None,
+            settings: SettingsState::new(data_dirs),
+            database: database.clone(),
+            domain_saves: SaveQueue::default(),
+            ledger_saves: SaveQueue::default(),
+            route_dir,
+            plugin_dir,
+            load_error: None,
+            config_path,
+            config: UserConfig::default(),
+            config_modified,
+            today: None,
+            usage,
         };
+        if let Some(route) = route {
+            app.shell.open(route);
+        }
+        match config {
+            Ok(config) => app.apply_config(config),
+            Err(e) => app.shell.notify(e.to_string()),
+        }
 
-        let task = Task::perform(Domain::load_state_from_db(), AppMsg::DomainLoaded);
+        let ledger = database.as_deref().map(billing::ledger_path);
+        let task = Task::batch([
+            Task::perform(
+                async move {
+                    let domain = Domain::load_state_from_db(database).await?;
+                    Ok((Box::new(domain), Ledger::load(ledger).await?))
+                },
+                AppMsg::DomainLoaded,
+            ),
+            app.settings.load_reminder_log().map(AppMsg::Settings),
+        ]);
 
         (app, task)
     }
 
     pub fn update(&mut self, msg: AppMsg) -> Task<AppMsg> {
-        match msg {
-            AppMsg::Shell(msg) => {
-                shell::update(&mut self.shell, msg);
-                Task::none()
+        let route = self.shell.route().clone();
+        let revision = self.store.as_ref().map(DomainStore::revision);
+        let ledger = self.dashboard.ledger().revision();
+        let used = self.usage.total();
+        let mut task = Task::batch([self.dispatch(msg), self.open_screen()]);
+
+        if self.store.is_some() && self.dashboard.ledger().revision() != ledger {
+            if let Some(students) = &mut self.students {
+                students.attach_ledger(self.dashboard.ledger());
             }
+            task = Task::batch([task, self.save_ledger()]);
+        }
+
+        if let (Some(before), Some(store)) = (revision, &self.store)
+            && store.revision() != before
+        {
+            task = Task::batch([task, self.save_domain(), self.write_calendar_feed()]);
+        }
+        if let Some(dir) = &self.route_dir
+            && self.usage.total() != used
+        {
+            task = Task::batch([
+                task,
+                Task::perform(
+                    usage::save_usage(dir.clone(), self.usage.clone()),
+                    AppMsg::UsageSaved,
+                ),
+            ]);
+        }
+
+        if *self.shell.route() == route {
+            return task;
+        }
+
+        self.apply_route();
+        match &self.route_dir {
+            Some(dir) => Task::batch([
+                task,
+                Task::perform(
+                    routes::save_last_route(dir.clone(), self.shell.route().clone()),
+                    AppMsg::RouteSaved,
+                ),
+            ]),
+            None => task,
+        }
+    }
+
+    fn dispatch(&mut self, msg: AppMsg) -> Task<AppMsg> {
+        match msg {
+            AppMsg::Shell(msg) => shell::update(&mut self.shell, msg).map(AppMsg::Shell),
 
             AppMsg::Dashboard(msg) => {
+                match &msg {
+                    dashboard::Msg::SessionConfirmationToggled((student_id, at), confirmed) => {
+                        self.apply_edit(DomainEdit::SessionConfirmed {
+                            student_id: student_id.clone(),
+                            at: *at,
+                            confirmed: *confirmed,
+                        });
+                    }
+                    dashboard::Msg::MarkNoShow((student_id, at)) => {
+                        self.apply_edit(DomainEdit::SessionMarked {
+                            student_id: student_id.clone(),
+                            at: *at,
+                            status: Some(SessionStatus::MissedByStudent),
+                        });
+                    }
+                    dashboard::Msg::LogUnloggedSession((student_id, at)) => {
+                        self.apply_edit(DomainEdit::SessionMarked {
+                            student_id: student_id.clone(),
+                            at: *at,
+                            status: Some(SessionStatus::Completed),
+                        });
+                    }
+                    dashboard::Msg::CancelUnloggedSession((student_id, at)) => {
+                        self.apply_edit(DomainEdit::SessionMarked {
+                            student_id: student_id.clone(),
+                            at: *at,
+                            status: Some(SessionStatus::CancelledByTutor),
+                        });
+                    }
+                    dashboard::Msg::Effect(effect) => return self.shell.run(effect.clone()),
+                    dashboard::Msg::PaymentRecorded(payment) => {
+                        self.usage.record("Recorded a payment");
+                        self.publish(DomainChanged::PaymentRecorded(payment.clone()));
+                    }
+                    _ => (),
+                }
                 dashboard::update(&mut self.dashboard, msg).map(AppMsg::Dashboard)
             }
 
             AppMsg::StudentManager(msg) => {
-                students::update(&mut self.students, msg).map(AppMsg::StudentManager)
+                match &msg {
+                    students::Msg::StudentAdded(Ok(student)) => {
+                        self.apply_edit(DomainEdit::StudentAdded(student.as_ref().clone()));
+                    }
+                    students::Msg::StudentUpdated(Ok(student)) => {
+                        self.apply_edit(DomainEdit::StudentUpdated(student.as_ref().clone()));
+                    }
+                    students::Msg::StudentDeleted(student_id) => {
+                        self.apply_edit(DomainEdit::StudentRemoved(student_id.clone()));
+                    }
+                    students::Msg::SessionLogged {
+                        student_id,
+                        at,
+                        location,
+                        distance_km,
+                        usual,
+                        engagement,
+                        notes,
+                        links,
+                    } => {
+                        self.apply_edit(DomainEdit::SessionMarked {
+                            student_id: student_id.clone(),
+                            at: *at,
+                            status: Some(SessionStatus::Completed),
+                        });
+                        if !notes.is_empty() {
+                            self.apply_edit(DomainEdit::SessionNoted {
+                                student_id: student_id.clone(),
+                                at: *at,
+                                notes: notes.clone(),
+                            });
+                        }
+                        if !links.is_empty() {
+                            self.apply_edit(DomainEdit::SessionLinked {
+                                student_id: student_id.clone(),
+                                at: *at,
+                                links: links.clone(),
+                            });
+                        }
+                        if let Some(location) = location {
+                            self.apply_edit(DomainEdit::SessionLocated {
+                                student_id: student_id.clone(),
+                                at: *at,
+                                location: location.clone(),
+                                distance_km: *distance_km,
+                                usual: *usual,
+                            });
+                        }
+                        if let Some(engagement) = engagement {
+                            self.apply_edit(DomainEdit::SessionRated {
+                                student_id: student_id.clone(),
+                                at: *at,
+                                engagement: *engagement,
+                            });
+                        }
+                    }
+                    students::Msg::SessionMarked {
+                        student_id,
+                        at,
+                        status,
+                    } => {
+                        self.apply_edit(DomainEdit::SessionMarked {
+                            student_id: student_id.clone(),
+                            at: *at,
+                            status: *status,
+                        });
+                    }
+                    students::Msg::RatesAdjusted { from, rates } => {
+                        self.apply_edit(DomainEdit::RatesAdjusted {
+                            from: *from,
+                            rates: rates.clone(),
+                        });
+                    }
+                    students::Msg::WaitlistChanged(waitlist) => {
+                        self.apply_edit(DomainEdit::WaitlistChanged(waitlist.clone()));
+                    }
+                    students::Msg::PaymentRecorded(payment) => {
+                        self.dashboard.ledger_mut().record_payment(payment.clone());
+                        self.usage.record("Recorded a payment");
+                        self.publish(DomainChanged::PaymentRecorded(payment.clone()));
+                    }
+                    students::Msg::CardDensitySelected(density) => {
+                        self.config.card_density = *density;
+                        if let Some(path) = &self.config_path {
+                            let save = Task::perform(
+                                config::write_card_density(path.clone(), *density),
+                                AppMsg::ConfigSaved,
+                            );
+                            let Some(state) = &mut self.students else {
+                                return save;
+                            };
+                            return Task::batch([
+                                save,
+                                students::update(state, msg).map(AppMsg::StudentManager),
+                            ]);
+                        }
+                    }
+                    students::Msg::Effect(effect) => return self.shell.run(effect.clone()),
+                    _ => (),
+                }
+                let Some(state) = &mut self.students else {
+                    return Task::none();
+                };
+                students::update(state, msg).map(AppMsg::StudentManager)
+            }
+
+            AppMsg::StudentDetail(msg) => match msg {
+                student_detail::Msg::DeletePayment(payment) => {
+                    let Some(today) = self.today else {
+                        return Task::none();
+                    };
+                    if self.dashboard.ledger_mut().delete_payment(&payment, today) {
+                        self.usage.record("Deleted a payment");
+                        self.shell
+                            .notify("Payment moved to the trash in Settings".to_string());
+                    }
+                    Task::none()
+                }
+                student_detail::Msg::Effect(effect) => self.shell.run(effect),
+                msg => {
+                    if let Some(state) = &mut self.student_detail {
+                        student_detail::update(state, msg, self.dashboard.ledger());
+                    }
+                    Task::none()
+                }
+            },
+
+            AppMsg::Compare(msg) => {
+                if let compare::Msg::Effect(effect) = msg {
+                    return self.shell.run(effect);
+                }
+                if let Some(state) = &mut self.compare {
+                    compare::update(state, msg);
+                }
+                Task::none()
+            }
+
+            AppMsg::Calendar(msg) => {
+                match &msg {
+                    calendar::Msg::Effect(effect) => return self.shell.run(effect.clone()),
+                    calendar::Msg::SessionRescheduled { student_id, at, to } => {
+                        self.apply_edit(DomainEdit::SessionRescheduled {
+                            student_id: student_id.clone(),
+                            at: *at,
+                            to: *to,
+                        });
+                    }
+                    _ => (),
+                }
+                let Some(state) = &mut self.calendar else {
+                    return Task::none();
+                };
+                calendar::update(state, msg).map(AppMsg::Calendar)
+            }
+
+            AppMsg::Reconciliation(msg) => {
+                let Some(state) = &mut self.reconciliation else {
+                    return Task::none();
+                };
+                reconciliation::update(state, msg).map(AppMsg::Reconciliation)
+            }
+
+            AppMsg::Invoices(msg) => {
+                if let invoices::Msg::Effect(effect) = &msg {
+                    return self.shell.run(effect.clone());
+                }
+                let Some(state) = &mut self.invoices else {
+                    return Task::none();
+                };
+                invoices::update(state, msg, self.dashboard.ledger_mut()).map(AppMsg::Invoices)
+            }
+
+            AppMsg::Reports(msg) => {
+                let Some(state) = &mut self.reports else {
+                    return Task::none();
+                };
+                let task =
+                    reports::update(state, msg, self.dashboard.ledger()).map(AppMsg::Reports);
+                if let Route::Reports(_) = self.shell.route() {
+                    self.shell
+                        .replace_route(Route::Reports(Some(state.month())));
+                }
+                task
+            }
+
+            AppMsg::Settings(msg) => {
+                match &msg {
+                    settings::Msg::MoneyFormatChanged(money_format) => {
+                        self.apply_edit(DomainEdit::MoneyFormatChanged(*money_format));
+                    }
+                    settings::Msg::SavePaymentInstructions(instructions) => {
+                        self.apply_edit(DomainEdit::PaymentInstructionsChanged(
+                            instructions.trim().to_string(),
+                        ));
+                    }
+                    settings::Msg::BlackoutDatesChanged(blackout_dates) => {
+                        self.apply_edit(DomainEdit::BlackoutDatesChanged(blackout_dates.clone()));
+                    }
+                    settings::Msg::LocationsChanged(locations) => {
+                        self.apply_edit(DomainEdit::LocationsChanged(locations.clone()));
+                    }
+                    settings::Msg::SubjectsChanged(subjects) => {
+                        self.apply_edit(DomainEdit::SubjectsChanged(subjects.clone()));
+                    }
+                    settings::Msg::RatesChanged(rates) => {
+                        self.apply_edit(DomainEdit::RatesChanged(rates.clone()));
+                    }
+                    // Settings goes on to recompute the analytics.
+                    settings::Msg::ImportConfirmed(interchange) => {
+                        self.import(Interchange::clone(interchange));
+                    }
+                    settings::Msg::SnapshotsTaken(summaries) => {
+                        self.apply_edit(DomainEdit::MonthsSnapshotted(summaries.clone()));
+                    }
+                    settings::Msg::RestoreTrashed(index) => {
+                        let trashed = |app: &Self| {
+                            app.store
+                                .as_ref()
+                                .map_or(0, |store| store.domain().trash.len())
+                        };
+                        let before = trashed(self);
+                        self.apply_edit(DomainEdit::TrashRestored(*index));
+                        if trashed(self) == before {
+                            self.shell.notify(
+                                "Could not restore: it has been replaced since".to_string(),
+                            );
+                        }
+                    }
+                    settings::Msg::DiscardTrashed(index) => {
+                        self.apply_edit(DomainEdit::TrashDiscarded(*index));
+                    }
+                    settings::Msg::RestorePayment(index) => {
+                        let restored = self.dashboard.ledger_mut().restore_payment(*index);
+                        if restored {
+                            self.usage.record("Restored from the trash");
+                            self.shell.notify("Payment restored".to_string());
+                        }
+                    }
+                    settings::Msg::DiscardPayment(index) => {
+                        let discarded = self.dashboard.ledger_mut().discard_payment(*index);
+                        if discarded {
+                            self.usage.record("Deleted from the trash");
+                        }
+                    }
+                    settings::Msg::ClearUsage => self.usage = UsageStats::default(),
+                    settings::Msg::ReminderLeadChanged(hours) => {
+                        self.config.reminder_lead_hours = *hours;
+                        if let Some(path) = &self.config_path {
+                            return Task::batch([
+                                Task::perform(
+                                    config::write_reminder_lead_hours(path.clone(), *hours),
+                                    AppMsg::ConfigSaved,
+                                ),
+                                settings::update(&mut self.settings, msg, self.dashboard.ledger())
+                                    .map(AppMsg::Settings),
+                            ]);
+                        }
+                    }
+                    settings::Msg::NoticeMinutesChanged(minutes) => {
+                        self.config.notice_minutes = *minutes;
+                        if let Some(path) = &self.config_path {
+                            return Task::batch([
+                                Task::perform(
+                                    config::write_notice_minutes(path.clone(), *minutes),
+                                    AppMsg::ConfigSaved,
+                                ),
+                                settings::update(&mut self.settings, msg, self.dashboard.ledger())
+                                    .map(AppMsg::Settings),
+                            ]);
+                        }
+                    }
+                    settings::Msg::ReminderOptInToggled(student_id, opted_in) => {
+                        if *opted_in {
+                            self.config.reminder_opt_ins.insert(student_id.clone());
+                        } else {
+                            self.config.reminder_opt_ins.remove(student_id);
+                        }
+                        if let Some(path) = &self.config_path {
+                            return Task::batch([
+                                Task::perform(
+                                    config::write_reminder_opt_in(
+                                        path.clone(),
+                                        student_id.clone(),
+                                        *opted_in,
+                                    ),
+                                    AppMsg::ConfigSaved,
+                                ),
+                                settings::update(&mut self.settings, msg, self.dashboard.ledger())
+                                    .map(AppMsg::Settings),
+                            ]);
+                        }
+                    }
+                    settings::Msg::ReminderMuted(student_id, muted_until) => {
+                        match muted_until {
+                            Some(muted_until) => {
+                                self.config
+                                    .reminder_mutes
+                                    .insert(student_id.clone(), *muted_until);
+                            }
+                            None => {
+                                self.config.reminder_mutes.remove(student_id);
+                            }
+                        }
+                        if let Some(path) = &self.config_path {
+                            return Task::batch([
+                                Task::perform(
+                                    config::write_reminder_mute(
+                                        path.clone(),
+                                        student_id.clone(),
+                                        *muted_until,
+                                    ),
+                                    AppMsg::ConfigSaved,
+                                ),
+                                settings::update(&mut self.settings, msg, self.dashboard.ledger())
+                                    .map(AppMsg::Settings),
+                            ]);
+                        }
+                    }
+                    settings::Msg::SnoozeReminder(reminder) => {
+                        let reminder = reminder.clone();
+                        let snoozed =
+                            settings::update(&mut self.settings, msg, self.dashboard.ledger())
+                                .map(AppMsg::Settings);
+                        let Some(until) = self.settings.snoozed_until(&reminder) else {
+                            return snoozed;
+                        };
+                        let key = (reminder.student_id.clone(), reminder.session_at);
+                        self.config.snoozed_reminders.insert(key, until);
+                        if let Some(path) = &self.config_path {
+                            return Task::batch([
+                                Task::perform(
+                                    config::write_snoozed_reminder(
+                                        path.clone(),
+                                        reminder.student_id,
+                                        reminder.session_at,
+                                        until,
+                                        self.settings.clock().now(),
+                                    ),
+                                    AppMsg::ConfigSaved,
+                                ),
+                                snoozed,
+                            ]);
+                        }
+                        return snoozed;
+                    }
+                    settings::Msg::StudentNoticesChanged(student_id, notices) => {
+                        if *notices == StudentNotices::AsSet {
+                            self.config.student_notices.remove(student_id);
+                        } else {
+                            self.config
+                                .student_notices
+                                .insert(student_id.clone(), *notices);
+                        }
+                        if let Some(path) = &self.config_path {
+                            return Task::batch([
+                                Task::perform(
+                                    config::write_student_notices(
+                                        path.clone(),
+                                        student_id.clone(),
+                                        *notices,
+                                    ),
+                                    AppMsg::ConfigSaved,
+                                ),
+                                settings::update(&mut self.settings, msg, self.dashboard.ledger())
+                                    .map(AppMsg::Settings),
+                            ]);
+                        }
+                    }
+                    settings::Msg::Effect(effect) => return self.shell.run(effect.clone()),
+                    _ => (),
+                }
+                settings::update(&mut self.settings, msg, self.dashboard.ledger())
+                    .map(AppMsg::Settings)
+            }
+
+            AppMsg::DomainLoaded(Err(e)) => {
+                self.load_error = Some(e.to_string());
+                Task::none()
+            }
+
+            AppMsg::DomainLoaded(Ok((domain, ledger))) => {
+                let store = DomainStore::new(*domain);
+                let domain = store.domain();
+
+                self.today = Some(domain.clock.today());
+                self.dashboard.attach_domain(&domain);
+                self.dashboard.attach_ledger(ledger);
+                self.settings.attach_domain(domain);
+
+                self.store = Some(store);
+                self.domain_saves = SaveQueue::default();
+                self.ledger_saves = SaveQueue::default();
+                self.purge_trash();
+                self.apply_currency();
+                let task = Task::batch([
+                    self.open_screen(),
+                    self.snapshot_ended_months(),
+                    self.write_calendar_feed(),
+                ]);
+                self.apply_route();
+
+                task
+            }
+
+            AppMsg::DomainSaved(Ok(())) => Task::none(),
+            AppMsg::DomainSaved(Err(e)) => {
+                self.shell.notify(e.to_string());
+                Task::none()
             }
 
-            AppMsg::DomainLoaded(domain) => {
-                let domain = Rc::new(domain);
+            // Failing to save only means the next run starts on the dashboard.
+            AppMsg::RouteSaved(_) => Task::none(),
+            // The counts are saved again with the next action.
+            AppMsg::UsageSaved(_) => Task::none(),
+            AppMsg::CalendarFeedWritten(Ok(())) => Task::none(),
+            AppMsg::CalendarFeedWritten(Err(e)) => {
+                self.shell
+                    .notify(format!("Could not update the calendar feed: {}", e));
+                Task::none()
+            }
+
+            AppMsg::MonthsSnapshotted(summaries) => {
+                self.apply_edit(DomainEdit::MonthsSnapshotted(summaries));
+                Task::none()
+            }
 
-                self.dashboard.attach_domain(&Rc::clone(&domain));
-                self.students.attach_domain(Rc::clone(&domain));
+            AppMsg::CheckConfig => {
+                let Some(path) = &self.config_path else {
+                    return Task::none();
+                };
+                let modified = config::modified(path);
+                if modified == self.config_modified {
+                    return Task::none();
+                }
 
-                self.domain = Some(domain);
+                self.config_modified = modified;
+                Task::perform(config::read_config(path.clone()), AppMsg::ConfigLoaded)
+            }
+            AppMsg::ClockTicked => {
+                let Some(store) = &self.store else {
+                    return Task::none();
+                };
 
+                let today = store.domain().clock.today();
+                let mut snapshots = Task::none();
+                if self.today != Some(today) {
+                    self.today = Some(today);
+                    self.publish(DomainChanged::DayChanged);
+                    self.purge_trash();
+                    snapshots = self.snapshot_ended_months();
+                }
+                Task::batch([
+                    Task::done(AppMsg::Dashboard(dashboard::Msg::ClockTicked)),
+                    Task::done(AppMsg::StudentManager(students::Msg::ClockTicked)),
+                    snapshots,
+                ])
+            }
+            AppMsg::ConfigLoaded(Ok(config)) => {
+                if config.data_path != self.config.data_path {
+                    self.shell
+                        .notify("Restart to use the new data folder".to_string());
+                } else {
+                    self.shell
+                        .notify(format!("Applied {}", config::CONFIG_FILE));
+                }
+                let feed_moved = config.calendar_feed != self.config.calendar_feed;
+                self.apply_config(config);
+                if feed_moved {
+                    self.write_calendar_feed()
+                } else {
+                    Task::none()
+                }
+            }
+            AppMsg::ConfigLoaded(Err(e)) => {
+                self.shell.notify(e.to_string());
+                Task::none()
+            }
+            // Noting the new write time keeps the watcher from reading
+            // back, and announcing, what was just saved.
+            AppMsg::ConfigSaved(Ok(())) => {
+                self.config_modified = self.config_path.as_deref().and_then(config::modified);
                 Task::none()
             }
+            AppMsg::ConfigSaved(Err(e)) => {
+                self.shell.notify(e.to_string());
+                Task::none()
+            }
+        }
+    }
+
+    /// Writes the store's current revision to the database, if there is
+    /// somewhere to write it. `update` calls this whenever an edit moved
+    /// the store on, so a session logged with Add Session is kept.
+    fn save_domain(&self) -> Task<AppMsg> {
+        let (Some(store), Some(path)) = (&self.store, &self.database) else {
+            return Task::none();
+        };
+
+        let domain = Domain::clone(&store.domain());
+        Task::perform(
+            domain.save_revision(path.clone(), self.domain_saves.clone(), store.revision()),
+            AppMsg::DomainSaved,
+        )
+    }
+
+    /// Writes the ledger beside the database, if there is one.
+    fn save_ledger(&self) -> Task<AppMsg> {
+        let Some(path) = &self.database else {
+            return Task::none();
+        };
+
+        let ledger = self.dashboard.ledger().clone();
+        Task::perform(
+            ledger.save_revision(billing::ledger_path(path), self.ledger_saves.clone()),
+            AppMsg::DomainSaved,
+        )
+    }
+
+    /// Rewrites the timetable at `calendar_feed`, when one is set.
+    fn write_calendar_feed(&self) -> Task<AppMsg> {
+        let (Some(store), Some(path)) = (&self.store, &self.config.calendar_feed) else {
+            return Task::none();
+        };
+
+        let ics = ical::timetable_ics(&store.domain(), chrono::Utc::now());
+        Task::perform(
+            ical::write_feed(path.clone(), ics),
+            AppMsg::CalendarFeedWritten,
+        )
+    }
+
+    fn apply_edit(&mut self, edit: DomainEdit) {
+        let Some(store) = &mut self.store else {
+            return;
+        };
+        let logged_at = match &edit {
+            DomainEdit::SessionMarked { at, .. } | DomainEdit::SessionNoted { at, .. } => Some(*at),
+            DomainEdit::StudentAdded(_)
+            | DomainEdit::StudentUpdated(_)
+            | DomainEdit::StudentRemoved(_)
+            | DomainEdit::SessionRescheduled { .. }
+            | DomainEdit::SessionLinked { .. }
+            | DomainEdit::RatesAdjusted { .. }
+            | DomainEdit::SessionRated { .. }
+            | DomainEdit::SessionConfirmed { .. }
+            | DomainEdit::MoneyFormatChanged(_)
+            | DomainEdit::PaymentInstructionsChanged(_)
+            | DomainEdit::BlackoutDatesChanged(_)
+            | DomainEdit::SessionLocated { .. }
+            | DomainEdit::LocationsChanged(_)
+            | DomainEdit::SubjectsChanged(_)
+            | DomainEdit::RatesChanged(_)
+            | DomainEdit::WaitlistChanged(_)
+            | DomainEdit::MonthsSnapshotted(_)
+            | DomainEdit::TrashRestored(_)
+            | DomainEdit::TrashDiscarded(_)
+            | DomainEdit::TrashPurged
+            | DomainEdit::Imported(_) => None,
+        };
+        let action = edit.action();
+        let held_on = match &edit {
+            DomainEdit::SessionMarked {
+                at,
+                status: Some(_),
+                ..
+            } => Some(at.date_naive()),
+            _ => None,
+        };
+        let matched_before = domain::waitlist_matches(&store.domain());
+        if !store.apply(edit) {
+            return;
+        }
+        if let Some(action) = action {
+            self.usage.record(action);
+        }
+        if let (Some(held_on), Some(today)) = (held_on, self.today) {
+            self.usage.session_logged(held_on, today);
+        }
+
+        // Only matches this edit made are announced. All of them stay listed
+        // on the dashboard.
+        let opened = domain::waitlist_matches(&store.domain())
+            .into_iter()
+            .filter(|matched| !matched_before.contains(matched))
+            .collect::<Vec<_>>();
+        self.publish(DomainChanged::Edited { logged_at });
+        match opened.as_slice() {
+            [] => (),
+            [matched] => self.shell.notify(format!(
+                "{} is free for {} on the waitlist",
+                matched.slot, matched.prospect
+            )),
+            _ => self.shell.notify(format!(
+                "{} waitlist matches for free slots, see the dashboard",
+                opened.len()
+            )),
+        }
+    }
+
+    /// Hands a change to each screen that shows it. A logged session only
+    /// touches one month of the dashboard, so it refreshes just that.
+    fn publish(&mut self, event: DomainChanged) {
+        let Some(store) = &self.store else {
+            return;
+        };
+        let domain = store.domain();
+
+        match event {
+            DomainChanged::Edited {
+                logged_at: Some(at),
+            } => {
+                self.dashboard.session_logged(&domain, at);
+                self.refresh_screens(domain);
+            }
+            DomainChanged::Edited { logged_at: None } | DomainChanged::DayChanged => {
+                self.dashboard.attach_domain(&domain);
+                self.refresh_screens(domain);
+            }
+            // Screens read the ledger as they draw, so only the toast is left.
+            DomainChanged::PaymentRecorded(payment) => {
+                let student = domain
+                    .students
+                    .iter()
+                    .find(|student| student.id == payment.student_id)
+                    .map(|student| student.name.full_name())
+                    .unwrap_or(payment.student_id);
+                self.shell.notify(format!(
+                    "Recorded {} from {}",
+                    domain.money_format.format(payment.amount),
+                    student
+                ));
+            }
+        }
+    }
+
+    /// Hands `domain` to every screen built so far but the dashboard,
+    /// which works out for itself how much to redo.
+    fn refresh_screens(&mut self, domain: Rc<Domain>) {
+        if let Some(students) = &mut self.students {
+            students.refresh(Rc::clone(&domain));
+        }
+        if let Some(student_detail) = &mut self.student_detail {
+            student_detail.attach_domain(Rc::clone(&domain));
+        }
+        if let Some(compare) = &mut self.compare {
+            compare.attach_domain(Rc::clone(&domain));
+        }
+        if let Some(calendar) = &mut self.calendar {
+            calendar.refresh(Rc::clone(&domain));
+        }
+        if let Some(reconciliation) = &mut self.reconciliation {
+            reconciliation.attach_domain(Rc::clone(&domain));
+        }
+        if let Some(invoices) = &mut self.invoices {
+            invoices.attach_domain(Rc::clone(&domain));
+        }
+        if let Some(reports) = &mut self.reports {
+            reports.refresh(Rc::clone(&domain));
+        }
+        self.settings.attach_domain(domain);
+    }
+
+    /// Puts a newly read config into effect. The data folder is only read
+    /// at startup, so a changed `data_path` waits for the next run.
+    fn apply_config(&mut self, config: UserConfig) {
+        self.settings
+            .set_reminder_lead_hours(config.reminder_lead_hours);
+        self.settings.set_notice_minutes(config.notice_minutes);
+        self.settings
+            .set_student_notices(config.student_notices.clone());
+        self.settings
+            .set_reminder_contacts(&config.reminder_opt_ins, &config.reminder_mutes);
+        self.settings
+            .set_snoozed_reminders(config.snoozed_reminders.clone());
+        if let Some(calendar) = &mut self.calendar {
+            calendar.set_first_day_of_week(config.first_day_of_week);
+        }
+        if let Some(students) = &mut self.students {
+            students.card_density = config.card_density;
+        }
+        self.config = config;
+        self.apply_currency();
+    }
+
+    fn apply_currency(&mut self) {
+        let (Some(store), Some(currency)) = (&self.store, self.config.currency) else {
+            return;
+        };
+
+        let money_format = store.domain().money_format;
+        self.apply_edit(DomainEdit::MoneyFormatChanged(MoneyFormat {
+            currency,
+            ..money_format
+        }));
+    }
+
+    /// Builds the current screen's state if this is its first visit, or
+    /// drops every lazily built screen on logout. Returns whatever the new
+    /// screen still has to load.
+    fn open_screen(&mut self) -> Task<AppMsg> {
+        let Some(store) = &self.store else {
+            return Task::none();
+        };
+        let domain = store.domain();
+
+        match self.shell.current_screen {
+            Screen::StudentManager if self.students.is_none() => {
+                let mut students = StudentManagerState::empty();
+                students.attach_domain(domain);
+                students.attach_ledger(self.dashboard.ledger());
+                students.card_density = self.config.card_density;
+                self.students = Some(students);
+            }
+            Screen::StudentDetail if self.student_detail.is_none() => {
+                let mut student_detail = StudentDetailState::default();
+                student_detail.attach_domain(domain);
+                self.student_detail = Some(student_detail);
+            }
+            Screen::Compare if self.compare.is_none() => {
+                let mut compare = CompareState::default();
+                compare.attach_domain(domain);
+                self.compare = Some(compare);
+            }
+            Screen::Calendar if self.calendar.is_none() => {
+                let mut calendar = CalendarState::default();
+                calendar.attach_domain(domain);
+                calendar.set_first_day_of_week(self.config.first_day_of_week);
+                self.calendar = Some(calendar);
+            }
+            Screen::Reconciliation if self.reconciliation.is_none() => {
+                let mut reconciliation = ReconciliationState::default();
+                reconciliation.attach_domain(domain);
+                self.reconciliation = Some(reconciliation);
+            }
+            Screen::Invoices if self.invoices.is_none() => {
+                let mut invoices = InvoicesState::default();
+                invoices.attach_domain(domain);
+                self.invoices = Some(invoices);
+            }
+            Screen::Reports if self.reports.is_none() => {
+                let mut reports = ReportsState::default();
+                reports.attach_domain(domain);
+                let task = match self.plugin_dir.clone() {
+                    Some(dir) => reports.load_plugins(dir).map(AppMsg::Reports),
+                    None => Task::none(),
+                };
+                self.reports = Some(reports);
+                return task;
+            }
+            Screen::Logout => {
+                self.students = None;
+                self.student_detail = None;
+                self.compare = None;
+                self.calendar = None;
+                self.reconciliation = None;
+                self.invoices = None;
+                self.reports = None;
+            }
+            _ => (),
+        }
+
+        Task::none()
+    }
+
+    /// Swaps in imported students, payments and invoices. The ledger is
+    /// set first so the dashboard's refresh already counts it.
+    fn import(&mut self, interchange: Interchange) {
+        let Some(store) = &self.store else {
+            return;
+        };
+
+        let (domain, ledger) = interchange.into_domain(store.domain().clock);
+        self.dashboard.ledger_mut().replace_imported(ledger);
+        self.apply_edit(DomainEdit::Imported(domain));
+    }
+
+    /// Drops whatever has been in the trash longer than it is kept.
+    fn purge_trash(&mut self) {
+        let Some(today) = self.today else {
+            return;
+        };
+        self.dashboard.ledger_mut().purge_trash(today);
+        self.apply_edit(DomainEdit::TrashPurged);
+    }
+
+    /// Records the totals of every month that has ended without a
+    /// snapshot, as happens once a month rolls over.
+    fn snapshot_ended_months(&self) -> Task<AppMsg> {
+        let Some(store) = &self.store else {
+            return Task::none();
+        };
+
+        let domain = store.domain();
+        let months = domain.unsnapshotted_months();
+        if months.is_empty() {
+            return Task::none();
+        }
+        Task::perform(
+            Domain::clone(&domain).take_snapshots(months),
+            AppMsg::MonthsSnapshotted,
+        )
+    }
+
+    /// Opens what the current route points at within its screen.
+    fn apply_route(&mut self) {
+        match self.shell.route() {
+            Route::Students(Some(student_id)) => {
+                if let Some(students) = &mut self.students {
+                    students.focus_student(student_id);
+                }
+            }
+            Route::StudentDetail(student_id) => {
+                if let Some(student_detail) = &mut self.student_detail {
+                    student_detail.show_student(student_id);
+                }
+            }
+            Route::Reports(Some((year, month))) => {
+                if let Some(reports) = &mut self.reports {
+                    reports.show_month(*year, *month);
+                }
+            }
+            _ => (),
         }
     }
 
@@ -71,25 +1125,129 @@ impl App {
         String::from("Tutor Manager")
     }
 
+    /// `None` follows the system's light or dark setting.
+    pub fn theme(&self) -> Option<Theme> {
+        self.config.theme.theme()
+    }
+
     pub fn subscription(&self) -> Subscription<AppMsg> {
-        shell::subscription(&self.shell).map(AppMsg::Shell)
+        let config = if self.config_path.is_some() {
+            iced::time::every(CONFIG_CHECK_INTERVAL).map(|_| AppMsg::CheckConfig)
+        } else {
+            Subscription::none()
+        };
+
+        Subscription::batch([
+            shell::subscription(&self.shell).map(AppMsg::Shell),
+            dashboard::subscription(&self.dashboard).map(AppMsg::Dashboard),
+            self.students
+                .as_ref()
+                .map(students::subscription)
+                .unwrap_or_else(Subscription::none)
+                .map(AppMsg::StudentManager),
+            settings::subscription(&self.settings).map(AppMsg::Settings),
+            iced::time::every(CLOCK_INTERVAL).map(|_| AppMsg::ClockTicked),
+            config,
+        ])
     }
 }
 
 impl App {
     pub fn view(&self) -> Element<'_, AppMsg> {
+        if let Some(error) = &self.load_error {
+            return center(text(error)).into();
+        }
+
+        let ledger = self.dashboard.ledger();
         let content = match self.shell.current_screen {
             Screen::Dashboard => dashboard::view(&self.dashboard).map(AppMsg::Dashboard),
-            Screen::StudentManager => {
-                // Placeholder until I implement students view
-                students::view(&self.students).map(AppMsg::StudentManager)
-            }
-            Screen::Settings | Screen::Logout => {
-                // Placeholder for other screens
-                dashboard::view(&self.dashboard).map(AppMsg::Dashboard)
+            Screen::StudentManager => match &self.students {
+                Some(students) => students::view(students).map(AppMsg::StudentManager),
+                None => loading(),
+            },
+            Screen::StudentDetail => match &self.student_detail {
+                Some(student_detail) => {
+                    student_detail::view(student_detail, ledger).map(AppMsg::StudentDetail)
+                }
+                None => loading(),
+            },
+            Screen::Compare => match &self.compare {
+                Some(compare) => compare::view(compare).map(AppMsg::Compare),
+                None => loading(),
+            },
+            Screen::Calendar => match &self.calendar {
+                Some(calendar) => calendar::view(calendar).map(AppMsg::Calendar),
+                None => loading(),
+            },
+            Screen::Reconciliation => match &self.reconciliation {
+                Some(reconciliation) => {
+                    reconciliation::view(reconciliation, ledger).map(AppMsg::Reconciliation)
+                }
+                None => loading(),
+            },
+            Screen::Invoices => match &self.invoices {
+                Some(invoices) => invoices::view(invoices, ledger).map(AppMsg::Invoices),
+                None => loading(),
+            },
+            Screen::Reports => match &self.reports {
+                Some(reports) => reports::view(reports, ledger).map(AppMsg::Reports),
+                None => loading(),
+            },
+            Screen::Settings => {
+                settings::view(&self.settings, self.dashboard.ledger(), &self.usage)
+                    .map(AppMsg::Settings)
             }
+            Screen::Logout => not_implemented("Logout"),
         };
 
-        shell::view(&self.shell, content, AppMsg::Shell)
+        shell::view(
+            &self.shell,
+            self.quick_stats(),
+            self.search_results(),
+            content,
+            AppMsg::Shell,
+        )
     }
+
+    fn search_results(&self) -> Vec<SearchResult> {
+        match &self.store {
+            Some(store) if self.shell.search_open => search::search(
+                &store.domain().students,
+                self.dashboard.ledger().payments(),
+                &self.shell.search_query,
+                &store.domain().money_format,
+            ),
+            _ => Vec::new(),
+        }
+    }
+
+    fn quick_stats(&self) -> QuickStats {
+        let clock = self
+            .store
+            .as_ref()
+            .map(|store| store.domain().clock)
+            .unwrap_or_default();
+        let next_session = self.store.as_ref().and_then(|store| {
+            domain::next_scheduled_session(&store.domain().students, clock.now())
+                .map(|(at, student)| (at, student.name.full_name()))
+        });
+        let money = self
+            .store
+            .as_ref()
+            .map(|store| store.domain().money_format)
+            .unwrap_or_default();
+
+        QuickStats {
+            todays_sessions: self.dashboard.todays_session_count(),
+            unpaid_balance: self.dashboard.unpaid_balance(),
+            money,
+            next_session,
+            clock,
+        }
+    }
+}
+
+/// Shown in place of a screen until the data it is built from has loaded.
+fn loading<'a>() -> Element<'a, AppMsg> {
+    center(text("Loading…")).into()
 }
@@ -0,0 +1,707 @@
+use chrono::{DateTime, Datelike, Duration, Local, NaiveDate};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+use crate::domain::{
+    DomainError, Location, SaveQueue, Student, Trashed, compute_monthly_completed_sessions,
+    compute_monthly_sum, monthly_surcharges, next_month,
+};
+
+/// Kept beside the database, so invoice numbers carry on between runs.
+const LEDGER_FILE: &str = "ledger.json";
+
+/// What to charge when a student does not turn up. With `auto_apply` on,
+/// marking a no-show offers the fee straight away, still open to change.
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub struct NoShowPolicy {
+    pub auto_apply: bool,
+    pub fee: f32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ChargeReason {
+    NoShow,
+}
+
+impl std::fmt::Display for ChargeReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ChargeReason::NoShow => write!(f, "No-show fee"),
+        }
+    }
+}
+
+/// An amount owed on top of regular session fees, held until it goes out
+/// on the student's next invoice.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Charge {
+    pub student_id: String,
+    pub student_name: String,
+    pub session_at: DateTime<Local>,
+    pub reason: ChargeReason,
+    pub amount: f32,
+    pub invoiced: bool,
+}
+
+/// Money received from a student. It need not match what was billed:
+/// underpayments stay owed and overpayments become credit.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Payment {
+    pub student_id: String,
+    pub paid_on: NaiveDate,
+    pub amount: f32,
+    /// The invoice this pays towards, if any.
+    #[serde(default)]
+    pub invoice_number: Option<u32>,
+}
+
+/// Days a sent invoice has before it is overdue.
+pub const INVOICE_TERMS_DAYS: i64 = 14;
+
+/// Where an invoice stands. Only drafts, sent and void invoices are
+/// recorded as such; paid and overdue follow from linked payments and
+/// the due date.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InvoiceStatus {
+    Draft,
+    Sent,
+    Paid,
+    Overdue,
+    Void,
+}
+
+impl InvoiceStatus {
+    pub const ALL: [InvoiceStatus; 5] = [
+        InvoiceStatus::Draft,
+        InvoiceStatus::Sent,
+        InvoiceStatus::Paid,
+        InvoiceStatus::Overdue,
+        InvoiceStatus::Void,
+    ];
+}
+
+impl std::fmt::Display for InvoiceStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InvoiceStatus::Draft => write!(f, "Draft"),
+            InvoiceStatus::Sent => write!(f, "Sent"),
+            InvoiceStatus::Paid => write!(f, "Paid"),
+            InvoiceStatus::Overdue => write!(f, "Overdue"),
+            InvoiceStatus::Void => write!(f, "Void"),
+        }
+    }
+}
+
+/// A student's bill for one month of sessions and charges.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Invoice {
+    /// Given out in order and never reused, even once voided.
+    pub number: u32,
+    pub student_id: String,
+    pub year: i32,
+    pub month: u32,
+    pub amount: f32,
+    pub sent_on: Option<NaiveDate>,
+    pub void: bool,
+}
+
+impl Invoice {
+    /// The number as printed, e.g. `INV-0007`.
+    pub fn reference(&self) -> String {
+        format!("INV-{:04}", self.number)
+    }
+
+    pub fn due_on(&self) -> Option<NaiveDate> {
+        self.sent_on
+            .map(|sent_on| sent_on + Duration::days(INVOICE_TERMS_DAYS))
+    }
+}
+
+/// One billing cycle (a calendar month) of a student's account.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct MonthlyAccount {
+    /// Owed from earlier months, negative when the student is in credit.
+    pub opening: f32,
+    pub billed: f32,
+    pub paid: f32,
+}
+
+impl MonthlyAccount {
+    /// What is carried into the next month; negative means credit.
+    pub fn closing(&self) -> f32 {
+        self.opening + self.billed - self.paid
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Ledger {
+    charges: Vec<Charge>,
+    payments: Vec<Payment>,
+    invoices: Vec<Invoice>,
+    /// Deleted payments, newest last, until they expire.
+    #[serde(default)]
+    trashed_payments: Vec<Trashed<Payment>>,
+    #[serde(default)]
+    no_show_policy: NoShowPolicy,
+    /// Moves on with every change, so the app knows when to save.
+    #[serde(skip)]
+    revision: u64,
+}
+
+impl Ledger {
+    /// Reads the ledger saved at `path`, starting empty when nothing has
+    /// been saved yet or there is nowhere to save to.
+    pub async fn load(path: Option<PathBuf>) -> Result<Self, DomainError> {
+        let Some(path) = path else {
+            return Ok(Self::default());
+        };
+
+        match std::fs::read_to_string(&path) {
+            Ok(json) => {
+                serde_json::from_str(&json).map_err(|e| DomainError::Corrupt(e.to_string()))
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(DomainError::ReadFailed(e.to_string())),
+        }
+    }
+
+    pub async fn save(self, path: PathBuf) -> Result<(), DomainError> {
+        self.save_revision(path, SaveQueue::default()).await
+    }
+
+    /// Saves this revision through `saves`, which every save of the ledger
+    /// shares, so an older revision never overwrites a newer.
+    pub async fn save_revision(self, path: PathBuf, saves: SaveQueue) -> Result<(), DomainError> {
+        let json = serde_json::to_string_pretty(&self)
+            .map_err(|e| DomainError::WriteFailed(e.to_string()))?;
+
+        saves
+            .write(&path, self.revision, &json)
+            .map_err(|e| DomainError::WriteFailed(e.to_string()))
+    }
+
+    pub fn revision(&self) -> u64 {
+        self.revision
+    }
+
+    pub fn no_show_policy(&self) -> NoShowPolicy {
+        self.no_show_policy
+    }
+
+    pub fn set_no_show_policy(&mut self, policy: NoShowPolicy) {
+        if policy != self.no_show_policy {
+            self.no_show_policy = policy;
+            self.revision += 1;
+        }
+    }
+
+    pub fn add(&mut self, charge: Charge) {
+        self.charges.push(charge);
+        self.revision += 1;
+    }
+
+    /// Charges still waiting for the next invoice.
+    pub fn uninvoiced(&self) -> impl Iterator<Item = &Charge> {
+        self.charges.iter().filter(|charge| !charge.invoiced)
+    }
+
+    pub fn uninvoiced_total(&self, student_id: &str) -> f32 {
+        self.uninvoiced()
+            .filter(|charge| charge.student_id == student_id)
+            .map(|charge| charge.amount)
+            .sum()
+    }
+
+    /// Records a payment, linking it to the student's oldest unpaid sent
+    /// invoice unless it already names one.
+    pub fn record_payment(&mut self, mut payment: Payment) {
+        if payment.invoice_number.is_none() {
+            payment.invoice_number = self
+                .invoices
+                .iter()
+                .filter(|invoice| invoice.student_id == payment.student_id)
+                .filter(|invoice| !invoice.void && invoice.sent_on.is_some())
+                .find(|invoice| self.paid_towards(invoice.number) < invoice.amount)
+                .map(|invoice| invoice.number);
+        }
+        self.payments.push(payment);
+        self.revision += 1;
+    }
+
+    pub fn payments(&self) -> &[Payment] {
+        &self.payments
+    }
+
+    /// Moves the payment to the trash. False when it is not recorded.
+    pub fn delete_payment(&mut self, payment: &Payment, today: NaiveDate) -> bool {
+        let Some(index) = self
+            .payments
+            .iter()
+            .position(|recorded| recorded == payment)
+        else {
+            return false;
+        };
+
+        let payment = self.payments.remove(index);
+        self.trashed_payments.push(Trashed::new(payment, today));
+        self.revision += 1;
+        true
+    }
+
+    pub fn trashed_payments(&self) -> &[Trashed<Payment>] {
+        &self.trashed_payments
+    }
+
+    /// Records the trashed payment at `index` again, as it was.
+    pub fn restore_payment(&mut self, index: usize) -> bool {
+        if index >= self.trashed_payments.len() {
+            return false;
+        }
+
+        let payment = self.trashed_payments.remove(index).item;
+        self.payments.push(payment);
+        self.payments.sort_by_key(|payment| payment.paid_on);
+        self.revision += 1;
+        true
+    }
+
+    pub fn discard_payment(&mut self, index: usize) -> bool {
+        if index >= self.trashed_payments.len() {
+            return false;
+        }
+
+        self.trashed_payments.remove(index);
+        self.revision += 1;
+        true
+    }
+
+    /// Lets go of payments kept in the trash past its retention.
+    pub fn purge_trash(&mut self, today: NaiveDate) {
+        let before = self.trashed_payments.len();
+        self.trashed_payments
+            .retain(|trashed| !trashed.is_expired(today));
+        if self.trashed_payments.len() != before {
+            self.revision += 1;
+        }
+    }
+
+    /// A ledger read from a data file, with nothing in its trash.
+    pub fn imported(charges: Vec<Charge>, payments: Vec<Payment>, invoices: Vec<Invoice>) -> Self {
+        Self {
+            charges,
+            payments,
+            invoices,
+            ..Self::default()
+        }
+    }
+
+    /// Takes everything from an imported ledger but the trash and the
+    /// no-show fee, which are kept so deleted payments can still be
+    /// restored.
+    pub fn replace_imported(&mut self, imported: Ledger) {
+        self.charges = imported.charges;
+        self.payments = imported.payments;
+        self.invoices = imported.invoices;
+        self.revision += 1;
+    }
+
+    pub fn charges(&self) -> &[Charge] {
+        &self.charges
+    }
+
+    pub fn invoices(&self) -> &[Invoice] {
+        &self.invoices
+    }
+
+    /// Drafts an invoice for what the student was billed in the month,
+    /// taking in that month's charges and location surcharges. Returns the
+    /// new invoice's number, or `None` when the month is already invoiced
+    /// or there is nothing owed.
+    pub fn draft_invoice(
+        &mut self,
+        student: &Student,
+        locations: &[Location],
+        year: i32,
+        month: u32,
+    ) -> Option<u32> {
+        let already_invoiced = self.invoices.iter().any(|invoice| {
+            invoice.student_id == student.id
+                && (invoice.year, invoice.month) == (year, month)
+                && !invoice.void
+        });
+        let amount = self.billed_in(student, locations, month, year);
+        if already_invoiced || amount <= 0.0 {
+            return None;
+        }
+
+        self.set_charges_invoiced(&student.id, year, month, true);
+        let number = self
+            .invoices
+            .iter()
+            .map(|invoice| invoice.number)
+            .max()
+            .unwrap_or(0)
+            + 1;
+        self.invoices.push(Invoice {
+            number,
+            student_id: student.id.clone(),
+            year,
+            month,
+            amount,
+            sent_on: None,
+            void: false,
+        });
+        self.revision += 1;
+
+        Some(number)
+    }
+
+    pub fn send_invoice(&mut self, number: u32, today: NaiveDate) {
+        if let Some(invoice) = self.invoice_mut(number).filter(|invoice| !invoice.void) {
+            invoice.sent_on.get_or_insert(today);
+            self.revision += 1;
+        }
+    }
+
+    /// Voids an unpaid invoice. Its charges go back to waiting for the next
+    /// one, and its number stays taken.
+    pub fn void_invoice(&mut self, number: u32) {
+        if self.paid_towards(number) > 0.0 {
+            return;
+        }
+        let Some(invoice) = self.invoice_mut(number) else {
+            return;
+        };
+
+        invoice.void = true;
+        let (student_id, year, month) = (invoice.student_id.clone(), invoice.year, invoice.month);
+        self.set_charges_invoiced(&student_id, year, month, false);
+        self.revision += 1;
+    }
+
+    /// Payments linked to the invoice so far.
+    pub fn paid_towards(&self, number: u32) -> f32 {
+        self.payments
+            .iter()
+            .filter(|payment| payment.invoice_number == Some(number))
+            .fold(0.0, |total, payment| total + payment.amount)
+    }
+
+    pub fn invoice_status(&self, invoice: &Invoice, today: NaiveDate) -> InvoiceStatus {
+        match invoice.due_on() {
+            _ if invoice.void => InvoiceStatus::Void,
+            None => InvoiceStatus::Draft,
+            Some(_) if self.paid_towards(invoice.number) >= invoice.amount - 0.005 => {
+                InvoiceStatus::Paid
+            }
+            Some(due_on) if today > due_on => InvoiceStatus::Overdue,
+            Some(_) => InvoiceStatus::Sent,
+        }
+    }
+
+    /// How many days the student's most overdue unpaid invoice is past its
+    /// due date, or `None` when nothing is overdue.
+    pub fn days_overdue(&self, student_id: &str, today: NaiveDate) -> Option<i64> {
+        self.invoices
+            .iter()
+            .filter(|invoice| invoice.student_id == student_id)
+            .filter(|invoice| self.invoice_status(invoice, today) == InvoiceStatus::Overdue)
+            .filter_map(|invoice| Some((today - invoice.due_on()?).num_days()))
+            .max()
+    }
+
+    fn invoice_mut(&mut self, number: u32) -> Option<&mut Invoice> {
+        self.invoices
+            .iter_mut()
+            .find(|invoice| invoice.number == number)
+    }
+
+    fn set_charges_invoiced(&mut self, student_id: &str, year: i32, month: u32, invoiced: bool) {
+        self.charges
+            .iter_mut()
+            .filter(|charge| charge.student_id == student_id)
+            .filter(|charge| (charge.session_at.year(), charge.session_at.month()) == (year, month))
+            .for_each(|charge| charge.invoiced = invoiced);
+    }
+
+    /// The student's account for the month containing `today`, with every
+    /// month since tuition started rolled into the opening balance.
+    pub fn account_for(
+        &self,
+        student: &Student,
+        locations: &[Location],
+        today: NaiveDate,
+    ) -> MonthlyAccount {
+        let start = student.tution_start_date.date_naive();
+        let mut account = MonthlyAccount::default();
+        let (mut year, mut month) = (start.year(), start.month());
+
+        while (year, month) <= (today.year(), today.month()) {
+            account = MonthlyAccount {
+                opening: account.closing(),
+                billed: self.billed_in(student, locations, month, year),
+                paid: self.paid_in(&student.id, month, year),
+            };
+
+            (year, month) = next_month(year, month);
+        }
+
+        account
+    }
+
+    /// Charges for sessions in the given month, invoiced or not.
+    pub fn charges_in(&self, month: u32, year: i32) -> impl Iterator<Item = &Charge> {
+        self.charges.iter().filter(move |charge| {
+            charge.session_at.month() == month && charge.session_at.year() == year
+        })
+    }
+
+    fn billed_in(&self, student: &Student, locations: &[Location], month: u32, year: i32) -> f32 {
+        let sessions =
+            compute_monthly_sum(student, month, year, compute_monthly_completed_sessions)
+                + monthly_surcharges(student, locations, month, year);
+        let charges = self
+            .charges_in(month, year)
+            .filter(|charge| charge.student_id == student.id)
+            .fold(0.0, |total, charge| total + charge.amount);
+
+        sessions + charges
+    }
+
+    fn paid_in(&self, student_id: &str, month: u32, year: i32) -> f32 {
+        self.payments
+            .iter()
+            .filter(|payment| payment.student_id == student_id)
+            .filter(|payment| payment.paid_on.month() == month && payment.paid_on.year() == year)
+            .fold(0.0, |total, payment| total + payment.amount)
+    }
+}
+
+/// Where the ledger goes for the domain saved at `database`.
+pub fn ledger_path(database: &Path) -> PathBuf {
+    database.with_file_name(LEDGER_FILE)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::SessionLocation;
+    use crate::domain::fixtures::{date, tue_thu_student};
+    use chrono::TimeZone;
+    use iced::futures::executor::block_on;
+
+    fn no_show(student_id: &str, amount: f32) -> Charge {
+        Charge {
+            student_id: student_id.to_string(),
+            student_name: student_id.to_string(),
+            session_at: Local.with_ymd_and_hms(2026, 2, 12, 17, 30, 0).unwrap(),
+            reason: ChargeReason::NoShow,
+            amount,
+            invoiced: false,
+        }
+    }
+
+    #[test]
+    fn balance_counts_only_the_students_uninvoiced_charges() {
+        let mut ledger = Ledger::default();
+        ledger.add(no_show("mary", 50.0));
+        ledger.add(no_show("peter", 40.0));
+        ledger.add(Charge {
+            invoiced: true,
+            ..no_show("mary", 30.0)
+        });
+
+        assert_eq!(ledger.uninvoiced_total("mary"), 50.0);
+        assert_eq!(ledger.uninvoiced().count(), 2);
+    }
+
+    #[test]
+    fn underpayments_carry_forward_and_overpayments_become_credit() {
+        // Per-session at 100 with two January sessions and one in February.
+        let student = tue_thu_student(&[(2026, 1, 6), (2026, 1, 8), (2026, 2, 3)]);
+        let payment = |day: u32, month: u32, amount: f32| Payment {
+            student_id: student.id.clone(),
+            paid_on: date(2026, month, day),
+            amount,
+            invoice_number: None,
+        };
+
+        let mut ledger = Ledger::default();
+        ledger.record_payment(payment(20, 1, 150.0));
+        let february = ledger.account_for(&student, &[], date(2026, 2, 10));
+        assert_eq!(february.opening, 50.0);
+        assert_eq!(february.billed, 100.0);
+        assert_eq!(february.closing(), 150.0);
+
+        ledger.record_payment(payment(10, 2, 200.0));
+        let february = ledger.account_for(&student, &[], date(2026, 2, 10));
+        assert_eq!(february.closing(), -50.0);
+    }
+
+    #[test]
+    fn invoices_add_the_surcharge_for_where_each_session_was_held() {
+        let mut student = tue_thu_student(&[(2026, 1, 6), (2026, 1, 8)]);
+        student.location = Some("Home visit".to_string());
+        student.session_locations.push(SessionLocation {
+            at: Local.with_ymd_and_hms(2026, 1, 8, 17, 0, 0).unwrap(),
+            location: "Online".to_string(),
+        });
+        let locations = [
+            Location {
+                name: "Home visit".to_string(),
+                surcharge: 30.0,
+                home_visit: true,
+            },
+            Location {
+                name: "Online".to_string(),
+                surcharge: 0.0,
+                home_visit: false,
+            },
+        ];
+
+        let mut ledger = Ledger::default();
+        let number = ledger.draft_invoice(&student, &locations, 2026, 1).unwrap();
+
+        assert_eq!(ledger.invoices()[0].number, number);
+        assert_eq!(ledger.invoices()[0].amount, 230.0);
+    }
+
+    #[test]
+    fn deleted_payments_can_be_restored_until_the_trash_lets_them_go() {
+        let payment = |paid_on| Payment {
+            student_id: "mary".to_string(),
+            paid_on,
+            amount: 100.0,
+            invoice_number: None,
+        };
+        let mut ledger = Ledger::default();
+        ledger.record_payment(payment(date(2026, 1, 5)));
+        ledger.record_payment(payment(date(2026, 2, 5)));
+
+        assert!(ledger.delete_payment(&payment(date(2026, 1, 5)), date(2026, 3, 1)));
+        assert!(!ledger.delete_payment(&payment(date(2026, 1, 5)), date(2026, 3, 1)));
+        assert_eq!(ledger.payments().len(), 1);
+
+        assert!(ledger.restore_payment(0));
+        assert_eq!(ledger.payments()[0].paid_on, date(2026, 1, 5));
+
+        ledger.delete_payment(&payment(date(2026, 2, 5)), date(2026, 3, 1));
+        ledger.purge_trash(date(2026, 3, 30));
+        assert_eq!(ledger.trashed_payments().len(), 1);
+        ledger.purge_trash(date(2026, 3, 31));
+        assert!(ledger.trashed_payments().is_empty());
+    }
+
+    #[test]
+    fn invoice_numbers_carry_on_after_the_ledger_is_saved_and_read_back() {
+        let dir = std::env::temp_dir().join("tutor-mgr-ledger");
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = ledger_path(&dir.join("tutor-mgr.db"));
+        let student = tue_thu_student(&[(2026, 1, 6), (2026, 2, 3)]);
+
+        let mut ledger = Ledger::default();
+        assert_eq!(ledger.draft_invoice(&student, &[], 2026, 1), Some(1));
+        block_on(ledger.save(path.clone())).unwrap();
+
+        let mut reloaded = block_on(Ledger::load(Some(path))).unwrap();
+        assert_eq!(reloaded.invoices().len(), 1);
+        assert_eq!(reloaded.draft_invoice(&student, &[], 2026, 2), Some(2));
+    }
+
+    #[test]
+    fn payments_and_balances_are_the_same_after_a_reload() {
+        let dir = std::env::temp_dir().join("tutor-mgr-ledger-payments");
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = ledger_path(&dir.join("tutor-mgr.db"));
+        let student = tue_thu_student(&[(2026, 1, 6), (2026, 1, 8)]);
+
+        let mut ledger = Ledger::default();
+        ledger.add(no_show(&student.id, 40.0));
+        ledger.record_payment(Payment {
+            student_id: student.id.clone(),
+            paid_on: date(2026, 1, 20),
+            amount: 150.0,
+            invoice_number: None,
+        });
+        let account = ledger.account_for(&student, &[], date(2026, 2, 10));
+        block_on(ledger.clone().save(path.clone())).unwrap();
+
+        let reloaded = block_on(Ledger::load(Some(path))).unwrap();
+        assert_eq!(reloaded.payments(), ledger.payments());
+        assert_eq!(reloaded.uninvoiced_total(&student.id), 40.0);
+        assert_eq!(
+            reloaded.account_for(&student, &[], date(2026, 2, 10)),
+            account
+        );
+    }
+
+    #[test]
+    fn a_late_save_of_an_older_ledger_is_dropped() {
+        let dir = std::env::temp_dir().join("tutor-mgr-ledger-late-save");
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = ledger_path(&dir.join("tutor-mgr.db"));
+        let saves = SaveQueue::default();
+
+        let older = Ledger::default();
+        let mut newer = older.clone();
+        newer.add(no_show("test-student", 40.0));
+        block_on(newer.save_revision(path.clone(), saves.clone())).unwrap();
+        block_on(older.save_revision(path.clone(), saves)).unwrap();
+
+        let reloaded = block_on(Ledger::load(Some(path))).unwrap();
+        assert_eq!(reloaded.uninvoiced_total("test-student"), 40.0);
+    }
+
+    #[test]
+    fn invoiced_no_show_fees_are_not_billed_again_after_a_reload() {
+        let dir = std::env::temp_dir().join("tutor-mgr-ledger-no-shows");
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = ledger_path(&dir.join("tutor-mgr.db"));
+        let student = tue_thu_student(&[(2026, 2, 3)]);
+
+        let mut ledger = Ledger::default();
+        ledger.set_no_show_policy(NoShowPolicy {
+            auto_apply: true,
+            fee: 50.0,
+        });
+        ledger.add(no_show(&student.id, 50.0));
+        assert_eq!(ledger.draft_invoice(&student, &[], 2026, 2), Some(1));
+        block_on(ledger.clone().save(path.clone())).unwrap();
+
+        let mut reloaded = block_on(Ledger::load(Some(path))).unwrap();
+        assert_eq!(reloaded.no_show_policy(), ledger.no_show_policy());
+        assert_eq!(reloaded.uninvoiced().count(), 0);
+        assert_eq!(reloaded.draft_invoice(&student, &[], 2026, 2), None);
+        reloaded.void_invoice(1);
+        assert_eq!(reloaded.draft_invoice(&student, &[], 2026, 2), Some(2));
+        assert_eq!(reloaded.invoices()[1].amount, 150.0);
+    }
+
+    #[test]
+    fn days_overdue_counts_from_the_due_date_of_the_oldest_unpaid_invoice() {
+        let student = tue_thu_student(&[(2026, 1, 6), (2026, 2, 3)]);
+        let mut ledger = Ledger::default();
+        let january = ledger.draft_invoice(&student, &[], 2026, 1).unwrap();
+        let february = ledger.draft_invoice(&student, &[], 2026, 2).unwrap();
+        ledger.send_invoice(january, date(2026, 2, 1));
+        ledger.send_invoice(february, date(2026, 3, 1));
+        let due = ledger.invoices()[0].due_on().unwrap();
+
+        assert_eq!(ledger.days_overdue(&student.id, due), None);
+        assert_eq!(
+            ledger.days_overdue(&student.id, due + Duration::days(10)),
+            Some(10)
+        );
+
+        ledger.record_payment(Payment {
+            student_id: student.id.clone(),
+            paid_on: due,
+            amount: 100.0,
+            invoice_number: Some(january),
+        });
+        assert_eq!(
+            ledger.days_overdue(&student.id, due + Duration::days(10)),
+            None
+        );
+    }
+}
@@ -0,0 +1,934 @@
+use chrono::{DateTime, Datelike, Duration, Local, NaiveDate, NaiveTime, TimeZone, Weekday};
+use iced::advanced::graphics::core::font;
+use iced::widget::{Column, Row, button, column, container, mouse_area, row, text, text_input};
+use iced::{Background, Border, Center, Color, Element, Font, Length, Task, Theme};
+use std::path::PathBuf;
+use std::rc::Rc;
+
+use crate::domain::{
+    Clock, Domain, PaymentType, SessionStatus, Student, TutorSubject, is_active_on, next_month,
+    parse_session_time, previous_month, rate_in, scheduled_sessions_on,
+};
+use crate::export::{ExportError, LANDSCAPE_LINE_WIDTH, landscape_text_pdf, write_export};
+use crate::ical::{ICS_FILE_NAME, timetable_ics};
+use crate::money::MoneyFormat;
+use crate::routes::Route;
+use crate::shell::Effect;
+use crate::theme;
+use crate::ui_components::{global_content_container, page_header, popover};
+
+/// Sessions listed in a day of the month view before the rest are counted.
+const MONTH_CELL_ENTRIES: usize = 3;
+/// Characters of the last note shown when hovering a session.
+const NOTE_EXCERPT_CHARS: usize = 80;
+const TIMETABLE_FILE_NAME: &str = "timetable.pdf";
+/// Characters in the time column of the printed timetable.
+const TIMETABLE_TIME_WIDTH: usize = 8;
+/// Subjects take these in the order the tutor lists them, wrapping round.
+const SUBJECT_COLORS: [Color; 6] = [
+    Color::from_rgb(0.2, 0.45, 0.85),
+    Color::from_rgb(0.55, 0.3, 0.75),
+    Color::from_rgb(0.1, 0.6, 0.5),
+    Color::from_rgb(0.85, 0.5, 0.15),
+    Color::from_rgb(0.8, 0.3, 0.4),
+    Color::from_rgb(0.45, 0.55, 0.2),
+];
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum CalendarSpan {
+    #[default]
+    Week,
+    Month,
+}
+
+impl CalendarSpan {
+    pub const ALL: [CalendarSpan; 2] = [CalendarSpan::Week, CalendarSpan::Month];
+}
+
+impl std::fmt::Display for CalendarSpan {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CalendarSpan::Week => write!(f, "Week"),
+            CalendarSpan::Month => write!(f, "Month"),
+        }
+    }
+}
+
+/// One session on the calendar, tabled or logged.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CalendarEntry {
+    pub student_id: String,
+    pub student: String,
+    pub subject: TutorSubject,
+    pub at: DateTime<Local>,
+    /// `None` until the session is marked.
+    pub status: Option<SessionStatus>,
+    /// The student's rate in the session's month.
+    pub rate: f32,
+    pub payment_type: PaymentType,
+    /// The latest note logged up to the session.
+    pub last_note: Option<String>,
+    /// Where the timetable had the session, when it was moved for once.
+    pub moved_from: Option<DateTime<Local>>,
+}
+
+/// Sessions on `date`, earliest first: every active student's tabled
+/// sessions, plus marked ones held away from the timetable. Each carries
+/// the subject and rate of the slot it falls in.
+pub fn entries_on(students: &[Student], date: NaiveDate) -> Vec<CalendarEntry> {
+    let entry = |student: &Student, at| CalendarEntry {
+        student_id: student.id.clone(),
+        student: student.name.full_name(),
+        subject: student.subject.clone(),
+        at,
+        status: student.session_status(at),
+        rate: rate_in(student, at.year(), at.month()),
+        payment_type: student.payment_data.payment_type,
+        last_note: student.last_note(at).map(str::to_string),
+        moved_from: student.moved_from(at),
+    };
+
+    let mut entries: Vec<CalendarEntry> = students
+        .iter()
+        .flat_map(Student::enrolments)
+        .flat_map(|student| {
+            let tabled = if is_active_on(&student, date) {
+                scheduled_sessions_on(&student, date)
+            } else {
+                Vec::new()
+            };
+            let off_timetable = student
+                .sessions
+                .iter()
+                .map(|record| record.at)
+                .filter(|at| at.date_naive() == date && !tabled.contains(at))
+                .collect::<Vec<_>>();
+
+            tabled
+                .into_iter()
+                .chain(off_timetable)
+                .map(|at| entry(&student, at))
+                .collect::<Vec<_>>()
+        })
+        .collect();
+
+    entries.sort_by(|a, b| a.at.cmp(&b.at).then_with(|| a.student.cmp(&b.student)));
+    entries
+}
+
+/// An upcoming session being moved to another day or time.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RescheduleForm {
+    pub student_id: String,
+    pub student: String,
+    pub at: DateTime<Local>,
+    pub moved_from: Option<DateTime<Local>>,
+    /// As typed, in `YYYY-MM-DD` form.
+    pub date: String,
+    /// As typed, e.g. "4:30 PM".
+    pub time: String,
+    pub error: Option<String>,
+}
+
+impl RescheduleForm {
+    fn new(entry: &CalendarEntry) -> Self {
+        Self {
+            student_id: entry.student_id.clone(),
+            student: entry.student.clone(),
+            at: entry.at,
+            moved_from: entry.moved_from,
+            date: entry.at.format("%Y-%m-%d").to_string(),
+            time: entry.at.format("%-I:%M %p").to_string(),
+            error: None,
+        }
+    }
+
+    /// The new start time, which must still be ahead of `now`.
+    fn to(&self, now: DateTime<Local>) -> Result<DateTime<Local>, String> {
+        let date = NaiveDate::parse_from_str(self.date.trim(), "%Y-%m-%d")
+            .map_err(|_| "Enter the date as YYYY-MM-DD".to_string())?;
+        let time = parse_session_time(&self.time)
+            .ok_or_else(|| "Enter the time like 4:30 PM".to_string())?;
+        let to = Local
+            .from_local_datetime(&date.and_time(time))
+            .earliest()
+            .ok_or_else(|| "That time does not exist on that day".to_string())?;
+
+        if to <= now {
+            return Err("Pick a time that is still to come".to_string());
+        }
+        Ok(to)
+    }
+}
+
+pub struct CalendarState {
+    domain: Option<Rc<Domain>>,
+    span: CalendarSpan,
+    /// A day in the week or month on show.
+    anchor: NaiveDate,
+    rescheduling: Option<RescheduleForm>,
+    export_message: Option<String>,
+    first_day_of_week: Weekday,
+}
+
+impl Default for CalendarState {
+    fn default() -> Self {
+        Self {
+            domain: None,
+            span: CalendarSpan::default(),
+            anchor: Clock::default().today(),
+            rescheduling: None,
+            export_message: None,
+            first_day_of_week: Weekday::Mon,
+        }
+    }
+}
+
+impl CalendarState {
+    /// Starts on the domain's today.
+    pub fn attach_domain(&mut self, domain: Rc<Domain>) {
+        self.anchor = domain.clock.today();
+        self.domain = Some(domain);
+    }
+
+    /// Picks up a new revision of the domain, keeping the dates shown.
+    pub fn refresh(&mut self, domain: Rc<Domain>) {
+        self.domain = Some(domain);
+    }
+
+    pub fn set_first_day_of_week(&mut self, day: Weekday) {
+        self.first_day_of_week = day;
+    }
+
+    fn week_start(&self, date: NaiveDate) -> NaiveDate {
+        date.week(self.first_day_of_week).first_day()
+    }
+
+    fn now(&self) -> DateTime<Local> {
+        self.domain
+            .as_ref()
+            .map(|domain| domain.clock)
+            .unwrap_or_default()
+            .now()
+    }
+
+    fn today(&self) -> NaiveDate {
+        self.domain
+            .as_ref()
+            .map(|domain| domain.clock.today())
+            .unwrap_or(self.anchor)
+    }
+
+    /// Every day shown, in whole weeks from the first day of the week.
+    /// The month view runs from the week the month starts in to the week
+    /// it ends in.
+    pub fn days(&self) -> Vec<NaiveDate> {
+        let (first, last) = match self.span {
+            CalendarSpan::Week => {
+                let start = self.week_start(self.anchor);
+                (start, start + Duration::days(6))
+            }
+            CalendarSpan::Month => {
+                let first = self.anchor.with_day(1).unwrap_or(self.anchor);
+                let (year, month) = next_month(first.year(), first.month());
+                let last = NaiveDate::from_ymd_opt(year, month, 1)
+                    .and_then(|next| next.pred_opt())
+                    .unwrap_or(first);
+                (
+                    self.week_start(first),
+                    self.week_start(last) + Duration::days(6),
+                )
+            }
+        };
+
+        first.iter_days().take_while(|day| *day <= last).collect()
+    }
+
+    pub fn title(&self) -> String {
+        match self.span {
+            CalendarSpan::Week => {
+                let start = self.week_start(self.anchor);
+                let end = start + Duration::days(6);
+                format!("{} – {}", start.format("%-d %b"), end.format("%-d %b %Y"))
+            }
+            CalendarSpan::Month => self.anchor.format("%B %Y").to_string(),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub enum Msg {
+    SpanSelected(CalendarSpan),
+    Previous,
+    Next,
+    Today,
+    /// Saves the whole timetable as an `.ics` file for calendar apps.
+    ExportIcs,
+    /// Saves the weekly timetable as a PDF to print and put up.
+    PrintTimetable,
+    Exported(Result<PathBuf, ExportError>),
+    /// Opens the form to move an upcoming session, on a right click.
+    StartReschedule(Box<CalendarEntry>),
+    RescheduleDateChanged(String),
+    RescheduleTimeChanged(String),
+    ConfirmReschedule,
+    /// Puts a moved session back at its tabled time.
+    UndoReschedule,
+    CancelReschedule,
+    /// Applied by the app through the domain store.
+    SessionRescheduled {
+        student_id: String,
+        at: DateTime<Local>,
+        to: Option<DateTime<Local>>,
+    },
+    /// Carried out by the app, e.g. opening a student's card.
+    Effect(Effect),
+}
+
+pub fn update(state: &mut CalendarState, msg: Msg) -> Task<Msg> {
+    match msg {
+        Msg::SpanSelected(span) => state.span = span,
+        Msg::Previous => {
+            state.anchor = match state.span {
+                CalendarSpan::Week => state.anchor - Duration::weeks(1),
+                CalendarSpan::Month => {
+                    let (year, month) = previous_month(state.anchor.year(), state.anchor.month());
+                    NaiveDate::from_ymd_opt(year, month, 1).unwrap_or(state.anchor)
+                }
+            }
+        }
+        Msg::Next => {
+            state.anchor = match state.span {
+                CalendarSpan::Week => state.anchor + Duration::weeks(1),
+                CalendarSpan::Month => {
+                    let (year, month) = next_month(state.anchor.year(), state.anchor.month());
+                    NaiveDate::from_ymd_opt(year, month, 1).unwrap_or(state.anchor)
+                }
+            }
+        }
+        Msg::Today => state.anchor = state.today(),
+        Msg::ExportIcs => {
+            if let Some(domain) = &state.domain {
+                return Task::perform(
+                    write_export(
+                        ICS_FILE_NAME.to_string(),
+                        timetable_ics(domain, chrono::Utc::now()),
+                    ),
+                    Msg::Exported,
+                );
+            }
+        }
+        Msg::PrintTimetable => {
+            if let Some(domain) = &state.domain {
+                let lines = weekly_timetable(domain, state.first_day_of_week);
+                return Task::perform(
+                    write_export(TIMETABLE_FILE_NAME.to_string(), landscape_text_pdf(&lines)),
+                    Msg::Exported,
+                );
+            }
+        }
+        Msg::Exported(result) => {
+            state.export_message = Some(match result {
+                Ok(path) => format!("Exported to {}", path.display()),
+                Err(e) => e.to_string(),
+            });
+        }
+        Msg::StartReschedule(entry) => state.rescheduling = Some(RescheduleForm::new(&entry)),
+        Msg::RescheduleDateChanged(date) => {
+            if let Some(form) = &mut state.rescheduling {
+                form.date = date;
+                form.error = None;
+            }
+        }
+        Msg::RescheduleTimeChanged(time) => {
+            if let Some(form) = &mut state.rescheduling {
+                form.time = time;
+                form.error = None;
+            }
+        }
+        Msg::ConfirmReschedule => {
+            let now = state.now();
+            let Some(form) = &mut state.rescheduling else {
+                return Task::none();
+            };
+
+            match form.to(now) {
+                Ok(to) => {
+                    return Task::done(Msg::SessionRescheduled {
+                        student_id: form.student_id.clone(),
+                        at: form.at,
+                        to: Some(to),
+                    });
+                }
+                Err(e) => form.error = Some(e),
+            }
+        }
+        Msg::UndoReschedule => {
+            if let Some(form) = &state.rescheduling {
+                return Task::done(Msg::SessionRescheduled {
+                    student_id: form.student_id.clone(),
+                    at: form.at,
+                    to: None,
+                });
+            }
+        }
+        Msg::CancelReschedule | Msg::SessionRescheduled { .. } => state.rescheduling = None,
+        Msg::Effect(_) => (),
+    }
+
+    Task::none()
+}
+
+/// Tells subjects apart on the calendar.
+fn subject_color(subject: &TutorSubject, subjects: &[TutorSubject]) -> Color {
+    let index = subjects
+        .iter()
+        .position(|listed| listed == subject)
+        .unwrap_or(subjects.len());
+    SUBJECT_COLORS[index % SUBJECT_COLORS.len()]
+}
+
+pub fn view(state: &CalendarState) -> Element<'_, Msg> {
+    let Some(domain) = &state.domain else {
+        return column![page_header("Calendar")].into();
+    };
+
+    let span_button = |span: CalendarSpan| {
+        button(text(span.to_string()).size(12))
+            .style(if span == state.span {
+                button::primary
+            } else {
+                button::secondary
+            })
+            .on_press(Msg::SpanSelected(span))
+    };
+    let toolbar = row![
+        Row::new()
+            .extend(CalendarSpan::ALL.map(|span| span_button(span).into()))
+            .spacing(4),
+        button(text("Previous").size(12))
+            .style(button::text)
+            .on_press(Msg::Previous),
+        button(text("Today").size(12))
+            .style(button::text)
+            .on_press(Msg::Today),
+        button(text("Next").size(12))
+            .style(button::text)
+            .on_press(Msg::Next),
+        text(state.title()).size(16).font(Font {
+            weight: font::Weight::Semibold,
+            ..Default::default()
+        }),
+        button(text("Export .ics").size(12))
+            .style(button::secondary)
+            .on_press(Msg::ExportIcs),
+        button(text("Print timetable").size(12))
+            .style(button::secondary)
+            .on_press(Msg::PrintTimetable),
+    ]
+    .spacing(20)
+    .align_y(Center);
+
+    let legend = Row::new()
+        .extend(domain.tutor.subjects.iter().map(|subject| {
+            let color = subject_color(subject, &domain.tutor.subjects);
+            row![
+                container(text(""))
+                    .width(10)
+                    .height(10)
+                    .style(move |_: &Theme| container::Style {
+                        background: Some(Background::Color(color)),
+                        border: theme::rounded(),
+                        ..Default::default()
+                    }),
+                text(subject.to_string()).size(12),
+            ]
+            .spacing(6)
+            .align_y(Center)
+            .into()
+        }))
+        .spacing(16);
+
+    let today = state.today();
+    let days = state.days();
+    let weekdays = Row::new()
+        .extend(days.iter().take(7).map(|day| {
+            text(day.format("%a").to_string())
+                .size(12)
+                .width(Length::FillPortion(1))
+                .style(|_: &Theme| text::Style {
+                    color: Some(theme::MUTED),
+                })
+                .into()
+        }))
+        .spacing(6);
+
+    let weeks = Column::new()
+        .extend(days.chunks(7).map(|week| {
+            Row::new()
+                .extend(week.iter().map(|day| view_day(state, domain, *day, today)))
+                .spacing(6)
+                .into()
+        }))
+        .spacing(6);
+
+    let mut page = column![toolbar].spacing(16);
+    if let Some(message) = &state.export_message {
+        page = page.push(text(message.clone()).size(12));
+    }
+    if let Some(form) = &state.rescheduling {
+        page = page.push(view_reschedule_form(form));
+    }
+    let content =
+        global_content_container(page.extend([legend.into(), weekdays.into(), weeks.into()]))
+            .width(Length::Fill)
+            .height(Length::Fill);
+
+    column![page_header("Calendar"), content].into()
+}
+
+fn view_day<'a>(
+    state: &CalendarState,
+    domain: &Domain,
+    day: NaiveDate,
+    today: NaiveDate,
+) -> Element<'a, Msg> {
+    let entries = entries_on(&domain.students, day);
+    let in_view = state.span == CalendarSpan::Week || day.month() == state.anchor.month();
+    let shown = match state.span {
+        CalendarSpan::Week => entries.len(),
+        CalendarSpan::Month => MONTH_CELL_ENTRIES,
+    };
+
+    let day_number = text(day.day().to_string())
+        .size(12)
+        .font(Font {
+            weight: if day == today {
+                font::Weight::Bold
+            } else {
+                font::Weight::Normal
+            },
+            ..Default::default()
+        })
+        .style(move |_: &Theme| text::Style {
+            color: (!in_view).then_some(theme::MUTED),
+        });
+
+    let mut cell = column![day_number].spacing(4).extend(
+        entries
+            .iter()
+            .take(shown)
+            .map(|entry| view_entry(entry, state.span, domain)),
+    );
+    if entries.len() > shown {
+        cell = cell.push(
+            text(format!("+{} more", entries.len() - shown))
+                .size(11)
+                .style(|_: &Theme| text::Style {
+                    color: Some(theme::MUTED),
+                }),
+        );
+    }
+
+    let height = match state.span {
+        CalendarSpan::Week => Length::Shrink,
+        CalendarSpan::Month => Length::Fixed(110.0),
+    };
+
+    container(cell)
+        .width(Length::FillPortion(1))
+        .height(height)
+        .padding(6)
+        .style(move |theme: &Theme| {
+            let palette = theme.extended_palette();
+
+            container::Style {
+                background: Some(palette.background.weak.color.into()),
+                border: Border {
+                    color: if day == today {
+                        theme::ACCENT
+                    } else {
+                        palette.background.strong.color
+                    },
+                    width: 1.0,
+                    radius: theme::RADIUS.into(),
+                },
+                ..Default::default()
+            }
+        })
+        .into()
+}
+
+/// A coloured chip that opens the student's card, with the session's
+/// details on hover. Sessions that did not go ahead are faded.
+fn view_entry<'a>(entry: &CalendarEntry, span: CalendarSpan, domain: &Domain) -> Element<'a, Msg> {
+    let first_name = entry.student.split_whitespace().next().unwrap_or_default();
+    let label = match (span, entry.status) {
+        (CalendarSpan::Week, Some(status)) => format!(
+            "{} {}\n{}",
+            entry.at.format("%-I:%M %p"),
+            entry.student,
+            status
+        ),
+        (CalendarSpan::Week, None) => format!("{} {}", entry.at.format("%-I:%M %p"), entry.student),
+        (CalendarSpan::Month, _) => format!("{} {}", entry.at.format("%-I:%M"), first_name),
+    };
+    let faded = entry
+        .status
+        .is_some_and(|status| status != SessionStatus::Completed);
+    let color = subject_color(&entry.subject, &domain.tutor.subjects);
+    let open = Route::Students(Some(entry.student_id.clone()));
+
+    let chip = button(text(label).size(11))
+        .width(Length::Fill)
+        .padding([2, 6])
+        .style(move |_: &Theme, status: button::Status| button::Style {
+            background: Some(Background::Color(Color {
+                a: match (faded, status) {
+                    (true, _) => 0.35,
+                    (false, button::Status::Hovered) => 1.0,
+                    (false, _) => 0.85,
+                },
+                ..color
+            })),
+            text_color: Color::WHITE,
+            border: theme::rounded(),
+            ..Default::default()
+        })
+        .on_press(Msg::Effect(Effect::Navigate(open)));
+    let movable = entry.status.is_none() && entry.at > domain.clock.now();
+    let chip: Element<'a, Msg> = if movable {
+        mouse_area(chip)
+            .on_right_press(Msg::StartReschedule(Box::new(entry.clone())))
+            .into()
+    } else {
+        chip.into()
+    };
+
+    popover(
+        chip,
+        view_entry_details(entry, &domain.money_format, movable),
+    )
+}
+
+/// Moves one session without touching the weekly timetable.
+fn view_reschedule_form(form: &RescheduleForm) -> Element<'_, Msg> {
+    let title = text(format!(
+        "Move {}'s session on {}",
+        form.student,
+        form.at.format("%a %-d %b, %-I:%M %p")
+    ))
+    .size(14)
+    .font(Font {
+        weight: font::Weight::Semibold,
+        ..Default::default()
+    });
+
+    let controls = row![
+        text_input("YYYY-MM-DD", &form.date)
+            .on_input(Msg::RescheduleDateChanged)
+            .on_submit(Msg::ConfirmReschedule)
+            .size(12)
+            .width(Length::Fixed(120.0)),
+        text_input("e.g. 4:30 PM", &form.time)
+            .on_input(Msg::RescheduleTimeChanged)
+            .on_submit(Msg::ConfirmReschedule)
+            .size(12)
+            .width(Length::Fixed(100.0)),
+        button(text("Move").size(12)).on_press(Msg::ConfirmReschedule),
+        button(text("Cancel").size(12))
+            .style(button::text)
+            .on_press(Msg::CancelReschedule),
+    ]
+    .push(form.moved_from.map(|from| {
+        button(text(format!("Back to {}", from.format("%a %-d %b, %-I:%M %p"))).size(12))
+            .style(button::text)
+            .on_press(Msg::UndoReschedule)
+    }))
+    .spacing(10)
+    .align_y(Center);
+
+    let mut section = column![
+        title,
+        text("The weekly timetable stays as it is.")
+            .size(12)
+            .style(|_: &Theme| text::Style {
+                color: Some(theme::MUTED),
+            }),
+        controls
+    ]
+    .spacing(8);
+    if let Some(error) = &form.error {
+        section = section.push(text(error.clone()).size(12).style(|_: &Theme| text::Style {
+            color: Some(theme::DANGER),
+        }));
+    }
+
+    container(section)
+        .padding(12)
+        .style(container::rounded_box)
+        .into()
+}
+
+fn view_entry_details<'a>(
+    entry: &CalendarEntry,
+    money: &MoneyFormat,
+    movable: bool,
+) -> Element<'a, Msg> {
+    let per = match entry.payment_type {
+        PaymentType::PerSession => "session",
+        PaymentType::Monthly => "month",
+    };
+    let when = match entry.status {
+        Some(status) => format!("{}, {}", entry.at.format("%a %-d %b, %-I:%M %p"), status),
+        None => entry.at.format("%a %-d %b, %-I:%M %p").to_string(),
+    };
+    let moved = entry.moved_from.map(|from| {
+        text(format!(
+            "Moved from {}",
+            from.format("%a %-d %b, %-I:%M %p")
+        ))
+        .size(12)
+    });
+    let note = entry
+        .last_note
+        .as_deref()
+        .map(|note| excerpt(note, NOTE_EXCERPT_CHARS))
+        .unwrap_or_else(|| "No notes yet".to_string());
+
+    column![
+        text(entry.student.clone()).size(14).font(Font {
+            weight: font::Weight::Bold,
+            ..Default::default()
+        }),
+        text(entry.subject.to_string()).size(12),
+        text(format!("{} per {}", money.format(entry.rate), per)).size(12),
+        text(when).size(12).style(|_: &Theme| text::Style {
+            color: Some(theme::MUTED),
+        }),
+    ]
+    .push(moved)
+    .push(text(note).size(12))
+    .push(movable.then(|| {
+        text("Right-click to move this session")
+            .size(11)
+            .style(|_: &Theme| text::Style {
+                color: Some(theme::MUTED),
+            })
+    }))
+    .spacing(4)
+    .into()
+}
+
+/// Every current student's weekly slots as a grid for printing, the days
+/// across from `first_day` and start times down, with one student per
+/// line in a cell.
+pub fn weekly_timetable(domain: &Domain, first_day: Weekday) -> Vec<String> {
+    let today = domain.clock.today();
+    let week: Vec<NaiveDate> = today
+        .week(first_day)
+        .first_day()
+        .iter_days()
+        .take(7)
+        .collect();
+    let slots: Vec<(Weekday, NaiveTime, String)> = domain
+        .students
+        .iter()
+        .filter(|student| is_active_on(student, today))
+        .flat_map(|student| {
+            student.timetable().filter_map(|slot| {
+                let time = parse_session_time(&slot.time)?;
+                Some((slot.day, time, student.name.full_name()))
+            })
+        })
+        .collect();
+    let mut times: Vec<NaiveTime> = slots.iter().map(|(_, time, _)| *time).collect();
+    times.sort();
+    times.dedup();
+
+    let width = (LANDSCAPE_LINE_WIDTH - TIMETABLE_TIME_WIDTH) / week.len() - 1;
+    let row = |time: String, cells: Vec<String>| {
+        let cells: String = cells
+            .iter()
+            .map(|cell| format!("|{:<width$}", cell.chars().take(width).collect::<String>()))
+            .collect();
+        format!("{:<TIMETABLE_TIME_WIDTH$}{}", time, cells)
+            .trim_end()
+            .to_string()
+    };
+    let rule = "-".repeat(TIMETABLE_TIME_WIDTH) + &format!("+{}", "-".repeat(width)).repeat(7);
+
+    let mut lines = vec![
+        format!("Weekly timetable - {}", domain.tutor.name.full_name()),
+        format!("As of {}", today.format("%-d %B %Y")),
+        String::new(),
+        row(
+            String::new(),
+            week.iter()
+                .map(|day| day.format("%A").to_string())
+                .collect(),
+        ),
+        rule.clone(),
+    ];
+    for time in times {
+        let cells: Vec<Vec<&str>> = week
+            .iter()
+            .map(|day| {
+                slots
+                    .iter()
+                    .filter(|(slot_day, slot_time, _)| {
+                        *slot_day == day.weekday() && *slot_time == time
+                    })
+                    .map(|(_, _, name)| name.as_str())
+                    .collect()
+            })
+            .collect();
+        let height = cells.iter().map(Vec::len).max().unwrap_or(1);
+
+        for line in 0..height {
+            let label = if line == 0 {
+                time.format("%-I:%M %p").to_string()
+            } else {
+                String::new()
+            };
+            lines.push(row(
+                label,
+                cells
+                    .iter()
+                    .map(|names| names.get(line).copied().unwrap_or_default().to_string())
+                    .collect(),
+            ));
+        }
+        lines.push(rule.clone());
+    }
+
+    lines
+}
+
+/// The first `max` characters of `note`, cut at a word where possible.
+fn excerpt(note: &str, max: usize) -> String {
+    if note.chars().count() <= max {
+        return note.to_string();
+    }
+
+    let cut: String = note.chars().take(max).collect();
+    let cut = cut.rsplit_once(' ').map_or(cut.as_str(), |(head, _)| head);
+    format!("{}…", cut.trim_end())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::fixtures::{date, sample_domain};
+    use chrono::TimeZone;
+
+    fn labels(entries: Vec<CalendarEntry>) -> Vec<(String, String, Option<SessionStatus>)> {
+        entries
+            .into_iter()
+            .map(|entry| {
+                (
+                    entry.at.format("%H:%M").to_string(),
+                    entry.student,
+                    entry.status,
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn days_list_tabled_and_logged_sessions_in_time_order() {
+        let mut domain = sample_domain();
+        let peter = &mut domain.students[1];
+        peter.sessions.push(crate::domain::SessionRecord {
+            at: Local.with_ymd_and_hms(2026, 2, 10, 9, 0, 0).unwrap(),
+            status: SessionStatus::Rescheduled,
+            notes: String::new(),
+            links: Vec::new(),
+        });
+
+        domain.students[0].sessions[6].notes = "Vectors  ".to_string();
+
+        let entries = entries_on(&domain.students, date(2026, 2, 10));
+        assert_eq!(entries[1].rate, 150.0);
+        assert_eq!(entries[1].last_note.as_deref(), Some("Vectors"));
+        assert_eq!(excerpt("Vectors and matrices", 12), "Vectors and…");
+        assert_eq!(excerpt("Vectors", 12), "Vectors");
+
+        assert_eq!(
+            labels(entries_on(&domain.students, date(2026, 2, 10))),
+            vec![
+                (
+                    "09:00".to_string(),
+                    "Peter Parker".to_string(),
+                    Some(SessionStatus::Rescheduled)
+                ),
+                (
+                    "17:00".to_string(),
+                    "Mary Jane".to_string(),
+                    Some(SessionStatus::Completed)
+                ),
+                ("17:30".to_string(), "Mary Jane".to_string(), None),
+            ]
+        );
+
+        domain.students[0].tution_end_date =
+            Some(Local.with_ymd_and_hms(2026, 2, 1, 0, 0, 0).unwrap());
+        assert_eq!(
+            labels(entries_on(&domain.students, date(2026, 2, 12))),
+            Vec::new()
+        );
+    }
+
+    #[test]
+    fn weeks_and_months_step_and_fill_whole_weeks() {
+        let mut state = CalendarState::default();
+        state.attach_domain(Rc::new(sample_domain()));
+
+        let week = state.days();
+        assert_eq!((week[0], week[6]), (date(2026, 2, 9), date(2026, 2, 15)));
+        assert_eq!(state.title(), "9 Feb – 15 Feb 2026");
+
+        let _ = update(&mut state, Msg::SpanSelected(CalendarSpan::Month));
+        let _ = update(&mut state, Msg::Next);
+        let month = state.days();
+        assert_eq!(state.title(), "March 2026");
+        assert_eq!(
+            (month[0], *month.last().unwrap(), month.len()),
+            (date(2026, 2, 23), date(2026, 4, 5), 42)
+        );
+
+        let _ = update(&mut state, Msg::Today);
+        let _ = update(&mut state, Msg::SpanSelected(CalendarSpan::Week));
+        let _ = update(&mut state, Msg::Previous);
+        assert_eq!(state.days()[0], date(2026, 2, 2));
+    }
+
+    #[test]
+    fn weeks_start_on_the_chosen_day() {
+        let mut state = CalendarState::default();
+        state.attach_domain(Rc::new(sample_domain()));
+        state.set_first_day_of_week(Weekday::Sun);
+
+        let week = state.days();
+        assert_eq!((week[0], week[6]), (date(2026, 2, 8), date(2026, 2, 14)));
+        assert_eq!(state.title(), "8 Feb – 14 Feb 2026");
+    }
+
+    #[test]
+    fn printed_timetable_lists_students_under_their_day_and_time() {
+        let domain = sample_domain();
+        let lines = weekly_timetable(&domain, Weekday::Sun);
+
+        assert!(lines[3].starts_with("        |Sunday         |Monday         |Tuesday"));
+        assert!(lines.iter().all(|line| line.len() <= LANDSCAPE_LINE_WIDTH));
+        let slot = &domain.students[0].tabled_sessions[0];
+        let time = parse_session_time(&slot.time).unwrap();
+        let row = lines
+            .iter()
+            .find(|line| line.starts_with(&time.format("%-I:%M %p").to_string()))
+            .unwrap();
+        let column = slot.day.days_since(Weekday::Sun) as usize;
+        let cell = row.split('|').nth(column + 1).unwrap();
+        assert_eq!(cell.trim(), "Mary Jane");
+    }
+}
@@ -0,0 +1,386 @@
+//! Two to four students side by side, e.g. for talking a parent through
+//! how their children are getting on.
+
+use chrono::{Datelike, NaiveDate};
+use iced::widget::canvas::{self, Path, Stroke, Text};
+use iced::widget::{Canvas, Column, button, checkbox, column, row, scrollable, text};
+use iced::{Color, Element, Length, Point, Rectangle, Renderer, Theme, mouse};
+use std::collections::HashMap;
+use std::rc::Rc;
+
+use crate::dashboard::{CHART_PADDING, STACK_COLORS, draw_axes, draw_legend, label_stride};
+use crate::domain::{
+    Domain, MAX_ENGAGEMENT, Student, StudentMonth, month_label, monthly_engagement, previous_month,
+    student_months,
+};
+use crate::routes::Route;
+use crate::shell::Effect;
+use crate::theme;
+use crate::ui_components::{chart_panel, global_content_container, page_header};
+
+pub const MAX_COMPARED: usize = STACK_COLORS.len();
+/// How far back the charts go, counting this month.
+const COMPARED_MONTHS: usize = 12;
+const CHART_HEIGHT: f32 = 220.0;
+
+#[derive(Default)]
+pub struct CompareState {
+    domain: Option<Rc<Domain>>,
+    /// Student ids in the order they were picked, which sets their colours.
+    selected: Vec<String>,
+}
+
+impl CompareState {
+    pub fn attach_domain(&mut self, domain: Rc<Domain>) {
+        self.selected
+            .retain(|id| domain.students.iter().any(|student| &student.id == id));
+        self.domain = Some(domain);
+    }
+}
+
+#[derive(Clone, Debug)]
+pub enum Msg {
+    StudentToggled(String, bool),
+    /// Carried out by the app, e.g. going back to the Student Manager.
+    Effect(Effect),
+}
+
+pub fn update(state: &mut CompareState, msg: Msg) {
+    match msg {
+        Msg::StudentToggled(student_id, true) => {
+            if state.selected.len() < MAX_COMPARED && !state.selected.contains(&student_id) {
+                state.selected.push(student_id);
+            }
+        }
+        Msg::StudentToggled(student_id, false) => {
+            state.selected.retain(|id| *id != student_id);
+        }
+        Msg::Effect(_) => {}
+    }
+}
+
+/// Month by month figures for each compared student, over the same
+/// months. A month is `None` for a student not enrolled then, or with
+/// nothing to show for it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Comparison {
+    /// Oldest first.
+    pub months: Vec<String>,
+    pub students: Vec<ComparedStudent>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ComparedStudent {
+    pub name: String,
+    /// Share of tabled sessions held.
+    pub attendance: Vec<Option<f32>>,
+    pub income: Vec<Option<f32>>,
+    /// Average engagement of the rated sessions.
+    pub engagement: Vec<Option<f32>>,
+}
+
+/// Covers the last twelve months, or fewer when every student started
+/// more recently.
+pub fn comparison(domain: &Domain, student_ids: &[String], today: NaiveDate) -> Comparison {
+    let students: Vec<&Student> = student_ids
+        .iter()
+        .filter_map(|id| domain.students.iter().find(|student| &student.id == id))
+        .collect();
+
+    let this_month = (today.year(), today.month());
+    let earliest = students
+        .iter()
+        .map(|student| {
+            let start = student.tution_start_date.date_naive();
+            (start.year(), start.month())
+        })
+        .min()
+        .unwrap_or(this_month);
+
+    let mut months = vec![this_month];
+    while months.len() < COMPARED_MONTHS && months[0] > earliest {
+        let (year, month) = months[0];
+        months.insert(0, previous_month(year, month));
+    }
+
+    Comparison {
+        months: months
+            .iter()
+            .map(|(year, month)| month_label(*year, *month))
+            .collect(),
+        students: students
+            .iter()
+            .map(|student| {
+                let enrolled: HashMap<(i32, u32), StudentMonth> = student_months(student, today)
+                    .into_iter()
+                    .map(|month| ((month.year, month.month), month))
+                    .collect();
+                let each = |figure: &dyn Fn(&StudentMonth) -> Option<f32>| {
+                    months
+                        .iter()
+                        .map(|key| enrolled.get(key).and_then(figure))
+                        .collect()
+                };
+
+                ComparedStudent {
+                    name: student.name.full_name(),
+                    attendance: each(&|month| month.attendance_rate),
+                    income: each(&|month| Some(month.earned)),
+                    engagement: each(&|month| monthly_engagement(student, month.month, month.year)),
+                }
+            })
+            .collect(),
+    }
+}
+
+pub fn view(state: &CompareState) -> Element<'_, Msg> {
+    let back = button(text("← Student Manager").size(12))
+        .style(button::text)
+        .on_press(Msg::Effect(Effect::Navigate(Route::Students(None))));
+    let header = column![page_header("Compare Students")];
+
+    let Some(domain) = state.domain.as_deref() else {
+        return header.into();
+    };
+
+    let mut students: Vec<&Student> = domain.students.iter().collect();
+    students.sort_by_key(|student| student.name.full_name());
+    let is_full = state.selected.len() >= MAX_COMPARED;
+    let picker = Column::new()
+        .push(text(format!("Pick two to {} students", MAX_COMPARED)).size(12))
+        .extend(students.into_iter().map(|student| {
+            let is_selected = state.selected.contains(&student.id);
+            let id = student.id.clone();
+            checkbox(is_selected)
+                .label(student.name.full_name())
+                .text_size(12)
+                .on_toggle_maybe(
+                    (is_selected || !is_full)
+                        .then_some(move |checked| Msg::StudentToggled(id.clone(), checked)),
+                )
+                .into()
+        }))
+        .spacing(8)
+        .width(Length::Fixed(220.0));
+
+    let charts: Element<'_, Msg> = if state.selected.len() < 2 {
+        text("Choose at least two students to see them side by side.")
+            .size(14)
+            .color(theme::MUTED)
+            .into()
+    } else {
+        let comparison = comparison(domain, &state.selected, domain.clock.today());
+        let money = domain.money_format;
+        column![
+            overlay_panel(
+                "Attendance",
+                &comparison,
+                |student| &student.attendance,
+                1.0,
+                |rate| format!("{:.0}%", rate * 100.0),
+            ),
+            overlay_panel(
+                "Income",
+                &comparison,
+                |student| &student.income,
+                0.0,
+                move |amount| money.format(amount),
+            ),
+            overlay_panel(
+                "Engagement",
+                &comparison,
+                |student| &student.engagement,
+                MAX_ENGAGEMENT as f32,
+                |engagement| format!("{:.1}", engagement),
+            ),
+        ]
+        .spacing(20)
+        .into()
+    };
+
+    let content = global_content_container(scrollable(
+        column![back, row![picker, charts].spacing(40)].spacing(24),
+    ))
+    .width(Length::Fill)
+    .height(Length::Fill);
+
+    header.push(content).into()
+}
+
+/// One figure for every compared student as lines on the same axes. The
+/// scale runs to `max`, or further if any figure is higher.
+fn overlay_panel<'a>(
+    title: &'a str,
+    comparison: &Comparison,
+    figures: impl Fn(&ComparedStudent) -> &Vec<Option<f32>>,
+    max: f32,
+    format: impl Fn(f32) -> String + 'static,
+) -> Element<'a, Msg> {
+    let chart = OverlayChart {
+        months: comparison.months.clone(),
+        series: comparison
+            .students
+            .iter()
+            .zip(STACK_COLORS)
+            .map(|(student, color)| (student.name.clone(), color, figures(student).clone()))
+            .collect(),
+        max,
+        format: Box::new(format),
+    };
+
+    chart_panel(
+        title,
+        None,
+        Canvas::new(chart)
+            .width(Length::Fill)
+            .height(Length::Fixed(CHART_HEIGHT)),
+    )
+    .into()
+}
+
+struct OverlayChart {
+    months: Vec<String>,
+    /// Each student's name, colour and figures.
+    series: Vec<(String, Color, Vec<Option<f32>>)>,
+    max: f32,
+    format: Box<dyn Fn(f32) -> String>,
+}
+
+impl canvas::Program<Msg> for OverlayChart {
+    type State = ();
+
+    fn draw(
+        &self,
+        _state: &Self::State,
+        renderer: &Renderer,
+        _theme: &Theme,
+        bounds: Rectangle,
+        _cursor: mouse::Cursor,
+    ) -> Vec<canvas::Geometry> {
+        let mut frame = canvas::Frame::new(renderer, bounds.size());
+        let padding = CHART_PADDING;
+        let chart_width = frame.width() - padding * 2.0;
+        let chart_height = frame.height() - padding * 2.5;
+
+        let peak = self
+            .series
+            .iter()
+            .flat_map(|(_, _, figures)| figures.iter().flatten())
+            .fold(self.max, |peak, figure| peak.max(*figure));
+        let scale = chart_height / (peak.max(f32::EPSILON) * 1.1);
+        let group_width = chart_width / self.months.len().max(1) as f32;
+        let point_at = |i: usize, figure: f32| {
+            Point::new(
+                padding + i as f32 * group_width + group_width / 2.0,
+                padding + chart_height - figure * scale,
+            )
+        };
+
+        draw_axes(&mut frame, padding, chart_width, chart_height);
+        draw_legend(
+            &mut frame,
+            padding,
+            self.series
+                .iter()
+                .map(|(name, color, _)| (name.as_str(), *color)),
+        );
+        frame.fill_text(Text {
+            content: format!("Peak {}", (self.format)(peak)),
+            position: Point::new(frame.width() - padding, 0.0),
+            color: theme::MUTED,
+            size: 10.0.into(),
+            align_x: iced::advanced::text::Alignment::Right,
+            ..Default::default()
+        });
+
+        for (_, color, figures) in &self.series {
+            let points: Vec<Option<Point>> = figures
+                .iter()
+                .enumerate()
+                .map(|(i, figure)| figure.map(|figure| point_at(i, figure)))
+                .collect();
+
+            // Lines break over months without a figure.
+            for window in points.windows(2) {
+                if let [Some(from), Some(to)] = window {
+                    frame.stroke(
+                        &Path::line(*from, *to),
+                        Stroke::default().with_color(*color).with_width(2.0),
+                    );
+                }
+            }
+            for point in points.iter().flatten() {
+                frame.fill(&Path::circle(*point, 3.5), *color);
+            }
+        }
+
+        let label_stride = label_stride(group_width);
+        for (i, month) in self.months.iter().enumerate() {
+            if !i.is_multiple_of(label_stride) {
+                continue;
+            }
+            frame.fill_text(Text {
+                content: month.clone(),
+                position: Point::new(
+                    padding + i as f32 * group_width + group_width / 2.0,
+                    padding + chart_height + 10.0,
+                ),
+                color: Color::BLACK,
+                size: 11.0.into(),
+                align_x: iced::advanced::text::Alignment::Center,
+                ..Default::default()
+            });
+        }
+
+        vec![frame.into_geometry()]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::SessionRating;
+    use crate::domain::fixtures::{date, sample_domain};
+    use chrono::{Local, TimeZone};
+
+    #[test]
+    fn students_line_up_month_by_month_from_the_earliest_start() {
+        let mut domain = sample_domain();
+        domain.students[1].tution_start_date = Local.with_ymd_and_hms(2026, 1, 1, 0, 0, 0).unwrap();
+        domain.students[0].session_ratings.push(SessionRating {
+            at: Local.with_ymd_and_hms(2026, 2, 3, 17, 30, 0).unwrap(),
+            engagement: 4,
+        });
+        let ids: Vec<String> = domain.students.iter().map(|s| s.id.clone()).collect();
+
+        let comparison = comparison(&domain, &ids, date(2026, 2, 12));
+
+        assert_eq!(
+            comparison.months,
+            [
+                "Sep '25", "Oct '25", "Nov '25", "Dec '25", "Jan '26", "Feb '26"
+            ]
+        );
+        let [mary, peter] = &comparison.students[..] else {
+            panic!("expected two students");
+        };
+        assert_eq!(mary.income[4], Some(750.0));
+        assert_eq!(mary.engagement[5], Some(4.0));
+        assert_eq!(mary.engagement[4], None);
+        assert_eq!(peter.attendance[..4], [None, None, None, None]);
+        assert!(peter.income[4].is_some());
+    }
+
+    #[test]
+    fn no_more_than_four_students_can_be_picked() {
+        let mut state = CompareState::default();
+        for id in ["a", "b", "c", "d", "e"] {
+            update(&mut state, Msg::StudentToggled(id.to_string(), true));
+        }
+        assert_eq!(state.selected, ["a", "b", "c", "d"]);
+
+        update(&mut state, Msg::StudentToggled("b".to_string(), false));
+        update(&mut state, Msg::StudentToggled("e".to_string(), true));
+        assert_eq!(state.selected, ["a", "c", "d", "e"]);
+    }
+}
@@ -0,0 +1,771 @@
+use chrono::{DateTime, Local, NaiveDate, SecondsFormat, Weekday};
+use directories::ProjectDirs;
+use iced::Theme;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+use toml_edit::{DocumentMut, Item};
+
+use crate::data_dirs::portable_root;
+use crate::money::Currency;
+use crate::notifications::{DEFAULT_NOTICE_MINUTES, MAX_NOTICE_MINUTES, StudentNotices};
+use crate::reminders::DEFAULT_LEAD_HOURS;
+use crate::students::CardDensity;
+
+pub const CONFIG_FILE: &str = "config.toml";
+const STUDENT_NOTICES: &str = "student_notices";
+const REMINDER_OPT_INS: &str = "reminder_opt_ins";
+const REMINDER_MUTES: &str = "reminder_mutes";
+const SNOOZED_REMINDERS: &str = "snoozed_reminders";
+const SNOOZES_NOT_TABLES: &str = "snoozed_reminders should be [[snoozed_reminders]] tables";
+/// Longest reminder lead time the config accepts, a week.
+pub const MAX_LEAD_HOURS: i64 = 7 * 24;
+/// How each card density is written in the file.
+const CARD_DENSITIES: [(&str, CardDensity); 3] = [
+    ("compact", CardDensity::Compact),
+    ("comfortable", CardDensity::Comfortable),
+    ("detailed", CardDensity::Detailed),
+];
+
+#[derive(Debug, Clone)]
+pub enum ConfigError {
+    ReadFailed(String),
+    Invalid(String),
+    WriteFailed(String),
+}
+
+impl std::fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ConfigError::ReadFailed(reason) => {
+                write!(f, "Could not read {}: {}", CONFIG_FILE, reason)
+            }
+            ConfigError::Invalid(reason) => write!(f, "{} is not valid: {}", CONFIG_FILE, reason),
+            ConfigError::WriteFailed(reason) => {
+                write!(f, "Could not save {}: {}", CONFIG_FILE, reason)
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ThemeChoice {
+    /// Follows the system's light or dark setting.
+    #[default]
+    System,
+    Light,
+    Dark,
+}
+
+impl ThemeChoice {
+    pub fn theme(&self) -> Option<Theme> {
+        match self {
+            ThemeChoice::System => None,
+            ThemeChoice::Light => Some(Theme::Light),
+            ThemeChoice::Dark => Some(Theme::Dark),
+        }
+    }
+}
+
+/// Preferences kept in `config.toml` for those who would rather set them
+/// in a file. Every key may be left out:
+///
+/// ```toml
+/// theme = "dark"              # "system", "light" or "dark"
+/// currency = "GHS"
+/// locale = "en-GH"
+/// reminder_lead_hours = 24
+/// notice_minutes = 15       # desktop notifications before a session
+/// data_path = "/home/ama/Tutoring"
+/// calendar_feed = "/home/ama/Dropbox/timetable.ics"
+/// first_day_of_week = "sunday"
+/// card_density = "compact"  # "compact", "comfortable" or "detailed"
+/// reminder_opt_ins = ["4f1c…"] # student ids
+///
+/// [student_notices]         # by student id, minutes or "off"
+/// "4f1c…" = 30
+///
+/// [reminder_mutes]          # by student id, no reminders up to the day
+/// "4f1c…" = 2026-12-31
+///
+/// [[snoozed_reminders]]
+/// student = "4f1c…"
+/// session = 2026-10-16T17:30:00+00:00
+/// until = 2026-10-16T11:30:00+00:00
+/// ```
+///
+/// The file is watched while the app runs. `data_path` only takes effect
+/// on the next start, and `locale` is read but not yet applied. With
+/// `calendar_feed` the timetable is written there as an `.ics` file after
+/// every change, for calendar apps to subscribe to.
+#[derive(Debug, Clone, PartialEq)]
+pub struct UserConfig {
+    pub theme: ThemeChoice,
+    /// Replaces the currency shown with every amount.
+    pub currency: Option<Currency>,
+    pub locale: Option<String>,
+    pub reminder_lead_hours: i64,
+    pub notice_minutes: i64,
+    /// Keeps the data here instead of the usual folder.
+    pub data_path: Option<PathBuf>,
+    pub calendar_feed: Option<PathBuf>,
+    /// Where weeks start in the calendar and the printed timetable.
+    pub first_day_of_week: Weekday,
+    /// How much the Student Manager's cards show, as last picked there.
+    pub card_density: CardDensity,
+    /// Students whose notifications differ from `notice_minutes`.
+    pub student_notices: HashMap<String, StudentNotices>,
+    /// Students whose session reminders are saved to the outbox.
+    pub reminder_opt_ins: HashSet<String>,
+    pub reminder_mutes: HashMap<String, NaiveDate>,
+    /// Held-back reminders by student id and session, with when they may go.
+    pub snoozed_reminders: HashMap<(String, DateTime<Local>), DateTime<Local>>,
+}
+
+impl Default for UserConfig {
+    fn default() -> Self {
+        Self {
+            theme: ThemeChoice::default(),
+            currency: None,
+            locale: None,
+            reminder_lead_hours: DEFAULT_LEAD_HOURS,
+            notice_minutes: DEFAULT_NOTICE_MINUTES,
+            data_path: None,
+            calendar_feed: None,
+            first_day_of_week: Weekday::Mon,
+            card_density: CardDensity::default(),
+            student_notices: HashMap::new(),
+            reminder_opt_ins: HashSet::new(),
+            reminder_mutes: HashMap::new(),
+            snoozed_reminders: HashMap::new(),
+        }
+    }
+}
+
+impl UserConfig {
+    pub fn parse(toml: &str) -> Result<Self, ConfigError> {
+        let document = parse_document(toml)?;
+        let invalid = |reason: &str| ConfigError::Invalid(reason.to_string());
+
+        let theme = match text(&document, "theme")? {
+            None | Some("system") => ThemeChoice::System,
+            Some("light") => ThemeChoice::Light,
+            Some("dark") => ThemeChoice::Dark,
+            Some(_) => return Err(invalid("theme should be \"system\", \"light\" or \"dark\"")),
+        };
+        let currency = text(&document, "currency")?
+            .map(|code| {
+                Currency::parse(code)
+                    .ok_or_else(|| invalid("currency should be a three-letter code like \"GHS\""))
+            })
+            .transpose()?;
+        let reminder_lead_hours = whole_number(&document, "reminder_lead_hours", MAX_LEAD_HOURS)?
+            .unwrap_or(DEFAULT_LEAD_HOURS);
+        let notice_minutes = whole_number(&document, "notice_minutes", MAX_NOTICE_MINUTES)?
+            .unwrap_or(DEFAULT_NOTICE_MINUTES);
+        let first_day_of_week = text(&document, "first_day_of_week")?
+            .map(|day| {
+                day.parse::<Weekday>()
+                    .map_err(|_| invalid("first_day_of_week should be a day like \"sunday\""))
+            })
+            .transpose()?
+            .unwrap_or(Weekday::Mon);
+        let card_density = text(&document, "card_density")?
+            .map(|name| {
+                CARD_DENSITIES
+                    .into_iter()
+                    .find(|(known, _)| *known == name)
+                    .map(|(_, density)| density)
+                    .ok_or_else(|| {
+                        invalid(
+                            "card_density should be \"compact\", \"comfortable\" or \"detailed\"",
+                        )
+                    })
+            })
+            .transpose()?
+            .unwrap_or_default();
+        let student_notices = match document.get(STUDENT_NOTICES) {
+            None => HashMap::new(),
+            Some(item) => item
+                .as_table_like()
+                .ok_or_else(|| invalid("student_notices should be a table of student ids"))?
+                .iter()
+                .map(|(student_id, item)| {
+                    let notices = match (item.as_integer(), item.as_str()) {
+                        (Some(minutes), _) if (1..=MAX_NOTICE_MINUTES).contains(&minutes) => {
+                            StudentNotices::Minutes(minutes)
+                        }
+                        (_, Some("off")) => StudentNotices::Off,
+                        _ => {
+                            return Err(ConfigError::Invalid(format!(
+                                "student_notices for {} should be \"off\" or minutes from 1 to {}",
+                                student_id, MAX_NOTICE_MINUTES
+                            )));
+                        }
+                    };
+                    Ok((student_id.to_string(), notices))
+                })
+                .collect::<Result<_, _>>()?,
+        };
+        let reminder_opt_ins = match document.get(REMINDER_OPT_INS) {
+            None => HashSet::new(),
+            Some(item) => item
+                .as_array()
+                .and_then(|ids| {
+                    ids.iter()
+                        .map(|id| id.as_str().map(str::to_string))
+                        .collect()
+                })
+                .ok_or_else(|| invalid("reminder_opt_ins should be a list of student ids"))?,
+        };
+        let reminder_mutes = match document.get(REMINDER_MUTES) {
+            None => HashMap::new(),
+            Some(item) => item
+                .as_table_like()
+                .ok_or_else(|| invalid("reminder_mutes should be a table of student ids"))?
+                .iter()
+                .map(|(student_id, item)| {
+                    let muted_until = item
+                        .as_datetime()
+                        .and_then(|datetime| datetime.to_string().parse::<NaiveDate>().ok())
+                        .ok_or_else(|| {
+                            ConfigError::Invalid(format!(
+                                "reminder_mutes for {} should be a day like 2026-12-31",
+                                student_id
+                            ))
+                        })?;
+                    Ok((student_id.to_string(), muted_until))
+                })
+                .collect::<Result<_, _>>()?,
+        };
+        let snoozed_reminders = match document.get(SNOOZED_REMINDERS) {
+            None => HashMap::new(),
+            Some(item) => item
+                .as_array_of_tables()
+                .ok_or_else(|| invalid(SNOOZES_NOT_TABLES))?
+                .iter()
+                .map(|snoozed| {
+                    let student_id = snoozed.get("student").and_then(Item::as_str);
+                    let session_at = date_time(snoozed.get("session"));
+                    let until = date_time(snoozed.get("until"));
+                    match (student_id, session_at, until) {
+                        (Some(student_id), Some(session_at), Some(until)) => {
+                            Ok(((student_id.to_string(), session_at), until))
+                        }
+                        _ => Err(invalid(
+                            "each of snoozed_reminders needs a student, session and until",
+                        )),
+                    }
+                })
+                .collect::<Result<_, _>>()?,
+        };
+
+        Ok(Self {
+            theme,
+            currency,
+            locale: text(&document, "locale")?.map(str::to_string),
+            reminder_lead_hours,
+            notice_minutes,
+            data_path: text(&document, "data_path")?.map(PathBuf::from),
+            calendar_feed: text(&document, "calendar_feed")?.map(PathBuf::from),
+            first_day_of_week,
+            card_density,
+            student_notices,
+            reminder_opt_ins,
+            reminder_mutes,
+            snoozed_reminders,
+        })
+    }
+}
+
+fn text<'a>(document: &'a DocumentMut, key: &str) -> Result<Option<&'a str>, ConfigError> {
+    document
+        .get(key)
+        .map(|item: &Item| {
+            item.as_str()
+                .ok_or_else(|| ConfigError::Invalid(format!("{} should be text in quotes", key)))
+        })
+        .transpose()
+}
+
+/// A date and time with its offset, e.g. `2026-10-16T17:30:00+00:00`.
+fn date_time(item: Option<&Item>) -> Option<DateTime<Local>> {
+    let datetime = item?.as_datetime()?;
+    DateTime::parse_from_rfc3339(&datetime.to_string())
+        .ok()
+        .map(|at| at.with_timezone(&Local))
+}
+
+fn toml_date_time(at: DateTime<Local>) -> toml_edit::Value {
+    let datetime: toml_edit::Datetime = at
+        .to_rfc3339_opts(SecondsFormat::Secs, false)
+        .parse()
+        .expect("RFC 3339 times are TOML datetimes");
+    datetime.into()
+}
+
+/// A whole number from 1 to `max`, if the key is there.
+fn whole_number(document: &DocumentMut, key: &str, max: i64) -> Result<Option<i64>, ConfigError> {
+    document
+        .get(key)
+        .map(|item| {
+            item.as_integer()
+                .filter(|value| (1..=max).contains(value))
+                .ok_or_else(|| {
+                    ConfigError::Invalid(format!(
+                        "{} should be a whole number from 1 to {}",
+                        key, max
+                    ))
+                })
+        })
+        .transpose()
+}
+
+/// The data folder in portable mode, otherwise the platform's usual
+/// config folder, e.g. `~/.config` on Linux.
+pub fn config_path() -> Option<PathBuf> {
+    if let Some(root) = portable_root() {
+        return Some(root.join(CONFIG_FILE));
+    }
+
+    ProjectDirs::from("com", "Nhoma", "Tutor Manager")
+        .map(|project| project.config_dir().join(CONFIG_FILE))
+}
+
+/// A missing file reads as the defaults.
+pub fn load_config(path: &Path) -> Result<UserConfig, ConfigError> {
+    match std::fs::read_to_string(path) {
+        Ok(toml) => UserConfig::parse(&toml),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(UserConfig::default()),
+        Err(e) => Err(ConfigError::ReadFailed(e.to_string())),
+    }
+}
+
+pub async fn read_config(path: PathBuf) -> Result<UserConfig, ConfigError> {
+    load_config(&path)
+}
+
+/// `toml` with the reminder lead time set, keeping everything else,
+/// comments included, as written.
+pub fn with_reminder_lead_hours(toml: &str, hours: i64) -> Result<String, ConfigError> {
+    with_value(toml, "reminder_lead_hours", hours)
+}
+
+fn with_value(
+    toml: &str,
+    key: &str,
+    value: impl Into<toml_edit::Value>,
+) -> Result<String, ConfigError> {
+    let mut document = parse_document(toml)?;
+    document[key] = toml_edit::value(value);
+
+    Ok(document.to_string())
+}
+
+fn parse_document(toml: &str) -> Result<DocumentMut, ConfigError> {
+    toml.parse()
+        .map_err(|e: toml_edit::TomlError| ConfigError::Invalid(e.message().to_string()))
+}
+
+/// The table under `key`, added if missing.
+fn table_mut<'a>(
+    document: &'a mut DocumentMut,
+    key: &str,
+) -> Result<&'a mut dyn toml_edit::TableLike, ConfigError> {
+    document
+        .entry(key)
+        .or_insert(toml_edit::table())
+        .as_table_like_mut()
+        .ok_or_else(|| ConfigError::Invalid(format!("{} should be a table of student ids", key)))
+}
+
+/// `toml` with one student's notifications set under `[student_notices]`.
+/// `AsSet` takes the student out of the table.
+pub fn with_student_notices(
+    toml: &str,
+    student_id: &str,
+    notices: StudentNotices,
+) -> Result<String, ConfigError> {
+    let mut document = parse_document(toml)?;
+    let table = table_mut(&mut document, STUDENT_NOTICES)?;
+    match notices {
+        StudentNotices::AsSet => {
+            table.remove(student_id);
+        }
+        StudentNotices::Minutes(minutes) => {
+            table.insert(student_id, toml_edit::value(minutes));
+        }
+        StudentNotices::Off => {
+            table.insert(student_id, toml_edit::value("off"));
+        }
+    }
+
+    Ok(document.to_string())
+}
+
+/// Saves the reminder lead time set in Settings, creating the file if
+/// there is none yet.
+pub async fn write_reminder_lead_hours(path: PathBuf, hours: i64) -> Result<(), ConfigError> {
+    write_value(&path, "reminder_lead_hours", hours)
+}
+
+/// Saves how far ahead desktop notifications show, as set in Settings.
+pub async fn write_notice_minutes(path: PathBuf, minutes: i64) -> Result<(), ConfigError> {
+    write_value(&path, "notice_minutes", minutes)
+}
+
+/// Saves the card density picked in the Student Manager.
+pub async fn write_card_density(path: PathBuf, density: CardDensity) -> Result<(), ConfigError> {
+    let name = CARD_DENSITIES
+        .into_iter()
+        .find(|(_, known)| *known == density)
+        .map_or("detailed", |(name, _)| name);
+
+    write_value(&path, "card_density", name)
+}
+
+/// `toml` with a student added to or taken off `reminder_opt_ins`.
+pub fn with_reminder_opt_in(
+    toml: &str,
+    student_id: &str,
+    opted_in: bool,
+) -> Result<String, ConfigError> {
+    let mut document = parse_document(toml)?;
+    let ids = document
+        .entry(REMINDER_OPT_INS)
+        .or_insert(toml_edit::value(toml_edit::Array::new()))
+        .as_array_mut()
+        .ok_or_else(|| {
+            ConfigError::Invalid("reminder_opt_ins should be a list of student ids".to_string())
+        })?;
+    ids.retain(|id| id.as_str() != Some(student_id));
+    if opted_in {
+        ids.push(student_id);
+    }
+
+    Ok(document.to_string())
+}
+
+/// `toml` with one student's reminders muted up to `muted_until` under
+/// `[reminder_mutes]`, or unmuted.
+pub fn with_reminder_mute(
+    toml: &str,
+    student_id: &str,
+    muted_until: Option<NaiveDate>,
+) -> Result<String, ConfigError> {
+    let mut document = parse_document(toml)?;
+    let table = table_mut(&mut document, REMINDER_MUTES)?;
+    match muted_until {
+        Some(muted_until) => {
+            let day: toml_edit::Datetime = muted_until
+                .to_string()
+                .parse()
+                .expect("ISO 8601 days are TOML dates");
+            table.insert(student_id, toml_edit::value(day));
+        }
+        None => {
+            table.remove(student_id);
+        }
+    }
+
+    Ok(document.to_string())
+}
+
+/// `toml` with a reminder snoozed until `until`, replacing any earlier
+/// snooze of it. Snoozes of sessions that started before `now` are
+/// dropped, as they no longer hold anything back.
+pub fn with_snoozed_reminder(
+    toml: &str,
+    student_id: &str,
+    session_at: DateTime<Local>,
+    until: DateTime<Local>,
+    now: DateTime<Local>,
+) -> Result<String, ConfigError> {
+    let mut document = parse_document(toml)?;
+    let snoozes = document
+        .entry(SNOOZED_REMINDERS)
+        .or_insert(Item::ArrayOfTables(toml_edit::ArrayOfTables::new()))
+        .as_array_of_tables_mut()
+        .ok_or_else(|| ConfigError::Invalid(SNOOZES_NOT_TABLES.to_string()))?;
+    snoozes.retain(|snoozed| {
+        let session = date_time(snoozed.get("session"));
+        let same = snoozed.get("student").and_then(Item::as_str) == Some(student_id)
+            && session == Some(session_at);
+        !same && session.is_some_and(|session| session > now)
+    });
+
+    let mut snoozed = toml_edit::Table::new();
+    snoozed.insert("student", toml_edit::value(student_id));
+    snoozed.insert("session", toml_edit::value(toml_date_time(session_at)));
+    snoozed.insert("until", toml_edit::value(toml_date_time(until)));
+    snoozes.push(snoozed);
+
+    Ok(document.to_string())
+}
+
+/// Saves how a student's sessions are announced, as set in Settings.
+pub async fn write_student_notices(
+    path: PathBuf,
+    student_id: String,
+    notices: StudentNotices,
+) -> Result<(), ConfigError> {
+    write_edited(&path, |toml| {
+        with_student_notices(toml, &student_id, notices)
+    })
+}
+
+/// Saves whether a student gets reminders, as set in Settings.
+pub async fn write_reminder_opt_in(
+    path: PathBuf,
+    student_id: String,
+    opted_in: bool,
+) -> Result<(), ConfigError> {
+    write_edited(&path, |toml| {
+        with_reminder_opt_in(toml, &student_id, opted_in)
+    })
+}
+
+/// Saves a student's reminder mute, as set in Settings.
+pub async fn write_reminder_mute(
+    path: PathBuf,
+    student_id: String,
+    muted_until: Option<NaiveDate>,
+) -> Result<(), ConfigError> {
+    write_edited(&path, |toml| {
+        with_reminder_mute(toml, &student_id, muted_until)
+    })
+}
+
+/// Saves a reminder snoozed in Settings.
+pub async fn write_snoozed_reminder(
+    path: PathBuf,
+    student_id: String,
+    session_at: DateTime<Local>,
+    until: DateTime<Local>,
+    now: DateTime<Local>,
+) -> Result<(), ConfigError> {
+    write_edited(&path, |toml| {
+        with_snoozed_reminder(toml, &student_id, session_at, until, now)
+    })
+}
+
+fn write_value(
+    path: &Path,
+    key: &str,
+    value: impl Into<toml_edit::Value>,
+) -> Result<(), ConfigError> {
+    write_edited(path, |toml| with_value(toml, key, value))
+}
+
+/// Rewrites the file with `edit`, starting from an empty one if there is
+/// none yet.
+fn write_edited(
+    path: &Path,
+    edit: impl FnOnce(&str) -> Result<String, ConfigError>,
+) -> Result<(), ConfigError> {
+    let toml = match std::fs::read_to_string(path) {
+        Ok(toml) => toml,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => String::new(),
+        Err(e) => return Err(ConfigError::ReadFailed(e.to_string())),
+    };
+    let toml = edit(&toml)?;
+
+    std::fs::write(path, toml).map_err(|e| ConfigError::WriteFailed(e.to_string()))
+}
+
+/// When the file was last written, to tell when it needs reading again.
+pub fn modified(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn every_key_is_optional_and_checked() {
+        assert_eq!(UserConfig::parse("").unwrap(), UserConfig::default());
+
+        let config = UserConfig::parse(
+            "theme = \"dark\"\n\
+             currency = \"usd\"\n\
+             locale = \"en-GH\"\n\
+             reminder_lead_hours = 3\n\
+             notice_minutes = 10\n\
+             data_path = \"/srv/tutoring\"\n\
+             calendar_feed = \"/srv/timetable.ics\"\n\
+             first_day_of_week = \"Sunday\"\n\
+             card_density = \"compact\"\n\
+             reminder_opt_ins = [\"s1\", \"s2\"]\n\
+             [student_notices]\n\
+             s1 = 30\n\
+             s2 = \"off\"\n\
+             [reminder_mutes]\n\
+             s1 = 2026-12-31\n\
+             [[snoozed_reminders]]\n\
+             student = \"s1\"\n\
+             session = 2026-10-16T17:30:00+00:00\n\
+             until = 2026-10-16T11:30:00+00:00\n",
+        )
+        .unwrap();
+        assert_eq!(config.theme, ThemeChoice::Dark);
+        assert_eq!(config.currency, Currency::parse("USD"));
+        assert_eq!(config.locale.as_deref(), Some("en-GH"));
+        assert_eq!(config.reminder_lead_hours, 3);
+        assert_eq!(config.notice_minutes, 10);
+        assert_eq!(config.data_path, Some(PathBuf::from("/srv/tutoring")));
+        assert_eq!(
+            config.calendar_feed,
+            Some(PathBuf::from("/srv/timetable.ics"))
+        );
+        assert_eq!(config.first_day_of_week, Weekday::Sun);
+        assert_eq!(config.card_density, CardDensity::Compact);
+        assert_eq!(
+            config.student_notices,
+            HashMap::from([
+                ("s1".to_string(), StudentNotices::Minutes(30)),
+                ("s2".to_string(), StudentNotices::Off),
+            ])
+        );
+        assert_eq!(
+            config.reminder_opt_ins,
+            HashSet::from(["s1".to_string(), "s2".to_string()])
+        );
+        assert_eq!(
+            config.reminder_mutes["s1"],
+            NaiveDate::from_ymd_opt(2026, 12, 31).unwrap()
+        );
+        let utc = |hour| {
+            DateTime::parse_from_rfc3339(&format!("2026-10-16T{:02}:30:00Z", hour))
+                .unwrap()
+                .with_timezone(&Local)
+        };
+        assert_eq!(
+            config.snoozed_reminders,
+            HashMap::from([(("s1".to_string(), utc(17)), utc(11))])
+        );
+
+        for invalid in [
+            "theme = \"blue\"",
+            "currency = \"cedis\"",
+            "reminder_lead_hours = 0",
+            "reminder_lead_hours = \"24\"",
+            "notice_minutes = 1441",
+            "data_path = 3",
+            "calendar_feed = true",
+            "first_day_of_week = \"someday\"",
+            "card_density = \"cosy\"",
+            "student_notices = 30",
+            "[student_notices]\ns1 = 0",
+            "[student_notices]\ns1 = \"never\"",
+            "reminder_opt_ins = \"s1\"",
+            "reminder_opt_ins = [1]",
+            "[reminder_mutes]\ns1 = \"soon\"",
+            "[reminder_mutes]\ns1 = 2026-12-31T09:00:00Z",
+            "snoozed_reminders = 6",
+            "[[snoozed_reminders]]\nstudent = \"s1\"",
+            "theme =",
+        ] {
+            assert!(
+                matches!(UserConfig::parse(invalid), Err(ConfigError::Invalid(_))),
+                "{}",
+                invalid
+            );
+        }
+    }
+
+    #[test]
+    fn setting_the_lead_time_keeps_the_rest_of_the_file() {
+        let toml =
+            with_reminder_lead_hours("# Mine\ntheme = \"dark\"\nreminder_lead_hours = 24\n", 6)
+                .unwrap();
+
+        assert_eq!(toml, "# Mine\ntheme = \"dark\"\nreminder_lead_hours = 6\n");
+        assert_eq!(
+            UserConfig::parse(&with_reminder_lead_hours("", 48).unwrap())
+                .unwrap()
+                .reminder_lead_hours,
+            48
+        );
+    }
+
+    #[test]
+    fn card_density_is_written_as_it_is_read() {
+        for (name, density) in CARD_DENSITIES {
+            let path = std::env::temp_dir().join(format!("tutor-mgr-density-{}.toml", name));
+            std::fs::write(&path, "theme = \"dark\"\n").unwrap();
+
+            iced::futures::executor::block_on(write_card_density(path.clone(), density)).unwrap();
+
+            let config = load_config(&path).unwrap();
+            assert_eq!(config.card_density, density);
+            assert_eq!(config.theme, ThemeChoice::Dark);
+        }
+    }
+
+    #[test]
+    fn student_notices_are_written_as_they_are_read() {
+        let toml = with_student_notices("theme = \"dark\"\n", "s1", StudentNotices::Off).unwrap();
+        let toml = with_student_notices(&toml, "s2", StudentNotices::Minutes(45)).unwrap();
+        let config = UserConfig::parse(&toml).unwrap();
+        assert_eq!(config.theme, ThemeChoice::Dark);
+        assert_eq!(config.student_notices["s1"], StudentNotices::Off);
+        assert_eq!(config.student_notices["s2"], StudentNotices::Minutes(45));
+
+        let toml = with_student_notices(&toml, "s1", StudentNotices::AsSet).unwrap();
+        let config = UserConfig::parse(&toml).unwrap();
+        assert!(!config.student_notices.contains_key("s1"));
+        assert_eq!(config.student_notices.len(), 1);
+    }
+
+    #[test]
+    fn opt_ins_are_written_as_they_are_read() {
+        let toml = with_reminder_opt_in("", "s1", true).unwrap();
+        let toml = with_reminder_opt_in(&toml, "s2", true).unwrap();
+        let toml = with_reminder_opt_in(&toml, "s1", true).unwrap();
+        assert_eq!(
+            UserConfig::parse(&toml).unwrap().reminder_opt_ins,
+            HashSet::from(["s1".to_string(), "s2".to_string()])
+        );
+
+        let toml = with_reminder_opt_in(&toml, "s1", false).unwrap();
+        assert_eq!(
+            UserConfig::parse(&toml).unwrap().reminder_opt_ins,
+            HashSet::from(["s2".to_string()])
+        );
+    }
+
+    #[test]
+    fn mutes_and_snoozes_are_written_as_they_are_read() {
+        let at = |day, hour| Local.with_ymd_and_hms(2026, 10, day, hour, 0, 0).unwrap();
+        let toml = with_reminder_mute("", "s1", NaiveDate::from_ymd_opt(2026, 11, 1)).unwrap();
+        let toml = with_snoozed_reminder(&toml, "s1", at(16, 17), at(16, 9), at(15, 12)).unwrap();
+        let toml = with_snoozed_reminder(&toml, "s2", at(17, 17), at(17, 9), at(15, 12)).unwrap();
+        let toml = with_snoozed_reminder(&toml, "s1", at(16, 17), at(16, 15), at(15, 12)).unwrap();
+
+        let config = UserConfig::parse(&toml).unwrap();
+        assert_eq!(
+            config.reminder_mutes["s1"],
+            NaiveDate::from_ymd_opt(2026, 11, 1).unwrap()
+        );
+        assert_eq!(
+            config.snoozed_reminders,
+            HashMap::from([
+                (("s1".to_string(), at(16, 17)), at(16, 15)),
+                (("s2".to_string(), at(17, 17)), at(17, 9)),
+            ])
+        );
+
+        let toml = with_reminder_mute(&toml, "s1", None).unwrap();
+        let toml = with_snoozed_reminder(&toml, "s3", at(20, 17), at(20, 9), at(18, 12)).unwrap();
+        let config = UserConfig::parse(&toml).unwrap();
+        assert!(config.reminder_mutes.is_empty());
+        assert_eq!(
+            config.snoozed_reminders.keys().collect::<Vec<_>>(),
+            [&("s3".to_string(), at(20, 17))]
+        );
+    }
+}
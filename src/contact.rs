@@ -0,0 +1,136 @@
+use crate::domain::Student;
+
+/// Added to local numbers, which start with a 0, to dial them from abroad
+/// and to find them on WhatsApp.
+const COUNTRY_CODE: &str = "233";
+/// Fewer digits than this cannot be a phone number.
+const MIN_PHONE_DIGITS: usize = 9;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ContactAction {
+    Call,
+    WhatsApp,
+    Email,
+}
+
+impl ContactAction {
+    pub const ALL: [ContactAction; 3] = [
+        ContactAction::Call,
+        ContactAction::WhatsApp,
+        ContactAction::Email,
+    ];
+
+    /// The link that hands the action to the system, or `None` when the
+    /// student has no phone number or email to act on.
+    pub fn link(&self, student: &Student) -> Option<String> {
+        match self {
+            ContactAction::Call => international_number(student.phone.as_deref()?)
+                .map(|number| format!("tel:+{}", number)),
+            ContactAction::WhatsApp => international_number(student.phone.as_deref()?)
+                .map(|number| format!("https://wa.me/{}", number)),
+            ContactAction::Email => student
+                .email
+                .as_deref()
+                .map(str::trim)
+                .filter(|email| is_email(email))
+                .map(|email| format!("mailto:{}", email)),
+        }
+    }
+}
+
+impl std::fmt::Display for ContactAction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ContactAction::Call => write!(f, "Call"),
+            ContactAction::WhatsApp => write!(f, "WhatsApp"),
+            ContactAction::Email => write!(f, "Email"),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum ContactError {
+    /// Nothing on this computer opens the link.
+    NoHandler(ContactAction, String),
+}
+
+impl std::fmt::Display for ContactError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ContactError::NoHandler(ContactAction::Call, reason) => {
+                write!(f, "No app is set up to make calls: {}", reason)
+            }
+            ContactError::NoHandler(ContactAction::WhatsApp, reason) => {
+                write!(f, "WhatsApp could not be opened: {}", reason)
+            }
+            ContactError::NoHandler(ContactAction::Email, reason) => {
+                write!(f, "No email app is set up: {}", reason)
+            }
+        }
+    }
+}
+
+/// `phone` as digits with the country code, ignoring spaces, dashes and
+/// brackets. `None` when it is not a phone number.
+pub fn international_number(phone: &str) -> Option<String> {
+    let phone = phone.trim();
+    if !phone
+        .chars()
+        .all(|c| c.is_ascii_digit() || matches!(c, ' ' | '-' | '(' | ')' | '+'))
+    {
+        return None;
+    }
+
+    let digits: String = phone.chars().filter(char::is_ascii_digit).collect();
+    let number = if phone.starts_with('+') {
+        digits
+    } else if let Some(local) = digits.strip_prefix('0') {
+        format!("{}{}", COUNTRY_CODE, local)
+    } else {
+        digits
+    };
+
+    (number.len() >= MIN_PHONE_DIGITS).then_some(number)
+}
+
+pub fn is_email(email: &str) -> bool {
+    email.split_once('@').is_some_and(|(name, domain)| {
+        !name.is_empty() && domain.contains('.') && !domain.starts_with('.')
+    }) && !email.contains(char::is_whitespace)
+}
+
+/// Waits for the system to take the link, so a missing handler is reported.
+pub async fn open_contact(action: ContactAction, link: String) -> Result<(), ContactError> {
+    open::that(&link).map_err(|e| ContactError::NoHandler(action, e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::fixtures::sample_domain;
+
+    #[test]
+    fn links_use_the_international_number_and_skip_missing_details() {
+        let mut student = sample_domain().students.remove(0);
+        assert_eq!(ContactAction::Call.link(&student), None);
+
+        student.phone = Some("024 123-4567".to_string());
+        student.email = Some(" parent@example.com ".to_string());
+        assert_eq!(
+            ContactAction::ALL.map(|action| action.link(&student)),
+            [
+                Some("tel:+233241234567".to_string()),
+                Some("https://wa.me/233241234567".to_string()),
+                Some("mailto:parent@example.com".to_string()),
+            ]
+        );
+
+        assert_eq!(
+            international_number("+44 (20) 7946 0958"),
+            Some("442079460958".to_string())
+        );
+        assert_eq!(international_number("024 12"), None);
+        assert_eq!(international_number("call after 5"), None);
+        assert!(!is_email("parent@example"));
+    }
+}
@@ -1,44 +1,443 @@
-use chrono::{Datelike, Local};
+use chrono::{DateTime, Datelike, Local, NaiveDate};
 use iced::advanced::graphics::core::font;
 use iced::alignment::Vertical;
-use iced::widget::canvas::{self, Frame, Path, Stroke, Text};
-use iced::widget::{Canvas, Column, Grid, column, container, grid, mouse_area, row, svg, text};
+use iced::widget::canvas::{self, Frame, LineDash, Path, Stroke, Text};
+use iced::widget::{
+    Canvas, Column, Grid, button, checkbox, column, container, grid, mouse_area, pick_list,
+    progress_bar, row, space, stack, text, text_input,
+};
 use iced::{
-    Background, Border, Center, Color, Element, Font, Length, Point, Rectangle, Renderer, Shadow,
-    Size, Task, Theme, Vector,
+    Background, Border, Center, Color, Element, Font, Length, Point, Rectangle, Renderer, Size,
+    Subscription, Task, Theme, mouse,
 };
+use iced::window::frames;
+use std::cell::Cell;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::time::{Duration, Instant};
+
+use lilt::{Animated, Easing};
 
+use crate::billing::{Charge, ChargeReason, Ledger, NoShowPolicy, Payment};
 use crate::domain::*;
-use crate::icons;
-use crate::ui_components::{global_content_container, page_header};
+use crate::kpi::{Aggregate, KpiExpression, KpiInputs};
+use crate::money::MoneyFormat;
+use crate::routes::Route;
+use crate::shell::Effect;
+use crate::theme;
+use crate::ui_components::{
+    TrendChip, chart_panel, global_content_container, metric_card, page_header, progress_ring,
+    session_countdown,
+};
 
 pub struct DashboardState {
     hovered_dashboard_card: Option<usize>,
     barchart: GroupedBarChart,
     linechart: LineChart,
+    cumulative_chart: CumulativeIncomeChart,
+    weekday_sessions: Vec<WeekdaySessions>,
+    /// This month's income from each subject, largest first.
+    subject_income: Vec<(TutorSubject, f32)>,
+    /// Sessions held this month and the month's total, as its goal.
+    month_progress: (i32, i32),
+    /// The goal ring's fill, eased towards the latest progress.
+    goal_ring: Animated<f32, Instant>,
+    /// Every year from the first session to this one, a month at a time.
+    year_at_a_glance: BTreeMap<i32, Vec<MonthAtAGlance>>,
+    glance_year: Option<i32>,
+    attendance_grouping: AttendanceGrouping,
+    monthly_attendance: Vec<Attendance>,
+    weekly_attendance: Vec<Attendance>,
     dashboard_summary: DashboardSummary,
+    custom_kpis: Vec<CustomKpi>,
+    kpi_builder: KpiBuilder,
+    trend_display: TrendDisplay,
+    smooth_charts: bool,
+    stack_earnings: bool,
+    chart_viewport: Option<ChartViewport>,
+    show_diagnostics: bool,
+    last_update: Option<Duration>,
+    todays_sessions: Vec<TodaySession>,
+    no_shows: HashSet<SessionKey>,
+    unlogged_sessions: Vec<PastOccurrence>,
+    waitlist_matches: Vec<WaitlistMatch>,
+    no_show_fee_input: String,
+    no_show_prompt: Option<NoShowPrompt>,
+    ledger: Ledger,
+    students: Vec<Student>,
+    locations: Vec<Location>,
+    today: Option<NaiveDate>,
+    clock: Clock,
+    /// What countdowns count from, moved on by the clock tick.
+    now: DateTime<Local>,
+    payment_inputs: HashMap<String, String>,
+    payment_error: Option<(String, String)>,
+    money: MoneyFormat,
 
     is_ready: bool,
 }
 
+/// The fee about to be charged for a no-show, editable before applying.
+struct NoShowPrompt {
+    session: SessionKey,
+    amount: String,
+    error: Option<String>,
+}
+
+/// A session is identified by its student and start time.
+type SessionKey = (String, DateTime<Local>);
+
+struct TodaySession {
+    student_id: String,
+    student_name: String,
+    session_at: DateTime<Local>,
+}
+
+impl TodaySession {
+    fn key(&self) -> SessionKey {
+        (self.student_id.clone(), self.session_at)
+    }
+}
+
+/// How far back sessions nobody logged are looked for.
+const UNLOGGED_LOOKBACK_DAYS: i64 = 14;
+/// How often unlogged sessions are checked for, so ones from yesterday
+/// show up once the day rolls over.
+const UNLOGGED_CHECK_INTERVAL: Duration = Duration::from_secs(15 * 60);
+
+/// How many weeks the attendance chart shows when grouped by week.
+const ATTENDANCE_WEEKS: usize = 12;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum AttendanceGrouping {
+    #[default]
+    Month,
+    Week,
+}
+
+impl AttendanceGrouping {
+    const ALL: [AttendanceGrouping; 2] = [AttendanceGrouping::Month, AttendanceGrouping::Week];
+}
+
+impl std::fmt::Display for AttendanceGrouping {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AttendanceGrouping::Month => write!(f, "Monthly"),
+            AttendanceGrouping::Week => write!(f, "Last {} weeks", ATTENDANCE_WEEKS),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum TrendDisplay {
+    #[default]
+    Percentage,
+    Absolute,
+}
+
+#[derive(Clone, Copy)]
+enum TrendUnit {
+    Currency,
+    Sessions,
+    /// Fractional session counts, such as an average per student.
+    AverageSessions,
+    Students,
+}
+
+impl TrendUnit {
+    fn format_delta(&self, delta: f32, money: &MoneyFormat) -> String {
+        match self {
+            TrendUnit::Currency => money.format(delta),
+            TrendUnit::Sessions if delta == 1.0 => "1 session".to_string(),
+            TrendUnit::Sessions => format!("{:.0} sessions", delta),
+            TrendUnit::AverageSessions => format!("{:.1} sessions", delta),
+            TrendUnit::Students if delta == 1.0 => "1 student".to_string(),
+            TrendUnit::Students => format!("{:.0} students", delta),
+        }
+    }
+}
+
+pub struct CustomKpi {
+    name: String,
+    expression: KpiExpression,
+    format: KpiFormat,
+}
+
+#[derive(Default)]
+pub struct KpiBuilder {
+    name: String,
+    expression: String,
+    format: KpiFormat,
+    error: Option<String>,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum KpiFormat {
+    #[default]
+    Number,
+    Percentage,
+    Currency,
+}
+
+impl KpiFormat {
+    const ALL: [KpiFormat; 3] = [KpiFormat::Number, KpiFormat::Percentage, KpiFormat::Currency];
+
+    fn format_value(&self, value: f32, money: &MoneyFormat) -> String {
+        match self {
+            KpiFormat::Number => format!("{:.2}", value),
+            KpiFormat::Percentage => format!("{:.0}%", value * 100.0),
+            KpiFormat::Currency => money.format(value),
+        }
+    }
+}
+
+impl std::fmt::Display for KpiFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            KpiFormat::Number => write!(f, "Number"),
+            KpiFormat::Percentage => write!(f, "Percentage"),
+            KpiFormat::Currency => write!(f, "Currency"),
+        }
+    }
+}
+
 impl DashboardState {
     pub fn attach_domain(&mut self, domain: &Domain) {
+        let started = Instant::now();
         let income_data = domain.compute_income_data();
-        let attendance_data = domain.compute_attendance_data();
+        let this_year = domain.clock.today().year();
+        let first_year = income_data
+            .first()
+            .map(|income| income.year_month.0)
+            .unwrap_or(this_year);
+        self.year_at_a_glance = (first_year..=this_year)
+            .map(|year| (year, domain.year_at_a_glance(year)))
+            .collect();
+        self.glance_year = self
+            .glance_year
+            .filter(|year| self.year_at_a_glance.contains_key(year))
+            .or(Some(this_year));
+        self.monthly_attendance = domain.compute_attendance_data();
+        self.weekly_attendance = domain.compute_weekly_attendance_data(ATTENDANCE_WEEKS);
 
         self.barchart = GroupedBarChart::new(income_data);
-        self.linechart = LineChart::new(attendance_data);
+        self.barchart.set_smoothing(self.smooth_charts);
+        self.barchart.set_stacking(self.stack_earnings);
+        self.barchart.set_viewport(self.chart_viewport);
+        self.barchart.set_money_format(domain.money_format);
+        self.rebuild_linechart();
+        self.cumulative_chart =
+            CumulativeIncomeChart::new(domain.cumulative_income_this_month(), domain.money_format);
+        self.weekday_sessions = domain.weekday_sessions_this_month();
+        self.subject_income = subject_income(domain);
+        self.set_month_progress(domain.month_progress());
         self.dashboard_summary = DashboardSummary::compute_from_domain_state(domain);
+        self.todays_sessions = todays_sessions(domain);
+        self.students = domain.students.clone();
+        self.locations = domain.locations.clone();
+        self.today = Some(domain.clock.today());
+        self.clock = domain.clock;
+        self.now = domain.clock.now();
+        self.refresh_unlogged_sessions();
+        self.waitlist_matches = waitlist_matches(domain);
+        self.money = domain.money_format;
+        self.last_update = Some(started.elapsed());
 
         self.is_ready = true;
     }
 
+    /// Brings the dashboard up to date after one session was logged or
+    /// taken back. Only the month and week the session falls in are
+    /// recomputed, and only charts whose data moved are redrawn.
+    pub fn session_logged(&mut self, domain: &Domain, at: DateTime<Local>) {
+        if !self.is_ready {
+            return self.attach_domain(domain);
+        }
+
+        let started = Instant::now();
+        let (year, month) = (at.year(), at.month());
+        let today = domain.clock.today();
+
+        // Income and monthly attendance are both bucketed by the months
+        // that have sessions, so a month sits at the same index in each.
+        let position = self
+            .barchart
+            .data
+            .binary_search_by_key(&(year, month), |data| data.year_month);
+        let buckets = (
+            domain.income_for_month(year, month),
+            domain.attendance_for_month(year, month),
+        );
+        let (income_changed, monthly_changed) = match (position, buckets) {
+            (Ok(index), (Some(income), Some(attendance))) => {
+                let income_changed = self.barchart.data[index] != income;
+                let monthly_changed = self.monthly_attendance[index] != attendance;
+                self.barchart.data[index] = income;
+                self.monthly_attendance[index] = attendance;
+                (income_changed, monthly_changed)
+            }
+            (Err(index), (Some(income), Some(attendance))) => {
+                self.barchart.data.insert(index, income);
+                self.monthly_attendance.insert(index, attendance);
+                (true, true)
+            }
+            (Ok(index), _) => {
+                self.barchart.data.remove(index);
+                self.monthly_attendance.remove(index);
+                (true, true)
+            }
+            (Err(_), _) => (false, false),
+        };
+
+        let weeks_ago = (week_start(today) - week_start(at.date_naive())).num_weeks();
+        let weekly_changed = (0..ATTENDANCE_WEEKS as i64).contains(&weeks_ago) && {
+            let index = ATTENDANCE_WEEKS - 1 - weeks_ago as usize;
+            let attendance = domain.attendance_for_week(week_start(at.date_naive()));
+            let changed = self.weekly_attendance[index] != attendance;
+            self.weekly_attendance[index] = attendance;
+            changed
+        };
+
+        if income_changed {
+            self.barchart.cache.clear();
+        }
+        if let Some(months) = self
+            .year_at_a_glance
+            .get_mut(&year)
+            .filter(|_| income_changed || monthly_changed)
+        {
+            *months = domain.year_at_a_glance(year);
+        }
+        let shown_changed = match self.attendance_grouping {
+            AttendanceGrouping::Month => monthly_changed,
+            AttendanceGrouping::Week => weekly_changed,
+        };
+        if shown_changed {
+            self.rebuild_linechart();
+        }
+
+        // The summary covers this month against the last one.
+        if (year, month) >= previous_month(today.year(), today.month()) {
+            self.dashboard_summary = DashboardSummary::compute_from_domain_state(domain);
+        }
+        if (year, month) == (today.year(), today.month()) {
+            self.cumulative_chart =
+                CumulativeIncomeChart::new(domain.cumulative_income_this_month(), self.money);
+            self.weekday_sessions = domain.weekday_sessions_this_month();
+            self.subject_income = subject_income(domain);
+            self.set_month_progress(domain.month_progress());
+        }
+        self.students = domain.students.clone();
+        self.refresh_unlogged_sessions();
+        self.last_update = Some(started.elapsed());
+    }
+
+    fn set_month_progress(&mut self, progress: (i32, i32)) {
+        let (held, goal) = progress;
+        let fill = if goal > 0 {
+            (held as f32 / goal as f32).min(1.0)
+        } else {
+            0.0
+        };
+
+        self.month_progress = progress;
+        self.goal_ring.transition(fill, Instant::now());
+    }
+
+    fn refresh_unlogged_sessions(&mut self) {
+        self.unlogged_sessions =
+            unlogged_sessions(&self.students, self.clock.today(), UNLOGGED_LOOKBACK_DAYS);
+    }
+
+    fn rebuild_linechart(&mut self) {
+        let data = match self.attendance_grouping {
+            AttendanceGrouping::Month => self.monthly_attendance.clone(),
+            AttendanceGrouping::Week => self.weekly_attendance.clone(),
+        };
+
+        self.linechart = LineChart::new(data);
+        self.linechart.set_smoothing(self.smooth_charts);
+        self.linechart.set_viewport(self.chart_viewport);
+    }
+
+    pub fn todays_session_count(&self) -> usize {
+        self.todays_sessions.len()
+    }
+
+    /// What all students owe between them, leaving out anyone in credit.
+    pub fn unpaid_balance(&self) -> f32 {
+        let Some(today) = self.today else {
+            return 0.0;
+        };
+
+        self.students
+            .iter()
+            .map(|student| {
+                self.ledger
+                    .account_for(student, &self.locations, today)
+                    .closing()
+                    .max(0.0)
+            })
+            .sum()
+    }
+
+    pub fn ledger(&self) -> &Ledger {
+        &self.ledger
+    }
+
+    pub fn ledger_mut(&mut self) -> &mut Ledger {
+        &mut self.ledger
+    }
+
+    /// Takes over the ledger read at startup, with its no-show fee.
+    pub fn attach_ledger(&mut self, ledger: Ledger) {
+        let fee = ledger.no_show_policy().fee;
+        self.no_show_fee_input = if fee > 0.0 {
+            self.money.plain(fee)
+        } else {
+            String::new()
+        };
+        self.ledger = ledger;
+    }
+
     pub fn empty() -> Self {
         Self {
             hovered_dashboard_card: None,
             barchart: GroupedBarChart::empty(),
             linechart: LineChart::empty(),
+            cumulative_chart: CumulativeIncomeChart::new(Vec::new(), MoneyFormat::default()),
+            weekday_sessions: Vec::new(),
+            subject_income: Vec::new(),
+            month_progress: (0, 0),
+            goal_ring: Animated::new(0.0).duration(600.).easing(Easing::EaseOut),
+            year_at_a_glance: BTreeMap::new(),
+            glance_year: None,
+            attendance_grouping: AttendanceGrouping::default(),
+            monthly_attendance: Vec::new(),
+            weekly_attendance: Vec::new(),
             dashboard_summary: DashboardSummary::empty(),
+            custom_kpis: Vec::new(),
+            kpi_builder: KpiBuilder::default(),
+            trend_display: TrendDisplay::default(),
+            smooth_charts: false,
+            stack_earnings: false,
+            chart_viewport: None,
+            show_diagnostics: false,
+            last_update: None,
+            todays_sessions: Vec::new(),
+            no_shows: HashSet::new(),
+            unlogged_sessions: Vec::new(),
+            waitlist_matches: Vec::new(),
+            no_show_fee_input: String::new(),
+            no_show_prompt: None,
+            ledger: Ledger::default(),
+            students: Vec::new(),
+            locations: Vec::new(),
+            today: None,
+            clock: Clock::default(),
+            now: Local::now(),
+            payment_inputs: HashMap::new(),
+            payment_error: None,
+            money: MoneyFormat::default(),
 
             is_ready: false,
         }
@@ -48,14 +447,278 @@ impl DashboardState {
 #[derive(Clone, Debug)]
 pub enum Msg {
     DashboardCardHovered(Option<usize>),
+    KpiNameChanged(String),
+    KpiExpressionChanged(String),
+    KpiFormatSelected(KpiFormat),
+    AddCustomKpi,
+    RemoveCustomKpi(usize),
+    ToggleTrendDisplay,
+    SmoothingToggled(bool),
+    StackingToggled(bool),
+    ChartViewportChanged(ChartViewport),
+    ResetChartZoom,
+    AttendanceGroupingSelected(AttendanceGrouping),
+    DiagnosticsToggled(bool),
+    SessionConfirmationToggled(SessionKey, bool),
+    MarkNoShow(SessionKey),
+    CheckUnloggedSessions,
+    /// Moves the countdowns to today's sessions on. Sent by the app
+    /// every minute.
+    ClockTicked,
+    LogUnloggedSession(SessionKey),
+    CancelUnloggedSession(SessionKey),
+    NoShowFeeChanged(String),
+    NoShowAutoApplyToggled(bool),
+    NoShowChargeChanged(String),
+    ApplyNoShowCharge,
+    WaiveNoShowCharge,
+    PaymentAmountChanged(String, String),
+    RecordPayment(String),
+    /// Told to the app once the payment is in the ledger.
+    PaymentRecorded(Payment),
+    GlanceYearSelected(i32),
+    /// A frame of the goal ring filling, to redraw.
+    Tick,
+    /// Carried out by the app, e.g. opening a month's report.
+    Effect(Effect),
+}
+
+pub fn subscription(state: &DashboardState) -> Subscription<Msg> {
+    if !state.is_ready {
+        return Subscription::none();
+    }
+
+    let unlogged = iced::time::every(UNLOGGED_CHECK_INTERVAL).map(|_| Msg::CheckUnloggedSessions);
+    if state.goal_ring.in_progress(Instant::now()) {
+        Subscription::batch([unlogged, frames().map(|_| Msg::Tick)])
+    } else {
+        unlogged
+    }
 }
 
 pub fn update(state: &mut DashboardState, msg: Msg) -> Task<Msg> {
+    let started = Instant::now();
+    let task = handle_msg(state, msg);
+    state.last_update = Some(started.elapsed());
+
+    task
+}
+
+fn handle_msg(state: &mut DashboardState, msg: Msg) -> Task<Msg> {
     match msg {
         Msg::DashboardCardHovered(card_index) => {
             state.hovered_dashboard_card = card_index;
             Task::none()
         }
+        Msg::KpiNameChanged(name) => {
+            state.kpi_builder.name = name;
+            Task::none()
+        }
+        Msg::KpiExpressionChanged(expression) => {
+            state.kpi_builder.expression = expression;
+            state.kpi_builder.error = None;
+            Task::none()
+        }
+        Msg::KpiFormatSelected(format) => {
+            state.kpi_builder.format = format;
+            Task::none()
+        }
+        Msg::AddCustomKpi => {
+            let builder = &mut state.kpi_builder;
+            let name = builder.name.trim().to_string();
+
+            if name.is_empty() {
+                builder.error = Some("Give the metric a name".to_string());
+                return Task::none();
+            }
+
+            match KpiExpression::parse(&builder.expression) {
+                Ok(expression) => {
+                    state.custom_kpis.push(CustomKpi {
+                        name,
+                        expression,
+                        format: builder.format,
+                    });
+                    *builder = KpiBuilder::default();
+                }
+                Err(e) => builder.error = Some(e.to_string()),
+            }
+            Task::none()
+        }
+        Msg::RemoveCustomKpi(index) => {
+            if index < state.custom_kpis.len() {
+                state.custom_kpis.remove(index);
+            }
+            state.hovered_dashboard_card = None;
+            Task::none()
+        }
+        Msg::ToggleTrendDisplay => {
+            state.trend_display = match state.trend_display {
+                TrendDisplay::Percentage => TrendDisplay::Absolute,
+                TrendDisplay::Absolute => TrendDisplay::Percentage,
+            };
+            Task::none()
+        }
+        Msg::SmoothingToggled(is_smoothed) => {
+            state.smooth_charts = is_smoothed;
+            state.barchart.set_smoothing(is_smoothed);
+            state.linechart.set_smoothing(is_smoothed);
+            Task::none()
+        }
+        Msg::StackingToggled(is_stacked) => {
+            state.stack_earnings = is_stacked;
+            state.barchart.set_stacking(is_stacked);
+            Task::none()
+        }
+        Msg::ChartViewportChanged(viewport) => {
+            state.chart_viewport = Some(viewport);
+            state.barchart.set_viewport(state.chart_viewport);
+            state.linechart.set_viewport(state.chart_viewport);
+            Task::none()
+        }
+        Msg::ResetChartZoom => {
+            state.chart_viewport = None;
+            state.barchart.set_viewport(None);
+            state.linechart.set_viewport(None);
+            Task::none()
+        }
+        Msg::AttendanceGroupingSelected(grouping) => {
+            state.attendance_grouping = grouping;
+            state.rebuild_linechart();
+            Task::none()
+        }
+        Msg::DiagnosticsToggled(show) => {
+            state.show_diagnostics = show;
+            Task::none()
+        }
+        // The app records the confirmation in the domain.
+        Msg::SessionConfirmationToggled(..) => Task::none(),
+        Msg::MarkNoShow(session) => {
+            state.no_shows.insert(session.clone());
+            let policy = state.ledger.no_show_policy();
+            if policy.auto_apply {
+                state.no_show_prompt = Some(NoShowPrompt {
+                    session,
+                    amount: state.money.plain(policy.fee),
+                    error: None,
+                });
+            }
+            Task::none()
+        }
+        Msg::CheckUnloggedSessions => {
+            state.refresh_unlogged_sessions();
+            Task::none()
+        }
+        Msg::ClockTicked => {
+            state.now = state.clock.now();
+            Task::none()
+        }
+        // Logged or cancelled by the app, which then refreshes the list.
+        Msg::LogUnloggedSession(_) | Msg::CancelUnloggedSession(_) => Task::none(),
+        Msg::NoShowFeeChanged(fee) => {
+            if let Ok(amount) = fee.trim().parse::<f32>() {
+                state.ledger.set_no_show_policy(NoShowPolicy {
+                    fee: amount.max(0.0),
+                    ..state.ledger.no_show_policy()
+                });
+            }
+            state.no_show_fee_input = fee;
+            Task::none()
+        }
+        Msg::NoShowAutoApplyToggled(auto_apply) => {
+            state.ledger.set_no_show_policy(NoShowPolicy {
+                auto_apply,
+                ..state.ledger.no_show_policy()
+            });
+            Task::none()
+        }
+        Msg::NoShowChargeChanged(amount) => {
+            if let Some(prompt) = &mut state.no_show_prompt {
+                prompt.amount = amount;
+                prompt.error = None;
+            }
+            Task::none()
+        }
+        Msg::ApplyNoShowCharge => {
+            let Some(prompt) = &mut state.no_show_prompt else {
+                return Task::none();
+            };
+
+            let amount = match prompt.amount.trim().parse::<f32>() {
+                Ok(amount) if amount >= 0.0 => amount,
+                _ => {
+                    prompt.error = Some("Enter the fee as a number, e.g. 50".to_string());
+                    return Task::none();
+                }
+            };
+
+            let (student_id, session_at) = prompt.session.clone();
+            let student_name = state
+                .todays_sessions
+                .iter()
+                .find(|session| session.key() == prompt.session)
+                .map(|session| session.student_name.clone())
+                .unwrap_or_else(|| student_id.clone());
+
+            state.ledger.add(Charge {
+                student_id,
+                student_name,
+                session_at,
+                reason: ChargeReason::NoShow,
+                amount,
+                invoiced: false,
+            });
+            state.no_show_prompt = None;
+            Task::none()
+        }
+        Msg::WaiveNoShowCharge => {
+            state.no_show_prompt = None;
+            Task::none()
+        }
+        Msg::PaymentAmountChanged(student_id, amount) => {
+            state.payment_inputs.insert(student_id, amount);
+            state.payment_error = None;
+            Task::none()
+        }
+        Msg::RecordPayment(student_id) => {
+            let Some(today) = state.today else {
+                return Task::none();
+            };
+            let input = state
+                .payment_inputs
+                .get(&student_id)
+                .map(|amount| amount.trim())
+                .unwrap_or_default();
+
+            match input.parse::<f32>() {
+                Ok(amount) if amount > 0.0 => {
+                    let payment = Payment {
+                        student_id: student_id.clone(),
+                        paid_on: today,
+                        amount,
+                        invoice_number: None,
+                    };
+                    state.ledger.record_payment(payment.clone());
+                    state.payment_inputs.remove(&student_id);
+                    Task::done(Msg::PaymentRecorded(payment))
+                }
+                _ => {
+                    state.payment_error = Some((
+                        student_id,
+                        "Enter the amount received, e.g. 150".to_string(),
+                    ));
+                    Task::none()
+                }
+            }
+        }
+        Msg::GlanceYearSelected(year) => {
+            if state.year_at_a_glance.contains_key(&year) {
+                state.glance_year = Some(year);
+            }
+            Task::none()
+        }
+        Msg::Tick => Task::none(),
+        Msg::PaymentRecorded(_) | Msg::Effect(_) => Task::none(),
     }
 }
 
@@ -63,11 +726,41 @@ pub fn view<'a>(state: &'a DashboardState) -> Element<'a, Msg> {
     view_dashboard(state)
 }
 
+fn subject_income(domain: &Domain) -> Vec<(TutorSubject, f32)> {
+    let today = domain.clock.today();
+    domain.income_by_subject(today.year(), today.month())
+}
+
+fn todays_sessions(domain: &Domain) -> Vec<TodaySession> {
+    let today = domain.clock.today();
+
+    let mut sessions: Vec<TodaySession> = domain
+        .students
+        .iter()
+        .flat_map(|student| {
+            scheduled_sessions_on(student, today)
+                .into_iter()
+                .map(|session_at| TodaySession {
+                    student_id: student.id.clone(),
+                    student_name: student.name.full_name(),
+                    session_at,
+                })
+        })
+        .collect();
+
+    sessions.sort_by_key(|session| session.session_at);
+    sessions
+}
+
 struct DashboardSummary {
     attendance: AttendanceSummary,
     actual_revenue: ActualRevenueSummary,
     potential_revenue: PotentialRevenueSummary,
     lost_revenue: LostRevenueSummary,
+    remaining: RemainingSummary,
+    students: StudentsSummary,
+    averages: MonthlyAverages,
+    average_trends: [NumberTrend; 3],
 }
 
 impl DashboardSummary {
@@ -76,6 +769,7 @@ impl DashboardSummary {
             attendance: AttendanceSummary {
                 total_scheduled_sessions: 0,
                 total_actual_sessions: 0,
+                trend: NumberTrend::NoData,
             },
             actual_revenue: ActualRevenueSummary {
                 amount: 0.0f32,
@@ -86,80 +780,88 @@ impl DashboardSummary {
                 amount: 0.0f32,
                 trend: NumberTrend::NoData,
             },
+            remaining: RemainingSummary {
+                sessions: 0,
+                income: 0.0f32,
+            },
+            students: StudentsSummary {
+                active: 0,
+                gained: 0,
+                lost: 0,
+                active_trend: NumberTrend::NoData,
+                gained_trend: NumberTrend::NoData,
+            },
+            averages: MonthlyAverages::default(),
+            average_trends: [
+                NumberTrend::NoData,
+                NumberTrend::NoData,
+                NumberTrend::NoData,
+            ],
+        }
+    }
+
+    fn kpi_inputs(&self) -> KpiInputs {
+        KpiInputs {
+            actual: self.actual_revenue.amount,
+            potential: self.potential_revenue.amount,
+            lost: self.lost_revenue.amount,
+            scheduled: self.attendance.total_scheduled_sessions as f32,
+            completed: self.attendance.total_actual_sessions as f32,
+            students: self.students.active as f32,
         }
     }
 
     fn compute_from_domain_state(domain: &Domain) -> Self {
-        let today = Local::now().naive_local().date();
+        let today = domain.clock.today();
         let current_year = today.year();
         let current_month = today.month();
 
-        let total_actual_sessions = domain
-            .students
-            .iter()
-            .map(|student| {
-                compute_monthly_completed_sessions(student, current_month, current_year) as usize
-            })
-            .sum();
-
-        let total_scheduled_sessions = domain
-            .students
-            .iter()
-            .map(|student| {
-                compute_monthly_scheduled_sessions(student, current_month, current_year) as usize
-            })
-            .sum();
-
-        let potential_earnings = domain
-            .students
-            .iter()
-            .map(|std| {
-                compute_monthly_sum(
-                    std,
-                    current_month,
-                    current_year,
-                    compute_monthly_scheduled_sessions,
-                )
-            })
-            .sum();
-
-        let actual_earnings = domain
-            .students
-            .iter()
-            .map(|std| {
-                compute_monthly_sum(
-                    std,
-                    current_month,
-                    current_year,
-                    compute_monthly_completed_sessions,
-                )
-            })
-            .sum();
+        let month = domain.monthly_summary(current_year, current_month);
+        let trend = domain.current_trend();
 
         let attendance = AttendanceSummary {
-            total_actual_sessions,
-            total_scheduled_sessions,
+            total_actual_sessions: month.completed_sessions,
+            total_scheduled_sessions: month.scheduled_sessions,
+            trend: trend.sessions_trend,
         };
-
-        let actual_income_trend = domain.get_actual_income_trend_direction();
-
         let actual_revenue = ActualRevenueSummary {
-            amount: actual_earnings,
-            trend: actual_income_trend,
+            amount: month.actual_revenue,
+            trend: trend.revenue_trend,
         };
         let potential_revenue = PotentialRevenueSummary {
-            amount: potential_earnings,
+            amount: month.potential_revenue,
         };
         let lost_revenue = LostRevenueSummary {
-            amount: potential_earnings - actual_earnings,
-            trend: NumberTrend::NoData,
+            amount: month.lost_revenue(),
+            trend: trend.lost_revenue_trend,
+        };
+
+        let (remaining_sessions, remaining_income) = domain.remaining_in_month();
+        let remaining = RemainingSummary {
+            sessions: remaining_sessions,
+            income: remaining_income,
+        };
+
+        let (gained, lost) = domain.students_gained_and_lost(current_year, current_month);
+        let students = StudentsSummary {
+            active: domain.active_student_count(),
+            gained,
+            lost,
+            active_trend: domain.get_active_students_trend_direction(),
+            gained_trend: domain.get_new_students_trend_direction(),
         };
 
+        let (averages, average_trends) = domain.monthly_averages_with_trends();
+
         Self {
             attendance,
             actual_revenue,
             potential_revenue,
             lost_revenue,
+            remaining,
+            students,
+            averages,
+            average_trends,
         }
     }
 }
@@ -178,58 +880,165 @@ struct LostRevenueSummary {
     trend: NumberTrend,
 }
 
-struct AttendanceSummary {
-    total_scheduled_sessions: usize,
-    total_actual_sessions: usize,
+/// What the rest of the month still holds, blackout dates left out.
+struct RemainingSummary {
+    sessions: usize,
+    income: f32,
 }
 
-struct MonthlySummaryWithTrend {
-    month: YearMonth,
+struct StudentsSummary {
+    active: usize,
+    gained: usize,
+    lost: usize,
+    active_trend: NumberTrend,
+    gained_trend: NumberTrend,
+}
 
-    attendance: AttendanceSummary,
-    actual_revenue: ActualRevenueSummary,
-    potential_revenue: PotentialRevenueSummary,
-    lost_revenue: LostRevenueSummary,
+struct AttendanceSummary {
+    total_scheduled_sessions: usize,
+    total_actual_sessions: usize,
+    trend: NumberTrend,
 }
 
 pub struct GroupedBarChart {
     data: Vec<IncomeData>,
+    show_smoothing: bool,
+    stack_by_student: bool,
+    viewport: Option<ChartViewport>,
+    money: MoneyFormat,
     cache: canvas::Cache,
+    stats: Cell<RenderStats>,
 }
 
 impl GroupedBarChart {
     fn new(data: Vec<IncomeData>) -> Self {
         Self {
             data,
+            show_smoothing: false,
+            stack_by_student: false,
+            viewport: None,
+            money: MoneyFormat::default(),
             cache: canvas::Cache::new(),
+            stats: Cell::default(),
         }
     }
 
     fn empty() -> Self {
         Self {
             data: Vec::new(),
+            show_smoothing: false,
+            stack_by_student: false,
+            viewport: None,
+            money: MoneyFormat::default(),
             cache: canvas::Cache::new(),
+            stats: Cell::default(),
+        }
+    }
+
+    fn set_smoothing(&mut self, show_smoothing: bool) {
+        self.show_smoothing = show_smoothing;
+        self.cache.clear();
+    }
+
+    fn set_stacking(&mut self, stack_by_student: bool) {
+        self.stack_by_student = stack_by_student;
+        self.cache.clear();
+    }
+
+    fn set_viewport(&mut self, viewport: Option<ChartViewport>) {
+        self.viewport = viewport;
+        self.cache.clear();
+    }
+
+    fn set_money_format(&mut self, money: MoneyFormat) {
+        self.money = money;
+        self.cache.clear();
+    }
+
+    fn visible_viewport(&self) -> ChartViewport {
+        ChartViewport::visible(self.viewport, self.data.len())
+    }
+
+    /// Students with the highest total actual earnings across the chart,
+    /// biggest first. Everyone else is grouped under "Others".
+    fn top_earners(&self) -> Vec<String> {
+        let mut totals: BTreeMap<&str, f32> = BTreeMap::new();
+        for data in &self.data {
+            for (name, earned) in &data.actual_by_student {
+                *totals.entry(name.as_str()).or_default() += earned;
+            }
         }
+
+        let mut totals: Vec<(&str, f32)> = totals.into_iter().collect();
+        totals.sort_by(|a, b| b.1.total_cmp(&a.1));
+
+        totals
+            .into_iter()
+            .filter(|(_, total)| *total > 0.0)
+            .take(STACK_COLORS.len())
+            .map(|(name, _)| name.to_string())
+            .collect()
     }
 }
 
-impl<Msg> canvas::Program<Msg> for GroupedBarChart {
-    type State = ();
+pub const STACK_COLORS: [Color; 4] = [
+    Color::from_rgb(0.20, 0.45, 0.75),
+    Color::from_rgb(0.90, 0.55, 0.15),
+    Color::from_rgb(0.30, 0.65, 0.40),
+    Color::from_rgb(0.65, 0.35, 0.65),
+];
+const STACK_OTHERS_COLOR: Color = Color::from_rgb(0.7, 0.7, 0.7);
+const POTENTIAL_COLOR: Color = Color::from_rgb(0.3, 0.6, 0.9);
+const ACTUAL_COLOR: Color = Color::from_rgba(0.7, 0.7, 0.7, 0.5);
+
+impl canvas::Program<Msg> for GroupedBarChart {
+    type State = ChartState;
+
+    fn update(
+        &self,
+        state: &mut Self::State,
+        event: &canvas::Event,
+        bounds: Rectangle,
+        cursor: mouse::Cursor,
+    ) -> Option<canvas::Action<Msg>> {
+        update_chart(
+            state,
+            event,
+            bounds,
+            cursor,
+            self.visible_viewport(),
+            self.data.len(),
+        )
+    }
+
+    fn mouse_interaction(
+        &self,
+        state: &Self::State,
+        bounds: Rectangle,
+        cursor: mouse::Cursor,
+    ) -> mouse::Interaction {
+        chart_mouse_interaction(state, bounds, cursor, self.viewport.is_some())
+    }
 
     fn draw(
         &self,
-        _state: &Self::State,
+        state: &Self::State,
         renderer: &Renderer,
         _theme: &Theme,
         bounds: Rectangle,
-        _cursor: iced::advanced::mouse::Cursor,
+        cursor: mouse::Cursor,
     ) -> Vec<canvas::Geometry> {
+        let started = Instant::now();
+        let redrawn = Cell::new(false);
+
         let geometry = self.cache.draw(renderer, bounds.size(), |frame| {
+            redrawn.set(true);
+
             if self.data.is_empty() {
                 frame.fill_text(Text {
                     content: "No attendance data yet".into(),
                     position: Point::new(frame.width() / 2.0, frame.height() / 2.0),
-                    color: Color::from_rgb(0.5, 0.5, 0.5),
+                    color: theme::MUTED,
                     size: 14.0.into(),
                     align_x: iced::advanced::text::Alignment::Center,
                     align_y: iced::alignment::Vertical::Center,
@@ -238,26 +1047,57 @@ impl<Msg> canvas::Program<Msg> for GroupedBarChart {
                 return;
             }
 
-            let max_bar = self
-                .data
+            let viewport = self.visible_viewport();
+            let visible = &self.data[viewport.range()];
+
+            let max_bar = visible
                 .iter()
                 .flat_map(|data| [data.potential, data.potential])
                 .fold(0.0f32, f32::max);
 
-            let padding = 20.0;
+            let padding = CHART_PADDING;
             let chart_width = frame.width() - padding * 2.0;
             let chart_height = frame.height() - padding * 2.5;
 
-            let num_groups = self.data.len();
+            let num_groups = visible.len();
             let bar_scale = chart_height / (max_bar * 1.1);
             let group_width = chart_width / num_groups as f32;
             let bar_width = group_width * 0.30;
             let gap_between_bars = group_width * 0.1;
             let group_padding = group_width * 0.2;
 
+            let whole_units = MoneyFormat {
+                decimals: 0,
+                ..self.money
+            };
+            draw_y_ticks(
+                frame,
+                padding,
+                chart_width,
+                chart_height,
+                bar_scale,
+                &axis_ticks(max_bar * 1.1, 1.0),
+                |value| whole_units.amount(value),
+            );
             draw_axes(frame, padding, chart_width, chart_height);
+            frame.fill_text(Text {
+                content: format!("Peak {}", self.money.format(max_bar)),
+                position: Point::new(frame.width() - padding, 0.0),
+                color: theme::MUTED,
+                size: 10.0.into(),
+                align_x: iced::advanced::text::Alignment::Right,
+                ..Default::default()
+            });
+
+            let top_earners = if self.stack_by_student {
+                self.top_earners()
+            } else {
+                Vec::new()
+            };
 
-            for (i, data) in self.data.iter().enumerate() {
+            let label_stride = label_stride(group_width);
+
+            for (i, data) in visible.iter().enumerate() {
                 let group_x = padding + (i as f32 * group_width);
 
                 let potential_earnings_x = group_x + group_padding;
@@ -268,23 +1108,80 @@ impl<Msg> canvas::Program<Msg> for GroupedBarChart {
                     Point::new(potential_earnings_x, potential_earnings_y),
                     Size::new(bar_width, potential_earnings_bar_height),
                 );
-                frame.fill(&potential_earning_bar, Color::from_rgb(0.3, 0.6, 0.9));
+                frame.fill(&potential_earning_bar, POTENTIAL_COLOR);
 
                 let actual_earnings_x = potential_earnings_x + bar_width + gap_between_bars;
-                let actual_earnings_bar_height = data.actual * bar_scale;
-                let actual_earnings_y = padding + chart_height - actual_earnings_bar_height;
-
-                let actual_earning_bar = Path::rectangle(
-                    Point::new(actual_earnings_x, actual_earnings_y),
-                    Size::new(bar_width, actual_earnings_bar_height),
-                );
-                frame.fill(&actual_earning_bar, Color::from_rgba(0.7, 0.7, 0.7, 0.5));
+                let bar_label = |frame: &mut Frame, x: f32, value: f32| {
+                    draw_bar_label(
+                        frame,
+                        Point::new(
+                            x + bar_width / 2.0,
+                            padding + chart_height - value * bar_scale,
+                        ),
+                        bar_width + gap_between_bars,
+                        whole_units.amount(value),
+                    );
+                };
+                bar_label(frame, potential_earnings_x, data.potential);
+                bar_label(frame, actual_earnings_x, data.actual);
+
+                if self.stack_by_student {
+                    let mut segments: Vec<(f32, Color)> = top_earners
+                        .iter()
+                        .zip(STACK_COLORS)
+                        .map(|(name, color)| {
+                            let earned = data
+                                .actual_by_student
+                                .iter()
+                                .filter(|(student, _)| student == name)
+                                .map(|(_, earned)| earned)
+                                .sum::<f32>();
+                            (earned, color)
+                        })
+                        .collect();
+
+                    let others = data
+                        .actual_by_student
+                        .iter()
+                        .filter(|(student, _)| !top_earners.contains(student))
+                        .map(|(_, earned)| earned)
+                        .sum::<f32>();
+                    segments.push((others, STACK_OTHERS_COLOR));
+
+                    let mut segment_top = padding + chart_height;
+                    for (earned, color) in segments {
+                        let segment_height = earned * bar_scale;
+                        if segment_height <= 0.0 {
+                            continue;
+                        }
+                        segment_top -= segment_height;
+
+                        let segment = Path::rectangle(
+                            Point::new(actual_earnings_x, segment_top),
+                            Size::new(bar_width, segment_height),
+                        );
+                        frame.fill(&segment, color);
+                    }
+                } else {
+                    let actual_earnings_bar_height = data.actual * bar_scale;
+                    let actual_earnings_y = padding + chart_height - actual_earnings_bar_height;
+
+                    let actual_earning_bar = Path::rectangle(
+                        Point::new(actual_earnings_x, actual_earnings_y),
+                        Size::new(bar_width, actual_earnings_bar_height),
+                    );
+                    frame.fill(&actual_earning_bar, ACTUAL_COLOR);
+                }
+
+                if !(viewport.first + i).is_multiple_of(label_stride) {
+                    continue;
+                }
 
                 let label_x = group_x + (group_width / 2.0);
                 let label_y = padding + chart_height + 10.0;
 
                 frame.fill_text(Text {
-                    content: data.month_year.0.clone(),
+                    content: month_label(data.year_month.0, data.year_month.1),
                     position: Point {
                         x: label_x,
                         y: label_y,
@@ -295,49 +1192,182 @@ impl<Msg> canvas::Program<Msg> for GroupedBarChart {
                     ..Default::default()
                 });
             }
+
+            if self.show_smoothing {
+                let actuals: Vec<f32> = self.data.iter().map(|data| data.actual).collect();
+                let averages = rolling_average(&actuals, SMOOTHING_WINDOW);
+                let points: Vec<Point> = averages[viewport.range()]
+                    .iter()
+                    .enumerate()
+                    .map(|(i, average)| {
+                        let x = padding + (i as f32 * group_width) + (group_width / 2.0);
+                        Point::new(x, padding + chart_height - average * bar_scale)
+                    })
+                    .collect();
+
+                draw_smoothed_line(frame, &points);
+            }
+
+            let actual_legend: Vec<(&str, Color)> = if self.stack_by_student {
+                top_earners
+                    .iter()
+                    .map(String::as_str)
+                    .zip(STACK_COLORS)
+                    .chain(std::iter::once(("Others", STACK_OTHERS_COLOR)))
+                    .collect()
+            } else {
+                vec![("Actual", ACTUAL_COLOR)]
+            };
+            draw_legend(
+                frame,
+                padding,
+                std::iter::once(("Potential", POTENTIAL_COLOR)).chain(actual_legend),
+            );
         });
-        vec![geometry]
+
+        record_draw(&self.stats, started.elapsed(), redrawn.get());
+
+        let viewport = self.visible_viewport();
+        let hovered = state
+            .hovered
+            .and_then(|i| Some((i, self.data[viewport.range()].get(i)?)));
+        let Some((i, data)) = hovered else {
+            return vec![geometry];
+        };
+        let lines = [
+            month_label(data.year_month.0, data.year_month.1),
+            format!("Potential {}", self.money.format(data.potential)),
+            format!("Actual {}", self.money.format(data.actual)),
+        ];
+        vec![
+            geometry,
+            draw_hover(renderer, bounds, cursor, i, viewport.months, &lines),
+        ]
     }
 }
 
+/// Draws a single row of colour swatches and labels above the plot area.
+pub fn draw_legend<'a>(
+    frame: &mut Frame,
+    padding: f32,
+    entries: impl Iterator<Item = (&'a str, Color)>,
+) {
+    let swatch_size = 8.0;
+    let mut x = padding;
+
+    for (label, color) in entries {
+        frame.fill(
+            &Path::rectangle(Point::new(x, 2.0), Size::new(swatch_size, swatch_size)),
+            color,
+        );
+
+        frame.fill_text(Text {
+            content: label.to_string(),
+            position: Point::new(x + swatch_size + 4.0, 0.0),
+            color: Color::BLACK,
+            size: 10.0.into(),
+            ..Default::default()
+        });
+
+        // Rough label width at this text size, enough to keep entries apart.
+        x += swatch_size + 4.0 + label.chars().count() as f32 * 6.0 + 12.0;
+    }
+}
+
+const SCHEDULED_COLOR: Color = Color::from_rgb(0.6, 0.6, 0.6);
+
 struct LineChart {
     data: Vec<Attendance>,
+    show_smoothing: bool,
+    viewport: Option<ChartViewport>,
     cache: canvas::Cache,
+    stats: Cell<RenderStats>,
 }
 
 impl LineChart {
     fn new(data: Vec<Attendance>) -> Self {
         Self {
             data,
+            show_smoothing: false,
+            viewport: None,
             cache: canvas::Cache::new(),
+            stats: Cell::default(),
         }
     }
 
     fn empty() -> Self {
         Self {
             data: Vec::new(),
+            show_smoothing: false,
+            viewport: None,
             cache: canvas::Cache::new(),
+            stats: Cell::default(),
         }
     }
-}
 
-impl<Msg> canvas::Program<Msg> for LineChart {
-    type State = ();
+    fn set_smoothing(&mut self, show_smoothing: bool) {
+        self.show_smoothing = show_smoothing;
+        self.cache.clear();
+    }
+
+    fn set_viewport(&mut self, viewport: Option<ChartViewport>) {
+        self.viewport = viewport;
+        self.cache.clear();
+    }
+
+    fn visible_viewport(&self) -> ChartViewport {
+        ChartViewport::visible(self.viewport, self.data.len())
+    }
+}
+
+impl canvas::Program<Msg> for LineChart {
+    type State = ChartState;
+
+    fn update(
+        &self,
+        state: &mut Self::State,
+        event: &canvas::Event,
+        bounds: Rectangle,
+        cursor: mouse::Cursor,
+    ) -> Option<canvas::Action<Msg>> {
+        update_chart(
+            state,
+            event,
+            bounds,
+            cursor,
+            self.visible_viewport(),
+            self.data.len(),
+        )
+    }
+
+    fn mouse_interaction(
+        &self,
+        state: &Self::State,
+        bounds: Rectangle,
+        cursor: mouse::Cursor,
+    ) -> mouse::Interaction {
+        chart_mouse_interaction(state, bounds, cursor, self.viewport.is_some())
+    }
 
     fn draw(
         &self,
-        _state: &Self::State,
+        state: &Self::State,
         renderer: &Renderer,
         _theme: &Theme,
         bounds: Rectangle,
-        _cursor: iced::advanced::mouse::Cursor,
+        cursor: mouse::Cursor,
     ) -> Vec<canvas::Geometry> {
+        let started = Instant::now();
+        let redrawn = Cell::new(false);
+
         let geometry = self.cache.draw(renderer, bounds.size(), |frame| {
+            redrawn.set(true);
+
             if self.data.is_empty() {
                 frame.fill_text(Text {
                     content: "No income data yet".into(),
                     position: Point::new(frame.width() / 2.0, frame.height() / 2.0),
-                    color: Color::from_rgb(0.5, 0.5, 0.5),
+                    color: theme::MUTED,
                     size: 14.0.into(),
                     align_x: iced::advanced::text::Alignment::Center,
                     align_y: iced::alignment::Vertical::Center,
@@ -346,32 +1376,75 @@ impl<Msg> canvas::Program<Msg> for LineChart {
                 return;
             }
 
-            let max_bar = self.data.iter().map(|dp| dp.attended_days).max().unwrap() as f32;
-            let padding = 20.0;
+            let viewport = self.visible_viewport();
+            let visible = &self.data[viewport.range()];
+
+            let max_bar = visible
+                .iter()
+                .map(|dp| dp.attended_days.max(dp.scheduled_sessions))
+                .max()
+                .unwrap()
+                .max(1) as f32;
+            let padding = CHART_PADDING;
             let chart_width = frame.width() - padding * 2.0;
             let chart_height = frame.height() - padding * 2.5;
             let bar_scale = chart_height / (max_bar * 1.1);
 
-            let num_groups = self.data.len();
+            let num_groups = visible.len();
             let group_width = chart_width / num_groups as f32;
 
             // for axes
+            draw_y_ticks(
+                frame,
+                padding,
+                chart_width,
+                chart_height,
+                bar_scale,
+                &axis_ticks(max_bar * 1.1, 1.0),
+                |value| format!("{}", value),
+            );
             draw_axes(frame, padding, chart_width, chart_height);
+            frame.fill_text(Text {
+                content: "Sessions".into(),
+                position: Point::new(frame.width() - padding, 0.0),
+                color: theme::MUTED,
+                size: 10.0.into(),
+                align_x: iced::advanced::text::Alignment::Right,
+                ..Default::default()
+            });
+            draw_legend(
+                frame,
+                padding,
+                [("Attended", Color::BLACK), ("Scheduled", SCHEDULED_COLOR)].into_iter(),
+            );
+
+            let point_at = |i: usize, value: i32| {
+                let group_x = padding + (i as f32 * group_width);
+                Point::new(
+                    group_x + (group_width / 2.0),
+                    padding + chart_height - value as f32 * bar_scale,
+                )
+            };
 
-            let points: Vec<Point> = self
-                .data
+            let scheduled: Vec<Point> = visible
                 .iter()
                 .enumerate()
-                .map(|(i, dp)| {
-                    let data = dp.attended_days as f32;
-                    let group_x = padding + (i as f32 * group_width);
-                    let income_y_scale = data * bar_scale;
+                .map(|(i, dp)| point_at(i, dp.scheduled_sessions))
+                .collect();
 
-                    let point_x = group_x + (group_width / 2.0);
-                    let point_y = padding + chart_height - income_y_scale;
+            for window in scheduled.windows(2) {
+                frame.stroke(
+                    &Path::line(window[0], window[1]),
+                    Stroke::default()
+                        .with_color(SCHEDULED_COLOR)
+                        .with_width(1.5),
+                );
+            }
 
-                    Point::new(point_x, point_y)
-                })
+            let points: Vec<Point> = visible
+                .iter()
+                .enumerate()
+                .map(|(i, dp)| point_at(i, dp.attended_days))
                 .collect();
 
             // for points
@@ -389,15 +1462,36 @@ impl<Msg> canvas::Program<Msg> for LineChart {
                 );
             }
 
+            if self.show_smoothing {
+                let attended: Vec<f32> =
+                    self.data.iter().map(|dp| dp.attended_days as f32).collect();
+                let averages = rolling_average(&attended, SMOOTHING_WINDOW);
+                let smoothed_points: Vec<Point> = averages[viewport.range()]
+                    .iter()
+                    .zip(&points)
+                    .map(|(average, point)| {
+                        Point::new(point.x, padding + chart_height - average * bar_scale)
+                    })
+                    .collect();
+
+                draw_smoothed_line(frame, &smoothed_points);
+            }
+
             // for labels
-            for (i, data) in self.data.iter().enumerate() {
+            let label_stride = label_stride(group_width);
+
+            for (i, data) in visible.iter().enumerate() {
+                if !(viewport.first + i).is_multiple_of(label_stride) {
+                    continue;
+                }
+
                 let group_x = padding + (i as f32 * group_width);
 
                 let label_x = group_x + (group_width / 2.0);
                 let label_y = padding + chart_height + 10.0;
 
                 frame.fill_text(Text {
-                    content: data.month.clone(),
+                    content: data.label.clone(),
                     position: Point {
                         x: label_x,
                         y: label_y,
@@ -409,11 +1503,572 @@ impl<Msg> canvas::Program<Msg> for LineChart {
                 });
             }
         });
+
+        record_draw(&self.stats, started.elapsed(), redrawn.get());
+
+        let viewport = self.visible_viewport();
+        let hovered = state
+            .hovered
+            .and_then(|i| Some((i, self.data[viewport.range()].get(i)?)));
+        let Some((i, data)) = hovered else {
+            return vec![geometry];
+        };
+        let lines = [
+            data.label.clone(),
+            format!("Attended {}", data.attended_days),
+            format!("Scheduled {}", data.scheduled_sessions),
+        ];
+        vec![
+            geometry,
+            draw_hover(renderer, bounds, cursor, i, viewport.months, &lines),
+        ]
+    }
+}
+
+const ON_TRACK_COLOR: Color = Color::from_rgb(0.9, 0.4, 0.1);
+
+/// Income so far this month, day by day, against the dashed line the
+/// timetable says it should be on.
+struct CumulativeIncomeChart {
+    data: Vec<CumulativeIncome>,
+    money: MoneyFormat,
+    cache: canvas::Cache,
+    stats: Cell<RenderStats>,
+}
+
+impl CumulativeIncomeChart {
+    fn new(data: Vec<CumulativeIncome>, money: MoneyFormat) -> Self {
+        Self {
+            data,
+            money,
+            cache: canvas::Cache::new(),
+            stats: Cell::default(),
+        }
+    }
+
+    /// How far actual income is above (or below) the on-track line as of
+    /// the last day with actual income.
+    fn lead(&self) -> Option<f32> {
+        self.data
+            .iter()
+            .rev()
+            .find_map(|day| Some(day.actual? - day.on_track))
+    }
+}
+
+impl canvas::Program<Msg> for CumulativeIncomeChart {
+    type State = ();
+
+    fn draw(
+        &self,
+        _state: &Self::State,
+        renderer: &Renderer,
+        _theme: &Theme,
+        bounds: Rectangle,
+        _cursor: mouse::Cursor,
+    ) -> Vec<canvas::Geometry> {
+        let started = Instant::now();
+        let redrawn = Cell::new(false);
+
+        let geometry = self.cache.draw(renderer, bounds.size(), |frame| {
+            redrawn.set(true);
+
+            let max_value = self
+                .data
+                .iter()
+                .map(|day| day.on_track.max(day.actual.unwrap_or(0.0)))
+                .fold(0.0, f32::max);
+            if max_value <= 0.0 {
+                frame.fill_text(Text {
+                    content: "No income expected this month".into(),
+                    position: Point::new(frame.width() / 2.0, frame.height() / 2.0),
+                    color: theme::MUTED,
+                    size: 14.0.into(),
+                    align_x: iced::advanced::text::Alignment::Center,
+                    align_y: iced::alignment::Vertical::Center,
+                    ..Default::default()
+                });
+                return;
+            }
+
+            let padding = CHART_PADDING;
+            let chart_width = frame.width() - padding * 2.0;
+            let chart_height = frame.height() - padding * 2.5;
+            let scale = chart_height / (max_value * 1.1);
+            let day_width = chart_width / self.data.len() as f32;
+
+            draw_axes(frame, padding, chart_width, chart_height);
+            draw_legend(
+                frame,
+                padding,
+                [("Earned", Color::BLACK), ("On track", ON_TRACK_COLOR)].into_iter(),
+            );
+            if let Some(lead) = self.lead() {
+                let label = if lead >= 0.0 { "Ahead by" } else { "Behind by" };
+                frame.fill_text(Text {
+                    content: format!("{} {}", label, self.money.format(lead.abs())),
+                    position: Point::new(frame.width() - padding, 0.0),
+                    color: theme::MUTED,
+                    size: 10.0.into(),
+                    align_x: iced::advanced::text::Alignment::Right,
+                    ..Default::default()
+                });
+            }
+
+            let point_at = |i: usize, value: f32| {
+                Point::new(
+                    padding + (i as f32 + 0.5) * day_width,
+                    padding + chart_height - value * scale,
+                )
+            };
+
+            let on_track: Vec<Point> = self
+                .data
+                .iter()
+                .enumerate()
+                .map(|(i, day)| point_at(i, day.on_track))
+                .collect();
+            for window in on_track.windows(2) {
+                frame.stroke(
+                    &Path::line(window[0], window[1]),
+                    Stroke {
+                        line_dash: LineDash {
+                            segments: &[6.0, 4.0],
+                            offset: 0,
+                        },
+                        ..Stroke::default().with_color(ON_TRACK_COLOR).with_width(1.5)
+                    },
+                );
+            }
+
+            let earned: Vec<Point> = self
+                .data
+                .iter()
+                .enumerate()
+                .filter_map(|(i, day)| Some(point_at(i, day.actual?)))
+                .collect();
+            for window in earned.windows(2) {
+                frame.stroke(
+                    &Path::line(window[0], window[1]),
+                    Stroke::default().with_color(Color::BLACK).with_width(2.0),
+                );
+            }
+            if let Some(last) = earned.last() {
+                frame.fill(&Path::circle(*last, 4.0), Color::BLACK);
+            }
+
+            // Weekly day labels, plus the last day of the month.
+            for (i, day) in self.data.iter().enumerate() {
+                if !i.is_multiple_of(7) && i + 1 != self.data.len() {
+                    continue;
+                }
+
+                frame.fill_text(Text {
+                    content: day.date.day().to_string(),
+                    position: Point::new(
+                        padding + (i as f32 + 0.5) * day_width,
+                        padding + chart_height + 10.0,
+                    ),
+                    color: Color::BLACK,
+                    size: 11.0.into(),
+                    align_x: iced::advanced::text::Alignment::Center,
+                    ..Default::default()
+                });
+            }
+        });
+
+        record_draw(&self.stats, started.elapsed(), redrawn.get());
         vec![geometry]
     }
 }
 
-fn draw_axes(frame: &mut Frame, padding: f32, width: f32, height: f32) {
+/// Draw timings and cache behaviour of one chart, shown by the
+/// diagnostics overlay.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+struct RenderStats {
+    last_draw: Duration,
+    cache_hits: u32,
+    cache_misses: u32,
+}
+
+impl RenderStats {
+    fn hit_rate(&self) -> Option<f32> {
+        let draws = self.cache_hits + self.cache_misses;
+        (draws > 0).then(|| self.cache_hits as f32 / draws as f32)
+    }
+
+    fn summary(&self) -> String {
+        let hit_rate = self
+            .hit_rate()
+            .map(|rate| format!("{:.0}%", rate * 100.0))
+            .unwrap_or_else(|| "--".to_string());
+
+        format!(
+            "draw {} · cache {} hits / {} misses ({})",
+            format_duration(self.last_draw),
+            self.cache_hits,
+            self.cache_misses,
+            hit_rate
+        )
+    }
+}
+
+/// A cache miss is a draw where the cached geometry had to be rebuilt.
+fn record_draw(stats: &Cell<RenderStats>, elapsed: Duration, redrawn: bool) {
+    let mut updated = stats.get();
+    updated.last_draw = elapsed;
+    if redrawn {
+        updated.cache_misses += 1;
+    } else {
+        updated.cache_hits += 1;
+    }
+    stats.set(updated);
+}
+
+fn format_duration(duration: Duration) -> String {
+    format!("{:.2} ms", duration.as_secs_f64() * 1000.0)
+}
+
+pub const CHART_PADDING: f32 = 20.0;
+const MIN_VISIBLE_MONTHS: usize = 3;
+/// Horizontal room a month label needs before neighbouring labels overlap.
+const MIN_LABEL_SPACING: f32 = 44.0;
+
+/// Window of months shown by the dashboard charts. Both charts share it
+/// so zooming or panning one keeps the other aligned.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ChartViewport {
+    first: usize,
+    months: usize,
+}
+
+impl ChartViewport {
+    /// Resolves an optional zoomed viewport against the data, showing
+    /// every month when not zoomed.
+    fn visible(viewport: Option<ChartViewport>, total: usize) -> Self {
+        viewport
+            .unwrap_or(ChartViewport {
+                first: 0,
+                months: total,
+            })
+            .clamped(total)
+    }
+
+    fn clamped(self, total: usize) -> Self {
+        let months = self.months.clamp(MIN_VISIBLE_MONTHS.min(total), total);
+        let first = self.first.min(total - months);
+        Self { first, months }
+    }
+
+    fn range(&self) -> std::ops::Range<usize> {
+        self.first..self.first + self.months
+    }
+
+    /// Zooms around `anchor`, the cursor position as a fraction of the
+    /// plot width, so the month under the cursor stays put.
+    fn zoomed(self, zoom_in: bool, anchor: f32, total: usize) -> Self {
+        let step = (self.months / 6).max(1);
+        let months = if zoom_in {
+            self.months.saturating_sub(step)
+        } else {
+            self.months + step
+        };
+
+        let anchor_month = self.first as f32 + anchor * self.months as f32;
+        let first = (anchor_month - anchor * months as f32).round().max(0.0) as usize;
+
+        Self { first, months }.clamped(total)
+    }
+
+    fn panned(self, by: isize, total: usize) -> Self {
+        Self {
+            first: self.first.saturating_add_signed(by),
+            months: self.months,
+        }
+        .clamped(total)
+    }
+}
+
+pub struct ChartDrag {
+    start_x: f32,
+    viewport: ChartViewport,
+}
+
+#[derive(Default)]
+pub struct ChartState {
+    drag: Option<ChartDrag>,
+    /// The visible month under the cursor, shown with its exact figures.
+    hovered: Option<usize>,
+}
+
+/// Pans and zooms like `update_chart_viewport`, and keeps track of the
+/// month under the cursor while not dragging.
+fn update_chart(
+    state: &mut ChartState,
+    event: &canvas::Event,
+    bounds: Rectangle,
+    cursor: mouse::Cursor,
+    viewport: ChartViewport,
+    total: usize,
+) -> Option<canvas::Action<Msg>> {
+    let action = update_chart_viewport(&mut state.drag, event, bounds, cursor, viewport, total);
+
+    let hovered = match state.drag {
+        Some(_) => None,
+        None => hovered_month(bounds, cursor, viewport.months),
+    };
+    if hovered == state.hovered {
+        return action;
+    }
+    state.hovered = hovered;
+    Some(action.unwrap_or_else(canvas::Action::request_redraw))
+}
+
+/// Which of `months` evenly spaced months the cursor is over, within the
+/// plot area.
+fn hovered_month(bounds: Rectangle, cursor: mouse::Cursor, months: usize) -> Option<usize> {
+    let position = cursor.position_in(bounds)?;
+    let plot_width = bounds.width - CHART_PADDING * 2.0;
+    let plot_bottom = bounds.height - CHART_PADDING * 1.5;
+    if months == 0 || position.y < CHART_PADDING || position.y > plot_bottom {
+        return None;
+    }
+
+    let offset = (position.x - CHART_PADDING) / plot_width;
+    (0.0..1.0)
+        .contains(&offset)
+        .then_some((offset * months as f32) as usize)
+}
+
+/// Shades the hovered month and lists `lines` in a box beside the cursor,
+/// kept inside the chart.
+fn draw_hover(
+    renderer: &Renderer,
+    bounds: Rectangle,
+    cursor: mouse::Cursor,
+    index: usize,
+    months: usize,
+    lines: &[String],
+) -> canvas::Geometry {
+    let mut frame = Frame::new(renderer, bounds.size());
+    let plot_width = frame.width() - CHART_PADDING * 2.0;
+    let plot_height = frame.height() - CHART_PADDING * 2.5;
+    let group_width = plot_width / months.max(1) as f32;
+    let group_x = CHART_PADDING + index as f32 * group_width;
+
+    frame.fill(
+        &Path::rectangle(
+            Point::new(group_x, CHART_PADDING),
+            Size::new(group_width, plot_height),
+        ),
+        Color::from_rgba(0.0, 0.0, 0.0, 0.06),
+    );
+
+    let anchor = cursor
+        .position_in(bounds)
+        .unwrap_or(Point::new(group_x + group_width / 2.0, CHART_PADDING));
+    let line_height = 14.0;
+    // Rough text width at this size, as for the legend.
+    let longest = lines.iter().map(|line| line.chars().count()).max();
+    let width = longest.unwrap_or(0) as f32 * 6.5 + 12.0;
+    let height = lines.len() as f32 * line_height + 8.0;
+    let x = if anchor.x + 12.0 + width > frame.width() {
+        anchor.x - 12.0 - width
+    } else {
+        anchor.x + 12.0
+    };
+    let y = anchor.y.min(frame.height() - height).max(0.0);
+
+    let tooltip = Path::rectangle(Point::new(x, y), Size::new(width, height));
+    frame.fill(&tooltip, Color::WHITE);
+    frame.stroke(
+        &tooltip,
+        Stroke::default().with_color(theme::MUTED).with_width(1.0),
+    );
+    for (i, line) in lines.iter().enumerate() {
+        frame.fill_text(Text {
+            content: line.clone(),
+            position: Point::new(x + 6.0, y + 4.0 + i as f32 * line_height),
+            color: Color::BLACK,
+            size: 11.0.into(),
+            ..Default::default()
+        });
+    }
+
+    frame.into_geometry()
+}
+
+/// Mouse wheel zooms and dragging pans; both publish the new viewport
+/// so the dashboard can apply it to every chart.
+fn update_chart_viewport(
+    drag: &mut Option<ChartDrag>,
+    event: &canvas::Event,
+    bounds: Rectangle,
+    cursor: mouse::Cursor,
+    viewport: ChartViewport,
+    total: usize,
+) -> Option<canvas::Action<Msg>> {
+    let plot_width = bounds.width - CHART_PADDING * 2.0;
+
+    let changed = match event {
+        canvas::Event::Mouse(mouse::Event::WheelScrolled { delta }) => {
+            let position = cursor.position_in(bounds)?;
+            let (mouse::ScrollDelta::Lines { y, .. } | mouse::ScrollDelta::Pixels { y, .. }) =
+                delta;
+            if *y == 0.0 {
+                return None;
+            }
+
+            let anchor = ((position.x - CHART_PADDING) / plot_width).clamp(0.0, 1.0);
+            viewport.zoomed(*y > 0.0, anchor, total)
+        }
+        canvas::Event::Mouse(mouse::Event::ButtonPressed(mouse::Button::Left)) => {
+            let position = cursor.position_in(bounds)?;
+            *drag = Some(ChartDrag {
+                start_x: position.x,
+                viewport,
+            });
+            return Some(canvas::Action::capture());
+        }
+        canvas::Event::Mouse(mouse::Event::CursorMoved { position }) => {
+            let start = drag.as_ref()?;
+            let month_width = plot_width / start.viewport.months as f32;
+            let months_moved = ((start.start_x - (position.x - bounds.x)) / month_width).round();
+
+            start.viewport.panned(months_moved as isize, total)
+        }
+        canvas::Event::Mouse(mouse::Event::ButtonReleased(mouse::Button::Left)) => {
+            drag.take()?;
+            return None;
+        }
+        _ => return None,
+    };
+
+    (changed != viewport)
+        .then(|| canvas::Action::publish(Msg::ChartViewportChanged(changed)).and_capture())
+}
+
+fn chart_mouse_interaction(
+    state: &ChartState,
+    bounds: Rectangle,
+    cursor: mouse::Cursor,
+    is_zoomed: bool,
+) -> mouse::Interaction {
+    if state.drag.is_some() {
+        mouse::Interaction::Grabbing
+    } else if is_zoomed && cursor.is_over(bounds) {
+        mouse::Interaction::Grab
+    } else {
+        mouse::Interaction::default()
+    }
+}
+
+/// Only every n-th month is labelled when months get too narrow to fit one.
+pub fn label_stride(group_width: f32) -> usize {
+    (MIN_LABEL_SPACING / group_width).ceil().max(1.0) as usize
+}
+
+const SMOOTHING_WINDOW: usize = 3;
+
+/// Trailing average over `window` points; the first points average
+/// whatever history is available.
+fn rolling_average(values: &[f32], window: usize) -> Vec<f32> {
+    (0..values.len())
+        .map(|i| {
+            let start = (i + 1).saturating_sub(window);
+            let slice = &values[start..=i];
+            slice.iter().sum::<f32>() / slice.len() as f32
+        })
+        .collect()
+}
+
+fn draw_smoothed_line(frame: &mut Frame, points: &[Point]) {
+    for window in points.windows(2) {
+        frame.stroke(
+            &Path::line(window[0], window[1]),
+            Stroke {
+                line_dash: LineDash {
+                    segments: &[6.0, 4.0],
+                    offset: 0,
+                },
+                ..Stroke::default()
+                    .with_color(Color::from_rgb(0.9, 0.4, 0.1))
+                    .with_width(2.0)
+            },
+        );
+    }
+}
+
+const Y_TICKS: usize = 4;
+const GRIDLINE_COLOR: Color = Color::from_rgba(0.0, 0.0, 0.0, 0.08);
+
+/// About `Y_TICKS` round values up to `top`, each a step of 1, 2 or 5
+/// times a power of ten and no finer than `min_step`.
+fn axis_ticks(top: f32, min_step: f32) -> Vec<f32> {
+    if top <= 0.0 {
+        return Vec::new();
+    }
+
+    // Rounding the step up only ever leaves fewer ticks, so aim for one more.
+    let rough = (top / (Y_TICKS + 1) as f32).max(min_step);
+    let magnitude = 10f32.powf(rough.log10().floor());
+    let step = [1.0, 2.0, 5.0, 10.0]
+        .into_iter()
+        .map(|factor| factor * magnitude)
+        .find(|step| *step >= rough)
+        .unwrap_or(rough);
+
+    (1..)
+        .map(|i| i as f32 * step)
+        .take_while(|tick| *tick <= top)
+        .collect()
+}
+
+/// A light gridline across the plot at each tick, labelled just above the
+/// line by the y-axis.
+fn draw_y_ticks(
+    frame: &mut Frame,
+    padding: f32,
+    width: f32,
+    height: f32,
+    scale: f32,
+    ticks: &[f32],
+    label: impl Fn(f32) -> String,
+) {
+    for tick in ticks {
+        let y = padding + height - tick * scale;
+        frame.stroke(
+            &Path::line(Point::new(padding, y), Point::new(padding + width, y)),
+            Stroke::default().with_color(GRIDLINE_COLOR).with_width(1.0),
+        );
+        frame.fill_text(Text {
+            content: label(*tick),
+            position: Point::new(padding + 3.0, y - 11.0),
+            color: theme::MUTED,
+            size: 9.0.into(),
+            ..Default::default()
+        });
+    }
+}
+
+/// The bar's value centred above `top`, left out when wider than `room`.
+fn draw_bar_label(frame: &mut Frame, top: Point, room: f32, label: String) {
+    // Rough label width at this text size.
+    if label.chars().count() as f32 * 5.0 > room {
+        return;
+    }
+
+    frame.fill_text(Text {
+        content: label,
+        position: Point::new(top.x, top.y - 11.0),
+        color: Color::BLACK,
+        size: 9.0.into(),
+        align_x: iced::advanced::text::Alignment::Center,
+        ..Default::default()
+    });
+}
+
+pub fn draw_axes(frame: &mut Frame, padding: f32, width: f32, height: f32) {
     // y-axis
     let y_axis = Path::line(
         Point::new(padding, padding),
@@ -421,9 +2076,7 @@ fn draw_axes(frame: &mut Frame, padding: f32, width: f32, height: f32) {
     );
     frame.stroke(
         &y_axis,
-        Stroke::default()
-            .with_color(Color::from_rgb(0.5, 0.5, 0.5))
-            .with_width(2.0),
+        Stroke::default().with_color(theme::MUTED).with_width(2.0),
     );
 
     // x-axis
@@ -433,102 +2086,434 @@ fn draw_axes(frame: &mut Frame, padding: f32, width: f32, height: f32) {
     );
     frame.stroke(
         &x_axis,
-        Stroke::default()
-            .with_color(Color::from_rgb(0.5, 0.5, 0.5))
-            .with_width(2.0),
+        Stroke::default().with_color(theme::MUTED).with_width(2.0),
     );
 }
 
-fn view_dashboard(state: &DashboardState) -> Element<'_, Msg> {
-    struct CardInfo {
-        title: String,
-        value: String,
-        trend: Option<(String, Option<bool>)>,
-        hovered_dashboard: Option<usize>,
-        variant: DashboardCardVariant,
-    }
+/// What the dashboard shows, already formatted, built from state before
+/// any widgets so the numbers and labels can be checked on their own.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DashboardViewModel {
+    pub cards: Vec<MetricCardModel>,
+    pub today: Vec<TodaySessionModel>,
+    pub needs_attention: Vec<UnloggedSessionModel>,
+    pub waitlist: Vec<WaitlistSuggestionModel>,
+    pub receivables: Vec<ReceivableModel>,
+    pub weekdays: Vec<WeekdayModel>,
+    pub subjects: Vec<SubjectIncomeModel>,
+    pub year_at_a_glance: Vec<MonthCellModel>,
+}
 
-    let summary = &state.dashboard_summary;
+/// A past session that was neither logged nor cancelled.
+#[derive(Debug, Clone, PartialEq)]
+pub struct UnloggedSessionModel {
+    pub student: String,
+    pub when: String,
+}
 
-    let attendance_rate = if summary.attendance.total_scheduled_sessions > 0 {
-        format!(
-            "{:.0}%",
-            summary.attendance.total_actual_sessions as f32
-                / summary.attendance.total_scheduled_sessions as f32
-                * 100.0
-        )
-    } else {
-        "--".to_string()
-    };
+/// A free slot to offer someone on the waitlist.
+#[derive(Debug, Clone, PartialEq)]
+pub struct WaitlistSuggestionModel {
+    pub slot: String,
+    pub prospect: String,
+    pub subject: String,
+}
+
+/// A student's account for the current month. The balance carries over
+/// from earlier months and reads as credit when they have overpaid.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReceivableModel {
+    pub student: String,
+    pub billed: String,
+    pub paid: String,
+    pub balance: String,
+}
+
+/// What one subject brought in this month. `share` is its part of the
+/// month's income, from 0 to 1.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SubjectIncomeModel {
+    pub subject: String,
+    pub income: String,
+    pub share: f32,
+}
+
+/// One month of the year-at-a-glance view. `shade` runs from 0 for the
+/// year's lowest income to 1 for its highest.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MonthCellModel {
+    pub label: String,
+    pub income: String,
+    pub attendance: String,
+    pub shade: f32,
+    pub year_month: (i32, u32),
+}
 
-    let trend_format = |trend: &NumberTrend| -> (String, Option<bool>) {
-        match trend {
-            NumberTrend::NoData => (format!("{:.1}%", 0.0), None),
-            NumberTrend::Trend {
-                trend_direction,
-                percentage_change,
-            } => match trend_direction {
-                TrendDirection::Up => (format!("{:.1}%", percentage_change), Some(true)),
-                TrendDirection::Down => (format!("{:.1}%", percentage_change), Some(true)),
+/// Sessions held against scheduled on one weekday this month.
+#[derive(Debug, Clone, PartialEq)]
+pub struct WeekdayModel {
+    pub label: String,
+    pub held: i32,
+    pub scheduled: i32,
+    pub often_skipped: bool,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TodaySessionModel {
+    pub time: String,
+    pub student: String,
+    /// How long until the session starts, e.g. "in 2h 15m".
+    pub countdown: Option<String>,
+    pub in_session: bool,
+    pub confirmed: bool,
+    pub no_show: bool,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct MetricCardModel {
+    pub title: String,
+    pub value: String,
+    pub trend: Option<TrendChip>,
+    pub variant: DashboardCardVariant,
+}
+
+impl DashboardViewModel {
+    pub fn from_state(state: &DashboardState) -> Self {
+        let summary = &state.dashboard_summary;
+
+        let attendance_rate = if summary.attendance.total_scheduled_sessions > 0 {
+            format!(
+                "{:.0}%",
+                summary.attendance.total_actual_sessions as f32
+                    / summary.attendance.total_scheduled_sessions as f32
+                    * 100.0
+            )
+        } else {
+            "--".to_string()
+        };
+
+        let trend_chip = |trend: &NumberTrend, unit: TrendUnit| {
+            format_trend_chip(trend, unit, state.trend_display, &state.money)
+        };
+
+        let mut cards = vec![
+            MetricCardModel {
+                title: "Attendance Rate".into(),
+                value: attendance_rate,
+                trend: Some(trend_chip(&summary.attendance.trend, TrendUnit::Sessions)),
+                variant: DashboardCardVariant::Attendance,
+            },
+            MetricCardModel {
+                title: "Actual Earnings".into(),
+                value: state.money.format(summary.actual_revenue.amount),
+                trend: Some(trend_chip(
+                    &summary.actual_revenue.trend,
+                    TrendUnit::Currency,
+                )),
+                variant: DashboardCardVariant::ActualEarnings,
+            },
+            MetricCardModel {
+                title: "Potential Earnings".into(),
+                value: state.money.format(summary.potential_revenue.amount),
+                trend: None,
+                variant: DashboardCardVariant::PotentialEarnings,
+            },
+            MetricCardModel {
+                title: "Revenue Lost".into(),
+                value: state.money.format(summary.lost_revenue.amount),
+                trend: Some(trend_chip(&summary.lost_revenue.trend, TrendUnit::Currency)),
+                variant: DashboardCardVariant::RevenueLost,
+            },
+            MetricCardModel {
+                title: "Sessions Left".into(),
+                value: summary.remaining.sessions.to_string(),
+                trend: None,
+                variant: DashboardCardVariant::Remaining,
+            },
+            MetricCardModel {
+                title: "Income Left".into(),
+                value: state.money.format(summary.remaining.income),
+                trend: None,
+                variant: DashboardCardVariant::Remaining,
             },
+            MetricCardModel {
+                title: "Active Students".into(),
+                value: summary.students.active.to_string(),
+                trend: Some(trend_chip(
+                    &summary.students.active_trend,
+                    TrendUnit::Students,
+                )),
+                variant: DashboardCardVariant::Students,
+            },
+            MetricCardModel {
+                title: "Gained / Lost".into(),
+                value: format!("+{} / -{}", summary.students.gained, summary.students.lost),
+                trend: Some(trend_chip(
+                    &summary.students.gained_trend,
+                    TrendUnit::Students,
+                )),
+                variant: DashboardCardVariant::Students,
+            },
+            MetricCardModel {
+                title: "Revenue / Student".into(),
+                value: state.money.format(summary.averages.revenue_per_student),
+                trend: Some(trend_chip(&summary.average_trends[0], TrendUnit::Currency)),
+                variant: DashboardCardVariant::Averages,
+            },
+            MetricCardModel {
+                title: "Rate / Session".into(),
+                value: state.money.format(summary.averages.revenue_per_session),
+                trend: Some(trend_chip(&summary.average_trends[1], TrendUnit::Currency)),
+                variant: DashboardCardVariant::Averages,
+            },
+            MetricCardModel {
+                title: "Sessions / Student".into(),
+                value: format!("{:.1}", summary.averages.sessions_per_student),
+                trend: Some(trend_chip(
+                    &summary.average_trends[2],
+                    TrendUnit::AverageSessions,
+                )),
+                variant: DashboardCardVariant::Averages,
+            },
+        ];
+
+        let kpi_inputs = summary.kpi_inputs();
+        cards.extend(state.custom_kpis.iter().map(|kpi| {
+            MetricCardModel {
+                title: kpi.name.clone(),
+                value: kpi
+                    .expression
+                    .evaluate(&kpi_inputs)
+                    .map(|value| kpi.format.format_value(value, &state.money))
+                    .unwrap_or_else(|_| "--".to_string()),
+                trend: None,
+                variant: DashboardCardVariant::Custom,
+            }
+        }));
+
+        let today = state
+            .todays_sessions
+            .iter()
+            .map(|session| {
+                let student = state
+                    .students
+                    .iter()
+                    .find(|student| student.id == session.student_id);
+                let timing = student
+                    .map(|student| SessionTiming::of(student, session.session_at, state.now));
+
+                TodaySessionModel {
+                    time: session.session_at.format("%-I:%M %p").to_string(),
+                    student: session.student_name.clone(),
+                    countdown: timing.and_then(|timing| timing.countdown()),
+                    in_session: timing == Some(SessionTiming::InSession),
+                    confirmed: student.is_some_and(|student| {
+                        student.confirmed_sessions.contains(&session.session_at)
+                    }),
+                    no_show: state.no_shows.contains(&session.key()),
+                }
+            })
+            .collect();
+
+        let needs_attention = state
+            .unlogged_sessions
+            .iter()
+            .map(|session| UnloggedSessionModel {
+                student: state
+                    .students
+                    .iter()
+                    .find(|student| student.id == session.student_id)
+                    .map(|student| student.name.full_name())
+                    .unwrap_or_else(|| session.student_id.clone()),
+                when: session.at.format("%a %-d %b, %-I:%M %p").to_string(),
+            })
+            .collect();
+
+        let waitlist = state
+            .waitlist_matches
+            .iter()
+            .map(|matched| WaitlistSuggestionModel {
+                slot: matched.slot.to_string(),
+                prospect: matched.prospect.clone(),
+                subject: matched.subject.to_string(),
+            })
+            .collect();
+
+        let receivables = match state.today {
+            Some(today) => state
+                .students
+                .iter()
+                .map(|student| {
+                    let account = state.ledger.account_for(student, &state.locations, today);
+                    let closing = account.closing();
+
+                    ReceivableModel {
+                        student: student.name.full_name(),
+                        billed: state.money.format(account.opening + account.billed),
+                        paid: state.money.format(account.paid),
+                        balance: if closing < 0.0 {
+                            format!("{} credit", state.money.format(-closing))
+                        } else {
+                            format!("{} due", state.money.format(closing))
+                        },
+                    }
+                })
+                .collect(),
+            None => Vec::new(),
+        };
+
+        let weekdays = state
+            .weekday_sessions
+            .iter()
+            .map(|day| WeekdayModel {
+                label: day.weekday.to_string(),
+                held: day.held,
+                scheduled: day.scheduled,
+                often_skipped: day.is_often_skipped(),
+            })
+            .collect();
+
+        let month_income: f32 = state.subject_income.iter().map(|(_, income)| income).sum();
+        let subjects = state
+            .subject_income
+            .iter()
+            .map(|(subject, income)| SubjectIncomeModel {
+                subject: subject.to_string(),
+                income: state.money.format(*income),
+                share: if month_income > 0.0 {
+                    income / month_income
+                } else {
+                    0.0
+                },
+            })
+            .collect();
+
+        let months = state
+            .glance_year
+            .and_then(|year| state.year_at_a_glance.get(&year))
+            .map(Vec::as_slice)
+            .unwrap_or_default();
+        let highest = months.iter().map(|month| month.income).fold(0.0, f32::max);
+        let year_at_a_glance = months
+            .iter()
+            .map(|month| MonthCellModel {
+                label: NaiveDate::from_ymd_opt(month.year, month.month, 1)
+                    .map(|date| date.format("%b").to_string())
+                    .unwrap_or_default(),
+                income: state.money.format(month.income),
+                attendance: month
+                    .attendance_rate
+                    .map(|rate| format!("{:.0}% held", rate * 100.0))
+                    .unwrap_or_else(|| "--".to_string()),
+                shade: if highest > 0.0 {
+                    month.income / highest
+                } else {
+                    0.0
+                },
+                year_month: (month.year, month.month),
+            })
+            .collect();
+
+        Self {
+            cards,
+            today,
+            needs_attention,
+            waitlist,
+            receivables,
+            weekdays,
+            subjects,
+            year_at_a_glance,
         }
-    };
+    }
+}
 
-    let card_data = [
-        CardInfo {
-            title: "Attendance Rate".into(),
-            value: attendance_rate,
-            trend: Some(trend_format(&summary.actual_revenue.trend)),
-            hovered_dashboard: state.hovered_dashboard_card,
-            variant: DashboardCardVariant::Attendance,
-        },
-        CardInfo {
-            title: "Actual Earnings".into(),
-            value: format!("GHS {:.2}", summary.actual_revenue.amount),
-            trend: Some(trend_format(&summary.actual_revenue.trend)),
-            hovered_dashboard: state.hovered_dashboard_card,
-            variant: DashboardCardVariant::ActualEarnings,
-        },
-        CardInfo {
-            title: "Potential Earnings".into(),
-            value: format!("GHS {:.2}", summary.potential_revenue.amount),
-            trend: None,
-            hovered_dashboard: state.hovered_dashboard_card,
-            variant: DashboardCardVariant::PotentialEarnings,
-        },
-        CardInfo {
-            title: "Revenue Lost".into(),
-            value: format!("GHS {:.2}", summary.lost_revenue.amount),
-            trend: None,
-            hovered_dashboard: state.hovered_dashboard_card,
-            variant: DashboardCardVariant::RevenueLost,
+fn format_trend_chip(
+    trend: &NumberTrend,
+    unit: TrendUnit,
+    display: TrendDisplay,
+    money: &MoneyFormat,
+) -> TrendChip {
+    match trend {
+        NumberTrend::NoData => TrendChip {
+            label: match display {
+                TrendDisplay::Percentage => format!("{:.1}%", 0.0),
+                TrendDisplay::Absolute => unit.format_delta(0.0, money),
+            },
+            is_positive: None,
+            detail: "No data for last month".to_string(),
         },
-    ];
+        NumberTrend::Trend {
+            trend_direction,
+            percentage_change,
+            absolute_change,
+        } => {
+            let (sign, is_positive) = match trend_direction {
+                TrendDirection::Up => ("+", true),
+                TrendDirection::Down => ("-", false),
+            };
+            let percentage = format!("{:.1}%", percentage_change);
+            let absolute = unit.format_delta(*absolute_change, money);
+
+            TrendChip {
+                label: match display {
+                    TrendDisplay::Percentage => percentage.clone(),
+                    TrendDisplay::Absolute => absolute.clone(),
+                },
+                is_positive: Some(is_positive),
+                detail: format!(
+                    "{}{} ({}{}) vs last month",
+                    sign, absolute, sign, percentage
+                ),
+            }
+        }
+    }
+}
+
+fn view_dashboard(state: &DashboardState) -> Element<'_, Msg> {
+    let view_model = DashboardViewModel::from_state(state);
+    let today_section = view_today(state, view_model.today);
+    let needs_attention_section = view_needs_attention(state, view_model.needs_attention);
+    let waitlist_section = view_waitlist_suggestions(view_model.waitlist);
+    let receivables_section = view_receivables(state, view_model.receivables);
+    let weekdays_section = view_weekdays(view_model.weekdays);
+    let subjects_section = view_subject_income(view_model.subjects);
+    let glance_section = view_year_at_a_glance(state, view_model.year_at_a_glance);
+    let card_data = view_model.cards;
+
+    let card_rows = card_data.len().div_ceil(4) as f32;
 
     let summary_section_title = text("Summary").size(14).font(Font {
         weight: font::Weight::Medium,
         ..Default::default()
     });
 
-    let summary_cards_row = grid(card_data.iter().enumerate().map(|(index, card)| {
-        let is_hovered = card.hovered_dashboard == Some(index);
-        metric_card(
-            card.title.clone(),
-            card.value.to_owned(),
-            card.trend.clone(),
+    let summary_cards_row = grid(card_data.into_iter().enumerate().map(|(index, card)| {
+        let is_hovered = state.hovered_dashboard_card == Some(index);
+        let variant = card.variant;
+        let card = metric_card(
+            card.title,
+            card.value,
+            card.trend,
             is_hovered,
-            Some(index),
-            card.variant,
-        )
+            move |theme| card_background(theme, variant),
+            Msg::ToggleTrendDisplay,
+        );
+
+        mouse_area(card)
+            .on_enter(Msg::DashboardCardHovered(Some(index)))
+            .on_exit(Msg::DashboardCardHovered(None))
+            .into()
     }))
     .columns(4)
     .width(800)
-    .height(Length::Fixed(100.0))
+    .height(Length::Fixed(card_rows * 100.0 + (card_rows - 1.0) * 16.0))
     .spacing(16);
 
     let summary_section = column![
         summary_section_title,
+        view_month_goal(state),
         container(summary_cards_row).align_x(Center).max_width(900),
+        view_kpi_builder(state),
     ]
     .spacing(12);
 
@@ -542,17 +2527,56 @@ fn view_dashboard(state: &DashboardState) -> Element<'_, Msg> {
     let graphs = Grid::new()
         .push(attendance_trend_chart)
         .push(potential_vs_actual_chart)
+        .push(view_cumulative_chart(state))
         .columns(3)
         .height(Length::Fixed(300.0))
         .width(1300)
         .spacing(16);
 
-    let graph_section = column![graphs_section_title, graphs,].spacing(12);
+    let smoothing_toggle = checkbox(state.smooth_charts)
+        .label("3-month average")
+        .text_size(12)
+        .on_toggle(Msg::SmoothingToggled);
+
+    let reset_zoom = button(text("Reset zoom").size(12))
+        .style(button::text)
+        .on_press_maybe(state.chart_viewport.map(|_| Msg::ResetChartZoom));
+
+    let diagnostics_toggle = checkbox(state.show_diagnostics)
+        .label("Diagnostics")
+        .text_size(12)
+        .on_toggle(Msg::DiagnosticsToggled);
+
+    let mut graph_header = row![
+        graphs_section_title,
+        smoothing_toggle,
+        reset_zoom,
+        diagnostics_toggle
+    ]
+    .spacing(20)
+    .align_y(Center);
+
+    if state.show_diagnostics {
+        let last_update = state
+            .last_update
+            .map(format_duration)
+            .unwrap_or_else(|| "--".to_string());
+        graph_header = graph_header.push(text(format!("update {}", last_update)).size(12));
+    }
+
+    let graph_section = column![graph_header, graphs].spacing(12);
 
     let content = global_content_container(
         Column::new()
             .spacing(40)
+            .push(today_section)
+            .push(needs_attention_section)
+            .push(waitlist_section)
             .push(summary_section)
+            .push(receivables_section)
+            .push(weekdays_section)
+            .push(subjects_section)
+            .push(glance_section)
             .push(graph_section),
     )
     .width(Length::Fill)
@@ -563,148 +2587,893 @@ fn view_dashboard(state: &DashboardState) -> Element<'_, Msg> {
     content_with_header.into()
 }
 
-fn view_trend_chart(state: &DashboardState) -> Element<'_, Msg> {
-    let chart = Canvas::new(&state.linechart)
-        .width(Length::Fill)
-        .height(Length::Fill);
-
-    container(column![
-        container(text!("Attendance Rate").size(20)).center_x(Length::Fill),
-        chart
-    ])
-    // .width(Length::FillPortion(2))
-    // .height(Length::Fixed(400.0))
-    .padding(20)
-    .style(|theme: &Theme| {
-        let palette = theme.extended_palette();
-
-        container::Style {
-            background: Some(palette.background.weak.color.into()),
+/// How far through this month's timetable the tutor is.
+fn view_month_goal(state: &DashboardState) -> Element<'_, Msg> {
+    let (held, goal) = state.month_progress;
+    let fill = state.goal_ring.animate_wrapped(Instant::now());
+    let percent = if goal > 0 {
+        held as f32 / goal as f32 * 100.0
+    } else {
+        0.0
+    };
+    let detail = match goal {
+        0 => "No sessions on the timetable this month".to_string(),
+        _ => format!("{} of {} sessions held this month", held, goal),
+    };
+
+    row![
+        progress_ring(fill, 64.0, theme::ACCENT, format!("{:.0}%", percent)),
+        column![
+            text("Month goal").size(12).font(Font {
+                weight: font::Weight::Medium,
+                ..Default::default()
+            }),
+            text(detail).size(12),
+        ]
+        .spacing(4),
+    ]
+    .spacing(16)
+    .align_y(Center)
+    .into()
+}
+
+/// Today's sessions, with the ones still waiting on a confirmation
+/// called out so the tutor knows who to chase.
+fn view_today(state: &DashboardState, sessions: Vec<TodaySessionModel>) -> Element<'_, Msg> {
+    let confirmed = sessions.iter().filter(|session| session.confirmed).count();
+
+    let title = text("Today").size(14).font(Font {
+        weight: font::Weight::Medium,
+        ..Default::default()
+    });
+    let status = if sessions.is_empty() {
+        "No sessions today".to_string()
+    } else {
+        format!("{} of {} confirmed", confirmed, sessions.len())
+    };
+
+    let rows = Column::new()
+        .extend(
+            sessions
+                .into_iter()
+                .zip(&state.todays_sessions)
+                .map(|(model, session)| {
+                    let key = session.key();
+                    let details = row![
+                        text(model.time).size(14).width(Length::Fixed(80.0)),
+                        text(model.student).size(14).width(Length::Fixed(180.0)),
+                        container(session_countdown(model.countdown, model.in_session))
+                            .width(Length::Fixed(90.0)),
+                    ];
+
+                    if model.no_show {
+                        return details
+                            .push(status_text("No-show", Color::from_rgb(0.8, 0.1, 0.1)))
+                            .spacing(16)
+                            .align_y(Center)
+                            .into();
+                    }
+
+                    let awaiting =
+                        (!model.confirmed).then(|| status_text("Not confirmed", theme::WARNING));
+                    let confirm_key = key.clone();
+
+                    details
+                        .push(
+                            checkbox(model.confirmed)
+                                .label("Confirmed")
+                                .text_size(12)
+                                .on_toggle(move |confirmed| {
+                                    Msg::SessionConfirmationToggled(confirm_key.clone(), confirmed)
+                                }),
+                        )
+                        .push(
+                            button(text("No-show").size(12))
+                                .style(button::text)
+                                .on_press(Msg::MarkNoShow(key)),
+                        )
+                        .push(awaiting)
+                        .spacing(16)
+                        .align_y(Center)
+                        .into()
+                }),
+        )
+        .spacing(6);
+
+    let mut section = column![
+        row![title, text(status).size(12)]
+            .spacing(20)
+            .align_y(Center),
+        rows
+    ]
+    .spacing(12);
+
+    if let Some(prompt) = &state.no_show_prompt {
+        section = section.push(view_no_show_prompt(state, prompt));
+    }
+
+    section.push(view_no_show_charges(state)).into()
+}
+
+/// Past sessions nobody logged, each with a quick way to log or cancel it.
+/// Left out altogether while there is nothing to chase.
+fn view_needs_attention(
+    state: &DashboardState,
+    sessions: Vec<UnloggedSessionModel>,
+) -> Option<Element<'_, Msg>> {
+    if sessions.is_empty() {
+        return None;
+    }
+
+    let title = text("Needs attention").size(14).font(Font {
+        weight: font::Weight::Medium,
+        ..Default::default()
+    });
+    let status = if sessions.len() == 1 {
+        "1 past session not logged".to_string()
+    } else {
+        format!("{} past sessions not logged", sessions.len())
+    };
+
+    let rows = Column::new()
+        .extend(
+            sessions
+                .into_iter()
+                .zip(&state.unlogged_sessions)
+                .map(|(model, session)| {
+                    let key = (session.student_id.clone(), session.at);
+
+                    row![
+                        text(model.when).size(14).width(Length::Fixed(160.0)),
+                        text(model.student).size(14).width(Length::Fixed(180.0)),
+                        button(text("Log").size(12)).on_press(Msg::LogUnloggedSession(key.clone())),
+                        button(text("Cancelled").size(12))
+                            .style(button::text)
+                            .on_press(Msg::CancelUnloggedSession(key)),
+                    ]
+                    .spacing(16)
+                    .align_y(Center)
+                    .into()
+                }),
+        )
+        .spacing(6);
+
+    Some(
+        column![
+            row![title, text(status).size(12)]
+                .spacing(20)
+                .align_y(Center),
+            rows
+        ]
+        .spacing(12)
+        .into(),
+    )
+}
+
+/// Free slots that someone on the waitlist asked for, to offer them.
+/// Left out while nothing matches.
+fn view_waitlist_suggestions<'a>(
+    suggestions: Vec<WaitlistSuggestionModel>,
+) -> Option<Element<'a, Msg>> {
+    if suggestions.is_empty() {
+        return None;
+    }
+
+    let title = text("Waitlist").size(14).font(Font {
+        weight: font::Weight::Medium,
+        ..Default::default()
+    });
+    let status = if suggestions.len() == 1 {
+        "1 match for a free slot".to_string()
+    } else {
+        format!("{} matches for free slots", suggestions.len())
+    };
+
+    let rows = Column::new()
+        .extend(suggestions.into_iter().map(|suggestion| {
+            row![
+                text(suggestion.slot).size(14).width(Length::Fixed(160.0)),
+                text(suggestion.prospect)
+                    .size(14)
+                    .width(Length::Fixed(180.0)),
+                text(suggestion.subject).size(12),
+            ]
+            .spacing(16)
+            .align_y(Center)
+            .into()
+        }))
+        .spacing(6);
+
+    Some(
+        column![
+            row![title, text(status).size(12)]
+                .spacing(20)
+                .align_y(Center),
+            rows
+        ]
+        .spacing(12)
+        .into(),
+    )
+}
+
+fn status_text<'a>(label: &'a str, color: Color) -> iced::widget::Text<'a> {
+    text(label)
+        .size(12)
+        .style(move |_theme: &Theme| text::Style { color: Some(color) })
+}
+
+fn view_no_show_prompt<'a>(
+    state: &'a DashboardState,
+    prompt: &'a NoShowPrompt,
+) -> Element<'a, Msg> {
+    let student_name = state
+        .todays_sessions
+        .iter()
+        .find(|session| session.key() == prompt.session)
+        .map(|session| session.student_name.as_str())
+        .unwrap_or_default();
+
+    let form = row![
+        text(format!(
+            "Charge {} a no-show fee of {}",
+            student_name, state.money.currency
+        ))
+        .size(12),
+        text_input("0.00", &prompt.amount)
+            .on_input(Msg::NoShowChargeChanged)
+            .on_submit(Msg::ApplyNoShowCharge)
+            .size(12)
+            .width(Length::Fixed(80.0)),
+        button(text("Apply").size(12)).on_press(Msg::ApplyNoShowCharge),
+        button(text("Waive").size(12))
+            .style(button::text)
+            .on_press(Msg::WaiveNoShowCharge),
+    ]
+    .spacing(10)
+    .align_y(Center);
+
+    match &prompt.error {
+        Some(error) => column![form, status_text(error, theme::DANGER)]
+            .spacing(4)
+            .into(),
+        None => form.into(),
+    }
+}
+
+/// The no-show fee setting, and fees waiting to go on the next invoice.
+fn view_no_show_charges(state: &DashboardState) -> Element<'_, Msg> {
+    let policy = row![
+        text(format!("No-show fee {}", state.money.currency)).size(12),
+        text_input("0.00", &state.no_show_fee_input)
+            .on_input(Msg::NoShowFeeChanged)
+            .size(12)
+            .width(Length::Fixed(80.0)),
+        checkbox(state.ledger.no_show_policy().auto_apply)
+            .label("Charge automatically")
+            .text_size(12)
+            .on_toggle(Msg::NoShowAutoApplyToggled),
+    ]
+    .spacing(10)
+    .align_y(Center);
+
+    let charges = Column::new()
+        .extend(state.ledger.uninvoiced().map(|charge| {
+            text(format!(
+                "For next invoice: {} · {} {} · {}",
+                charge.student_name,
+                charge.reason,
+                charge.session_at.format("%a %-d %b"),
+                state.money.format(charge.amount)
+            ))
+            .size(12)
+            .into()
+        }))
+        .spacing(4);
+
+    column![policy, charges].spacing(6).into()
+}
+
+/// What each student owes this month, including anything carried over,
+/// with a field to record money received in any amount.
+fn view_receivables(state: &DashboardState, receivables: Vec<ReceivableModel>) -> Element<'_, Msg> {
+    let title = text("Receivables").size(14).font(Font {
+        weight: font::Weight::Medium,
+        ..Default::default()
+    });
+
+    let rows = Column::new()
+        .extend(
+            receivables
+                .into_iter()
+                .zip(&state.students)
+                .map(|(model, student)| {
+                    let input_id = student.id.clone();
+                    let amount = state
+                        .payment_inputs
+                        .get(&student.id)
+                        .map(String::as_str)
+                        .unwrap_or_default();
+
+                    let account = row![
+                        text(model.student).size(14).width(Length::Fixed(180.0)),
+                        text(format!("Billed {}", model.billed))
+                            .size(12)
+                            .width(Length::Fixed(140.0)),
+                        text(format!("Paid {}", model.paid))
+                            .size(12)
+                            .width(Length::Fixed(130.0)),
+                        text(model.balance).size(12).width(Length::Fixed(140.0)),
+                        text_input("Amount", amount)
+                            .on_input(move |amount| {
+                                Msg::PaymentAmountChanged(input_id.clone(), amount)
+                            })
+                            .on_submit(Msg::RecordPayment(student.id.clone()))
+                            .size(12)
+                            .width(Length::Fixed(80.0)),
+                        button(text("Record payment").size(12))
+                            .on_press(Msg::RecordPayment(student.id.clone())),
+                    ]
+                    .spacing(10)
+                    .align_y(Center);
+
+                    match &state.payment_error {
+                        Some((student_id, error)) if *student_id == student.id => {
+                            column![account, status_text(error, theme::DANGER)]
+                                .spacing(4)
+                                .into()
+                        }
+                        _ => account.into(),
+                    }
+                }),
+        )
+        .spacing(6);
+
+    column![title, rows].spacing(12).into()
+}
+
+/// Tallest a weekday's bars get.
+const WEEKDAY_BAR_HEIGHT: f32 = 60.0;
+const SKIPPED_COLOR: Color = Color::from_rgb(0.85, 0.25, 0.2);
+
+/// Held and scheduled sessions side by side for each weekday, with the
+/// often skipped ones in red.
+fn view_weekdays<'a>(weekdays: Vec<WeekdayModel>) -> Element<'a, Msg> {
+    let title = text("Weekdays This Month").size(14).font(Font {
+        weight: font::Weight::Medium,
+        ..Default::default()
+    });
+
+    let max = weekdays
+        .iter()
+        .map(|day| day.scheduled.max(day.held))
+        .max()
+        .unwrap_or(0)
+        .max(1) as f32;
+    let bar = |count: i32, color: Color| {
+        container(space())
+            .width(Length::Fixed(10.0))
+            .height(Length::Fixed(count as f32 / max * WEEKDAY_BAR_HEIGHT))
+            .style(move |_: &Theme| container::Style {
+                background: Some(Background::Color(color)),
+                ..Default::default()
+            })
+    };
+
+    let skipped: Vec<String> = weekdays
+        .iter()
+        .filter(|day| day.often_skipped)
+        .map(|day| day.label.clone())
+        .collect();
+
+    let bars = row(weekdays.into_iter().map(|day| {
+        let held_color = if day.often_skipped {
+            SKIPPED_COLOR
+        } else {
+            Color::BLACK
+        };
+
+        column![
+            container(
+                row![
+                    bar(day.held, held_color),
+                    bar(day.scheduled, SCHEDULED_COLOR)
+                ]
+                .spacing(2)
+            )
+            .height(Length::Fixed(WEEKDAY_BAR_HEIGHT))
+            .align_bottom(Length::Fixed(WEEKDAY_BAR_HEIGHT)),
+            text(day.label).size(11),
+            text(format!("{}/{}", day.held, day.scheduled))
+                .size(11)
+                .style(move |_: &Theme| text::Style {
+                    color: Some(held_color),
+                }),
+        ]
+        .align_x(Center)
+        .spacing(4)
+        .width(Length::Fixed(40.0))
+        .into()
+    }))
+    .spacing(8);
+
+    let note = if skipped.is_empty() {
+        text("Held against scheduled sessions so far this month").size(12)
+    } else {
+        text(format!("Often skipped: {}", skipped.join(", ")))
+            .size(12)
+            .style(|_: &Theme| text::Style {
+                color: Some(SKIPPED_COLOR),
+            })
+    };
+
+    column![title, bars, note].spacing(12).into()
+}
+
+/// Each subject's share of this month's income, as bars.
+fn view_subject_income<'a>(subjects: Vec<SubjectIncomeModel>) -> Element<'a, Msg> {
+    let title = text("Income by Subject").size(14).font(Font {
+        weight: font::Weight::Medium,
+        ..Default::default()
+    });
+
+    let rows = Column::new()
+        .extend(subjects.into_iter().map(|subject| {
+            row![
+                text(subject.subject).size(12).width(Length::Fixed(180.0)),
+                progress_bar(0.0..=1.0, subject.share)
+                    .length(Length::Fixed(200.0))
+                    .girth(8),
+                text(subject.income).size(12),
+            ]
+            .spacing(12)
+            .align_y(Center)
+            .into()
+        }))
+        .spacing(6);
+
+    column![title, rows].spacing(12).into()
+}
+
+const GLANCE_COLOR: Color = Color::from_rgb(0.1, 0.55, 0.3);
+
+/// Twelve months of one year shaded by income, each opening that month's
+/// report when clicked.
+fn view_year_at_a_glance<'a>(
+    state: &DashboardState,
+    months: Vec<MonthCellModel>,
+) -> Element<'a, Msg> {
+    let title = text("Year at a Glance").size(14).font(Font {
+        weight: font::Weight::Medium,
+        ..Default::default()
+    });
+    let year = state.glance_year.unwrap_or_default();
+    let has_year = |year: i32| state.year_at_a_glance.contains_key(&year);
+    let year_picker = row![
+        button(text("<").size(12))
+            .style(button::text)
+            .on_press_maybe(has_year(year - 1).then_some(Msg::GlanceYearSelected(year - 1))),
+        text(year.to_string()).size(14),
+        button(text(">").size(12))
+            .style(button::text)
+            .on_press_maybe(has_year(year + 1).then_some(Msg::GlanceYearSelected(year + 1))),
+    ]
+    .spacing(8)
+    .align_y(Center);
+
+    let cell = |month: MonthCellModel| -> Element<'a, Msg> {
+        let background = Color {
+            a: 0.08 + 0.72 * month.shade,
+            ..GLANCE_COLOR
+        };
+        let text_color = if month.shade > 0.6 {
+            Color::WHITE
+        } else {
+            Color::BLACK
+        };
+
+        button(
+            column![
+                text(month.label).size(12),
+                text(month.income).size(13),
+                text(month.attendance).size(11),
+            ]
+            .spacing(2),
+        )
+        .width(Length::Fixed(120.0))
+        .padding(8)
+        .style(move |_theme, _status| button::Style {
+            background: Some(Background::Color(background)),
+            text_color,
+            border: Border {
+                radius: 6.0.into(),
+                ..Default::default()
+            },
             ..Default::default()
-        }
-    })
+        })
+        .on_press(Msg::Effect(Effect::Navigate(Route::Reports(Some(
+            month.year_month,
+        )))))
+        .into()
+    };
+
+    let mut months = months.into_iter();
+    let first_half = row(months.by_ref().take(6).map(cell)).spacing(8);
+    let second_half = row(months.map(cell)).spacing(8);
+
+    column![
+        row![title, year_picker].spacing(20).align_y(Center),
+        column![first_half, second_half].spacing(8),
+        text("Shaded by income, with the share of scheduled sessions held").size(12),
+    ]
+    .spacing(12)
     .into()
 }
 
+fn view_kpi_builder(state: &DashboardState) -> Element<'_, Msg> {
+    let builder = &state.kpi_builder;
+    let known_values: Vec<&str> = Aggregate::ALL.iter().map(|a| a.name()).collect();
+
+    let form = row![
+        text_input("Metric name", &builder.name)
+            .on_input(Msg::KpiNameChanged)
+            .width(Length::Fixed(160.0)),
+        text_input("e.g. actual / potential", &builder.expression)
+            .on_input(Msg::KpiExpressionChanged)
+            .on_submit(Msg::AddCustomKpi)
+            .width(Length::Fixed(260.0)),
+        pick_list(KpiFormat::ALL, Some(builder.format), Msg::KpiFormatSelected),
+        button(text("Add metric").size(14)).on_press(Msg::AddCustomKpi),
+    ]
+    .spacing(10)
+    .align_y(Center);
+
+    let hint = match &builder.error {
+        Some(error) => text(error.clone())
+            .size(12)
+            .style(|_theme: &Theme| text::Style {
+                color: Some(theme::DANGER),
+            }),
+        None => text(format!("Available values: {}", known_values.join(", "))).size(12),
+    };
+
+    let saved = Column::new()
+        .extend(state.custom_kpis.iter().enumerate().map(|(index, kpi)| {
+            row![
+                text(format!("{} = {}", kpi.name, kpi.expression.source())).size(12),
+                button(text("Remove").size(12))
+                    .padding([2, 8])
+                    .style(button::text)
+                    .on_press(Msg::RemoveCustomKpi(index)),
+            ]
+            .spacing(10)
+            .align_y(Center)
+            .into()
+        }))
+        .spacing(4);
+
+    column![form, hint, saved].spacing(6).into()
+}
+
+fn view_trend_chart(state: &DashboardState) -> Element<'_, Msg> {
+    let chart = with_diagnostics(
+        state,
+        &state.linechart.stats,
+        Canvas::new(&state.linechart)
+            .width(Length::Fill)
+            .height(Length::Fill),
+    );
+
+    let grouping_picker = pick_list(
+        AttendanceGrouping::ALL,
+        Some(state.attendance_grouping),
+        Msg::AttendanceGroupingSelected,
+    )
+    .text_size(12);
+
+    chart_panel("Attendance Rate", Some(grouping_picker.into()), chart).into()
+}
+
 fn view_grouped_chart(state: &DashboardState) -> Element<'_, Msg> {
-    let chart = Canvas::new(&state.barchart)
-        .width(Length::Fill)
-        .height(Length::Fill);
-
-    container(column![
-        container(text!("Actual vs Potential Earnings").size(20)).center_x(Length::Fill),
-        chart
-    ])
-    // .width(Length::FillPortion(3))
-    // .height(Length::Fixed(400.0))
-    .padding(20)
-    .style(|theme: &Theme| {
-        let palette = theme.extended_palette();
-
-        container::Style {
-            background: Some(palette.background.weak.color.into()),
-            // border: (),
+    let chart = with_diagnostics(
+        state,
+        &state.barchart.stats,
+        Canvas::new(&state.barchart)
+            .width(Length::Fill)
+            .height(Length::Fill),
+    );
+
+    let stacking_toggle = checkbox(state.stack_earnings)
+        .label("By student")
+        .text_size(12)
+        .on_toggle(Msg::StackingToggled);
+
+    chart_panel(
+        "Actual vs Potential Earnings",
+        Some(stacking_toggle.into()),
+        chart,
+    )
+    .into()
+}
+
+fn view_cumulative_chart(state: &DashboardState) -> Element<'_, Msg> {
+    let chart = with_diagnostics(
+        state,
+        &state.cumulative_chart.stats,
+        Canvas::new(&state.cumulative_chart)
+            .width(Length::Fill)
+            .height(Length::Fill),
+    );
+
+    chart_panel("Income This Month", None, chart).into()
+}
+
+/// Lays the chart's render stats over its top-right corner when
+/// diagnostics are on. The numbers are from the previous draw.
+fn with_diagnostics<'a>(
+    state: &DashboardState,
+    stats: &Cell<RenderStats>,
+    chart: impl Into<Element<'a, Msg>>,
+) -> Element<'a, Msg> {
+    if !state.show_diagnostics {
+        return chart.into();
+    }
+
+    let overlay = container(text(stats.get().summary()).size(11))
+        .padding([2, 6])
+        .style(|_theme: &Theme| container::Style {
+            background: Some(Color::from_rgba(0.0, 0.0, 0.0, 0.6).into()),
+            text_color: Some(Color::WHITE),
             ..Default::default()
-        }
-    })
+        });
+
+    stack![
+        chart.into(),
+        container(overlay)
+            .align_right(Length::Fill)
+            .align_top(Length::Fill)
+    ]
     .into()
 }
 
-#[derive(Clone, Copy)]
-enum DashboardCardVariant {
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DashboardCardVariant {
     Attendance,
     ActualEarnings,
     PotentialEarnings,
     RevenueLost,
+    Students,
+    Averages,
+    Remaining,
+    Custom,
 }
 
-fn metric_card<'a>(
-    title: String,
-    value: String,
-    trend: Option<(String, Option<bool>)>,
-    is_hovered: bool,
-    card_index: Option<usize>,
-    variant: DashboardCardVariant,
-) -> Element<'a, Msg> {
-    let mut content = column![
-        text(title).size(15).font(Font {
-            weight: font::Weight::Medium,
-            ..Default::default()
-        }),
-        text(value).size(25).font(Font {
-            weight: font::Weight::Medium,
-            ..Default::default()
-        }),
-    ]
-    .align_x(Center)
-    .spacing(5);
-
-    if let Some((trend_text, is_positive_opt)) = trend {
-        let trend_icon: Option<svg::Handle> = match is_positive_opt {
-            None => None,
-            Some(true) => Some(icons::arrow_up()),
-            Some(false) => Some(icons::arrow_down()),
-        };
+fn card_background(theme: &Theme, variant: DashboardCardVariant) -> Option<Color> {
+    match variant {
+        DashboardCardVariant::Attendance => Some(theme.extended_palette().primary.weak.color),
+        DashboardCardVariant::ActualEarnings => Some(Color::from_rgba(0.4, 1.0, 0.5, 0.6)),
+        DashboardCardVariant::PotentialEarnings => Some(Color::from_rgba(0.8, 0.7, 0.8, 0.4)),
+        DashboardCardVariant::RevenueLost => Some(Color::from_rgba(1.0, 0.5, 0.2, 0.6)),
+        DashboardCardVariant::Students => Some(Color::from_rgba(1.0, 0.85, 0.3, 0.5)),
+        DashboardCardVariant::Averages => Some(Color::from_rgba(0.3, 0.8, 0.8, 0.4)),
+        DashboardCardVariant::Remaining => Some(Color::from_rgba(0.6, 0.9, 0.4, 0.4)),
+        DashboardCardVariant::Custom => Some(Color::from_rgba(0.5, 0.7, 1.0, 0.4)),
+    }
+}
 
-        let trend_row = match trend_icon {
-            None => container(text(trend_text).size(12).font(Font {
-                weight: font::Weight::Medium,
-                ..Default::default()
-            })),
-            Some(icon) => container(row![
-                svg::Svg::new(icon).width(14).height(14),
-                text(trend_text).size(12).font(Font {
-                    weight: font::Weight::Medium,
-                    ..Default::default()
-                }),
-            ]),
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::fixtures::sample_domain;
+    use crate::store::{DomainEdit, DomainStore};
+    use chrono::TimeZone;
+
+    fn sample_dashboard() -> DashboardState {
+        let mut state = DashboardState::empty();
+        state.attach_domain(&sample_domain());
+        state
+    }
+
+    #[test]
+    fn summary_cards() {
+        let state = sample_dashboard();
+
+        insta::assert_debug_snapshot!(DashboardViewModel::from_state(&state));
+    }
+
+    #[test]
+    fn confirming_a_session_marks_it_in_today() {
+        let mut store = DomainStore::new(sample_domain());
+        let mut state = sample_dashboard();
+        let (student_id, at) = state.todays_sessions[0].key();
+
+        assert!(store.apply(DomainEdit::SessionConfirmed {
+            student_id,
+            at,
+            confirmed: true,
+        }));
+        state.attach_domain(&store.domain());
+
+        let today = DashboardViewModel::from_state(&state).today;
+        assert_eq!(today.len(), 1);
+        assert_eq!(today[0].time, "5:30 PM");
+        assert!(today[0].confirmed);
+        assert!(
+            store
+                .domain()
+                .students
+                .iter()
+                .all(|student| student.session_status(at).is_none())
+        );
+    }
+
+    #[test]
+    fn todays_sessions_show_as_in_session_while_they_run() {
+        let mut state = sample_dashboard();
+        state.now = state.todays_sessions[0].session_at + chrono::Duration::minutes(20);
+
+        let today = DashboardViewModel::from_state(&state).today;
+        assert_eq!(
+            (today[0].countdown.clone(), today[0].in_session),
+            (None, true)
+        );
+    }
+
+    #[test]
+    fn no_show_fee_is_offered_and_can_be_overridden() {
+        let mut state = sample_dashboard();
+        let session = state.todays_sessions[0].key();
+
+        let _ = update(&mut state, Msg::NoShowFeeChanged("50".to_string()));
+        let _ = update(&mut state, Msg::NoShowAutoApplyToggled(true));
+        let _ = update(&mut state, Msg::MarkNoShow(session));
+        assert_eq!(state.no_show_prompt.as_ref().unwrap().amount, "50.00");
+
+        let _ = update(&mut state, Msg::NoShowChargeChanged("30".to_string()));
+        let _ = update(&mut state, Msg::ApplyNoShowCharge);
+
+        assert!(state.no_show_prompt.is_none());
+        assert!(DashboardViewModel::from_state(&state).today[0].no_show);
+        assert_eq!(state.ledger.uninvoiced_total("mary-jane"), 30.0);
+    }
+
+    #[test]
+    fn logging_a_session_refreshes_the_same_as_a_full_rebuild() {
+        let mut store = DomainStore::new(sample_domain());
+        let mut state = sample_dashboard();
+        let mary = store.domain().students[0].clone();
+        let today_session = state.todays_sessions[0].session_at;
+        let edits = [
+            (mary.sessions[0].at, None),
+            (today_session, Some(SessionStatus::Completed)),
+            (
+                Local.with_ymd_and_hms(2025, 11, 4, 17, 30, 0).unwrap(),
+                Some(SessionStatus::Completed),
+            ),
+            // Unlogged, so it also drops out of "Needs attention".
+            (
+                Local.with_ymd_and_hms(2026, 1, 29, 17, 30, 0).unwrap(),
+                Some(SessionStatus::Completed),
+            ),
+            // Called off, so it no longer counts as scheduled.
+            (
+                Local.with_ymd_and_hms(2026, 2, 3, 17, 30, 0).unwrap(),
+                Some(SessionStatus::CancelledByTutor),
+            ),
+        ];
+
+        for (at, status) in edits {
+            assert!(store.apply(DomainEdit::SessionMarked {
+                student_id: mary.id.clone(),
+                at,
+                status,
+            }));
+            state.session_logged(&store.domain(), at);
+
+            let mut rebuilt = DashboardState::empty();
+            rebuilt.attach_domain(&store.domain());
+            assert_eq!(state.barchart.data, rebuilt.barchart.data);
+            assert_eq!(state.monthly_attendance, rebuilt.monthly_attendance);
+            assert_eq!(state.weekly_attendance, rebuilt.weekly_attendance);
+            assert_eq!(state.cumulative_chart.data, rebuilt.cumulative_chart.data);
+            assert_eq!(
+                format!("{:?}", DashboardViewModel::from_state(&state)),
+                format!("{:?}", DashboardViewModel::from_state(&rebuilt))
+            );
         }
-        .align_bottom(Length::Fill);
+    }
 
-        content = content.push(trend_row);
+    #[test]
+    fn cumulative_income_reports_how_far_ahead_or_behind() {
+        let mut state = sample_dashboard();
+        let days = state.cumulative_chart.data.clone();
+        let today = days.iter().rposition(|day| day.actual.is_some()).unwrap();
+        assert_eq!(today + 1, 12);
+
+        state.cumulative_chart = CumulativeIncomeChart::new(
+            days.into_iter()
+                .map(|day| CumulativeIncome {
+                    actual: day.actual.map(|_| day.on_track - 50.0),
+                    ..day
+                })
+                .collect(),
+            MoneyFormat::default(),
+        );
+        assert_eq!(state.cumulative_chart.lead(), Some(-50.0));
     }
 
-    let card = container(content)
-        .height(Length::Fixed(100.0))
-        .padding([10, 20])
-        .center_x(Length::Fixed(180.0))
-        .style(move |theme: &Theme| card_style_with_variant(theme, is_hovered, variant));
+    #[test]
+    fn payments_of_any_size_update_receivables() {
+        let mut state = sample_dashboard();
+        let pay = |state: &mut DashboardState, amount: &str| {
+            let _ = update(
+                state,
+                Msg::PaymentAmountChanged("mary-jane".to_string(), amount.to_string()),
+            );
+            let _ = update(state, Msg::RecordPayment("mary-jane".to_string()));
+        };
 
-    mouse_area(card)
-        .on_enter(Msg::DashboardCardHovered(card_index))
-        .on_exit(Msg::DashboardCardHovered(None))
-        .into()
-}
+        pay(&mut state, "abc");
+        assert!(state.payment_error.is_some());
 
-fn card_style_with_variant(
-    theme: &Theme,
-    is_hovered: bool,
-    variant: DashboardCardVariant,
-) -> container::Style {
-    let palette = theme.extended_palette();
+        pay(&mut state, "1000");
+        pay(&mut state, "500");
+        let receivables = DashboardViewModel::from_state(&state).receivables;
+        assert_eq!(receivables[0].paid, "GHS 1,500.00");
+        assert_eq!(receivables[0].balance, "GHS 300.00 credit");
+    }
 
-    let background_color = match variant {
-        DashboardCardVariant::Attendance => Some(palette.primary.weak.color),
-        DashboardCardVariant::ActualEarnings => Some(Color::from_rgba(0.4, 1.0, 0.5, 0.6)),
-        DashboardCardVariant::PotentialEarnings => Some(Color::from_rgba(0.8, 0.7, 0.8, 0.4)),
-        DashboardCardVariant::RevenueLost => Some(Color::from_rgba(1.0, 0.5, 0.2, 0.6)),
-    };
+    #[test]
+    fn summary_cards_with_absolute_trends_and_custom_kpi() {
+        let mut state = sample_dashboard();
+        let _ = update(&mut state, Msg::ToggleTrendDisplay);
+        let _ = update(&mut state, Msg::KpiNameChanged("Per student".to_string()));
+        let _ = update(
+            &mut state,
+            Msg::KpiExpressionChanged("actual / students".to_string()),
+        );
+        let _ = update(&mut state, Msg::KpiFormatSelected(KpiFormat::Currency));
+        let _ = update(&mut state, Msg::AddCustomKpi);
+
+        insta::assert_debug_snapshot!(DashboardViewModel::from_state(&state));
+    }
 
-    container::Style {
-        background: background_color.map(Background::Color),
-        border: Border {
-            radius: 10.0.into(),
-            ..Default::default()
-        },
-        shadow: if is_hovered {
-            Shadow {
-                color: Color::from_rgba(0.0, 0.0, 0.0, 0.25),
-                offset: Vector::new(0.4, 0.0),
-                blur_radius: 12.0,
-            }
-        } else {
-            Shadow::default()
-        },
-        ..Default::default()
+    #[test]
+    fn render_stats_count_cache_hits_and_misses() {
+        let stats = Cell::new(RenderStats::default());
+        assert_eq!(stats.get().hit_rate(), None);
+
+        record_draw(&stats, Duration::from_micros(1500), true);
+        record_draw(&stats, Duration::from_micros(250), false);
+        record_draw(&stats, Duration::from_micros(200), false);
+        record_draw(&stats, Duration::from_micros(100), false);
+
+        assert_eq!(
+            stats.get().summary(),
+            "draw 0.10 ms · cache 3 hits / 1 misses (75%)"
+        );
+    }
+
+    #[test]
+    fn hovering_finds_the_month_under_the_cursor_in_the_plot() {
+        // Four months across a 240 wide plot, 60 each, from x = 20.
+        let bounds = Rectangle::new(Point::new(100.0, 50.0), Size::new(280.0, 200.0));
+        let at = |x: f32, y: f32| mouse::Cursor::Available(Point::new(100.0 + x, 50.0 + y));
+
+        assert_eq!(hovered_month(bounds, at(25.0, 100.0), 4), Some(0));
+        assert_eq!(hovered_month(bounds, at(150.0, 100.0), 4), Some(2));
+        assert_eq!(hovered_month(bounds, at(259.0, 100.0), 4), Some(3));
+        assert_eq!(hovered_month(bounds, at(10.0, 100.0), 4), None);
+        assert_eq!(hovered_month(bounds, at(265.0, 100.0), 4), None);
+        assert_eq!(hovered_month(bounds, at(150.0, 190.0), 4), None);
+        assert_eq!(hovered_month(bounds, mouse::Cursor::Unavailable, 4), None);
+        assert_eq!(hovered_month(bounds, at(150.0, 100.0), 0), None);
+    }
+
+    #[test]
+    fn axis_ticks_fall_on_round_steps() {
+        assert_eq!(axis_ticks(1650.0, 1.0), vec![500.0, 1000.0, 1500.0]);
+        assert_eq!(axis_ticks(9.9, 1.0), vec![2.0, 4.0, 6.0, 8.0]);
+        assert_eq!(axis_ticks(2.2, 1.0), vec![1.0, 2.0]);
+        assert!(axis_ticks(0.0, 1.0).is_empty());
     }
 }
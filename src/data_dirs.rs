@@ -0,0 +1,162 @@
+use directories::ProjectDirs;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Clone)]
+pub enum DataDirError {
+    NoHomeDirectory,
+    CreateFailed(String),
+    OpenFailed(String),
+}
+
+impl std::fmt::Display for DataDirError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DataDirError::NoHomeDirectory => write!(f, "Could not find a folder to keep data in"),
+            DataDirError::CreateFailed(reason) => {
+                write!(f, "Could not create the data folder: {}", reason)
+            }
+            DataDirError::OpenFailed(reason) => {
+                write!(f, "Could not open the data folder: {}", reason)
+            }
+        }
+    }
+}
+
+/// Passing this flag, or placing a file with this name next to the
+/// executable, keeps all data beside the app so it can run off a USB stick.
+pub const PORTABLE_FLAG: &str = "--portable";
+pub const PORTABLE_MARKER: &str = "portable";
+
+/// Where the app keeps everything it writes for itself. Exports are
+/// separate because they are meant to be found and shared by the user.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DataDirs {
+    pub portable: bool,
+    pub root: PathBuf,
+    pub database: PathBuf,
+    pub backups: PathBuf,
+    pub logs: PathBuf,
+    pub attachments: PathBuf,
+    pub outbox: PathBuf,
+    /// Custom report definitions shown on the Reports screen.
+    pub plugins: PathBuf,
+}
+
+impl DataDirs {
+    /// The platform's usual data folders, e.g. under `~/.local/share` on
+    /// Linux or `%APPDATA%` on Windows.
+    /// In portable mode everything goes in a `data` folder next to the
+    /// executable instead.
+    pub fn locate() -> Result<Self, DataDirError> {
+        if let Some(root) = portable_root() {
+            return Ok(Self {
+                portable: true,
+                ..Self::rooted_at(&root)
+            });
+        }
+
+        let project = ProjectDirs::from("com", "Nhoma", "Tutor Manager")
+            .ok_or(DataDirError::NoHomeDirectory)?;
+
+        Ok(Self {
+            logs: project.data_local_dir().join("logs"),
+            ..Self::rooted_at(project.data_dir())
+        })
+    }
+
+    pub fn rooted_at(root: &Path) -> Self {
+        Self {
+            portable: false,
+            root: root.to_path_buf(),
+            database: root.join("tutor-mgr.db"),
+            backups: root.join("backups"),
+            logs: root.join("logs"),
+            attachments: root.join("attachments"),
+            outbox: root.join("outbox"),
+            plugins: root.join("plugins"),
+        }
+    }
+
+    pub fn ensure_created(&self) -> Result<(), DataDirError> {
+        [
+            &self.root,
+            &self.backups,
+            &self.logs,
+            &self.attachments,
+            &self.outbox,
+            &self.plugins,
+        ]
+        .into_iter()
+        .try_for_each(std::fs::create_dir_all)
+        .map_err(|e| DataDirError::CreateFailed(e.to_string()))
+    }
+
+    pub fn entries(&self) -> [(&'static str, &Path); 6] {
+        [
+            ("Database", &self.database),
+            ("Backups", &self.backups),
+            ("Logs", &self.logs),
+            ("Attachments", &self.attachments),
+            ("Outbox", &self.outbox),
+            ("Plugins", &self.plugins),
+        ]
+    }
+}
+
+/// The portable data folder, when portable mode was asked for.
+pub fn portable_root() -> Option<PathBuf> {
+    let exe = std::env::current_exe().ok()?;
+    portable_root_for(exe.parent()?, std::env::args())
+}
+
+fn portable_root_for(exe_dir: &Path, mut args: impl Iterator<Item = String>) -> Option<PathBuf> {
+    let requested = args.any(|arg| arg == PORTABLE_FLAG) || exe_dir.join(PORTABLE_MARKER).is_file();
+
+    requested.then(|| exe_dir.join("data"))
+}
+
+pub async fn open_folder(path: PathBuf) -> Result<(), DataDirError> {
+    open::that_detached(&path).map_err(|e| DataDirError::OpenFailed(e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn everything_lives_under_the_root() {
+        let root = std::env::temp_dir().join("tutor-mgr-data-dirs");
+        let dirs = DataDirs::rooted_at(&root);
+
+        dirs.ensure_created().unwrap();
+
+        assert_eq!(dirs.database, root.join("tutor-mgr.db"));
+        for (_, path) in dirs.entries() {
+            assert!(path.starts_with(&root));
+        }
+        assert!(dirs.backups.is_dir() && dirs.logs.is_dir() && dirs.attachments.is_dir());
+    }
+
+    #[test]
+    fn portable_mode_from_flag_or_marker_file() {
+        let exe_dir = std::env::temp_dir().join("tutor-mgr-portable");
+        std::fs::create_dir_all(&exe_dir).unwrap();
+        let _ = std::fs::remove_file(exe_dir.join(PORTABLE_MARKER));
+        let args = |list: &[&str]| list.iter().map(|arg| arg.to_string()).collect::<Vec<_>>();
+
+        assert_eq!(
+            portable_root_for(&exe_dir, args(&["tutor-mgr"]).into_iter()),
+            None
+        );
+        assert_eq!(
+            portable_root_for(&exe_dir, args(&["tutor-mgr", "--portable"]).into_iter()),
+            Some(exe_dir.join("data"))
+        );
+
+        std::fs::write(exe_dir.join(PORTABLE_MARKER), "").unwrap();
+        assert_eq!(
+            portable_root_for(&exe_dir, args(&["tutor-mgr"]).into_iter()),
+            Some(exe_dir.join("data"))
+        );
+    }
+}
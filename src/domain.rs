@@ -1,209 +1,1150 @@
-use chrono::{DateTime, Datelike, Duration, Local, Month, NaiveDate, TimeZone, Weekday};
+use chrono::{DateTime, Datelike, Duration, Local, NaiveDate, NaiveTime, TimeZone, Weekday};
 use common_macros::hash_map;
-use std::collections::{BTreeMap, HashMap};
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+use std::collections::{BTreeSet, HashMap};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, PoisonError};
+
+use crate::money::MoneyFormat;
 
 pub const WEEKDAYS_TIMES: &[&str] = &["05:00 PM"];
 pub const WEEKEND_SAT_TIMES: &[&str] = &["11:00 AM", "2:00 PM", "5:00 PM"];
 pub const WEEKEND_SUN_TIMES: &[&str] = &["10:00 AM", "2:00 PM"];
 
 #[derive(Debug, Clone)]
+pub enum DomainError {
+    ReadFailed(String),
+    WriteFailed(String),
+    Corrupt(String),
+}
+
+impl std::fmt::Display for DomainError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DomainError::ReadFailed(reason) => write!(f, "Could not read saved data: {}", reason),
+            DomainError::WriteFailed(reason) => write!(f, "Could not save data: {}", reason),
+            DomainError::Corrupt(reason) => write!(f, "Saved data is damaged: {}", reason),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Domain {
     pub tutor: Tutor,
     pub students: Vec<Student>,
-    // monthly_summaries: Vec<MonthlySummary>,
+    #[serde(default)]
+    pub money_format: MoneyFormat,
+    /// How students should pay, printed at the foot of fee notes.
+    #[serde(default)]
+    pub payment_instructions: String,
+    /// Days off, such as public holidays, when no sessions are held.
+    #[serde(default)]
+    pub blackout_dates: Vec<NaiveDate>,
+    /// Places sessions are held, each with its own surcharge.
+    #[serde(default)]
+    pub locations: Vec<Location>,
+    /// Hourly rates quoted to new students, by subject.
+    #[serde(default)]
+    pub rates: Vec<SubjectRate>,
+    /// Prospective students waiting for a slot, longest waiting first.
+    #[serde(default)]
+    pub waitlist: Vec<Prospect>,
+    /// Totals of months already over, oldest first, taken as each month
+    /// ended. Trends read these instead of working past months out again.
+    /// Edits reaching a closed month drop its snapshot until it is retaken.
+    #[serde(default)]
+    pub monthly_snapshots: Vec<MonthlySummary>,
+    /// Deleted students and sessions, newest last, until they expire.
+    #[serde(default)]
+    pub trash: Vec<Trashed<TrashItem>>,
+    /// Not saved: a loaded domain always runs on the system clock.
+    #[serde(skip)]
+    pub clock: Clock,
+}
+
+/// Writes `contents` beside `path` first and then moves it into place, so
+/// a crash part way through leaves the old file whole.
+pub fn write_replacing(path: &Path, contents: &str) -> std::io::Result<()> {
+    let mut partial = path.as_os_str().to_owned();
+    partial.push(".partial");
+    let partial = PathBuf::from(partial);
+
+    std::fs::write(&partial, contents)?;
+    std::fs::rename(&partial, path)
+}
+
+/// Keeps overlapping saves of one file in order. One save writes at a
+/// time, and a save of an older revision than the last one written is
+/// dropped, so the newest revision is what stays on disk.
+#[derive(Debug, Clone, Default)]
+pub struct SaveQueue(Arc<Mutex<Option<u64>>>);
+
+impl SaveQueue {
+    /// [`write_replacing`] for `revision`, unless a newer one is written.
+    pub fn write(&self, path: &Path, revision: u64, contents: &str) -> std::io::Result<()> {
+        let mut written = self.0.lock().unwrap_or_else(PoisonError::into_inner);
+        if written.is_some_and(|written| written > revision) {
+            return Ok(());
+        }
+
+        write_replacing(path, contents)?;
+        *written = Some(revision);
+        Ok(())
+    }
+}
+
+/// Where "today" comes from for anything computed relative to the
+/// current month. Pinning it makes month boundaries testable.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Clock {
+    #[default]
+    System,
+    Fixed(NaiveDate),
+}
+
+impl Clock {
+    pub fn today(&self) -> NaiveDate {
+        match self {
+            Clock::System => Local::now().naive_local().date(),
+            Clock::Fixed(date) => *date,
+        }
+    }
+
+    /// The current time, or the start of the pinned day.
+    pub fn now(&self) -> DateTime<Local> {
+        match self {
+            Clock::System => Local::now(),
+            Clock::Fixed(date) => Local
+                .from_local_datetime(&date.and_time(NaiveTime::MIN))
+                .earliest()
+                .unwrap_or_else(Local::now),
+        }
+    }
 }
 
 impl Domain {
-    pub async fn load_state_from_db() -> Self {
-        mock_domain()
+    /// Reads the domain saved at `path`, starting empty when nothing has
+    /// been saved yet or there is nowhere to save to.
+    pub async fn load_state_from_db(path: Option<PathBuf>) -> Result<Self, DomainError> {
+        let Some(path) = path else {
+            return Ok(empty_domain());
+        };
+
+        match std::fs::read_to_string(&path) {
+            Ok(json) => serde_json::from_str(&json)
+                .map(|mut domain: Domain| {
+                    domain.upgrade_sessions();
+                    domain
+                })
+                .map_err(|e| DomainError::Corrupt(e.to_string())),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(empty_domain()),
+            Err(e) => Err(DomainError::ReadFailed(e.to_string())),
+        }
     }
 
-    // pub fn compute_trend_history(&self) -> Vec<TrendData> {
-    //     compute_trend_history_internal(&self.monthly_summaries)
-    // }
+    /// Moves sessions called off in files from before sessions had a
+    /// status in with the rest.
+    pub fn upgrade_sessions(&mut self) {
+        for student in &mut self.students {
+            let cancelled = std::mem::take(&mut student.cancelled_sessions);
+            student
+                .sessions
+                .extend(cancelled.into_iter().map(|at| SessionRecord {
+                    at,
+                    status: SessionStatus::CancelledByTutor,
+                    notes: String::new(),
+                    links: Vec::new(),
+                }));
+        }
+    }
 
-    pub fn compute_income_data(&self) -> Vec<IncomeData> {
-        let students = &self.students;
+    pub async fn save_state_to_db(self, path: PathBuf) -> Result<(), DomainError> {
+        self.save_revision(path, SaveQueue::default(), 0).await
+    }
+
+    /// Saves the store's `revision` through `saves`, which every save of
+    /// this database shares, so an older revision never overwrites a newer.
+    pub async fn save_revision(
+        self,
+        path: PathBuf,
+        saves: SaveQueue,
+        revision: u64,
+    ) -> Result<(), DomainError> {
+        let json = serde_json::to_string_pretty(&self)
+            .map_err(|e| DomainError::WriteFailed(e.to_string()))?;
+
+        saves
+            .write(&path, revision, &json)
+            .map_err(|e| DomainError::WriteFailed(e.to_string()))
+    }
 
-        let mut students_grouped_by_month: BTreeMap<(u32, i32), Vec<&Student>> = BTreeMap::new();
+    /// A month's totals across every student: its snapshot once one has
+    /// been taken, otherwise worked out from the sessions.
+    pub fn monthly_summary(&self, year: i32, month: u32) -> MonthlySummary {
+        self.monthly_snapshots
+            .iter()
+            .find(|summary| (summary.year, summary.month) == (year, month))
+            .copied()
+            .unwrap_or_else(|| self.live_summary(year, month))
+    }
 
-        for student in students.iter() {
-            let student_months: Vec<(u32, i32)> = student
-                .actual_sessions
+    /// A month's totals worked out from the students' sessions as they
+    /// stand now.
+    pub fn live_summary(&self, year: i32, month: u32) -> MonthlySummary {
+        let revenue = |sessions_fn: fn(&Student, u32, i32) -> i32| -> f32 {
+            self.students
                 .iter()
-                .map(|dt| (dt.month(), dt.year()))
-                .collect::<std::collections::HashSet<_>>()
-                .into_iter()
-                .collect();
+                .map(|student| compute_monthly_sum(student, month, year, sessions_fn))
+                .sum()
+        };
+        let sessions = |sessions_fn: fn(&Student, u32, i32) -> i32| -> usize {
+            self.students
+                .iter()
+                .map(|student| sessions_fn(student, month, year) as usize)
+                .sum()
+        };
 
-            for month_key in student_months {
-                students_grouped_by_month
-                    .entry(month_key)
-                    .or_default()
-                    .push(student);
-            }
+        MonthlySummary {
+            year,
+            month,
+            actual_revenue: revenue(compute_monthly_completed_sessions),
+            potential_revenue: revenue(compute_monthly_scheduled_sessions),
+            scheduled_sessions: sessions(compute_monthly_scheduled_sessions),
+            completed_sessions: sessions(compute_monthly_completed_sessions),
+        }
+    }
+
+    /// Summaries for the `count` months up to and including this one,
+    /// oldest first.
+    pub fn monthly_summaries(&self, count: usize) -> Vec<MonthlySummary> {
+        let today = self.clock.today();
+        let mut months = vec![(today.year(), today.month())];
+        while months.len() < count {
+            let (year, month) = months[months.len() - 1];
+            months.push(previous_month(year, month));
         }
 
-        let income_data: Vec<IncomeData> = students_grouped_by_month
+        months
+            .into_iter()
+            .rev()
+            .map(|(year, month)| self.monthly_summary(year, month))
+            .collect()
+    }
+
+    /// Months that are over, from the one the first student started in up
+    /// to last month.
+    pub fn closed_months(&self) -> Vec<(i32, u32)> {
+        let Some(first) = self
+            .students
             .iter()
-            .map(|(&(m, y), stds)| {
-                let actual = stds
-                    .iter()
-                    .map(|std| compute_monthly_sum(std, m, y, compute_monthly_completed_sessions))
-                    .sum();
+            .map(|student| student.tution_start_date.date_naive())
+            .min()
+        else {
+            return Vec::new();
+        };
+        let today = self.clock.today();
+
+        let mut months = Vec::new();
+        let mut month = (first.year(), first.month());
+        while month < (today.year(), today.month()) {
+            months.push(month);
+            month = next_month(month.0, month.1);
+        }
+
+        months
+    }
 
-                let potential = stds
+    /// Closed months with no snapshot yet, as after a month rolls over.
+    pub fn unsnapshotted_months(&self) -> Vec<(i32, u32)> {
+        self.closed_months()
+            .into_iter()
+            .filter(|&(year, month)| {
+                !self
+                    .monthly_snapshots
                     .iter()
-                    .map(|std| compute_monthly_sum(std, m, y, compute_monthly_scheduled_sessions))
-                    .sum();
+                    .any(|summary| (summary.year, summary.month) == (year, month))
+            })
+            .collect()
+    }
 
-                let date = NaiveDate::from_ymd_opt(y, m, 1).expect("Invalid date construction");
-                let month = date.format("%b").to_string();
-                let month_year = (month, y);
+    /// Fresh summaries of `months`, to be recorded as their snapshots.
+    pub fn snapshot_months(&self, months: &[(i32, u32)]) -> Vec<MonthlySummary> {
+        months
+            .iter()
+            .map(|&(year, month)| self.live_summary(year, month))
+            .collect()
+    }
 
-                IncomeData {
-                    actual,
-                    potential,
-                    month_year,
-                }
+    /// [`Domain::snapshot_months`] run as a task, since going over every
+    /// month of a long history takes a while.
+    pub async fn take_snapshots(self, months: Vec<(i32, u32)>) -> Vec<MonthlySummary> {
+        self.snapshot_months(&months)
+    }
+
+    /// Each of the last `count` months against the one before it, oldest
+    /// first.
+    pub fn trend_history(&self, count: usize) -> TrendHistory {
+        compute_trend_history(&self.monthly_summaries(count + 1))
+    }
+
+    /// This month so far against last month.
+    pub fn current_trend(&self) -> TrendData {
+        let today = self.clock.today();
+        let (prev_year, prev_month) = previous_month(today.year(), today.month());
+
+        TrendData::between(
+            self.monthly_summary(prev_year, prev_month),
+            self.monthly_summary(today.year(), today.month()),
+        )
+    }
+
+    pub fn compute_income_data(&self) -> Vec<IncomeData> {
+        self.months_with_sessions()
+            .into_iter()
+            .filter_map(|(y, m)| self.income_for_month(y, m))
+            .collect()
+    }
+
+    /// Months in which any student attended a session, earliest first.
+    fn months_with_sessions(&self) -> BTreeSet<(i32, u32)> {
+        self.students
+            .iter()
+            .flat_map(|student| student.held_sessions())
+            .map(|dt| (dt.year(), dt.month()))
+            .collect()
+    }
+
+    /// Students with at least one attended session in the month.
+    fn students_attending_in(&self, y: i32, m: u32) -> Vec<&Student> {
+        self.students
+            .iter()
+            .filter(|student| {
+                student
+                    .held_sessions()
+                    .any(|dt| dt.year() == y && dt.month() == m)
+            })
+            .collect()
+    }
+
+    /// One month's bar of the income chart, or `None` when nobody
+    /// attended that month.
+    pub fn income_for_month(&self, y: i32, m: u32) -> Option<IncomeData> {
+        let stds = self.students_attending_in(y, m);
+        if stds.is_empty() {
+            return None;
+        }
+
+        let actual_by_student: Vec<(String, f32)> = stds
+            .iter()
+            .map(|std| {
+                let earned = compute_monthly_sum(std, m, y, compute_monthly_completed_sessions);
+                (std.name.full_name(), earned)
             })
             .collect();
 
-        income_data
+        let actual = actual_by_student.iter().map(|(_, earned)| earned).sum();
+
+        let potential = stds
+            .iter()
+            .map(|std| compute_monthly_sum(std, m, y, compute_monthly_scheduled_sessions))
+            .sum();
+
+        Some(IncomeData {
+            actual,
+            potential,
+            actual_by_student,
+            year_month: (y, m),
+        })
     }
 
     pub fn compute_attendance_data(&self) -> Vec<Attendance> {
-        let students = &self.students;
+        self.months_with_sessions()
+            .into_iter()
+            .filter_map(|(y, m)| self.attendance_for_month(y, m))
+            .collect()
+    }
 
-        let mut students_grouped_by_month: BTreeMap<(u32, i32), Vec<&Student>> = BTreeMap::new();
+    /// One month's point of the attendance chart, or `None` when nobody
+    /// attended that month.
+    pub fn attendance_for_month(&self, y: i32, m: u32) -> Option<Attendance> {
+        let stds = self.students_attending_in(y, m);
+        if stds.is_empty() {
+            return None;
+        }
 
-        for student in students.iter() {
-            let student_months: Vec<(u32, i32)> = student
-                .actual_sessions
-                .iter()
-                .map(|dt| (dt.month(), dt.year()))
-                .collect::<std::collections::HashSet<_>>()
-                .into_iter()
-                .collect();
+        let attended_days = stds
+            .iter()
+            .flat_map(|std| std.held_sessions())
+            .filter(|dt| dt.month() == m && dt.year() == y)
+            .count() as i32;
+
+        let today = self.clock.today();
+        let (month_start, month_end) = get_month_date_range(y, m);
+        let scheduled_sessions = self
+            .students
+            .iter()
+            .map(|std| scheduled_while_enrolled(std, month_start, month_end.min(today)))
+            .sum();
 
-            for month_key in student_months {
-                students_grouped_by_month
-                    .entry(month_key)
-                    .or_default()
-                    .push(student);
-            }
+        Some(Attendance {
+            attended_days,
+            scheduled_sessions,
+            label: month_label(y, m),
+        })
+    }
+
+    /// Each month of `year` with its actual income and the share of
+    /// scheduled sessions held, for the year-at-a-glance view.
+    pub fn year_at_a_glance(&self, year: i32) -> Vec<MonthAtAGlance> {
+        (1..=12)
+            .map(|month| MonthAtAGlance {
+                year,
+                month,
+                income: self
+                    .income_for_month(year, month)
+                    .map(|income| income.actual)
+                    .unwrap_or(0.0),
+                attendance_rate: self
+                    .attendance_for_month(year, month)
+                    .filter(|attendance| attendance.scheduled_sessions > 0)
+                    .map(|attendance| {
+                        attendance.attended_days as f32 / attendance.scheduled_sessions as f32
+                    }),
+            })
+            .collect()
+    }
+
+    /// Attended sessions per week for the last `weeks` weeks, up to and
+    /// including the current one. Weeks start on Monday and are labelled
+    /// by that date.
+    pub fn compute_weekly_attendance_data(&self, weeks: usize) -> Vec<Attendance> {
+        let this_week = week_start(self.clock.today());
+
+        (0..weeks as i64)
+            .rev()
+            .map(|weeks_ago| self.attendance_for_week(this_week - Duration::weeks(weeks_ago)))
+            .collect()
+    }
+
+    /// One week's point of the weekly attendance chart, for the week
+    /// starting on the Monday `start`.
+    pub fn attendance_for_week(&self, start: NaiveDate) -> Attendance {
+        let end = start + Duration::weeks(1);
+        let attended_days = self
+            .students
+            .iter()
+            .flat_map(|student| student.held_sessions())
+            .filter(|at| (start..end).contains(&at.date_naive()))
+            .count() as i32;
+        let last_day = (end - Duration::days(1)).min(self.clock.today());
+        let scheduled_sessions = self
+            .students
+            .iter()
+            .map(|student| scheduled_while_enrolled(student, start, last_day))
+            .sum();
+
+        Attendance {
+            label: start.format("%-d %b").to_string(),
+            attended_days,
+            scheduled_sessions,
         }
+    }
+
+    pub fn get_actual_income_trend_direction(&self) -> NumberTrend {
+        self.current_trend().revenue_trend
+    }
+
+    pub fn get_completed_sessions_trend_direction(&self) -> NumberTrend {
+        self.current_trend().sessions_trend
+    }
 
-        let attendance_data: Vec<Attendance> = students_grouped_by_month
+    pub fn active_student_count(&self) -> usize {
+        let today = self.clock.today();
+        self.students
             .iter()
-            .map(|(&(m, y), stds)| {
-                let attended_days =
-                    stds.iter().fold(0, |acc, &std| std.actual_sessions.len()) as i32;
-                // .map(|std| std.actual_sessions.len())
+            .filter(|student| is_active_on(student, today))
+            .count()
+    }
 
-                let date = NaiveDate::from_ymd_opt(y, m, 1).expect("Invalid date construction");
-                let month = date.format("%b").to_string();
+    /// Every student once per subject taken. See [`Student::enrolments`].
+    pub fn enrolments(&self) -> Vec<Cow<'_, Student>> {
+        self.students.iter().flat_map(Student::enrolments).collect()
+    }
 
-                Attendance {
-                    attended_days,
-                    month,
-                }
+    /// Income earned in the month from each subject taught, largest first.
+    pub fn income_by_subject(&self, year: i32, month: u32) -> Vec<(TutorSubject, f32)> {
+        let mut totals: Vec<(TutorSubject, f32)> = Vec::new();
+        for enrolment in self.enrolments() {
+            let earned =
+                compute_monthly_sum(&enrolment, month, year, compute_monthly_completed_sessions);
+            match totals
+                .iter_mut()
+                .find(|(subject, _)| *subject == enrolment.subject)
+            {
+                Some((_, total)) => *total += earned,
+                None => totals.push((enrolment.subject.clone(), earned)),
+            }
+        }
+        totals.sort_by(|a, b| b.1.total_cmp(&a.1));
+        totals
+    }
+
+    /// Sessions still scheduled after today in the current month, leaving
+    /// out blackout dates, and what the per-session ones among them would
+    /// add to income. Monthly fees are already counted in full.
+    pub fn remaining_in_month(&self) -> (usize, f32) {
+        let today = self.clock.today();
+        let (_, month_end) = get_month_date_range(today.year(), today.month());
+        let enrolments = self.enrolments();
+
+        today
+            .iter_days()
+            .skip(1)
+            .take_while(|date| *date <= month_end)
+            .filter(|date| !self.blackout_dates.contains(date))
+            .flat_map(|date| {
+                enrolments
+                    .iter()
+                    .map(|enrolment| enrolment.as_ref())
+                    .filter(move |student| is_active_on(student, date))
+                    .map(move |student| (student, scheduled_sessions_on(student, date).len()))
             })
-            .collect();
+            .fold((0, 0.0), |(sessions, income), (student, count)| {
+                let per_session = match student.payment_data.payment_type {
+                    PaymentType::PerSession => rate_in(student, today.year(), today.month()),
+                    PaymentType::Monthly => 0.0,
+                };
+                (sessions + count, income + per_session * count as f32)
+            })
+    }
 
-        attendance_data
+    /// Sessions held so far this month, and how many the month's timetable
+    /// holds in all, as the month's goal.
+    pub fn month_progress(&self) -> (i32, i32) {
+        let today = self.clock.today();
+        let (held, due) = self
+            .attendance_for_month(today.year(), today.month())
+            .map(|attendance| (attendance.attended_days, attendance.scheduled_sessions))
+            .unwrap_or((0, 0));
+
+        (held, due + self.remaining_in_month().0 as i32)
     }
 
-    pub fn get_actual_income_trend_direction(&self) -> NumberTrend {
-        let income_data = self.compute_income_data();
-        if income_data.len() < 2 {
-            return compute_trend(0.0, income_data[0].actual);
+    /// Sessions held and scheduled on each weekday of the current month up
+    /// to today, Monday first.
+    pub fn weekday_sessions_this_month(&self) -> Vec<WeekdaySessions> {
+        let today = self.clock.today();
+        let (month_start, _) = get_month_date_range(today.year(), today.month());
+        let mut weekdays: Vec<WeekdaySessions> = (0..7)
+            .map(|day| WeekdaySessions {
+                weekday: Weekday::try_from(day as u8).unwrap(),
+                held: 0,
+                scheduled: 0,
+            })
+            .collect();
+
+        for date in month_start.iter_days().take_while(|date| *date <= today) {
+            let weekday = &mut weekdays[date.weekday().num_days_from_monday() as usize];
+            for student in self.students.iter().filter(|s| is_active_on(s, date)) {
+                weekday.scheduled += scheduled_sessions_on(student, date).len() as i32;
+            }
+        }
+        for student in &self.students {
+            let session_days = get_scheduled_weekdays(student);
+            for held_on in student.held_sessions().map(|at| at.date_naive()) {
+                if held_on >= month_start
+                    && held_on <= today
+                    && session_days.contains(&held_on.weekday())
+                {
+                    weekdays[held_on.weekday().num_days_from_monday() as usize].held += 1;
+                }
+            }
         }
 
-        let now = Local::now();
-        let current_month = now.month();
-        let current_year = now.year();
+        weekdays
+    }
 
-        let prev_month = if current_month == 1 {
-            12
-        } else {
-            current_month - 1
+    /// Running income for each day of the current month. Held sessions
+    /// are counted up to today; the on-track line is what the timetable
+    /// would have earned by each day, blackout dates left out. Monthly
+    /// fees count from the 1st, as their contracts charge them.
+    pub fn cumulative_income_this_month(&self) -> Vec<CumulativeIncome> {
+        let today = self.clock.today();
+        let (month_start, month_end) = get_month_date_range(today.year(), today.month());
+        let enrolments = self.enrolments();
+        let monthly_fees: f32 = enrolments
+            .iter()
+            .map(|enrolment| enrolment.as_ref())
+            .filter(|student| student.payment_data.payment_type == PaymentType::Monthly)
+            .filter(|student| is_active_on(student, today))
+            .map(|student| monthly_fee(student, today.year(), today.month()))
+            .sum();
+        let per_session = |student: &Student| match student.payment_data.payment_type {
+            PaymentType::PerSession => rate_in(student, today.year(), today.month()),
+            PaymentType::Monthly => 0.0,
         };
-        let prev_year = current_year - 1;
 
-        let month_year_ctr = |month: u32, year: i32| {
-            let date = NaiveDate::from_ymd_opt(year, month, 1).expect("Invalid date construction");
-            let short_month = date.format("%b").to_string();
-            (short_month, year)
-        };
+        let (mut actual, mut on_track) = (monthly_fees, monthly_fees);
+        month_start
+            .iter_days()
+            .take_while(|date| *date <= month_end)
+            .map(|date| {
+                if !self.blackout_dates.contains(&date) {
+                    on_track += enrolments
+                        .iter()
+                        .map(|enrolment| enrolment.as_ref())
+                        .filter(|student| is_active_on(student, date))
+                        .map(|student| {
+                            per_session(student) * scheduled_sessions_on(student, date).len() as f32
+                        })
+                        .sum::<f32>();
+                }
+                actual += enrolments
+                    .iter()
+                    .map(|student| {
+                        let session_days = get_scheduled_weekdays(student);
+                        let held = student
+                            .held_sessions()
+                            .map(|session| session.date_naive())
+                            .filter(|held_on| {
+                                *held_on == date && session_days.contains(&held_on.weekday())
+                            })
+                            .count();
+                        per_session(student) * held as f32
+                    })
+                    .sum::<f32>();
+
+                CumulativeIncome {
+                    date,
+                    actual: (date <= today).then_some(actual),
+                    on_track,
+                }
+            })
+            .collect()
+    }
 
-        let prev_month_year = month_year_ctr(prev_month, prev_year);
+    /// Active students at the end of last month against today.
+    pub fn get_active_students_trend_direction(&self) -> NumberTrend {
+        let today = self.clock.today();
+        self.month_over_month_trend(|student, month, year| {
+            let (_, month_end) = get_month_date_range(year, month);
+            if is_active_on(student, month_end.min(today)) {
+                1.0
+            } else {
+                0.0
+            }
+        })
+    }
 
-        let current_month_year = month_year_ctr(current_month, current_year);
+    /// Students who started and stopped in the given month.
+    pub fn students_gained_and_lost(&self, year: i32, month: u32) -> (usize, usize) {
+        let in_month = |date: DateTime<Local>| date.year() == year && date.month() == month;
 
-        let rel_income_data: Vec<&IncomeData> = income_data
+        let gained = self
+            .students
+            .iter()
+            .filter(|student| in_month(student.tution_start_date))
+            .count();
+        let lost = self
+            .students
             .iter()
-            .filter(|data| {
-                data.month_year == prev_month_year || data.month_year == current_month_year
+            .filter(|student| student.tution_end_date.is_some_and(in_month))
+            .count();
+
+        (gained, lost)
+    }
+
+    pub fn get_new_students_trend_direction(&self) -> NumberTrend {
+        self.month_over_month_trend(|student, month, year| {
+            let start = student.tution_start_date;
+            if start.year() == year && start.month() == month {
+                1.0
+            } else {
+                0.0
+            }
+        })
+    }
+
+    /// Per-student and per-session averages for a month, over the students
+    /// active at its end (or today, for the current month).
+    pub fn monthly_averages(&self, year: i32, month: u32) -> MonthlyAverages {
+        let (_, month_end) = get_month_date_range(year, month);
+        let as_of = month_end.min(self.clock.today());
+
+        let students = self
+            .students
+            .iter()
+            .filter(|student| is_active_on(student, as_of))
+            .count() as f32;
+        let revenue: f32 = self
+            .students
+            .iter()
+            .map(|student| {
+                compute_monthly_sum(student, month, year, compute_monthly_completed_sessions)
             })
-            .collect();
+            .sum();
+        let sessions: f32 = self
+            .students
+            .iter()
+            .map(|student| compute_monthly_completed_sessions(student, month, year) as f32)
+            .sum();
 
-        compute_trend(rel_income_data[0].actual, rel_income_data[1].actual)
+        let per = |total: f32, count: f32| if count > 0.0 { total / count } else { 0.0 };
+
+        MonthlyAverages {
+            revenue_per_student: per(revenue, students),
+            revenue_per_session: per(revenue, sessions),
+            sessions_per_student: per(sessions, students),
+        }
+    }
+
+    /// This month's averages with their trends against last month.
+    pub fn monthly_averages_with_trends(&self) -> (MonthlyAverages, [NumberTrend; 3]) {
+        let today = self.clock.today();
+        let (prev_year, prev_month) = previous_month(today.year(), today.month());
+
+        let current = self.monthly_averages(today.year(), today.month());
+        let previous = self.monthly_averages(prev_year, prev_month);
+
+        let trends = [
+            compute_trend(previous.revenue_per_student, current.revenue_per_student),
+            compute_trend(previous.revenue_per_session, current.revenue_per_session),
+            compute_trend(previous.sessions_per_student, current.sessions_per_student),
+        ];
+
+        (current, trends)
     }
-}
 
-#[derive(Copy, Clone)]
-struct MonthlySummary {
-    year_month: YearMonth,
-    actual_revenue: f32,
-    potential_revenue: f32,
-    total_scheduled_sessions: usize,
-    total_actual_sessions: usize,
+    fn month_over_month_trend(&self, value_fn: impl Fn(&Student, u32, i32) -> f32) -> NumberTrend {
+        let today = self.clock.today();
+        let (prev_year, prev_month) = previous_month(today.year(), today.month());
+
+        let total_for = |month: u32, year: i32| -> f32 {
+            self.students
+                .iter()
+                .map(|student| value_fn(student, month, year))
+                .sum()
+        };
+
+        compute_trend(
+            total_for(prev_month, prev_year),
+            total_for(today.month(), today.year()),
+        )
+    }
 }
 
-#[derive(Clone)]
-pub struct TrendData {
-    pub revenue_trend: ActualRevenueTrendData,
-    pub sessions_trend: ActualSessionTrendData,
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct MonthlyAverages {
+    pub revenue_per_student: f32,
+    /// Average rate actually earned per delivered session.
+    pub revenue_per_session: f32,
+    pub sessions_per_student: f32,
 }
 
-#[derive(Clone)]
-pub struct ActualRevenueTrendData {
-    pub trend: NumberTrend,
-    pub current_revenue: f32,
-    pub previous_revenue: f32,
-    pub year_month: YearMonth,
+/// A month's totals, worked out from the students' sessions.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct MonthlySummary {
+    pub year: i32,
+    pub month: u32,
+    pub actual_revenue: f32,
+    pub potential_revenue: f32,
+    pub scheduled_sessions: usize,
+    pub completed_sessions: usize,
 }
 
-#[derive(Clone)]
-pub struct ActualSessionTrendData {
-    pub trend: NumberTrend,
-    pub current_sessions: f32,
-    pub previous_sessions: f32,
-    pub year_month: YearMonth,
+impl MonthlySummary {
+    /// Income from scheduled sessions that were not held.
+    pub fn lost_revenue(&self) -> f32 {
+        self.potential_revenue - self.actual_revenue
+    }
 }
 
-pub type TrendHistory = Vec<TrendData>;
+/// A month against the one before it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TrendData {
+    pub previous: MonthlySummary,
+    pub current: MonthlySummary,
+    pub revenue_trend: NumberTrend,
+    pub sessions_trend: NumberTrend,
+    pub lost_revenue_trend: NumberTrend,
+}
 
-#[derive(Copy, Clone)]
-pub struct YearMonth {
-    pub year: i32,
-    pub month: Month,
+impl TrendData {
+    pub fn between(previous: MonthlySummary, current: MonthlySummary) -> Self {
+        Self {
+            revenue_trend: compute_trend(previous.actual_revenue, current.actual_revenue),
+            sessions_trend: compute_trend(
+                previous.completed_sessions as f32,
+                current.completed_sessions as f32,
+            ),
+            lost_revenue_trend: compute_trend(previous.lost_revenue(), current.lost_revenue()),
+            previous,
+            current,
+        }
+    }
 }
 
-#[derive(Debug, Clone)]
+pub type TrendHistory = Vec<TrendData>;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Student {
     pub id: String,
     pub name: PersonalName,
     pub subject: TutorSubject,
+    /// Labels to group students by, such as "exam year".
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// How to reach the student or a parent, as typed.
+    #[serde(default)]
+    pub phone: Option<String>,
+    #[serde(default)]
+    pub email: Option<String>,
     pub tabled_sessions: Vec<SessionData>,
-    pub actual_sessions: Vec<DateTime<Local>>,
+    /// Further subjects taken, each on its own timetable and rate.
+    /// `subject`, `tabled_sessions` and `payment_data` are the first.
+    #[serde(default)]
+    pub other_subjects: Vec<Enrolment>,
+    /// One-off moves of tabled sessions, leaving the timetable as it is.
+    #[serde(default)]
+    pub reschedules: Vec<Reschedule>,
+    /// Sessions marked so far, held or not. Older files call this
+    /// `actual_sessions` and list only the held ones.
+    #[serde(alias = "actual_sessions")]
+    pub sessions: Vec<SessionRecord>,
+    /// Sessions called off, as older files kept them. Moved into
+    /// `sessions` by [`Domain::upgrade_sessions`] and never written.
+    #[serde(default, skip_serializing)]
+    pub cancelled_sessions: Vec<DateTime<Local>>,
+    /// Where sessions are usually held, by location name.
+    #[serde(default)]
+    pub location: Option<String>,
+    /// Held sessions that took place somewhere other than usual.
+    #[serde(default)]
+    pub session_locations: Vec<SessionLocation>,
+    /// Usual distance travelled for a home visit, there and back, in km.
+    #[serde(default)]
+    pub travel_km: Option<f32>,
+    #[serde(default)]
+    pub session_distances: Vec<SessionDistance>,
+    #[serde(default)]
+    pub session_ratings: Vec<SessionRating>,
+    /// Upcoming sessions the student has confirmed they will come to.
+    /// Kept apart from `sessions`, which only records what happened.
+    #[serde(default)]
+    pub confirmed_sessions: Vec<DateTime<Local>>,
 
     pub payment_data: PaymentData,
+    /// Earlier rates, oldest first. `payment_data` holds the latest.
+    #[serde(default)]
+    pub rate_changes: Vec<RateChange>,
     pub tution_start_date: DateTime<Local>,
+    /// When the student stopped, if they have.
+    pub tution_end_date: Option<DateTime<Local>>,
 }
 
-#[derive(Debug, Clone)]
+impl Student {
+    /// Every subject taken, the first one first.
+    pub fn subjects(&self) -> impl Iterator<Item = &TutorSubject> + '_ {
+        std::iter::once(&self.subject).chain(self.other_subjects.iter().map(|e| &e.subject))
+    }
+
+    /// The subjects taken, as one line, e.g. "Statistics, Physics".
+    pub fn subject_names(&self) -> String {
+        self.subjects()
+            .map(TutorSubject::name)
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    /// Every tabled slot across all the student's subjects.
+    pub fn timetable(&self) -> impl Iterator<Item = &SessionData> + '_ {
+        self.tabled_sessions
+            .iter()
+            .chain(self.other_subjects.iter().flat_map(|e| &e.tabled_sessions))
+    }
+
+    /// The student once per subject, each copy with just that subject's
+    /// timetable, rate and the sessions marked in its slots, so anything
+    /// worked out per student can be worked out per subject. A student
+    /// with one subject is lent as is.
+    pub fn enrolments(&self) -> Vec<Cow<'_, Student>> {
+        if self.other_subjects.is_empty() {
+            return vec![Cow::Borrowed(self)];
+        }
+
+        let subject_of = |at: DateTime<Local>| {
+            let at = self.moved_from(at).unwrap_or(at);
+            self.other_subjects
+                .iter()
+                .position(|enrolment| {
+                    enrolment
+                        .tabled_sessions
+                        .iter()
+                        .any(|slot| slot_starts_at(slot, at))
+                })
+                .map(|index| index + 1)
+                .unwrap_or(0)
+        };
+        let mut enrolments = vec![Student {
+            other_subjects: Vec::new(),
+            reschedules: self
+                .reschedules
+                .iter()
+                .filter(|moved| subject_of(moved.from) == 0)
+                .cloned()
+                .collect(),
+            sessions: self
+                .sessions
+                .iter()
+                .filter(|record| subject_of(record.at) == 0)
+                .cloned()
+                .collect(),
+            ..self.clone()
+        }];
+        enrolments.extend(
+            self.other_subjects
+                .iter()
+                .enumerate()
+                .map(|(index, enrolment)| Student {
+                    subject: enrolment.subject.clone(),
+                    tabled_sessions: enrolment.tabled_sessions.clone(),
+                    payment_data: enrolment.payment_data.clone(),
+                    rate_changes: Vec::new(),
+                    other_subjects: Vec::new(),
+                    reschedules: self
+                        .reschedules
+                        .iter()
+                        .filter(|moved| subject_of(moved.from) == index + 1)
+                        .cloned()
+                        .collect(),
+                    sessions: self
+                        .sessions
+                        .iter()
+                        .filter(|record| subject_of(record.at) == index + 1)
+                        .cloned()
+                        .collect(),
+                    ..self.clone()
+                }),
+        );
+
+        enrolments.into_iter().map(Cow::Owned).collect()
+    }
+
+    /// The subject of the slot starting at `at`, the first subject when
+    /// no other subject's slot does.
+    pub fn subject_at(&self, at: DateTime<Local>) -> &TutorSubject {
+        let at = self.moved_from(at).unwrap_or(at);
+        self.other_subjects
+            .iter()
+            .find(|enrolment| {
+                enrolment
+                    .tabled_sessions
+                    .iter()
+                    .any(|slot| slot_starts_at(slot, at))
+            })
+            .map(|enrolment| &enrolment.subject)
+            .unwrap_or(&self.subject)
+    }
+
+    /// The tabled time a session now at `at` was moved from, if it was.
+    pub fn moved_from(&self, at: DateTime<Local>) -> Option<DateTime<Local>> {
+        self.reschedules
+            .iter()
+            .find(|moved| moved.to == at)
+            .map(|moved| moved.from)
+    }
+
+    /// Moves the session at `at` to `to` for once, or back to its tabled
+    /// time when `to` is `None`. A moved session moves again from where
+    /// it was tabled.
+    pub fn reschedule(&mut self, at: DateTime<Local>, to: Option<DateTime<Local>>) {
+        let from = self.moved_from(at).unwrap_or(at);
+        self.reschedules.retain(|moved| moved.from != from);
+        if let Some(to) = to.filter(|to| *to != from) {
+            self.reschedules.push(Reschedule { from, to });
+            self.reschedules.sort_by_key(|moved| moved.from);
+        }
+    }
+
+    /// Start times of the sessions that went ahead, the ones charged for.
+    pub fn held_sessions(&self) -> impl Iterator<Item = DateTime<Local>> + '_ {
+        self.sessions
+            .iter()
+            .filter(|record| record.status == SessionStatus::Completed)
+            .map(|record| record.at)
+    }
+
+    /// The latest note written for a session up to `at`.
+    pub fn last_note(&self, at: DateTime<Local>) -> Option<&str> {
+        self.sessions
+            .iter()
+            .filter(|record| record.at <= at && !record.notes.trim().is_empty())
+            .max_by_key(|record| record.at)
+            .map(|record| record.notes.trim())
+    }
+
+    /// How the session at `at` went, or `None` while it is unmarked.
+    pub fn session_status(&self, at: DateTime<Local>) -> Option<SessionStatus> {
+        self.sessions
+            .iter()
+            .find(|record| record.at == at)
+            .map(|record| record.status)
+    }
+}
+
+/// A tabled session held at another time just this once.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Reschedule {
+    pub from: DateTime<Local>,
+    pub to: DateTime<Local>,
+}
+
+/// A subject taken alongside a student's first one.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Enrolment {
+    pub subject: TutorSubject,
+    pub tabled_sessions: Vec<SessionData>,
+    pub payment_data: PaymentData,
+}
+
+fn slot_starts_at(slot: &SessionData, at: DateTime<Local>) -> bool {
+    slot.day == at.weekday() && parse_session_time(&slot.time) == Some(at.time())
+}
+
+/// How a session went.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SessionStatus {
+    Completed,
+    MissedByStudent,
+    CancelledByTutor,
+    /// Moved to another time, which is logged on its own.
+    Rescheduled,
+}
+
+impl SessionStatus {
+    pub const ALL: [SessionStatus; 4] = [
+        SessionStatus::Completed,
+        SessionStatus::MissedByStudent,
+        SessionStatus::CancelledByTutor,
+        SessionStatus::Rescheduled,
+    ];
+
+    /// Whether the student was due to attend, so the session counts
+    /// towards their attendance. Sessions the tutor called off or moved
+    /// do not.
+    pub fn was_due(&self) -> bool {
+        matches!(
+            self,
+            SessionStatus::Completed | SessionStatus::MissedByStudent
+        )
+    }
+}
+
+impl std::fmt::Display for SessionStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SessionStatus::Completed => write!(f, "Completed"),
+            SessionStatus::MissedByStudent => write!(f, "Missed by student"),
+            SessionStatus::CancelledByTutor => write!(f, "Cancelled by tutor"),
+            SessionStatus::Rescheduled => write!(f, "Rescheduled"),
+        }
+    }
+}
+
+/// One marked session. Older files hold just the start time of a held
+/// session, which reads as completed.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(from = "StoredSessionRecord")]
+pub struct SessionRecord {
+    pub at: DateTime<Local>,
+    pub status: SessionStatus,
+    pub notes: String,
+    /// Recordings or whiteboard exports of an online lesson.
+    pub links: Vec<String>,
+}
+
+impl SessionRecord {
+    pub fn completed(at: DateTime<Local>) -> Self {
+        Self {
+            at,
+            status: SessionStatus::Completed,
+            notes: String::new(),
+            links: Vec::new(),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum StoredSessionRecord {
+    Held(DateTime<Local>),
+    Marked {
+        at: DateTime<Local>,
+        status: SessionStatus,
+        #[serde(default)]
+        notes: String,
+        #[serde(default)]
+        links: Vec<String>,
+    },
+}
+
+impl From<StoredSessionRecord> for SessionRecord {
+    fn from(stored: StoredSessionRecord) -> Self {
+        match stored {
+            StoredSessionRecord::Held(at) => SessionRecord::completed(at),
+            StoredSessionRecord::Marked {
+                at,
+                status,
+                notes,
+                links,
+            } => SessionRecord {
+                at,
+                status,
+                notes,
+                links,
+            },
+        }
+    }
+}
+
+/// A place sessions are held, such as online or at the student's home.
+/// The surcharge is added to the fee of every session held there.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Location {
+    pub name: String,
+    #[serde(default)]
+    pub surcharge: f32,
+    /// Travel to the student is logged for sessions held here.
+    #[serde(default)]
+    pub home_visit: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SessionLocation {
+    pub at: DateTime<Local>,
+    pub location: String,
+}
+
+/// Distance travelled for one home visit, when it differs from the
+/// student's usual.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SessionDistance {
+    pub at: DateTime<Local>,
+    pub km: f32,
+}
+
+/// How engaged the student was in a held session, from 1 to
+/// [`MAX_ENGAGEMENT`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SessionRating {
+    pub at: DateTime<Local>,
+    pub engagement: u8,
+}
+
+pub const MAX_ENGAGEMENT: u8 = 5;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Tutor {
     pub id: String,
     pub name: PersonalName,
@@ -212,85 +1153,307 @@ pub struct Tutor {
     pub available_times: HashMap<Weekday, Vec<String>>,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct PersonalName {
     pub first: String,
     pub last: String,
     pub other: Option<String>,
 }
 
-#[derive(Debug, Clone)]
+impl PersonalName {
+    pub fn full_name(&self) -> String {
+        if let Some(other) = &self.other {
+            format!("{} {} {}", self.first, other, self.last)
+        } else {
+            format!("{} {}", self.first, self.last)
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct SessionData {
     pub day: Weekday,
     pub time: String,
+    pub duration_minutes: u32,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub enum TutorSubject {
-    AdditionalMathematics,
-    ExtendedMathematics,
-    Statistics,
-}
+/// Length assumed for a session when its slot does not say.
+pub const DEFAULT_SESSION_MINUTES: u32 = 60;
+
+/// A subject the tutor teaches, named however they like in Settings.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(from = "String", into = "String")]
+pub struct TutorSubject(String);
 
 impl TutorSubject {
-    pub const ALL: [TutorSubject; 3] = [
-        TutorSubject::ExtendedMathematics,
-        TutorSubject::AdditionalMathematics,
-        TutorSubject::Statistics,
-    ];
+    pub fn new(name: &str) -> Self {
+        Self(name.trim().to_string())
+    }
+
+    pub fn name(&self) -> &str {
+        &self.0
+    }
+
+    /// What a new tutor starts with.
+    pub fn defaults() -> Vec<TutorSubject> {
+        [
+            "Extended Mathematics",
+            "Additional Mathematics",
+            "Statistics",
+        ]
+        .map(TutorSubject::new)
+        .to_vec()
+    }
+}
+
+/// Older files name the three subjects that were once built in by their
+/// code names, e.g. `ExtendedMathematics`.
+impl From<String> for TutorSubject {
+    fn from(name: String) -> Self {
+        match name.as_str() {
+            "ExtendedMathematics" => TutorSubject::new("Extended Mathematics"),
+            "AdditionalMathematics" => TutorSubject::new("Additional Mathematics"),
+            _ => TutorSubject::new(&name),
+        }
+    }
+}
+
+impl From<TutorSubject> for String {
+    fn from(subject: TutorSubject) -> Self {
+        subject.0
+    }
 }
 
 impl std::fmt::Display for TutorSubject {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SubjectRate {
+    pub subject: TutorSubject,
+    pub per_hour: f32,
+}
+
+/// A weekly time the tutor teaches, as entered, e.g. "5:30 PM".
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WeeklySlot {
+    pub day: Weekday,
+    pub time: String,
+}
+
+impl WeeklySlot {
+    /// Whether both name the same time, however each was typed.
+    pub fn same_as(&self, day: Weekday, time: &str) -> bool {
+        self.day == day
+            && match (parse_session_time(&self.time), parse_session_time(time)) {
+                (Some(ours), Some(theirs)) => ours == theirs,
+                _ => self.time.trim() == time.trim(),
+            }
+    }
+}
+
+impl std::fmt::Display for WeeklySlot {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {}", self.day, self.time)
+    }
+}
+
+/// Days a deleted item can be restored before it is gone for good.
+pub const TRASH_RETENTION_DAYS: i64 = 30;
+
+/// Something deleted on `deleted_on`, kept to be restored.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Trashed<T> {
+    pub item: T,
+    pub deleted_on: NaiveDate,
+}
+
+impl<T> Trashed<T> {
+    pub fn new(item: T, deleted_on: NaiveDate) -> Self {
+        Self { item, deleted_on }
+    }
+
+    /// The first day it is no longer kept.
+    pub fn expires_on(&self) -> NaiveDate {
+        self.deleted_on + Duration::days(TRASH_RETENTION_DAYS)
+    }
+
+    pub fn is_expired(&self, today: NaiveDate) -> bool {
+        today >= self.expires_on()
+    }
+}
+
+/// What the domain's trash holds. Payments sit in the ledger's own.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum TrashItem {
+    Student(Box<Student>),
+    /// A marked session, with its notes, whose mark was taken back.
+    Session {
+        student_id: String,
+        student_name: String,
+        record: SessionRecord,
+    },
+}
+
+impl std::fmt::Display for TrashItem {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            TutorSubject::Statistics => write!(f, "Statistics"),
-            TutorSubject::ExtendedMathematics => write!(f, "Extended Mathematics"),
-            TutorSubject::AdditionalMathematics => write!(f, "Additional Mathematics"),
+            TrashItem::Student(student) => write!(f, "Student {}", student.name.full_name()),
+            TrashItem::Session {
+                student_name,
+                record,
+                ..
+            } => write!(
+                f,
+                "{}'s session on {} ({})",
+                student_name,
+                record.at.format("%a %-d %b %Y, %-I:%M %p"),
+                record.status
+            ),
         }
     }
 }
 
-// impl TutorSubject {
-//     pub fn as_str(&self) -> &str {
-//         match self {
-//             TutorSubject::AdditionalMathematics => "Additional Mathematics",
-//             TutorSubject::ExtendedMathematics => "Extended Mathematics",
-//             TutorSubject::Statistics => "Statistics",
-//         }
-//     }
-// }
+/// Someone waiting to start, and the slots that would suit them.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Prospect {
+    pub name: String,
+    pub subject: TutorSubject,
+    pub slots: Vec<WeeklySlot>,
+    pub added_on: NaiveDate,
+}
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct PaymentData {
     pub payment_type: PaymentType,
     pub amount: f32,
+    /// Sessions a month a monthly fee is owed for. Sessions the tutor
+    /// cancels are only taken off below this; with none, every one is.
+    #[serde(default)]
+    pub min_sessions: Option<u32>,
+}
+
+/// A rise or cut in a student's rate. Months before `from` were charged
+/// at `previous`. Rates only change at the start of a month.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct RateChange {
+    pub from: NaiveDate,
+    pub previous: f32,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub enum PaymentType {
     PerSession,
     Monthly,
 }
 
+/// The student's rate for the month, per session or per month as their
+/// payment type says.
+pub fn rate_in(student: &Student, year: i32, month: u32) -> f32 {
+    let Some(month_start) = NaiveDate::from_ymd_opt(year, month, 1) else {
+        return student.payment_data.amount;
+    };
+
+    student
+        .rate_changes
+        .iter()
+        .filter(|change| change.from > month_start)
+        .min_by_key(|change| change.from)
+        .map(|change| change.previous)
+        .unwrap_or(student.payment_data.amount)
+}
+
 pub fn compute_monthly_sum(
     student: &Student,
     month: u32,
     year: i32,
     compute_sessions_fn: fn(&Student, u32, i32) -> i32,
 ) -> f32 {
+    if !student.other_subjects.is_empty() {
+        return student
+            .enrolments()
+            .iter()
+            .map(|enrolment| compute_monthly_sum(enrolment, month, year, compute_sessions_fn))
+            .sum();
+    }
+
+    let rate = rate_in(student, year, month);
     match student.payment_data.payment_type {
         PaymentType::PerSession => {
             let no_of_days = compute_sessions_fn(student, month, year);
-            student.payment_data.amount * (no_of_days as f32)
+            rate * (no_of_days as f32)
         }
-        // TODO: Logic for actual monthly payment taken vs agreed
-        // Maybe based on targets or missed sessions and
-        // deductions are per contract
-        PaymentType::Monthly => student.payment_data.amount,
+        PaymentType::Monthly => monthly_fee(student, year, month),
+    }
+}
+
+/// What a monthly contract charges for the month. The fee is worth an
+/// equal share of each session the month's timetable holds, so a month
+/// the student started or stopped part-way through is charged for the
+/// sessions tabled while enrolled. Sessions the tutor cancelled come off
+/// once fewer than the contract's minimum, pro-rated alike, were given.
+pub fn monthly_fee(student: &Student, year: i32, month: u32) -> f32 {
+    let fee = rate_in(student, year, month);
+    let tabled = compute_monthly_scheduled_sessions(student, month, year);
+    if tabled <= 0 {
+        return fee;
+    }
+
+    let (month_start, month_end) = get_month_date_range(year, month);
+    let start = month_start.max(student.tution_start_date.date_naive());
+    let end = student.tution_end_date.map_or(month_end, |end| {
+        month_end.min(end.date_naive() - Duration::days(1))
+    });
+    let enrolled = (count_scheduled_weekdays(student, &weekday_counts_between(start, end))
+        + moved_in_between(student, start, end))
+    .max(0) as f32;
+    let cancelled = student
+        .sessions
+        .iter()
+        .filter(|record| record.status == SessionStatus::CancelledByTutor)
+        .filter(|record| (start..=end).contains(&record.at.date_naive()))
+        .count() as f32;
+
+    let owed = student.payment_data.min_sessions.map_or(enrolled, |min| {
+        (min as f32 * enrolled / tabled as f32).min(enrolled)
+    });
+    let shortfall = (owed - (enrolled - cancelled)).max(0.0);
+    fee / tabled as f32 * (enrolled - shortfall)
+}
+
+/// The Monday of the week containing `date`, which weekly charts group
+/// sessions by.
+pub fn week_start(date: NaiveDate) -> NaiveDate {
+    date - Duration::days(date.weekday().num_days_from_monday() as i64)
+}
+
+/// Short month label for charts, e.g. "Jan '26", so months from
+/// different years are told apart.
+pub fn month_label(year: i32, month: u32) -> String {
+    NaiveDate::from_ymd_opt(year, month, 1)
+        .map(|date| date.format("%b '%y").to_string())
+        .unwrap_or_default()
+}
+
+pub fn previous_month(year: i32, month: u32) -> (i32, u32) {
+    if month == 1 {
+        (year - 1, 12)
+    } else {
+        (year, month - 1)
+    }
+}
+
+pub fn next_month(year: i32, month: u32) -> (i32, u32) {
+    if month == 12 {
+        (year + 1, 1)
+    } else {
+        (year, month + 1)
     }
 }
 
-fn get_month_date_range(year: i32, month: u32) -> (NaiveDate, NaiveDate) {
+pub fn get_month_date_range(year: i32, month: u32) -> (NaiveDate, NaiveDate) {
     let month_start = NaiveDate::from_ymd_opt(year, month, 1).unwrap();
     let month_end = if month == 12 {
         NaiveDate::from_ymd_opt(year + 1, 1, 1).unwrap()
@@ -301,119 +1464,752 @@ fn get_month_date_range(year: i32, month: u32) -> (NaiveDate, NaiveDate) {
     (month_start, month_end)
 }
 
-fn get_all_dates_in_month(year: i32, month: u32) -> Vec<NaiveDate> {
+/// How many times each weekday (indexed from Monday) falls between
+/// `start` and `end` inclusive, counted without walking the dates.
+fn weekday_counts_between(start: NaiveDate, end: NaiveDate) -> [i32; 7] {
+    let mut counts = [0; 7];
+    if end < start {
+        return counts;
+    }
+
+    let days = end.signed_duration_since(start).num_days() + 1;
+    let full_weeks = (days / 7) as i32;
+    let first = start.weekday().num_days_from_monday() as usize;
+
+    counts.iter_mut().for_each(|count| *count = full_weeks);
+    for offset in 0..(days % 7) as usize {
+        counts[(first + offset) % 7] += 1;
+    }
+
+    counts
+}
+
+fn count_scheduled_weekdays(student: &Student, weekday_counts: &[i32; 7]) -> i32 {
+    let mut scheduled = [false; 7];
+    for session in student.timetable() {
+        scheduled[session.day.num_days_from_monday() as usize] = true;
+    }
+
+    scheduled
+        .iter()
+        .zip(weekday_counts)
+        .filter(|(is_scheduled, _)| **is_scheduled)
+        .map(|(_, count)| count)
+        .sum()
+}
+
+fn get_scheduled_weekdays(student: &Student) -> Vec<Weekday> {
+    student.timetable().map(|session| session.day).collect()
+}
+
+pub fn compute_monthly_scheduled_sessions(student: &Student, month: u32, year: i32) -> i32 {
     let (month_start, month_end) = get_month_date_range(year, month);
-    let duration = month_end.signed_duration_since(month_start);
 
-    (0..=duration.num_days())
-        .map(|i| month_start + Duration::days(i))
+    count_scheduled_weekdays(student, &weekday_counts_between(month_start, month_end))
+        + moved_in_between(student, month_start, month_end)
+}
+
+/// Sessions moved into `start`..=`end` less those moved out of it.
+fn moved_in_between(student: &Student, start: NaiveDate, end: NaiveDate) -> i32 {
+    let within = |at: DateTime<Local>| (start..=end).contains(&at.date_naive());
+    student
+        .reschedules
+        .iter()
+        .map(|moved| within(moved.to) as i32 - within(moved.from) as i32)
+        .sum()
+}
+
+/// Sessions a weekly timetable gives in a month, one per entry in `days`,
+/// so a day listed twice counts twice.
+pub fn sessions_in_month(days: &[Weekday], year: i32, month: u32) -> i32 {
+    let (month_start, month_end) = get_month_date_range(year, month);
+    let counts = weekday_counts_between(month_start, month_end);
+
+    days.iter()
+        .map(|day| counts[day.num_days_from_monday() as usize])
+        .sum()
+}
+
+pub fn compute_monthly_completed_sessions(student: &Student, month: u32, year: i32) -> i32 {
+    let (month_start, month_end) = get_month_date_range(year, month);
+
+    held_sessions_between(student, month_start, month_end).count() as i32
+}
+
+/// Held sessions from `start` to `end` that count towards the student's
+/// timetable: those on a tabled weekday or on a date a session was moved to.
+fn held_sessions_between(
+    student: &Student,
+    start: NaiveDate,
+    end: NaiveDate,
+) -> impl Iterator<Item = DateTime<Local>> + '_ {
+    let session_days = get_scheduled_weekdays(student);
+
+    student.held_sessions().filter(move |session| {
+        let date = session.naive_local().date();
+        (start..=end).contains(&date)
+            && (session_days.contains(&date.weekday())
+                || student
+                    .reschedules
+                    .iter()
+                    .any(|moved| moved.to.date_naive() == date))
+    })
+}
+
+/// The latest `count` engagement ratings, oldest first.
+pub fn recent_engagement(student: &Student, count: usize) -> Vec<u8> {
+    let mut ratings: Vec<&SessionRating> = student.session_ratings.iter().collect();
+    ratings.sort_by_key(|rating| rating.at);
+
+    ratings[ratings.len().saturating_sub(count)..]
+        .iter()
+        .map(|rating| rating.engagement)
         .collect()
 }
 
-fn get_scheduled_weekdays(student: &Student) -> Vec<Weekday> {
+/// Average engagement over the month's rated sessions, if any were rated.
+pub fn monthly_engagement(student: &Student, month: u32, year: i32) -> Option<f32> {
+    let ratings: Vec<u8> = student
+        .session_ratings
+        .iter()
+        .filter(|rating| rating.at.year() == year && rating.at.month() == month)
+        .map(|rating| rating.engagement)
+        .collect();
+
+    (!ratings.is_empty())
+        .then(|| ratings.iter().map(|&rating| rating as f32).sum::<f32>() / ratings.len() as f32)
+}
+
+/// The held sessions `compute_monthly_completed_sessions` counts for the
+/// month, earliest first.
+pub fn completed_sessions_in(student: &Student, month: u32, year: i32) -> Vec<DateTime<Local>> {
+    let (month_start, month_end) = get_month_date_range(year, month);
+
+    let mut sessions: Vec<DateTime<Local>> =
+        held_sessions_between(student, month_start, month_end).collect();
+    sessions.sort();
+
+    sessions
+}
+
+/// Where the session at `at` was held: its own location if one was
+/// logged, otherwise the student's usual one.
+pub fn session_location(student: &Student, at: DateTime<Local>) -> Option<&str> {
+    student
+        .session_locations
+        .iter()
+        .find(|session| session.at == at)
+        .map(|session| session.location.as_str())
+        .or(student.location.as_deref())
+}
+
+/// The surcharge for the session at `at`. Locations since removed charge
+/// nothing.
+pub fn session_surcharge(student: &Student, locations: &[Location], at: DateTime<Local>) -> f32 {
+    session_location(student, at)
+        .and_then(|name| locations.iter().find(|location| location.name == name))
+        .map(|location| location.surcharge)
+        .unwrap_or(0.0)
+}
+
+/// Distance travelled for the session at `at`, if it was a home visit,
+/// or 0 when no distance was ever given.
+pub fn session_distance(
+    student: &Student,
+    locations: &[Location],
+    at: DateTime<Local>,
+) -> Option<f32> {
+    let name = session_location(student, at)?;
+    locations
+        .iter()
+        .find(|location| location.name == name && location.home_visit)?;
+
     student
-        .tabled_sessions
+        .session_distances
+        .iter()
+        .find(|session| session.at == at)
+        .map(|session| session.km)
+        .or(student.travel_km)
+        .or(Some(0.0))
+}
+
+/// Surcharges for the held sessions `completed_sessions_in` counts for
+/// the month, on top of the regular fee.
+pub fn monthly_surcharges(student: &Student, locations: &[Location], month: u32, year: i32) -> f32 {
+    completed_sessions_in(student, month, year)
+        .into_iter()
+        .map(|at| session_surcharge(student, locations, at))
+        .sum()
+}
+
+/// When each of the student's tabled sessions falls on `date`, earliest
+/// first, with sessions moved away left out and ones moved in added.
+pub fn scheduled_sessions_on(student: &Student, date: NaiveDate) -> Vec<DateTime<Local>> {
+    let mut sessions: Vec<DateTime<Local>> = student
+        .timetable()
+        .filter(|session| session.day == date.weekday())
+        .filter_map(|session| {
+            let time = parse_session_time(&session.time)?;
+            Local.from_local_datetime(&date.and_time(time)).earliest()
+        })
+        .filter(|at| !student.reschedules.iter().any(|moved| moved.from == *at))
+        .chain(
+            student
+                .reschedules
+                .iter()
+                .map(|moved| moved.to)
+                .filter(|to| to.date_naive() == date),
+        )
+        .collect();
+
+    sessions.sort();
+    sessions
+}
+
+/// How long an attended session ran, taken from the tabled slot on the
+/// same weekday that starts closest to it.
+pub fn session_minutes(student: &Student, at: DateTime<Local>) -> u32 {
+    let minutes_apart = |session: &SessionData| {
+        parse_session_time(&session.time)
+            .map(|time| (time - at.time()).num_minutes().abs())
+            .unwrap_or(i64::MAX)
+    };
+
+    student
+        .timetable()
+        .filter(|session| session.day == at.weekday())
+        .min_by_key(|session| minutes_apart(session))
+        .map(|session| session.duration_minutes)
+        .unwrap_or(DEFAULT_SESSION_MINUTES)
+}
+
+/// Where a session stands at a given time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SessionTiming {
+    /// Starts in this long.
+    Upcoming(Duration),
+    InSession,
+    Over,
+}
+
+impl SessionTiming {
+    pub fn of(student: &Student, at: DateTime<Local>, now: DateTime<Local>) -> Self {
+        let end = at + Duration::minutes(session_minutes(student, at) as i64);
+        if now < at {
+            SessionTiming::Upcoming(at - now)
+        } else if now < end {
+            SessionTiming::InSession
+        } else {
+            SessionTiming::Over
+        }
+    }
+
+    /// "in 2h 15m" before the session, rounded up to the minute.
+    pub fn countdown(&self) -> Option<String> {
+        let SessionTiming::Upcoming(until) = self else {
+            return None;
+        };
+
+        let minutes = (until.num_seconds() + 59) / 60;
+        let (days, hours, minutes) = (minutes / (24 * 60), minutes / 60 % 24, minutes % 60);
+        Some(match (days, hours) {
+            (0, 0) => format!("in {}m", minutes),
+            (0, _) => format!("in {}h {}m", hours, minutes),
+            _ => format!("in {}d {}h", days, hours),
+        })
+    }
+}
+
+/// The student's first tabled session that has not ended by `now`,
+/// looking up to a week ahead.
+pub fn upcoming_session(student: &Student, now: DateTime<Local>) -> Option<DateTime<Local>> {
+    now.date_naive()
+        .iter_days()
+        .take(8)
+        .filter(|date| is_active_on(student, *date))
+        .flat_map(|date| scheduled_sessions_on(student, date))
+        .find(|at| SessionTiming::of(student, *at, now) != SessionTiming::Over)
+}
+
+/// The first tabled session after `now` among active students, looking
+/// up to a week ahead.
+pub fn next_scheduled_session(
+    students: &[Student],
+    now: DateTime<Local>,
+) -> Option<(DateTime<Local>, &Student)> {
+    now.date_naive()
+        .iter_days()
+        .take(8)
+        .flat_map(|date| {
+            students
+                .iter()
+                .filter(move |student| is_active_on(student, date))
+                .flat_map(move |student| {
+                    scheduled_sessions_on(student, date)
+                        .into_iter()
+                        .map(move |at| (at, student))
+                })
+        })
+        .filter(|(at, _)| *at > now)
+        .min_by_key(|(at, _)| *at)
+}
+
+/// A tabled session that has already started, and whether it was logged.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PastOccurrence {
+    pub student_id: String,
+    pub at: DateTime<Local>,
+    /// `None` until the session is marked.
+    pub status: Option<SessionStatus>,
+}
+
+/// Tabled sessions on `date` that started before `now`, earliest first,
+/// so each can be marked after the fact.
+pub fn past_occurrences_on(
+    students: &[Student],
+    date: NaiveDate,
+    now: DateTime<Local>,
+) -> Vec<PastOccurrence> {
+    let mut occurrences: Vec<PastOccurrence> = students
         .iter()
-        .map(|session| session.day)
+        .filter(|student| is_active_on(student, date))
+        .flat_map(|student| {
+            scheduled_sessions_on(student, date)
+                .into_iter()
+                .filter(|at| *at <= now)
+                .map(|at| PastOccurrence {
+                    student_id: student.id.clone(),
+                    at,
+                    status: student.session_status(at),
+                })
+        })
+        .collect();
+
+    occurrences.sort_by_key(|occurrence| occurrence.at);
+    occurrences
+}
+
+/// The slot a session being logged most likely was: today's slot nearest
+/// to `now`, or else the latest one in the week before. Slots already
+/// marked are passed over.
+pub fn suggested_session(student: &Student, now: DateTime<Local>) -> Option<DateTime<Local>> {
+    let today = now.date_naive();
+    let unlogged = |date| {
+        scheduled_sessions_on(student, date)
+            .into_iter()
+            .filter(|at| student.session_status(*at).is_none())
+    };
+
+    unlogged(today)
+        .min_by_key(|at| (*at - now).num_minutes().abs())
+        .or_else(|| {
+            (1..=7)
+                .map(|ago| today - Duration::days(ago))
+                .find_map(|date| unlogged(date).max())
+        })
+}
+
+/// Tabled sessions in the `days` before `today` that are still unmarked,
+/// earliest first.
+pub fn unlogged_sessions(students: &[Student], today: NaiveDate, days: i64) -> Vec<PastOccurrence> {
+    let Some(start_of_today) = Local
+        .from_local_datetime(&today.and_time(NaiveTime::MIN))
+        .earliest()
+    else {
+        return Vec::new();
+    };
+
+    (1..=days)
+        .rev()
+        .flat_map(|ago| past_occurrences_on(students, today - Duration::days(ago), start_of_today))
+        .filter(|occurrence| occurrence.status.is_none())
         .collect()
 }
 
-pub fn compute_monthly_scheduled_sessions(student: &Student, month: u32, year: i32) -> i32 {
-    let all_dates = get_all_dates_in_month(year, month);
+/// Every weekly time the tutor is available, in week order.
+pub fn available_slots(tutor: &Tutor) -> Vec<WeeklySlot> {
+    let mut slots: Vec<WeeklySlot> = tutor
+        .available_times
+        .iter()
+        .flat_map(|(day, times)| {
+            times.iter().map(|time| WeeklySlot {
+                day: *day,
+                time: time.clone(),
+            })
+        })
+        .collect();
+
+    slots.sort_by_key(|slot| {
+        (
+            slot.day.num_days_from_monday(),
+            parse_session_time(&slot.time),
+        )
+    });
+    slots
+}
+
+/// A student's tabled slots in week order, each listed once however its
+/// time was typed.
+pub fn weekly_schedule(student: &Student) -> Vec<WeeklySlot> {
+    let mut slots: Vec<WeeklySlot> = student
+        .timetable()
+        .map(|session| WeeklySlot {
+            day: session.day,
+            time: session.time.clone(),
+        })
+        .collect();
+
+    slots.sort_by_key(|slot| {
+        (
+            slot.day.num_days_from_monday(),
+            parse_session_time(&slot.time),
+        )
+    });
+    slots.dedup_by(|later, earlier| earlier.same_as(later.day, &later.time));
+    slots
+}
+
+/// The tutor's available times no current student has tabled. Students
+/// who have stopped give their slots back.
+pub fn free_slots(domain: &Domain) -> Vec<WeeklySlot> {
+    let today = domain.clock.today();
+    let taken: Vec<&SessionData> = domain
+        .students
+        .iter()
+        .filter(|student| {
+            student
+                .tution_end_date
+                .is_none_or(|end| today < end.date_naive())
+        })
+        .flat_map(|student| student.timetable())
+        .collect();
+
+    available_slots(&domain.tutor)
+        .into_iter()
+        .filter(|slot| {
+            !taken
+                .iter()
+                .any(|session| slot.same_as(session.day, &session.time))
+        })
+        .collect()
+}
+
+/// A free slot someone on the waitlist asked for.
+#[derive(Debug, Clone, PartialEq)]
+pub struct WaitlistMatch {
+    pub slot: WeeklySlot,
+    pub prospect: String,
+    pub subject: TutorSubject,
+}
+
+/// Free slots paired with each prospect who would take them, in week
+/// order and then longest waiting first.
+pub fn waitlist_matches(domain: &Domain) -> Vec<WaitlistMatch> {
+    free_slots(domain)
+        .into_iter()
+        .flat_map(|slot| {
+            domain
+                .waitlist
+                .iter()
+                .filter(|prospect| {
+                    prospect
+                        .slots
+                        .iter()
+                        .any(|wanted| slot.same_as(wanted.day, &wanted.time))
+                })
+                .map(|prospect| WaitlistMatch {
+                    slot: slot.clone(),
+                    prospect: prospect.name.clone(),
+                    subject: prospect.subject.clone(),
+                })
+                .collect::<Vec<_>>()
+        })
+        .collect()
+}
+
+/// Session times are stored as entered, e.g. "5:00 PM" or "05:00 PM".
+pub fn parse_session_time(time: &str) -> Option<NaiveTime> {
+    NaiveTime::parse_from_str(time.trim(), "%I:%M %p").ok()
+}
+
+/// The first day after `today` with a session, counting one-off moves.
+/// `None` when nothing is tabled and nothing has been moved ahead.
+pub fn get_next_session(student: &Student, today: NaiveDate) -> Option<NaiveDate> {
+    // Every weekly slot comes round within a week, but a session can be
+    // moved further out than that.
+    let furthest = student
+        .reschedules
+        .iter()
+        .map(|moved| moved.to.date_naive())
+        .fold(today + Duration::days(7), NaiveDate::max);
+
+    today
+        .iter_days()
+        .skip(1)
+        .take_while(|date| *date <= furthest)
+        .find(|date| !scheduled_sessions_on(student, *date).is_empty())
+}
+
+/// Tabled sessions from `start` to `end`, less those the tutor called
+/// off or moved, which the student was never due at.
+fn count_scheduled_sessions_between(student: &Student, start: NaiveDate, end: NaiveDate) -> i32 {
     let session_days = get_scheduled_weekdays(student);
+    let not_due = student
+        .sessions
+        .iter()
+        .filter(|record| !record.status.was_due())
+        .map(|record| record.at.date_naive())
+        .filter(|date| (start..=end).contains(date) && session_days.contains(&date.weekday()))
+        .count() as i32;
+
+    (count_scheduled_weekdays(student, &weekday_counts_between(start, end))
+        + moved_in_between(student, start, end)
+        - not_due)
+        .max(0)
+}
+
+/// Enrolled on `date`: started by then and not yet stopped.
+pub fn is_active_on(student: &Student, date: NaiveDate) -> bool {
+    student.tution_start_date.date_naive() <= date
+        && student
+            .tution_end_date
+            .is_none_or(|end| date < end.date_naive())
+}
+
+/// Stopped on or before `date`, as an archived student has. Their past
+/// sessions still count towards income.
+pub fn has_left(student: &Student, date: NaiveDate) -> bool {
+    student
+        .tution_end_date
+        .is_some_and(|end| end.date_naive() <= date)
+}
 
-    all_dates
-        .iter()
-        .filter(|date| session_days.contains(&date.weekday()))
-        .count() as i32
+fn scheduled_while_enrolled(student: &Student, start: NaiveDate, end: NaiveDate) -> i32 {
+    let start = start.max(student.tution_start_date.date_naive());
+    count_scheduled_sessions_between(student, start, end)
 }
 
-pub fn compute_monthly_completed_sessions(student: &Student, month: u32, year: i32) -> i32 {
-    let (month_start, month_end) = get_month_date_range(year, month);
-    let session_days = get_scheduled_weekdays(student);
+/// Share of the sessions tabled from `start` to `end` while enrolled that
+/// were held, or `None` when none were due.
+pub fn attendance_rate(student: &Student, start: NaiveDate, end: NaiveDate) -> Option<f32> {
+    let scheduled = scheduled_while_enrolled(student, start, end);
+    let held = count_completed_sessions_between(student, start, end);
 
-    let actual_session_dates: Vec<NaiveDate> = student
-        .actual_sessions
-        .iter()
-        .map(|dt| dt.naive_local().date())
-        .filter(|date| date >= &month_start && date <= &month_end)
-        .collect();
+    (scheduled > 0).then(|| (held as f32 / scheduled as f32).min(1.0))
+}
 
-    actual_session_dates
-        .iter()
-        .filter(|date| session_days.contains(&date.weekday()))
-        .count() as i32
+/// One month of a student's record, as listed on their detail page.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StudentMonth {
+    pub year: i32,
+    pub month: u32,
+    pub held: i32,
+    /// Sessions tabled in the month up to today, while enrolled.
+    pub scheduled: i32,
+    pub attendance_rate: Option<f32>,
+    pub earned: f32,
 }
 
-pub fn get_next_session(student: &Student) -> NaiveDate {
-    let tabled_next_days: Vec<Weekday> = student
-        .tabled_sessions
-        .iter()
-        .map(|session| session.day)
-        .collect();
+/// Every month from the student's start to today, or to when they left,
+/// oldest first.
+pub fn student_months(student: &Student, today: NaiveDate) -> Vec<StudentMonth> {
+    let first = student.tution_start_date.date_naive();
+    let last = student
+        .tution_end_date
+        .map(|end| end.date_naive().min(today))
+        .unwrap_or(today);
+
+    let mut months = Vec::new();
+    let (mut year, mut month) = (first.year(), first.month());
+    while (year, month) <= (last.year(), last.month()) {
+        let (month_start, month_end) = get_month_date_range(year, month);
+        let month_end = month_end.min(last);
+
+        months.push(StudentMonth {
+            year,
+            month,
+            held: count_completed_sessions_between(student, month_start, month_end),
+            scheduled: scheduled_while_enrolled(student, month_start, month_end),
+            attendance_rate: attendance_rate(student, month_start, month_end),
+            earned: compute_monthly_sum(student, month, year, compute_monthly_completed_sessions),
+        });
+        (year, month) = next_month(year, month);
+    }
 
-    let today = Local::now().naive_local().date();
-    let next_seven_dates: Vec<NaiveDate> = (1..=7).map(|i| today + Duration::days(i)).collect();
+    months
+}
 
-    next_seven_dates
-        .into_iter()
-        .filter(|date| tabled_next_days.contains(&date.weekday()))
-        .min()
-        .unwrap()
+fn count_completed_sessions_between(student: &Student, start: NaiveDate, end: NaiveDate) -> i32 {
+    held_sessions_between(student, start, end).count() as i32
 }
 
-/// Computes month-over-month trends for some eligible data
-fn compute_trend_history_internal(monthly_summaries: &[MonthlySummary]) -> TrendHistory {
-    if monthly_summaries.len() < 2 {
-        return Vec::<TrendData>::new();
-    }
+const CHURN_WINDOW_DAYS: i64 = 28;
+const CHURN_ATTENDANCE_WEIGHT: f32 = 40.0;
+const CHURN_RECENCY_WEIGHT: f32 = 25.0;
+const CHURN_PAYMENT_WEIGHT: f32 = 20.0;
+const CHURN_NOTES_WEIGHT: f32 = 15.0;
+/// How long an invoice can be overdue before it counts in full.
+const CHURN_OVERDUE_DAYS: i64 = 30;
+
+/// Scores how likely a student is to drop off, from 0 (safe) to 100.
+///
+/// Attendance and how many held sessions got notes over the last four
+/// weeks, the gap since the last session, and `days_overdue`, how late
+/// the student's most overdue unpaid invoice is, each add points.
+pub fn compute_churn_risk(
+    student: &Student,
+    days_overdue: Option<i64>,
+    today: NaiveDate,
+) -> ChurnRisk {
+    let start_date = student.tution_start_date.naive_local().date();
+    let window_start = (today - Duration::days(CHURN_WINDOW_DAYS)).max(start_date);
+
+    let scheduled = count_scheduled_sessions_between(student, window_start, today);
+    let completed = count_completed_sessions_between(student, window_start, today);
+
+    let attendance_factor = if scheduled > 0 {
+        let missed_ratio = 1.0 - (completed.min(scheduled) as f32 / scheduled as f32);
+        RiskFactor {
+            label: "Attendance".to_string(),
+            detail: format!(
+                "{} of {} sessions in the last 4 weeks",
+                completed, scheduled
+            ),
+            points: missed_ratio * CHURN_ATTENDANCE_WEIGHT,
+        }
+    } else {
+        RiskFactor {
+            label: "Attendance".to_string(),
+            detail: "No sessions scheduled in the last 4 weeks".to_string(),
+            points: 0.0,
+        }
+    };
+
+    let last_session = student
+        .held_sessions()
+        .map(|dt| dt.naive_local().date())
+        .filter(|date| date <= &today)
+        .max();
+
+    let days_since_last = today
+        .signed_duration_since(last_session.unwrap_or(start_date))
+        .num_days()
+        .max(0);
+    let overdue_days = (days_since_last - 7).max(0);
+
+    let recency_factor = RiskFactor {
+        label: "Recency".to_string(),
+        detail: match last_session {
+            Some(_) => format!("{} days since the last session", days_since_last),
+            None => format!("No sessions logged in {} days", days_since_last),
+        },
+        points: (overdue_days as f32 / 14.0).min(1.0) * CHURN_RECENCY_WEIGHT,
+    };
+
+    let payment_factor = RiskFactor {
+        label: "Payments".to_string(),
+        detail: match days_overdue {
+            Some(1) => "An invoice is 1 day overdue".to_string(),
+            Some(days) => format!("An invoice is {} days overdue", days),
+            None => "No invoices overdue".to_string(),
+        },
+        points: (days_overdue.unwrap_or(0).max(0) as f32 / CHURN_OVERDUE_DAYS as f32).min(1.0)
+            * CHURN_PAYMENT_WEIGHT,
+    };
+
+    let held_in_window: Vec<&SessionRecord> = student
+        .sessions
+        .iter()
+        .filter(|record| record.status == SessionStatus::Completed)
+        .filter(|record| (window_start..=today).contains(&record.at.date_naive()))
+        .collect();
+    let noted = held_in_window
+        .iter()
+        .filter(|record| !record.notes.trim().is_empty())
+        .count();
+    let notes_factor = RiskFactor {
+        label: "Notes".to_string(),
+        detail: match held_in_window.len() {
+            0 => "No sessions held in the last 4 weeks".to_string(),
+            held => format!(
+                "{} of {} sessions in the last 4 weeks have notes",
+                noted, held
+            ),
+        },
+        points: match held_in_window.len() {
+            0 => 0.0,
+            held => (1.0 - noted as f32 / held as f32) * CHURN_NOTES_WEIGHT,
+        },
+    };
 
-    let mut sorted_summaries = monthly_summaries.to_vec();
-    sorted_summaries.sort_by_key(|summary| summary.year_month.month);
+    let factors = vec![
+        attendance_factor,
+        recency_factor,
+        payment_factor,
+        notes_factor,
+    ];
+    let score = factors.iter().map(|factor| factor.points).sum::<f32>();
 
-    let mut revenue_trend = Vec::new();
-    let mut sessions_trend = Vec::new();
+    ChurnRisk {
+        score,
+        level: RiskLevel::from_score(score),
+        factors,
+    }
+}
 
-    let mut trend_history = Vec::new();
+#[derive(Debug, Clone)]
+pub struct ChurnRisk {
+    pub score: f32,
+    pub level: RiskLevel,
+    pub factors: Vec<RiskFactor>,
+}
 
-    for i in 1..monthly_summaries.len() {
-        let previous = monthly_summaries[i - 1];
-        let current = monthly_summaries[i];
+#[derive(Debug, Clone)]
+pub struct RiskFactor {
+    pub label: String,
+    pub detail: String,
+    pub points: f32,
+}
 
-        revenue_trend.push(ActualRevenueTrendData {
-            trend: compute_trend(previous.actual_revenue, current.actual_revenue),
-            current_revenue: current.actual_revenue,
-            previous_revenue: previous.actual_revenue,
-            year_month: current.year_month,
-        });
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum RiskLevel {
+    Low,
+    Medium,
+    High,
+}
 
-        sessions_trend.push(ActualSessionTrendData {
-            trend: compute_trend(previous.actual_revenue, current.actual_revenue),
-            current_sessions: current.actual_revenue,
-            previous_sessions: previous.actual_revenue,
-            year_month: current.year_month,
-        });
+impl RiskLevel {
+    fn from_score(score: f32) -> Self {
+        if score >= 60.0 {
+            RiskLevel::High
+        } else if score >= 30.0 {
+            RiskLevel::Medium
+        } else {
+            RiskLevel::Low
+        }
+    }
+}
 
-        trend_history.push(TrendData {
-            revenue_trend: ActualRevenueTrendData {
-                trend: compute_trend(previous.actual_revenue, current.actual_revenue),
-                current_revenue: current.actual_revenue,
-                previous_revenue: previous.actual_revenue,
-                year_month: current.year_month,
-            },
-            sessions_trend: ActualSessionTrendData {
-                trend: compute_trend(
-                    previous.total_actual_sessions as f32,
-                    current.total_scheduled_sessions as f32,
-                ),
-                current_sessions: current.total_actual_sessions as f32,
-                previous_sessions: previous.total_actual_sessions as f32,
-                year_month: current.year_month,
-            },
-        })
+impl std::fmt::Display for RiskLevel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RiskLevel::Low => write!(f, "Low risk"),
+            RiskLevel::Medium => write!(f, "Medium risk"),
+            RiskLevel::High => write!(f, "High risk"),
+        }
     }
+}
+
+/// Each summarised month against the one before it, in calendar order
+/// whatever order the summaries come in.
+pub fn compute_trend_history(monthly_summaries: &[MonthlySummary]) -> TrendHistory {
+    let mut sorted = monthly_summaries.to_vec();
+    sorted.sort_by_key(|summary| (summary.year, summary.month));
 
-    trend_history
+    sorted
+        .windows(2)
+        .map(|pair| TrendData::between(pair[0], pair[1]))
+        .collect()
 }
 
 pub fn compute_trend(previous: f32, current: f32) -> NumberTrend {
@@ -428,55 +2224,107 @@ pub fn compute_trend(previous: f32, current: f32) -> NumberTrend {
                 TrendDirection::Down
             },
             percentage_change,
+            absolute_change: (current - previous).abs(),
         }
     }
 }
 
-#[derive(Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum NumberTrend {
     NoData,
     Trend {
         trend_direction: TrendDirection,
         percentage_change: f32,
+        absolute_change: f32,
     },
 }
 
-#[derive(Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum TrendDirection {
     Up,
     Down,
 }
 
+#[derive(Debug, Clone, PartialEq)]
 pub struct Attendance {
-    pub month: String,
+    /// The month or week the count is for, as shown on the chart.
+    pub label: String,
     pub attended_days: i32,
+    /// Sessions tabled in the same period up to today, while enrolled.
+    pub scheduled_sessions: i32,
+}
+
+/// Below this share of scheduled sessions held, a weekday counts as often
+/// skipped.
+const SKIPPED_WEEKDAY_SHARE: f32 = 0.5;
+/// Scheduled sessions a weekday needs before it can count as often skipped.
+const SKIPPED_WEEKDAY_MIN_SCHEDULED: i32 = 2;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct WeekdaySessions {
+    pub weekday: Weekday,
+    pub held: i32,
+    pub scheduled: i32,
+}
+
+impl WeekdaySessions {
+    pub fn is_often_skipped(&self) -> bool {
+        self.scheduled >= SKIPPED_WEEKDAY_MIN_SCHEDULED
+            && (self.held as f32) < self.scheduled as f32 * SKIPPED_WEEKDAY_SHARE
+    }
 }
 
-#[derive(Debug)]
+/// Income so far in the month as of one day.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CumulativeIncome {
+    pub date: NaiveDate,
+    /// `None` for days still to come.
+    pub actual: Option<f32>,
+    pub on_track: f32,
+}
+
+#[derive(Debug, PartialEq)]
 pub struct IncomeData {
     pub potential: f32,
     pub actual: f32,
-    pub month_year: (String, i32),
+    /// Actual earnings for the month split by student full name.
+    pub actual_by_student: Vec<(String, f32)>,
+    pub year_month: (i32, u32),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct MonthAtAGlance {
+    pub year: i32,
+    pub month: u32,
+    pub income: f32,
+    /// `None` when nothing was scheduled, as for months still to come.
+    pub attendance_rate: Option<f32>,
 }
 
 // =========================================
-// MOCK DATA & TESTING
+// FIRST RUN & TESTING
 // =========================================
-#[cfg(debug_assertions)]
-fn mock_domain() -> Domain {
+/// What a first run starts from: no students yet, and the usual days and
+/// times to tutor.
+fn empty_domain() -> Domain {
     Domain {
+        clock: Clock::System,
+        money_format: MoneyFormat::default(),
+        payment_instructions: String::new(),
+        blackout_dates: Vec::new(),
+        locations: Vec::new(),
+        rates: Vec::new(),
+        waitlist: Vec::new(),
+        monthly_snapshots: Vec::new(),
+        trash: Vec::new(),
         tutor: Tutor {
             id: "tutor1".to_owned(),
             name: PersonalName {
-                first: String::from("Andy"),
-                last: String::from("Murray"),
+                first: String::from("Tutor"),
+                last: String::new(),
                 other: None::<String>,
             },
-            subjects: vec![
-                TutorSubject::ExtendedMathematics,
-                TutorSubject::AdditionalMathematics,
-                TutorSubject::Statistics,
-            ],
+            subjects: TutorSubject::defaults(),
             tutoring_days: vec![
                 Weekday::Sun,
                 Weekday::Tue,
@@ -495,96 +2343,1029 @@ fn mock_domain() -> Domain {
             .map(|(day, times)| (*day, times.iter().map(|s| s.to_string()).collect()))
             .collect(),
         },
-        students: mock_student_data(),
-        // monthly_summaries: mock_monthly_summaries(),
+        students: Vec::new(),
     }
 }
 
-fn mock_student_data() -> Vec<Student> {
-    vec![
+#[cfg(test)]
+pub mod fixtures {
+    use super::*;
+
+    pub fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    pub fn student(
+        first: &str,
+        last: &str,
+        sessions: &[(Weekday, &str)],
+        payment_data: PaymentData,
+        attended: &[(i32, u32, u32)],
+    ) -> Student {
         Student {
-            id: String::from("student1"),
+            id: format!("{}-{}", first, last).to_lowercase(),
             name: PersonalName {
-                first: String::from("Mary"),
-                last: String::from("Jane"),
+                first: first.to_string(),
+                last: last.to_string(),
                 other: None,
             },
-            subject: TutorSubject::AdditionalMathematics,
-            tabled_sessions: vec![
-                SessionData {
-                    day: Weekday::Tue,
-                    time: String::from("5:30 PM"),
-                },
-                SessionData {
-                    day: Weekday::Thu,
-                    time: String::from("5:30 PM"),
+            subject: TutorSubject::new("Extended Mathematics"),
+            tags: Vec::new(),
+            phone: None,
+            email: None,
+            tabled_sessions: sessions
+                .iter()
+                .map(|(day, time)| SessionData {
+                    day: *day,
+                    time: time.to_string(),
+                    duration_minutes: DEFAULT_SESSION_MINUTES,
+                })
+                .collect(),
+            sessions: attended
+                .iter()
+                .map(|&(y, m, d)| {
+                    SessionRecord::completed(Local.with_ymd_and_hms(y, m, d, 17, 0, 0).unwrap())
+                })
+                .collect(),
+            other_subjects: Vec::new(),
+            reschedules: Vec::new(),
+            cancelled_sessions: Vec::new(),
+            location: None,
+            session_locations: Vec::new(),
+            travel_km: None,
+            session_distances: Vec::new(),
+            session_ratings: Vec::new(),
+            confirmed_sessions: Vec::new(),
+            payment_data,
+            rate_changes: Vec::new(),
+            tution_start_date: Local.with_ymd_and_hms(2025, 9, 1, 0, 0, 0).unwrap(),
+            tution_end_date: None,
+        }
+    }
+
+    /// Tuesday and Thursday student paying 100 per session.
+    pub fn tue_thu_student(attended: &[(i32, u32, u32)]) -> Student {
+        student(
+            "Test",
+            "Student",
+            &[(Weekday::Tue, "5:00 PM"), (Weekday::Thu, "5:00 PM")],
+            PaymentData {
+                payment_type: PaymentType::PerSession,
+                amount: 100.0,
+                min_sessions: None,
+            },
+            attended,
+        )
+    }
+
+    pub fn domain_on(today: NaiveDate, students: Vec<Student>) -> Domain {
+        Domain {
+            tutor: Tutor {
+                id: String::from("tutor"),
+                name: PersonalName {
+                    first: String::from("Test"),
+                    last: String::from("Tutor"),
+                    other: None,
                 },
+                subjects: vec![TutorSubject::new("Extended Mathematics")],
+                tutoring_days: vec![Weekday::Tue, Weekday::Thu],
+                available_times: HashMap::new(),
+            },
+            students,
+            money_format: MoneyFormat::default(),
+            payment_instructions: String::new(),
+            blackout_dates: Vec::new(),
+            locations: Vec::new(),
+            rates: Vec::new(),
+            waitlist: Vec::new(),
+            monthly_snapshots: Vec::new(),
+            trash: Vec::new(),
+            clock: Clock::Fixed(today),
+        }
+    }
+
+    /// Two students with January and February 2026 history, seen from
+    /// 12 February 2026.
+    pub fn sample_domain() -> Domain {
+        let mary = student(
+            "Mary",
+            "Jane",
+            &[(Weekday::Tue, "5:30 PM"), (Weekday::Thu, "5:30 PM")],
+            PaymentData {
+                payment_type: PaymentType::PerSession,
+                amount: 150.0,
+                min_sessions: None,
+            },
+            &[
+                (2026, 1, 6),
+                (2026, 1, 8),
+                (2026, 1, 13),
+                (2026, 1, 15),
+                (2026, 1, 20),
+                (2026, 2, 3),
+                (2026, 2, 5),
+                (2026, 2, 10),
             ],
-            actual_sessions: vec![
-                Local.with_ymd_and_hms(2025, 11, 4, 17, 30, 0).unwrap(),
-                Local.with_ymd_and_hms(2025, 11, 6, 13, 30, 0).unwrap(),
+        );
+        let peter = student(
+            "Peter",
+            "Parker",
+            &[(Weekday::Wed, "4:00 PM"), (Weekday::Sat, "1:30 PM")],
+            PaymentData {
+                payment_type: PaymentType::Monthly,
+                amount: 600.0,
+                min_sessions: None,
+            },
+            &[
+                (2026, 1, 7),
+                (2026, 1, 10),
+                (2026, 1, 14),
+                (2026, 2, 4),
+                (2026, 2, 7),
             ],
-            payment_data: PaymentData {
+        );
+
+        domain_on(date(2026, 2, 12), vec![mary, peter])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::fixtures::*;
+    use super::*;
+    use iced::futures::executor::block_on;
+
+    #[test]
+    fn a_first_run_starts_with_no_students() {
+        let dir = std::env::temp_dir().join("tutor-mgr-first-run");
+        let _ = std::fs::remove_dir_all(&dir);
+
+        let domain = block_on(Domain::load_state_from_db(Some(dir.join("tutor-mgr.db")))).unwrap();
+
+        assert!(domain.students.is_empty());
+        assert!(!domain.tutor.tutoring_days.is_empty());
+    }
+
+    #[test]
+    fn overlapping_saves_leave_the_newest_revision() {
+        let dir = std::env::temp_dir().join("tutor-mgr-overlapping-saves");
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("tutor-mgr.db");
+        let saves = SaveQueue::default();
+
+        let writers = (1..=8)
+            .map(|revision| {
+                let (path, saves) = (path.clone(), saves.clone());
+                std::thread::spawn(move || saves.write(&path, revision, &revision.to_string()))
+            })
+            .collect::<Vec<_>>();
+        for writer in writers {
+            writer.join().unwrap().unwrap();
+        }
+        saves.write(&path, 3, "3").unwrap();
+
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "8");
+        assert!(!dir.join("tutor-mgr.db.partial").exists());
+    }
+
+    #[test]
+    fn saving_replaces_the_file_in_one_step() {
+        let dir = std::env::temp_dir().join("tutor-mgr-save");
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("tutor-mgr.db");
+        std::fs::write(&path, "older").unwrap();
+
+        block_on(sample_domain().save_state_to_db(path.clone())).unwrap();
+
+        let loaded = block_on(Domain::load_state_from_db(Some(path))).unwrap();
+        assert_eq!(loaded.students, sample_domain().students);
+        assert!(!dir.join("tutor-mgr.db.partial").exists());
+    }
+
+    #[test]
+    fn domain_round_trips_through_its_saved_form() {
+        let domain = sample_domain();
+        let json = serde_json::to_string(&domain).unwrap();
+        let loaded: Domain = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(loaded.students, domain.students);
+        assert_eq!(loaded.tutor, domain.tutor);
+        assert_eq!(loaded.clock, Clock::System);
+    }
+
+    #[test]
+    fn income_trend_in_january_compares_against_december() {
+        let student = tue_thu_student(&[
+            (2025, 12, 2),
+            (2025, 12, 4),
+            (2026, 1, 6),
+            (2026, 1, 8),
+            (2026, 1, 13),
+        ]);
+        let domain = domain_on(date(2026, 1, 15), vec![student]);
+
+        match domain.get_actual_income_trend_direction() {
+            NumberTrend::Trend {
+                trend_direction: TrendDirection::Up,
+                absolute_change,
+                percentage_change,
+            } => {
+                assert_eq!(absolute_change, 100.0);
+                assert_eq!(percentage_change, 50.0);
+            }
+            _ => panic!("expected an upward trend from December to January"),
+        }
+    }
+
+    #[test]
+    fn sessions_trend_on_first_of_month_counts_only_the_new_month() {
+        let student = tue_thu_student(&[(2025, 10, 28), (2025, 10, 30)]);
+        let domain = domain_on(date(2025, 11, 1), vec![student]);
+
+        match domain.get_completed_sessions_trend_direction() {
+            NumberTrend::Trend {
+                trend_direction: TrendDirection::Down,
+                absolute_change,
+                ..
+            } => assert_eq!(absolute_change, 2.0),
+            _ => panic!("expected a downward trend into an empty month"),
+        }
+    }
+
+    #[test]
+    fn trend_without_previous_month_has_no_data() {
+        let student = tue_thu_student(&[(2026, 3, 3)]);
+        let domain = domain_on(date(2026, 3, 10), vec![student]);
+
+        assert!(matches!(
+            domain.get_actual_income_trend_direction(),
+            NumberTrend::NoData
+        ));
+    }
+
+    #[test]
+    fn monthly_attendance_counts_each_month_against_its_schedule() {
+        let student = tue_thu_student(&[(2026, 1, 6), (2026, 1, 8), (2026, 2, 3)]);
+        let domain = domain_on(date(2026, 2, 12), vec![student]);
+
+        let months: Vec<(String, i32, i32)> = domain
+            .compute_attendance_data()
+            .into_iter()
+            .map(|month| (month.label, month.attended_days, month.scheduled_sessions))
+            .collect();
+
+        assert_eq!(
+            months,
+            vec![("Jan '26".to_string(), 2, 9), ("Feb '26".to_string(), 1, 4)]
+        );
+    }
+
+    #[test]
+    fn students_are_counted_as_gained_lost_and_active() {
+        let mut joined = tue_thu_student(&[]);
+        joined.tution_start_date = Local.with_ymd_and_hms(2026, 2, 2, 0, 0, 0).unwrap();
+        let mut left = student("Left", "Early", &[], joined.payment_data.clone(), &[]);
+        left.tution_end_date = Some(Local.with_ymd_and_hms(2026, 2, 6, 0, 0, 0).unwrap());
+        let stayed = student("Still", "Here", &[], joined.payment_data.clone(), &[]);
+        let domain = domain_on(date(2026, 2, 12), vec![joined, left, stayed]);
+
+        assert_eq!(domain.active_student_count(), 2);
+        assert_eq!(domain.students_gained_and_lost(2026, 2), (1, 1));
+        // One joined and one left, so February ends where January did.
+        assert!(matches!(
+            domain.get_active_students_trend_direction(),
+            NumberTrend::Trend {
+                trend_direction: TrendDirection::Up,
+                absolute_change: 0.0,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn monthly_averages_divide_by_active_students_and_delivered_sessions() {
+        let per_session = tue_thu_student(&[(2026, 2, 3), (2026, 2, 5), (2026, 2, 10)]);
+        let monthly = student(
+            "Month",
+            "Payer",
+            &[(Weekday::Sat, "10:00 AM")],
+            PaymentData {
+                payment_type: PaymentType::Monthly,
+                amount: 500.0,
+                min_sessions: None,
+            },
+            &[(2026, 2, 7)],
+        );
+        let domain = domain_on(date(2026, 2, 12), vec![per_session, monthly]);
+
+        assert_eq!(
+            domain.monthly_averages(2026, 2),
+            MonthlyAverages {
+                revenue_per_student: 400.0,
+                revenue_per_session: 200.0,
+                sessions_per_student: 2.0,
+            }
+        );
+    }
+
+    #[test]
+    fn chart_months_are_ordered_and_labelled_by_year() {
+        let student = tue_thu_student(&[(2025, 12, 2), (2026, 1, 6), (2026, 1, 8)]);
+        let domain = domain_on(date(2026, 1, 15), vec![student]);
+
+        let income: Vec<(i32, u32)> = domain
+            .compute_income_data()
+            .iter()
+            .map(|month| month.year_month)
+            .collect();
+        let attendance: Vec<String> = domain
+            .compute_attendance_data()
+            .into_iter()
+            .map(|month| month.label)
+            .collect();
+
+        assert_eq!(income, vec![(2025, 12), (2026, 1)]);
+        assert_eq!(attendance, vec!["Dec '25", "Jan '26"]);
+    }
+
+    #[test]
+    fn attendance_rates_and_the_months_goal_count_up_to_today() {
+        let domain = sample_domain();
+        let (mary, peter) = (&domain.students[0], &domain.students[1]);
+
+        assert_eq!(
+            attendance_rate(mary, date(2026, 2, 1), date(2026, 2, 12)),
+            Some(0.75)
+        );
+        assert_eq!(
+            attendance_rate(peter, date(2026, 2, 1), date(2026, 2, 1)),
+            None
+        );
+        assert_eq!(domain.month_progress(), (5, 16));
+    }
+
+    #[test]
+    fn weekly_attendance_covers_the_weeks_up_to_today() {
+        let student = tue_thu_student(&[(2026, 1, 27), (2026, 2, 3), (2026, 2, 5), (2026, 2, 10)]);
+        // 2026-02-12 is a Thursday in the week starting Monday 9 February.
+        let domain = domain_on(date(2026, 2, 12), vec![student]);
+
+        let weeks = domain.compute_weekly_attendance_data(3);
+        let counts: Vec<(&str, i32)> = weeks
+            .iter()
+            .map(|week| (week.label.as_str(), week.attended_days))
+            .collect();
+
+        assert_eq!(counts, vec![("26 Jan", 1), ("2 Feb", 2), ("9 Feb", 1)]);
+        // The current week is only scheduled up to today, Thursday.
+        let scheduled: Vec<i32> = weeks.iter().map(|week| week.scheduled_sessions).collect();
+        assert_eq!(scheduled, vec![2, 2, 2]);
+    }
+
+    #[test]
+    fn past_occurrences_show_which_sessions_were_logged() {
+        // Logged at 17:00, which is when the Tuesday slot starts.
+        let student = tue_thu_student(&[(2026, 2, 3)]);
+        let students = [student];
+        let at = |d, h| Local.with_ymd_and_hms(2026, 2, d, h, 0, 0).unwrap();
+
+        let tuesday = past_occurrences_on(&students, date(2026, 2, 3), at(12, 9));
+        assert_eq!(
+            tuesday,
+            vec![PastOccurrence {
+                student_id: students[0].id.clone(),
+                at: at(3, 17),
+                status: Some(SessionStatus::Completed),
+            }]
+        );
+
+        let thursday = past_occurrences_on(&students, date(2026, 2, 5), at(12, 9));
+        assert_eq!(thursday[0].status, None);
+        // Not started yet, so there is nothing to mark.
+        assert!(past_occurrences_on(&students, date(2026, 2, 12), at(12, 9)).is_empty());
+    }
+
+    #[test]
+    fn suggested_session_is_todays_slot_or_the_last_unlogged_one() {
+        let at = |d, h| Local.with_ymd_and_hms(2026, 2, d, h, 0, 0).unwrap();
+
+        // Tuesday 10th, before or after the 17:00 slot.
+        let student = tue_thu_student(&[]);
+        assert_eq!(suggested_session(&student, at(10, 9)), Some(at(10, 17)));
+        assert_eq!(suggested_session(&student, at(10, 21)), Some(at(10, 17)));
+
+        // Wednesday 11th has no slot, so Tuesday's, unless that was logged.
+        assert_eq!(suggested_session(&student, at(11, 12)), Some(at(10, 17)));
+        let logged = tue_thu_student(&[(2026, 2, 10)]);
+        assert_eq!(suggested_session(&logged, at(11, 12)), Some(at(5, 17)));
+    }
+
+    #[test]
+    fn unlogged_sessions_leave_out_marked_sessions_and_today() {
+        // The week before Thursday 12 February: Tue 3, Thu 5, Tue 10.
+        let mut student = tue_thu_student(&[(2026, 2, 3)]);
+        let at = |d| Local.with_ymd_and_hms(2026, 2, d, 17, 0, 0).unwrap();
+        student.sessions.push(SessionRecord {
+            at: at(10),
+            status: SessionStatus::CancelledByTutor,
+            notes: String::new(),
+            links: Vec::new(),
+        });
+
+        let unlogged = unlogged_sessions(&[student], date(2026, 2, 12), 7);
+
+        let times: Vec<_> = unlogged.iter().map(|occurrence| occurrence.at).collect();
+        assert_eq!(times, vec![at(5)]);
+    }
+
+    #[test]
+    fn remaining_sessions_skip_blackout_dates() {
+        // From Thursday 19 February: the 24th and 26th remain.
+        let mut domain = domain_on(date(2026, 2, 19), vec![tue_thu_student(&[])]);
+        assert_eq!(domain.remaining_in_month(), (2, 200.0));
+
+        domain.blackout_dates.push(date(2026, 2, 24));
+        assert_eq!(domain.remaining_in_month(), (1, 100.0));
+    }
+
+    #[test]
+    fn older_files_read_and_sessions_called_off_leave_attendance() {
+        let at = |d| Local.with_ymd_and_hms(2026, 2, d, 17, 0, 0).unwrap();
+        let json = serde_json::to_string(&domain_on(date(2026, 2, 12), vec![tue_thu_student(&[])]))
+            .unwrap()
+            .replace(
+                "\"sessions\":[]",
+                &format!(
+                    "\"actual_sessions\":[\"{}\"],\"cancelled_sessions\":[\"{}\"]",
+                    at(3).to_rfc3339(),
+                    at(5).to_rfc3339()
+                ),
+            );
+        let mut domain: Domain = serde_json::from_str(&json).unwrap();
+        domain.upgrade_sessions();
+        domain.clock = Clock::Fixed(date(2026, 2, 12));
+
+        let student = &domain.students[0];
+        assert_eq!(
+            student.session_status(at(3)),
+            Some(SessionStatus::Completed)
+        );
+        assert_eq!(
+            student.session_status(at(5)),
+            Some(SessionStatus::CancelledByTutor)
+        );
+        assert!(student.cancelled_sessions.is_empty());
+
+        // Tue 3, Tue 10 and Thu 12 were due; Thu 5 was called off.
+        let attendance = domain.attendance_for_month(2026, 2).unwrap();
+        assert_eq!(
+            (attendance.attended_days, attendance.scheduled_sessions),
+            (1, 3)
+        );
+
+        domain.students[0].sessions.push(SessionRecord {
+            at: at(10),
+            status: SessionStatus::MissedByStudent,
+            notes: "Exams".to_string(),
+            links: Vec::new(),
+        });
+        let attendance = domain.attendance_for_month(2026, 2).unwrap();
+        assert_eq!(
+            (attendance.attended_days, attendance.scheduled_sessions),
+            (1, 3)
+        );
+        assert_eq!(domain.income_for_month(2026, 2).unwrap().actual, 100.0);
+    }
+
+    #[test]
+    fn weekdays_count_held_against_scheduled_sessions() {
+        // Two Tuesdays held and neither Thursday, as of Monday 16 February.
+        let student = tue_thu_student(&[(2026, 2, 3), (2026, 2, 10)]);
+        let days = domain_on(date(2026, 2, 16), vec![student]).weekday_sessions_this_month();
+
+        let tuesday = &days[1];
+        assert_eq!(
+            (tuesday.weekday, tuesday.held, tuesday.scheduled),
+            (Weekday::Tue, 2, 2)
+        );
+        assert!(!tuesday.is_often_skipped());
+        let thursday = &days[3];
+        assert_eq!((thursday.held, thursday.scheduled), (0, 2));
+        assert!(thursday.is_often_skipped());
+        assert_eq!(days[0].scheduled, 0);
+    }
+
+    #[test]
+    fn cumulative_income_runs_to_today_against_the_timetable() {
+        // Tuesday 3 February was held and Thursday 5th missed.
+        let mut domain = domain_on(date(2026, 2, 6), vec![tue_thu_student(&[(2026, 2, 3)])]);
+        domain.blackout_dates.push(date(2026, 2, 10));
+        let days = domain.cumulative_income_this_month();
+
+        assert_eq!(days.len(), 28);
+        assert_eq!(days[4].actual, Some(100.0));
+        assert_eq!(days[4].on_track, 200.0);
+        assert_eq!(days[5].actual, Some(100.0));
+        assert_eq!(days[6].actual, None);
+        // Six sessions are left after the 10th is taken out.
+        assert_eq!(days[27].on_track, 700.0);
+    }
+
+    /// Every Tuesday and Thursday in the four weeks up to 2026-02-12.
+    const CHURN_WINDOW: [(i32, u32, u32); 9] = [
+        (2026, 1, 15),
+        (2026, 1, 20),
+        (2026, 1, 22),
+        (2026, 1, 27),
+        (2026, 1, 29),
+        (2026, 2, 3),
+        (2026, 2, 5),
+        (2026, 2, 10),
+        (2026, 2, 12),
+    ];
+
+    fn noted(mut student: Student) -> Student {
+        for record in &mut student.sessions {
+            record.notes = "Worked through past papers".to_string();
+        }
+        student
+    }
+
+    fn risk_points(risk: &ChurnRisk, label: &str) -> f32 {
+        risk.factors
+            .iter()
+            .find(|factor| factor.label == label)
+            .map(|factor| factor.points)
+            .unwrap()
+    }
+
+    #[test]
+    fn churn_attendance_counts_the_share_of_sessions_missed() {
+        let today = date(2026, 2, 12);
+        let all = noted(tue_thu_student(&CHURN_WINDOW));
+        let half_missed = noted(tue_thu_student(&CHURN_WINDOW[4..]));
+
+        assert_eq!(
+            risk_points(&compute_churn_risk(&all, None, today), "Attendance"),
+            0.0
+        );
+        let points = risk_points(&compute_churn_risk(&half_missed, None, today), "Attendance");
+        assert!((points - 4.0 / 9.0 * CHURN_ATTENDANCE_WEIGHT).abs() < 0.01);
+    }
+
+    #[test]
+    fn churn_recency_grows_after_a_week_without_a_session() {
+        let student = noted(tue_thu_student(&[(2026, 1, 15)]));
+        let recency = |today| risk_points(&compute_churn_risk(&student, None, today), "Recency");
+
+        assert_eq!(recency(date(2026, 1, 22)), 0.0);
+        assert_eq!(recency(date(2026, 1, 29)), CHURN_RECENCY_WEIGHT / 2.0);
+        assert_eq!(recency(date(2026, 2, 12)), CHURN_RECENCY_WEIGHT);
+    }
+
+    #[test]
+    fn churn_payments_grow_with_how_late_an_invoice_is() {
+        let student = noted(tue_thu_student(&CHURN_WINDOW));
+        let payments = |overdue| {
+            risk_points(
+                &compute_churn_risk(&student, overdue, date(2026, 2, 12)),
+                "Payments",
+            )
+        };
+
+        assert_eq!(payments(None), 0.0);
+        assert_eq!(payments(Some(15)), CHURN_PAYMENT_WEIGHT / 2.0);
+        assert_eq!(payments(Some(90)), CHURN_PAYMENT_WEIGHT);
+    }
+
+    #[test]
+    fn churn_notes_count_held_sessions_left_without_notes() {
+        let today = date(2026, 2, 12);
+        let mut student = tue_thu_student(&CHURN_WINDOW[5..]);
+        student.sessions[0].notes = "Vectors".to_string();
+        student.sessions[1].notes = "Vectors again".to_string();
+
+        let points = risk_points(&compute_churn_risk(&student, None, today), "Notes");
+        assert_eq!(points, CHURN_NOTES_WEIGHT / 2.0);
+
+        let nothing_held = tue_thu_student(&[]);
+        let points = risk_points(&compute_churn_risk(&nothing_held, None, today), "Notes");
+        assert_eq!(points, 0.0);
+    }
+
+    #[test]
+    fn churn_factors_add_up_to_the_level_shown() {
+        let today = date(2026, 2, 12);
+        let level = |student: &Student, overdue| compute_churn_risk(student, overdue, today).level;
+
+        let steady = noted(tue_thu_student(&CHURN_WINDOW));
+        assert_eq!(level(&steady, None), RiskLevel::Low);
+        // 20 for the unpaid invoice and 15 for the missing notes.
+        let unpaid_and_quiet = tue_thu_student(&CHURN_WINDOW);
+        assert_eq!(level(&unpaid_and_quiet, Some(30)), RiskLevel::Medium);
+        // Nothing held in four weeks: 40 for attendance and 25 for recency.
+        let gone = tue_thu_student(&[(2025, 12, 2)]);
+        assert_eq!(level(&gone, None), RiskLevel::High);
+        assert_eq!(compute_churn_risk(&gone, Some(60), today).score, 85.0);
+    }
+
+    #[test]
+    fn next_session_looks_past_a_week_when_the_only_slot_is_moved_away() {
+        let mut student = student(
+            "Test",
+            "Student",
+            &[(Weekday::Mon, "5:00 PM")],
+            PaymentData {
                 payment_type: PaymentType::PerSession,
-                amount: 150.0,
+                amount: 100.0,
+                min_sessions: None,
             },
+            &[],
+        );
+        student.reschedule(
+            Local.with_ymd_and_hms(2026, 10, 19, 17, 0, 0).unwrap(),
+            Some(Local.with_ymd_and_hms(2026, 10, 30, 17, 0, 0).unwrap()),
+        );
+
+        assert_eq!(
+            get_next_session(&student, date(2026, 10, 18)),
+            Some(date(2026, 10, 26))
+        );
+        student.tabled_sessions.clear();
+        assert_eq!(
+            get_next_session(&student, date(2026, 10, 18)),
+            Some(date(2026, 10, 30))
+        );
+        assert_eq!(get_next_session(&student, date(2026, 10, 30)), None);
+    }
 
-            tution_start_date: Local.with_ymd_and_hms(2025, 11, 1, 00, 00, 00).unwrap(),
-        },
-        Student {
-            id: String::from("student2"),
-            name: PersonalName {
-                first: String::from("Peter"),
-                last: String::from("Parker"),
-                other: None,
+    #[test]
+    fn a_held_session_moved_to_an_untabled_day_counts_everywhere() {
+        // Thursday 5 February was moved to Wednesday the 4th and held.
+        let mut student = tue_thu_student(&[(2026, 2, 3), (2026, 2, 4)]);
+        student.reschedule(
+            Local.with_ymd_and_hms(2026, 2, 5, 17, 0, 0).unwrap(),
+            Some(Local.with_ymd_and_hms(2026, 2, 4, 17, 0, 0).unwrap()),
+        );
+
+        assert_eq!(compute_monthly_completed_sessions(&student, 2, 2026), 2);
+        assert_eq!(
+            completed_sessions_in(&student, 2, 2026),
+            [
+                Local.with_ymd_and_hms(2026, 2, 3, 17, 0, 0).unwrap(),
+                Local.with_ymd_and_hms(2026, 2, 4, 17, 0, 0).unwrap(),
+            ]
+        );
+        assert_eq!(
+            attendance_rate(&student, date(2026, 2, 2), date(2026, 2, 8)),
+            Some(1.0)
+        );
+    }
+
+    #[test]
+    fn next_session_is_after_today_even_on_a_session_day() {
+        let student = tue_thu_student(&[]);
+
+        // 2026-02-03 is a Tuesday, so the next session is Thursday.
+        assert_eq!(
+            get_next_session(&student, date(2026, 2, 3)),
+            Some(date(2026, 2, 5))
+        );
+        // Thursday wraps to the following Tuesday.
+        assert_eq!(
+            get_next_session(&student, date(2026, 2, 5)),
+            Some(date(2026, 2, 10))
+        );
+    }
+
+    #[test]
+    fn trend_history_compares_each_month_with_the_one_before() {
+        let domain = sample_domain();
+        let history = domain.trend_history(2);
+        assert_eq!(
+            history
+                .iter()
+                .map(|trend| (trend.current.month, trend.current.completed_sessions))
+                .collect::<Vec<_>>(),
+            [(1, 8), (2, 5)]
+        );
+
+        let february = &history[1];
+        assert_eq!(
+            february.sessions_trend,
+            NumberTrend::Trend {
+                trend_direction: TrendDirection::Down,
+                percentage_change: 37.5,
+                absolute_change: 3.0,
+            }
+        );
+        assert_eq!(february.current.lost_revenue(), 750.0);
+        assert!(matches!(
+            february.lost_revenue_trend,
+            NumberTrend::Trend {
+                trend_direction: TrendDirection::Up,
+                ..
+            }
+        ));
+        assert_eq!(domain.current_trend(), *february);
+
+        let shuffled = [february.current, february.previous];
+        assert_eq!(compute_trend_history(&shuffled), vec![february.clone()]);
+    }
+
+    #[test]
+    fn monthly_contracts_prorate_part_months_and_deduct_below_the_minimum() {
+        // Tuesdays give four sessions in January and February, five in March.
+        let mut ama = student(
+            "Ama",
+            "Owusu",
+            &[(Weekday::Tue, "5:00 PM")],
+            PaymentData {
+                payment_type: PaymentType::Monthly,
+                amount: 400.0,
+                min_sessions: None,
             },
-            subject: TutorSubject::ExtendedMathematics,
-            tabled_sessions: vec![
-                SessionData {
-                    day: Weekday::Wed,
-                    time: String::from("4:00 PM"),
-                },
-                SessionData {
-                    day: Weekday::Sat,
-                    time: String::from("1:30 PM"),
-                },
-            ],
-            actual_sessions: vec![
-                Local.with_ymd_and_hms(2025, 11, 5, 16, 0, 0).unwrap(),
-                Local.with_ymd_and_hms(2025, 11, 8, 13, 30, 0).unwrap(),
-                Local.with_ymd_and_hms(2025, 11, 22, 13, 30, 0).unwrap(),
-            ],
+            &[],
+        );
+        let cancel = |ama: &mut Student, day| {
+            ama.sessions.push(SessionRecord {
+                at: Local.with_ymd_and_hms(2026, 3, day, 17, 0, 0).unwrap(),
+                status: SessionStatus::CancelledByTutor,
+                notes: String::new(),
+                links: Vec::new(),
+            })
+        };
+        let fee = |ama: &Student, month| {
+            compute_monthly_sum(ama, month, 2026, compute_monthly_completed_sessions)
+        };
+
+        ama.tution_start_date = Local.with_ymd_and_hms(2026, 2, 11, 0, 0, 0).unwrap();
+        assert_eq!(fee(&ama, 1), 0.0);
+        assert_eq!(fee(&ama, 2), 200.0);
+        assert_eq!(fee(&ama, 3), 400.0);
+
+        cancel(&mut ama, 10);
+        assert_eq!(fee(&ama, 3), 320.0);
+        ama.payment_data.min_sessions = Some(4);
+        assert_eq!(fee(&ama, 3), 400.0);
+        cancel(&mut ama, 17);
+        assert_eq!(fee(&ama, 3), 320.0);
+
+        ama.payment_data.min_sessions = None;
+        ama.tution_end_date = Some(Local.with_ymd_and_hms(2026, 3, 18, 0, 0, 0).unwrap());
+        assert_eq!(fee(&ama, 3), 80.0);
+    }
+
+    #[test]
+    fn each_subject_earns_at_its_own_rate_in_its_own_slots() {
+        let mut domain = sample_domain();
+        let mary = &mut domain.students[0];
+        mary.tabled_sessions.retain(|slot| slot.day == Weekday::Tue);
+        mary.other_subjects.push(Enrolment {
+            subject: TutorSubject::new("Statistics"),
+            tabled_sessions: vec![SessionData {
+                day: Weekday::Thu,
+                time: "5:00 PM".to_string(),
+                duration_minutes: DEFAULT_SESSION_MINUTES,
+            }],
             payment_data: PaymentData {
                 payment_type: PaymentType::PerSession,
-                amount: 150.0,
+                amount: 200.0,
+                min_sessions: None,
             },
+        });
 
-            tution_start_date: Local.with_ymd_and_hms(2025, 11, 1, 00, 00, 00).unwrap(),
-        },
-    ]
-}
+        let mary = &domain.students[0];
+        assert_eq!(mary.subject_names(), "Extended Mathematics, Statistics");
+        assert_eq!(mary.timetable().count(), 2);
+        assert_eq!(
+            compute_monthly_sum(mary, 2, 2026, compute_monthly_completed_sessions),
+            2.0 * 150.0 + 200.0
+        );
+        assert_eq!(
+            domain.income_by_subject(2026, 2),
+            vec![
+                (TutorSubject::new("Extended Mathematics"), 900.0),
+                (TutorSubject::new("Statistics"), 200.0),
+            ]
+        );
+
+        let legacy: TutorSubject = serde_json::from_str("\"AdditionalMathematics\"").unwrap();
+        assert_eq!(legacy, TutorSubject::new("Additional Mathematics"));
+    }
 
-fn mock_monthly_summaries() -> Vec<MonthlySummary> {
-    vec![
-        MonthlySummary {
-            year_month: YearMonth {
-                year: 2025,
-                month: Month::October,
+    #[test]
+    fn moved_sessions_leave_the_timetable_alone() {
+        let mut mary = sample_domain().students.remove(0);
+        let at = |d, h, m| Local.with_ymd_and_hms(2026, 2, d, h, m, 0).unwrap();
+        let (feb, mar) = (
+            compute_monthly_scheduled_sessions(&mary, 2, 2026),
+            compute_monthly_scheduled_sessions(&mary, 3, 2026),
+        );
+
+        mary.reschedule(at(17, 17, 30), Some(at(16, 16, 0)));
+        mary.reschedule(at(26, 17, 30), Some(at(28, 10, 0)));
+        mary.reschedule(
+            at(28, 10, 0),
+            Some(Local.with_ymd_and_hms(2026, 3, 2, 9, 0, 0).unwrap()),
+        );
+        assert_eq!(mary.reschedules.len(), 2);
+        assert_eq!(mary.tabled_sessions.len(), 2);
+
+        assert_eq!(
+            get_next_session(&mary, date(2026, 2, 12)),
+            Some(date(2026, 2, 16))
+        );
+        assert_eq!(
+            scheduled_sessions_on(&mary, date(2026, 2, 16)),
+            vec![at(16, 16, 0)]
+        );
+        assert!(scheduled_sessions_on(&mary, date(2026, 2, 17)).is_empty());
+        assert_eq!(compute_monthly_scheduled_sessions(&mary, 2, 2026), feb - 1);
+        assert_eq!(compute_monthly_scheduled_sessions(&mary, 3, 2026), mar + 1);
+
+        mary.reschedule(at(16, 16, 0), None);
+        assert_eq!(
+            scheduled_sessions_on(&mary, date(2026, 2, 17)),
+            vec![at(17, 17, 30)]
+        );
+    }
+
+    #[test]
+    fn student_months_run_from_the_start_date_to_today() {
+        let mut student = tue_thu_student(&[
+            (2025, 12, 16),
+            (2025, 12, 18),
+            (2026, 1, 6),
+            (2026, 1, 8),
+            (2026, 1, 13),
+            (2026, 2, 3),
+            (2026, 2, 5),
+            (2026, 2, 10),
+            (2026, 2, 12),
+        ]);
+        student.tution_start_date = Local.with_ymd_and_hms(2025, 12, 15, 0, 0, 0).unwrap();
+
+        let months: Vec<_> = student_months(&student, date(2026, 2, 12))
+            .into_iter()
+            .map(|month| {
+                (
+                    month.month,
+                    month.held,
+                    month.scheduled,
+                    month.attendance_rate,
+                    month.earned,
+                )
+            })
+            .collect();
+        assert_eq!(
+            months,
+            vec![
+                (12, 2, 5, Some(0.4), 200.0),
+                (1, 3, 9, Some(3.0 / 9.0), 300.0),
+                (2, 4, 4, Some(1.0), 400.0),
+            ]
+        );
+    }
+
+    #[test]
+    fn sessions_count_down_then_run_for_their_slot() {
+        let domain = sample_domain();
+        let mary = &domain.students[0];
+        let at = |h, m| Local.with_ymd_and_hms(2026, 2, 12, h, m, 0).unwrap();
+
+        let session = upcoming_session(mary, at(15, 14)).unwrap();
+        assert_eq!(session, at(17, 30));
+        assert_eq!(
+            SessionTiming::of(mary, session, at(15, 14)).countdown(),
+            Some("in 2h 16m".to_string())
+        );
+        assert_eq!(
+            SessionTiming::of(mary, session, at(18, 0)),
+            SessionTiming::InSession
+        );
+        assert_eq!(
+            SessionTiming::of(mary, session, at(18, 0)).countdown(),
+            None
+        );
+
+        let next = upcoming_session(mary, at(18, 30)).unwrap();
+        assert_eq!(next.date_naive(), date(2026, 2, 17));
+        assert_eq!(
+            SessionTiming::of(mary, next, at(18, 30)).countdown(),
+            Some("in 4d 23h".to_string())
+        );
+    }
+
+    #[test]
+    fn schedules_list_each_slot_once_in_week_order() {
+        let student = student(
+            "Ama",
+            "Owusu",
+            &[
+                (Weekday::Sat, "9:00 AM"),
+                (Weekday::Tue, "5:30 PM"),
+                (Weekday::Tue, "10:00 AM"),
+                (Weekday::Sat, "09:00 am"),
+            ],
+            PaymentData {
+                payment_type: PaymentType::PerSession,
+                amount: 100.0,
+                min_sessions: None,
             },
-            actual_revenue: 1500.0,
-            potential_revenue: 2000.0,
-            total_actual_sessions: 5,
-            total_scheduled_sessions: 8,
-        },
-        MonthlySummary {
-            year_month: YearMonth {
-                year: 2025,
-                month: Month::November,
+            &[],
+        );
+
+        assert_eq!(
+            weekly_schedule(&student)
+                .iter()
+                .map(WeeklySlot::to_string)
+                .collect::<Vec<_>>(),
+            ["Tue 10:00 AM", "Tue 5:30 PM", "Sat 9:00 AM"]
+        );
+    }
+
+    #[test]
+    fn slots_freed_by_a_leaving_student_match_the_waitlist() {
+        let mut domain = sample_domain();
+        domain.tutor.available_times = HashMap::from([
+            (Weekday::Tue, vec!["05:30 PM".to_string()]),
+            (
+                Weekday::Wed,
+                vec!["4:00 PM".to_string(), "6:00 PM".to_string()],
+            ),
+        ]);
+        let wanted = |day, time: &str| WeeklySlot {
+            day,
+            time: time.to_string(),
+        };
+        domain.waitlist = vec![
+            Prospect {
+                name: "Kofi Boateng".to_string(),
+                subject: TutorSubject::new("Statistics"),
+                slots: vec![wanted(Weekday::Tue, "5:30 PM")],
+                added_on: date(2026, 1, 20),
             },
-            actual_revenue: 1200.0,
-            potential_revenue: 1800.0,
-            total_actual_sessions: 3,
-            total_scheduled_sessions: 4,
-        },
-    ]
+            Prospect {
+                name: "Esi Owusu".to_string(),
+                subject: TutorSubject::new("Extended Mathematics"),
+                slots: vec![
+                    wanted(Weekday::Wed, "6:00 PM"),
+                    wanted(Weekday::Tue, "5:30 PM"),
+                ],
+                added_on: date(2026, 2, 1),
+            },
+        ];
+
+        assert_eq!(free_slots(&domain), [wanted(Weekday::Wed, "6:00 PM")]);
+        let matched: Vec<_> = waitlist_matches(&domain)
+            .into_iter()
+            .map(|matched| (matched.slot.to_string(), matched.prospect))
+            .collect();
+        assert_eq!(
+            matched,
+            [("Wed 6:00 PM".to_string(), "Esi Owusu".to_string())]
+        );
+
+        // Mary stops today, so her Tuesday slot opens up.
+        domain.students[0].tution_end_date =
+            Some(Local.with_ymd_and_hms(2026, 2, 12, 0, 0, 0).unwrap());
+        let matched: Vec<_> = waitlist_matches(&domain)
+            .into_iter()
+            .map(|matched| (matched.slot.to_string(), matched.prospect))
+            .collect();
+        assert_eq!(
+            matched,
+            [
+                ("Tue 05:30 PM".to_string(), "Kofi Boateng".to_string()),
+                ("Tue 05:30 PM".to_string(), "Esi Owusu".to_string()),
+                ("Wed 6:00 PM".to_string(), "Esi Owusu".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn previous_month_wraps_the_year() {
+        assert_eq!(previous_month(2026, 1), (2025, 12));
+        assert_eq!(previous_month(2026, 7), (2026, 6));
+    }
+
+    #[test]
+    fn weekday_counts_match_walking_the_dates() {
+        let start = date(2026, 1, 14);
+
+        for len in 0..40 {
+            let end = start + Duration::days(len);
+            let mut expected = [0; 7];
+            for i in 0..=len {
+                let weekday = (start + Duration::days(i)).weekday();
+                expected[weekday.num_days_from_monday() as usize] += 1;
+            }
+
+            assert_eq!(weekday_counts_between(start, end), expected);
+        }
+
+        let before_start = start - Duration::days(1);
+        assert_eq!(weekday_counts_between(start, before_start), [0; 7]);
+    }
 }
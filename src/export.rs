@@ -0,0 +1,187 @@
+use std::path::PathBuf;
+
+use crate::data_dirs::portable_root;
+
+#[derive(Debug, Clone)]
+pub enum ExportError {
+    NoExportDirectory,
+    WriteFailed(String),
+}
+
+impl std::fmt::Display for ExportError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ExportError::NoExportDirectory => write!(f, "Could not find a folder to export to"),
+            ExportError::WriteFailed(reason) => write!(f, "Export failed: {}", reason),
+        }
+    }
+}
+
+/// Exports stay with the portable data folder so nothing is left on the
+/// computer in portable mode.
+pub fn export_directory() -> Option<PathBuf> {
+    if let Some(root) = portable_root() {
+        return Some(root.join("exports"));
+    }
+
+    std::env::home_dir().map(|home| home.join("Tutor Manager Exports"))
+}
+
+pub async fn write_export(
+    file_name: String,
+    contents: impl AsRef<[u8]>,
+) -> Result<PathBuf, ExportError> {
+    let directory = export_directory().ok_or(ExportError::NoExportDirectory)?;
+    std::fs::create_dir_all(&directory).map_err(|e| ExportError::WriteFailed(e.to_string()))?;
+
+    let path = directory.join(file_name);
+    std::fs::write(&path, contents).map_err(|e| ExportError::WriteFailed(e.to_string()))?;
+
+    Ok(path)
+}
+
+/// Quotes a CSV field when it contains a separator, quote or line break.
+pub fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+pub fn csv_line<'a>(fields: impl IntoIterator<Item = &'a str>) -> String {
+    fields.into_iter().map(csv_field).collect::<Vec<_>>().join(",")
+}
+
+/// Width, height and lines of text of a page, in points.
+struct PdfPage {
+    width: u32,
+    height: u32,
+    lines: usize,
+}
+
+const A4_PORTRAIT: PdfPage = PdfPage {
+    width: 595,
+    height: 842,
+    lines: 54,
+};
+const A4_LANDSCAPE: PdfPage = PdfPage {
+    width: 842,
+    height: 595,
+    lines: 36,
+};
+/// Characters of 10 pt Courier that fit across an A4 landscape page.
+pub const LANDSCAPE_LINE_WIDTH: usize = 123;
+
+/// A plain A4 PDF of monospaced lines, enough for printable reports
+/// without a PDF library. Characters outside ASCII print as `?`.
+pub fn text_pdf(lines: &[String]) -> Vec<u8> {
+    paged_pdf(lines, A4_PORTRAIT)
+}
+
+/// As [`text_pdf`] with the page turned sideways, for wide tables.
+pub fn landscape_text_pdf(lines: &[String]) -> Vec<u8> {
+    paged_pdf(lines, A4_LANDSCAPE)
+}
+
+fn paged_pdf(lines: &[String], size: PdfPage) -> Vec<u8> {
+    let pages: Vec<&[String]> = if lines.is_empty() {
+        vec![&[]]
+    } else {
+        lines.chunks(size.lines).collect()
+    };
+
+    // Objects 1-3 are the catalog, page tree and font; each page then
+    // takes two: the page itself and its content stream.
+    let page_ids: Vec<usize> = (0..pages.len()).map(|index| 4 + index * 2).collect();
+    let kids = page_ids
+        .iter()
+        .map(|id| format!("{} 0 R", id))
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    let mut objects = vec![
+        "<< /Type /Catalog /Pages 2 0 R >>".to_string(),
+        format!("<< /Type /Pages /Kids [{}] /Count {} >>", kids, pages.len()),
+        "<< /Type /Font /Subtype /Type1 /BaseFont /Courier >>".to_string(),
+    ];
+
+    for (page, id) in pages.iter().zip(&page_ids) {
+        let mut content = format!("BT /F1 10 Tf 14 TL 50 {} Td\n", size.height - 42);
+        for line in page.iter() {
+            content.push_str(&format!("({}) Tj T*\n", pdf_escape(line)));
+        }
+        content.push_str("ET");
+
+        objects.push(format!(
+            "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 {} {}] \
+             /Resources << /Font << /F1 3 0 R >> >> /Contents {} 0 R >>",
+            size.width,
+            size.height,
+            id + 1
+        ));
+        objects.push(format!(
+            "<< /Length {} >>\nstream\n{}\nendstream",
+            content.len(),
+            content
+        ));
+    }
+
+    let mut pdf = String::from("%PDF-1.4\n");
+    let mut offsets = Vec::with_capacity(objects.len());
+    for (index, object) in objects.iter().enumerate() {
+        offsets.push(pdf.len());
+        pdf.push_str(&format!("{} 0 obj\n{}\nendobj\n", index + 1, object));
+    }
+
+    let xref = pdf.len();
+    pdf.push_str(&format!(
+        "xref\n0 {}\n0000000000 65535 f \n",
+        objects.len() + 1
+    ));
+    for offset in offsets {
+        pdf.push_str(&format!("{:010} 00000 n \n", offset));
+    }
+    pdf.push_str(&format!(
+        "trailer\n<< /Size {} /Root 1 0 R >>\nstartxref\n{}\n%%EOF\n",
+        objects.len() + 1,
+        xref
+    ));
+
+    pdf.into_bytes()
+}
+
+fn pdf_escape(line: &str) -> String {
+    line.chars()
+        .map(|c| match c {
+            '\\' | '(' | ')' => format!("\\{}", c),
+            c if c.is_ascii() && !c.is_ascii_control() => c.to_string(),
+            _ => "?".to_string(),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn text_pdf_points_startxref_at_the_cross_reference_table() {
+        let pdf = String::from_utf8(text_pdf(&["Total (2026): 13 h".to_string()])).unwrap();
+
+        let startxref = pdf.rsplit("startxref\n").next().unwrap();
+        let offset: usize = startxref.lines().next().unwrap().parse().unwrap();
+
+        assert!(pdf.starts_with("%PDF-1.4"));
+        assert!(pdf[offset..].starts_with("xref"));
+        assert!(pdf.contains("(Total \\(2026\\): 13 h) Tj"));
+    }
+
+    #[test]
+    fn landscape_pages_are_a4_turned_sideways() {
+        let pdf = String::from_utf8(landscape_text_pdf(&["Monday".to_string()])).unwrap();
+
+        assert!(pdf.contains("/MediaBox [0 0 842 595]"));
+        assert!(pdf.contains("BT /F1 10 Tf 14 TL 50 553 Td"));
+    }
+}
@@ -0,0 +1,322 @@
+use chrono::NaiveDate;
+
+use crate::billing::{Invoice, Ledger};
+use crate::domain::{
+    Location, MAX_ENGAGEMENT, PaymentType, PersonalName, Student, completed_sessions_in,
+    monthly_engagement, rate_in, session_location, session_surcharge,
+};
+use crate::money::MoneyFormat;
+
+/// A short plain-text account of an invoiced month, for pasting into a
+/// chat message when a PDF invoice would be too much. With `include_links`
+/// each session is followed by its recording links.
+pub fn fee_note(
+    student: &Student,
+    invoice: &Invoice,
+    ledger: &Ledger,
+    locations: &[Location],
+    money: &MoneyFormat,
+    payment_instructions: &str,
+    include_links: bool,
+) -> String {
+    let mut lines = vec![
+        format!(
+            "Fee note for {}, {} ({})",
+            student.name.full_name(),
+            invoice_month(invoice),
+            invoice.reference()
+        ),
+        String::new(),
+    ];
+    lines.extend(invoice_details(
+        student,
+        invoice,
+        ledger,
+        locations,
+        money,
+        payment_instructions,
+        include_links,
+    ));
+
+    lines.join("\n")
+}
+
+/// The invoice as lines for a printable PDF: who it is from and for, then
+/// the same sessions, charges and total as the fee note.
+pub fn invoice_lines(
+    student: &Student,
+    invoice: &Invoice,
+    ledger: &Ledger,
+    locations: &[Location],
+    money: &MoneyFormat,
+    payment_instructions: &str,
+    tutor: &PersonalName,
+) -> Vec<String> {
+    let mut lines = vec![
+        format!("Invoice {}", invoice.reference()),
+        String::new(),
+        format!("From: {}", tutor.full_name()),
+        format!("To: Parent of {}", student.name.full_name()),
+        format!(
+            "For: {} lessons, {}",
+            student.subject_names(),
+            invoice_month(invoice)
+        ),
+    ];
+    if let Some(sent_on) = invoice.sent_on {
+        lines.push(format!("Issued: {}", sent_on.format("%-d %b %Y")));
+    }
+    lines.push(String::new());
+    lines.extend(invoice_details(
+        student,
+        invoice,
+        ledger,
+        locations,
+        money,
+        payment_instructions,
+        false,
+    ));
+
+    lines
+}
+
+fn invoice_month(invoice: &Invoice) -> String {
+    NaiveDate::from_ymd_opt(invoice.year, invoice.month, 1)
+        .map(|first| first.format("%B %Y").to_string())
+        .unwrap_or_default()
+}
+
+/// Sessions held, rate, charges and total, then how to pay.
+fn invoice_details(
+    student: &Student,
+    invoice: &Invoice,
+    ledger: &Ledger,
+    locations: &[Location],
+    money: &MoneyFormat,
+    payment_instructions: &str,
+    include_links: bool,
+) -> Vec<String> {
+    let mut lines = Vec::new();
+    let sessions = completed_sessions_in(student, invoice.month, invoice.year);
+    if sessions.is_empty() {
+        lines.push("No sessions held".to_string());
+    } else {
+        lines.push(format!("Sessions held ({}):", sessions.len()));
+        for session in &sessions {
+            let date = session.format("%a %-d %b");
+            lines.push(match session_surcharge(student, locations, *session) {
+                surcharge if surcharge > 0.0 => format!(
+                    "- {} ({}, +{})",
+                    date,
+                    session_location(student, *session).unwrap_or_default(),
+                    money.format(surcharge)
+                ),
+                _ => format!("- {}", date),
+            });
+            if include_links {
+                lines.extend(
+                    student
+                        .sessions
+                        .iter()
+                        .filter(|record| record.at == *session)
+                        .flat_map(|record| &record.links)
+                        .map(|link| format!("  {}", link)),
+                );
+            }
+        }
+    }
+
+    // Each subject is billed at its own rate, so each gets its own line.
+    let enrolments = student.enrolments();
+    for enrolment in &enrolments {
+        let rate = money.format(rate_in(enrolment, invoice.year, invoice.month));
+        let per = match enrolment.payment_data.payment_type {
+            PaymentType::PerSession => "session",
+            PaymentType::Monthly => "month",
+        };
+        lines.push(match enrolments.len() {
+            1 => format!("Rate: {} per {}", rate, per),
+            _ => format!("Rate for {}: {} per {}", enrolment.subject, rate, per),
+        });
+    }
+    lines.extend(
+        ledger
+            .charges_in(invoice.month, invoice.year)
+            .filter(|charge| charge.student_id == student.id)
+            .map(|charge| {
+                format!(
+                    "{}, {}: {}",
+                    charge.reason,
+                    charge.session_at.format("%a %-d %b"),
+                    money.format(charge.amount)
+                )
+            }),
+    );
+
+    if let Some(engagement) = monthly_engagement(student, invoice.month, invoice.year) {
+        lines.push(format!(
+            "Engagement: {:.1} out of {} on average",
+            engagement, MAX_ENGAGEMENT
+        ));
+    }
+
+    lines.push(String::new());
+    lines.push(format!("Total: {}", money.format(invoice.amount)));
+    if let Some(due_on) = invoice.due_on() {
+        lines.push(format!("Please pay by {}.", due_on.format("%-d %b %Y")));
+    }
+
+    let payment_instructions = payment_instructions.trim();
+    if !payment_instructions.is_empty() {
+        lines.push(String::new());
+        lines.push(payment_instructions.to_string());
+    }
+
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::billing::{Charge, ChargeReason};
+    use crate::domain::fixtures::{date, tue_thu_student};
+    use crate::domain::{Enrolment, PaymentData, SessionRating, TutorSubject};
+    use chrono::{Local, TimeZone};
+
+    #[test]
+    fn fee_note_lists_sessions_charges_and_how_to_pay() {
+        let mut student = tue_thu_student(&[(2026, 1, 8), (2026, 1, 6), (2026, 2, 3)]);
+        student.session_ratings = [(6, 4), (8, 5)]
+            .map(|(day, engagement)| SessionRating {
+                at: Local.with_ymd_and_hms(2026, 1, day, 17, 0, 0).unwrap(),
+                engagement,
+            })
+            .to_vec();
+        let mut ledger = Ledger::default();
+        ledger.add(Charge {
+            student_id: student.id.clone(),
+            student_name: student.name.full_name(),
+            session_at: Local.with_ymd_and_hms(2026, 1, 13, 17, 0, 0).unwrap(),
+            reason: ChargeReason::NoShow,
+            amount: 50.0,
+            invoiced: false,
+        });
+        let number = ledger.draft_invoice(&student, &[], 2026, 1).unwrap();
+        ledger.send_invoice(number, date(2026, 2, 1));
+
+        let note = fee_note(
+            &student,
+            &ledger.invoices()[0],
+            &ledger,
+            &[],
+            &MoneyFormat::default(),
+            "MoMo 024 000 0000\n",
+            false,
+        );
+
+        assert_eq!(
+            note,
+            "Fee note for Test Student, January 2026 (INV-0001)\n\
+             \n\
+             Sessions held (2):\n\
+             - Tue 6 Jan\n\
+             - Thu 8 Jan\n\
+             Rate: GHS 100.00 per session\n\
+             No-show fee, Tue 13 Jan: GHS 50.00\n\
+             Engagement: 4.5 out of 5 on average\n\
+             \n\
+             Total: GHS 250.00\n\
+             Please pay by 15 Feb 2026.\n\
+             \n\
+             MoMo 024 000 0000"
+        );
+
+        let tutor = PersonalName {
+            first: "Ama".to_string(),
+            last: "Mensah".to_string(),
+            other: None,
+        };
+        let lines = invoice_lines(
+            &student,
+            &ledger.invoices()[0],
+            &ledger,
+            &[],
+            &MoneyFormat::default(),
+            "",
+            &tutor,
+        );
+        assert_eq!(
+            lines[..7],
+            [
+                "Invoice INV-0001",
+                "",
+                "From: Ama Mensah",
+                "To: Parent of Test Student",
+                "For: Extended Mathematics lessons, January 2026",
+                "Issued: 1 Feb 2026",
+                "",
+            ]
+        );
+        assert_eq!(lines.last().unwrap(), "Please pay by 15 Feb 2026.");
+    }
+
+    #[test]
+    fn invoices_list_every_subject_with_its_own_rate() {
+        let mut student = tue_thu_student(&[(2026, 1, 6)]);
+        student.other_subjects = vec![Enrolment {
+            subject: TutorSubject::new("Physics"),
+            tabled_sessions: Vec::new(),
+            payment_data: PaymentData {
+                payment_type: PaymentType::PerSession,
+                amount: 120.0,
+                min_sessions: None,
+            },
+        }];
+        let mut ledger = Ledger::default();
+        ledger.draft_invoice(&student, &[], 2026, 1).unwrap();
+        let tutor = PersonalName {
+            first: "Ama".to_string(),
+            last: "Mensah".to_string(),
+            other: None,
+        };
+
+        let lines = invoice_lines(
+            &student,
+            &ledger.invoices()[0],
+            &ledger,
+            &[],
+            &MoneyFormat::default(),
+            "",
+            &tutor,
+        );
+        assert!(
+            lines.contains(&"For: Extended Mathematics, Physics lessons, January 2026".to_string())
+        );
+        assert!(
+            lines.contains(&"Rate for Extended Mathematics: GHS 100.00 per session".to_string())
+        );
+        assert!(lines.contains(&"Rate for Physics: GHS 120.00 per session".to_string()));
+    }
+
+    #[test]
+    fn fee_note_can_follow_sessions_with_their_recordings() {
+        let mut student = tue_thu_student(&[(2026, 1, 6), (2026, 1, 8)]);
+        student.sessions[0].links = vec!["https://example.com/recording".to_string()];
+        let mut ledger = Ledger::default();
+        ledger.draft_invoice(&student, &[], 2026, 1).unwrap();
+        let note = |include_links| {
+            fee_note(
+                &student,
+                &ledger.invoices()[0],
+                &ledger,
+                &[],
+                &MoneyFormat::default(),
+                "",
+                include_links,
+            )
+        };
+
+        assert!(!note(false).contains("https://"));
+        assert!(note(true).contains("- Tue 6 Jan\n  https://example.com/recording\n- Thu 8 Jan\n"));
+    }
+}
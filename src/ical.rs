@@ -0,0 +1,254 @@
+//! The timetable as an iCalendar file, for importing into or subscribing
+//! from Google Calendar, Apple Calendar and the like.
+
+use chrono::{DateTime, Datelike, Duration, Local, NaiveDate, NaiveDateTime, Utc};
+use std::path::PathBuf;
+
+use crate::domain::{Domain, SessionData, Student, parse_session_time, session_minutes};
+use crate::export::ExportError;
+
+pub const ICS_FILE_NAME: &str = "timetable.ics";
+const PRODUCT_ID: &str = "-//Tutor Manager//Timetable//EN";
+/// Longest line the format allows, in bytes, before it is folded.
+const MAX_LINE_BYTES: usize = 75;
+
+/// Every tabled slot as a weekly recurring event from the student's start
+/// until they leave, less the sessions called off or moved, plus each
+/// moved session on its own. Times are local to this computer.
+pub fn timetable_ics(domain: &Domain, stamp: DateTime<Utc>) -> String {
+    let mut lines = vec![
+        "BEGIN:VCALENDAR".to_string(),
+        "VERSION:2.0".to_string(),
+        format!("PRODID:{}", PRODUCT_ID),
+        "CALSCALE:GREGORIAN".to_string(),
+        "X-WR-CALNAME:Tutoring".to_string(),
+    ];
+
+    for student in &domain.students {
+        for (index, enrolment) in student.enrolments().iter().enumerate() {
+            for slot in &enrolment.tabled_sessions {
+                lines.extend(slot_event(enrolment, index, slot, domain, stamp));
+            }
+            for moved in &enrolment.reschedules {
+                lines.extend(event(
+                    enrolment,
+                    format!("{}-moved-{}", enrolment.id, local_time(moved.from)),
+                    stamp,
+                    moved.to.naive_local(),
+                    session_minutes(enrolment, moved.from),
+                    Vec::new(),
+                ));
+            }
+        }
+    }
+    lines.push("END:VCALENDAR".to_string());
+
+    lines
+        .iter()
+        .map(|line| fold(line))
+        .collect::<Vec<_>>()
+        .join("\r\n")
+        + "\r\n"
+}
+
+fn slot_event(
+    student: &Student,
+    index: usize,
+    slot: &SessionData,
+    domain: &Domain,
+    stamp: DateTime<Utc>,
+) -> Vec<String> {
+    let Some(time) = parse_session_time(&slot.time) else {
+        return Vec::new();
+    };
+    let start = student.tution_start_date.date_naive();
+    let days_ahead =
+        slot.day.num_days_from_monday() as i64 - start.weekday().num_days_from_monday() as i64;
+    let first = start + Duration::days(days_ahead.rem_euclid(7));
+    let in_slot = |at: NaiveDateTime| at.weekday() == slot.day && at.time() == time;
+
+    let mut exceptions: Vec<NaiveDateTime> = student
+        .cancelled_sessions
+        .iter()
+        .chain(student.reschedules.iter().map(|moved| &moved.from))
+        .map(|at| at.naive_local())
+        .chain(domain.blackout_dates.iter().map(|date| date.and_time(time)))
+        .filter(|at| in_slot(*at) && at.date() >= first)
+        .collect();
+    exceptions.sort();
+    exceptions.dedup();
+
+    let mut rule = format!("RRULE:FREQ=WEEKLY;BYDAY={}", weekday_code(slot.day));
+    if let Some(end) = student.tution_end_date {
+        // The end date is the first day they no longer come.
+        let last = end.date_naive() - Duration::days(1);
+        rule.push_str(&format!(";UNTIL={}T235959", date_code(last)));
+    }
+
+    let mut details = vec![rule];
+    details.extend(
+        exceptions
+            .into_iter()
+            .map(|at| format!("EXDATE:{}", naive_time(at))),
+    );
+
+    event(
+        student,
+        format!(
+            "{}-{}-{}-{}",
+            student.id,
+            index,
+            weekday_code(slot.day),
+            time.format("%H%M")
+        ),
+        stamp,
+        first.and_time(time),
+        slot.duration_minutes,
+        details,
+    )
+}
+
+fn event(
+    student: &Student,
+    uid: String,
+    stamp: DateTime<Utc>,
+    start: NaiveDateTime,
+    minutes: u32,
+    details: Vec<String>,
+) -> Vec<String> {
+    let mut lines = vec![
+        "BEGIN:VEVENT".to_string(),
+        format!("UID:{}@tutor-mgr", uid),
+        format!("DTSTAMP:{}", stamp.format("%Y%m%dT%H%M%SZ")),
+        format!("DTSTART:{}", naive_time(start)),
+        format!("DURATION:PT{}M", minutes),
+        format!(
+            "SUMMARY:{} with {}",
+            escape(student.subject.name()),
+            escape(&student.name.full_name())
+        ),
+    ];
+    if let Some(location) = &student.location {
+        lines.push(format!("LOCATION:{}", escape(location)));
+    }
+    lines.extend(details);
+    lines.push("END:VEVENT".to_string());
+    lines
+}
+
+fn local_time(at: DateTime<Local>) -> String {
+    naive_time(at.naive_local())
+}
+
+fn naive_time(at: NaiveDateTime) -> String {
+    at.format("%Y%m%dT%H%M%S").to_string()
+}
+
+fn date_code(date: NaiveDate) -> String {
+    date.format("%Y%m%d").to_string()
+}
+
+fn weekday_code(day: chrono::Weekday) -> &'static str {
+    match day {
+        chrono::Weekday::Mon => "MO",
+        chrono::Weekday::Tue => "TU",
+        chrono::Weekday::Wed => "WE",
+        chrono::Weekday::Thu => "TH",
+        chrono::Weekday::Fri => "FR",
+        chrono::Weekday::Sat => "SA",
+        chrono::Weekday::Sun => "SU",
+    }
+}
+
+fn escape(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace('\n', "\\n")
+}
+
+/// Breaks long lines with a leading space on each continuation, never
+/// inside a character.
+fn fold(line: &str) -> String {
+    let mut folded = String::new();
+    let mut width = 0;
+    for c in line.chars() {
+        if width + c.len_utf8() > MAX_LINE_BYTES {
+            folded.push_str("\r\n ");
+            width = 1;
+        }
+        folded.push(c);
+        width += c.len_utf8();
+    }
+    folded
+}
+
+/// Rewrites the file calendar apps subscribe to, as set by `calendar_feed`
+/// in the config.
+pub async fn write_feed(path: PathBuf, ics: String) -> Result<(), ExportError> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| ExportError::WriteFailed(e.to_string()))?;
+    }
+    std::fs::write(path, ics).map_err(|e| ExportError::WriteFailed(e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::Reschedule;
+    use crate::domain::fixtures::{date, sample_domain};
+    use chrono::TimeZone;
+
+    #[test]
+    fn slots_recur_weekly_without_the_sessions_moved_or_off() {
+        let mut domain = sample_domain();
+        domain.students.truncate(1);
+        let student = &mut domain.students[0];
+        let slot = student.tabled_sessions[0].clone();
+        let time = parse_session_time(&slot.time).unwrap();
+        let on = |date: NaiveDate| Local.from_local_datetime(&date.and_time(time)).unwrap();
+        let tabled = date(2026, 2, 16)
+            .iter_days()
+            .find(|date| date.weekday() == slot.day)
+            .unwrap();
+        student.reschedules.push(Reschedule {
+            from: on(tabled),
+            to: on(tabled + Duration::days(1)),
+        });
+        student.tution_end_date = Some(on(date(2026, 6, 1)));
+        let stamp = Utc.with_ymd_and_hms(2026, 2, 12, 9, 0, 0).unwrap();
+
+        let ics = timetable_ics(&domain, stamp);
+
+        assert!(ics.starts_with("BEGIN:VCALENDAR\r\n"));
+        assert!(ics.ends_with("END:VCALENDAR\r\n"));
+        assert!(ics.contains(&format!(
+            "RRULE:FREQ=WEEKLY;BYDAY={};UNTIL=20260531T235959\r\n",
+            weekday_code(slot.day)
+        )));
+        assert!(ics.contains(&format!("EXDATE:{}\r\n", naive_time(tabled.and_time(time)))));
+        assert!(ics.contains(&format!(
+            "DTSTART:{}\r\n",
+            naive_time((tabled + Duration::days(1)).and_time(time))
+        )));
+        assert_eq!(
+            ics.matches("BEGIN:VEVENT").count(),
+            domain.students[0].tabled_sessions.len() + 1
+        );
+        assert!(ics.lines().all(|line| line.len() <= MAX_LINE_BYTES));
+    }
+
+    #[test]
+    fn long_lines_fold_between_characters() {
+        let line = format!("SUMMARY:{}", "é".repeat(60));
+        let folded = fold(&line);
+
+        assert!(
+            folded
+                .split("\r\n")
+                .all(|part| part.len() <= MAX_LINE_BYTES)
+        );
+        assert_eq!(folded.replace("\r\n ", ""), line);
+    }
+}
@@ -1,4 +1,6 @@
 use iced::widget::svg;
+use std::borrow::Cow;
+use std::path::{Path, PathBuf};
 use std::sync::OnceLock;
 
 static PLUS: OnceLock<svg::Handle> = OnceLock::new();
@@ -7,6 +9,8 @@ static CALENDAR: OnceLock<svg::Handle> = OnceLock::new();
 static SCHEDULE: OnceLock<svg::Handle> = OnceLock::new();
 static CHECK_CIRCLE: OnceLock<svg::Handle> = OnceLock::new();
 static PAYMENTS: OnceLock<svg::Handle> = OnceLock::new();
+static WALLET: OnceLock<svg::Handle> = OnceLock::new();
+static COPY: OnceLock<svg::Handle> = OnceLock::new();
 static DASHBOARD: OnceLock<svg::Handle> = OnceLock::new();
 static ARROW_DOWN: OnceLock<svg::Handle> = OnceLock::new();
 static ARROW_UP: OnceLock<svg::Handle> = OnceLock::new();
@@ -17,142 +21,213 @@ static SETTINGS: OnceLock<svg::Handle> = OnceLock::new();
 static LOGOUT: OnceLock<svg::Handle> = OnceLock::new();
 static CANCEL: OnceLock<svg::Handle> = OnceLock::new();
 static DELETE: OnceLock<svg::Handle> = OnceLock::new();
+static CALL: OnceLock<svg::Handle> = OnceLock::new();
+static CHAT: OnceLock<svg::Handle> = OnceLock::new();
+static MAIL: OnceLock<svg::Handle> = OnceLock::new();
 
-fn icon_path(name: &str) -> String {
-    format!("{}/resources/icons/{}", env!("CARGO_MANIFEST_DIR"), name)
+/// Icons in this directory replace the embedded ones with the same file name.
+const ICON_DIR_VAR: &str = "TUTOR_MGR_ICON_DIR";
+
+/// Shown in place of an icon whose data is not an SVG.
+const PLACEHOLDER: &[u8] = br##"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 24 24"><rect x="4" y="4" width="16" height="16" rx="2" fill="none" stroke="#9E9E9E" stroke-width="2" stroke-dasharray="3 2"/></svg>"##;
+
+macro_rules! embedded {
+    ($name:literal) => {
+        ($name, include_bytes!(concat!("../resources/icons/", $name)))
+    };
 }
 
-pub fn plus() -> svg::Handle {
-    PLUS.get_or_init(|| {
-        svg::Handle::from_path(icon_path("add_24dp_1F1F1F_FILL0_wght400_GRAD0_opsz24.svg"))
+type Embedded = (&'static str, &'static [u8]);
+
+fn icon(cell: &OnceLock<svg::Handle>, (name, embedded): Embedded) -> svg::Handle {
+    cell.get_or_init(|| {
+        let override_dir = std::env::var_os(ICON_DIR_VAR).map(PathBuf::from);
+        svg::Handle::from_memory(icon_bytes(override_dir.as_deref(), name, embedded))
     })
     .clone()
 }
 
+/// Prefers a readable override, then the embedded copy, then the placeholder.
+fn icon_bytes(
+    override_dir: Option<&Path>,
+    name: &str,
+    embedded: &'static [u8],
+) -> Cow<'static, [u8]> {
+    let overridden = override_dir
+        .and_then(|dir| std::fs::read(dir.join(name)).ok())
+        .filter(|bytes| looks_like_svg(bytes));
+
+    match overridden {
+        Some(bytes) => Cow::Owned(bytes),
+        None if looks_like_svg(embedded) => Cow::Borrowed(embedded),
+        None => Cow::Borrowed(PLACEHOLDER),
+    }
+}
+
+fn looks_like_svg(bytes: &[u8]) -> bool {
+    std::str::from_utf8(bytes).is_ok_and(|source| source.contains("<svg"))
+}
+
+pub fn plus() -> svg::Handle {
+    icon(
+        &PLUS,
+        embedded!("add_24dp_1F1F1F_FILL0_wght400_GRAD0_opsz24.svg"),
+    )
+}
+
 pub fn edit() -> svg::Handle {
-    EDIT.get_or_init(|| svg::Handle::from_path(icon_path("pen-to-square-regular-full.svg")))
-        .clone()
+    icon(&EDIT, embedded!("pen-to-square-regular-full.svg"))
 }
 
 pub fn calendar() -> svg::Handle {
-    CALENDAR
-        .get_or_init(|| {
-            svg::Handle::from_path(icon_path(
-                "calendar_today_24dp_1F1F1F_FILL0_wght400_GRAD0_opsz24.svg",
-            ))
-        })
-        .clone()
+    icon(
+        &CALENDAR,
+        embedded!("calendar_today_24dp_1F1F1F_FILL0_wght400_GRAD0_opsz24.svg"),
+    )
 }
 
 pub fn schedule() -> svg::Handle {
-    SCHEDULE
-        .get_or_init(|| {
-            svg::Handle::from_path(icon_path(
-                "schedule_24dp_1F1F1F_FILL0_wght400_GRAD0_opsz24.svg",
-            ))
-        })
-        .clone()
+    icon(
+        &SCHEDULE,
+        embedded!("schedule_24dp_1F1F1F_FILL0_wght400_GRAD0_opsz24.svg"),
+    )
 }
 
 pub fn check_circle() -> svg::Handle {
-    CHECK_CIRCLE
-        .get_or_init(|| {
-            svg::Handle::from_path(icon_path(
-                "check_circle_24dp_1F1F1F_FILL0_wght400_GRAD0_opsz24.svg",
-            ))
-        })
-        .clone()
+    icon(
+        &CHECK_CIRCLE,
+        embedded!("check_circle_24dp_1F1F1F_FILL0_wght400_GRAD0_opsz24.svg"),
+    )
 }
 
 pub fn payments() -> svg::Handle {
-    PAYMENTS
-        .get_or_init(|| {
-            svg::Handle::from_path(icon_path(
-                "payments_24dp_1F1F1F_FILL0_wght400_GRAD0_opsz24.svg",
-            ))
-        })
-        .clone()
+    icon(
+        &PAYMENTS,
+        embedded!("payments_24dp_1F1F1F_FILL0_wght400_GRAD0_opsz24.svg"),
+    )
+}
+
+pub fn wallet() -> svg::Handle {
+    icon(
+        &WALLET,
+        embedded!("account_balance_wallet_24dp_1F1F1F_FILL0_wght400_GRAD0_opsz24.svg"),
+    )
+}
+
+pub fn copy() -> svg::Handle {
+    icon(
+        &COPY,
+        embedded!("content_copy_24dp_1F1F1F_FILL0_wght400_GRAD0_opsz24.svg"),
+    )
 }
 
 pub fn dashboard() -> svg::Handle {
-    DASHBOARD
-        .get_or_init(|| {
-            svg::Handle::from_path(icon_path(
-                "dashboard_24dp_1F1F1F_FILL0_wght400_GRAD0_opsz24.svg",
-            ))
-        })
-        .clone()
+    icon(
+        &DASHBOARD,
+        embedded!("dashboard_24dp_1F1F1F_FILL0_wght400_GRAD0_opsz24.svg"),
+    )
 }
 
 pub fn student_manager() -> svg::Handle {
-    STUDENT
-        .get_or_init(|| {
-            svg::Handle::from_path(icon_path(
-                "school_24dp_1F1F1F_FILL0_wght400_GRAD0_opsz24.svg",
-            ))
-        })
-        .clone()
+    icon(
+        &STUDENT,
+        embedded!("school_24dp_1F1F1F_FILL0_wght400_GRAD0_opsz24.svg"),
+    )
 }
 
 pub fn arrow_up() -> svg::Handle {
-    ARROW_UP
-        .get_or_init(|| {
-            svg::Handle::from_path(icon_path(
-                "arrow_upward_24dp_1F1F1F_FILL0_wght400_GRAD0_opsz24.svg",
-            ))
-        })
-        .clone()
+    icon(
+        &ARROW_UP,
+        embedded!("arrow_upward_24dp_1F1F1F_FILL0_wght400_GRAD0_opsz24.svg"),
+    )
 }
 
 pub fn arrow_down() -> svg::Handle {
-    ARROW_DOWN
-        .get_or_init(|| {
-            svg::Handle::from_path(icon_path(
-                "arrow_downward_24dp_1F1F1F_FILL0_wght400_GRAD0_opsz24.svg",
-            ))
-        })
-        .clone()
+    icon(
+        &ARROW_DOWN,
+        embedded!("arrow_downward_24dp_1F1F1F_FILL0_wght400_GRAD0_opsz24.svg"),
+    )
 }
 
 pub fn logo() -> svg::Handle {
-    LOGO.get_or_init(|| svg::Handle::from_path(icon_path("nhoma_short_logo.svg")))
-        .clone()
+    icon(&LOGO, embedded!("nhoma_short_logo.svg"))
 }
 
 pub fn logo_expanded() -> svg::Handle {
-    LOGO_EXPANDED
-        .get_or_init(|| svg::Handle::from_path(icon_path("nhoma_logo.svg")))
-        .clone()
+    icon(&LOGO_EXPANDED, embedded!("nhoma_logo.svg"))
 }
 
 pub fn settings() -> svg::Handle {
-    SETTINGS
-        .get_or_init(|| {
-            svg::Handle::from_path(icon_path(
-                "settings_24dp_1F1F1F_FILL0_wght400_GRAD0_opsz24.svg",
-            ))
-        })
-        .clone()
+    icon(
+        &SETTINGS,
+        embedded!("settings_24dp_1F1F1F_FILL0_wght400_GRAD0_opsz24.svg"),
+    )
 }
 
 pub fn logout() -> svg::Handle {
-    LOGOUT
-        .get_or_init(|| {
-            svg::Handle::from_path(icon_path(
-                "logout_24dp_1F1F1F_FILL0_wght400_GRAD0_opsz24.svg",
-            ))
-        })
-        .clone()
+    icon(
+        &LOGOUT,
+        embedded!("logout_24dp_1F1F1F_FILL0_wght400_GRAD0_opsz24.svg"),
+    )
 }
 
 pub fn cancel() -> svg::Handle {
-    CANCEL
-        .get_or_init(|| svg::Handle::from_path(icon_path("cancel.svg")))
-        .clone()
+    icon(&CANCEL, embedded!("cancel.svg"))
 }
 
 pub fn delete() -> svg::Handle {
-    DELETE
-        .get_or_init(|| svg::Handle::from_path(icon_path("delete.svg")))
-        .clone()
+    icon(&DELETE, embedded!("delete.svg"))
+}
+
+pub fn call() -> svg::Handle {
+    icon(
+        &CALL,
+        embedded!("call_24dp_1F1F1F_FILL0_wght400_GRAD0_opsz24.svg"),
+    )
+}
+
+pub fn chat() -> svg::Handle {
+    icon(
+        &CHAT,
+        embedded!("chat_24dp_1F1F1F_FILL0_wght400_GRAD0_opsz24.svg"),
+    )
+}
+
+pub fn mail() -> svg::Handle {
+    icon(
+        &MAIL,
+        embedded!("mail_24dp_1F1F1F_FILL0_wght400_GRAD0_opsz24.svg"),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EMBEDDED: &[u8] = b"<svg xmlns=\"http://www.w3.org/2000/svg\"/>";
+
+    fn override_dir(test: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("tutor-mgr-icons-{}", test));
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn override_replaces_embedded_icon() {
+        let dir = override_dir("override");
+        let custom = b"<svg viewBox=\"0 0 1 1\"/>";
+        std::fs::write(dir.join("plus.svg"), custom).unwrap();
+
+        assert_eq!(&*icon_bytes(Some(&dir), "plus.svg", EMBEDDED), custom);
+        assert_eq!(&*icon_bytes(Some(&dir), "missing.svg", EMBEDDED), EMBEDDED);
+    }
+
+    #[test]
+    fn unreadable_icons_fall_back() {
+        let dir = override_dir("fallback");
+        std::fs::write(dir.join("plus.svg"), b"not an icon").unwrap();
+
+        assert_eq!(&*icon_bytes(Some(&dir), "plus.svg", EMBEDDED), EMBEDDED);
+        assert_eq!(&*icon_bytes(None, "plus.svg", b""), PLACEHOLDER);
+    }
 }
@@ -0,0 +1,416 @@
+use chrono::{Local, NaiveDate};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::path::PathBuf;
+
+use crate::billing::{Charge, Invoice, Ledger, Payment};
+use crate::domain::{Clock, Domain, Location, Prospect, Student, SubjectRate, Tutor};
+use crate::money::MoneyFormat;
+
+/// Names the format so other JSON files are turned away early.
+pub const INTERCHANGE_FORMAT: &str = "tutor-mgr";
+/// Raised whenever a field is renamed or removed. New optional fields
+/// keep the version.
+pub const INTERCHANGE_VERSION: u32 = 1;
+
+#[derive(Debug, Clone)]
+pub enum InterchangeError {
+    ReadFailed(String),
+    Invalid(String),
+    WrongFormat,
+    UnsupportedVersion(u32),
+}
+
+impl std::fmt::Display for InterchangeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InterchangeError::ReadFailed(reason) => {
+                write!(f, "Could not read the file: {}", reason)
+            }
+            InterchangeError::Invalid(reason) => write!(f, "The file is not valid: {}", reason),
+            InterchangeError::WrongFormat => write!(f, "This is not a Tutor Manager data file"),
+            InterchangeError::UnsupportedVersion(version) => write!(
+                f,
+                "The file is version {}, but only version {} can be read",
+                version, INTERCHANGE_VERSION
+            ),
+        }
+    }
+}
+
+/// Everything the app knows, in a form meant for backups, syncing and
+/// other tools. A version 1 file looks like:
+///
+/// ```json
+/// {
+///   "format": "tutor-mgr",
+///   "version": 1,
+///   "exported_on": "2026-02-12",
+///   "money_format": { "decimals": 2, "thousands_separator": true, "currency": "GHS" },
+///   "payment_instructions": "MoMo 024 000 0000 (A. Murray)",
+///   "blackout_dates": ["2026-03-06"],
+///   "locations": [{ "name": "Home visit", "surcharge": 30.0, "home_visit": true }],
+///   "rates": [{ "subject": "ExtendedMathematics", "per_hour": 150.0 }],
+///   "waitlist": [{ "name": "Kofi Boateng", "subject": "Statistics",
+///                  "slots": [{ "day": "Wed", "time": "5:00 PM" }], "added_on": "2026-02-01" }],
+///   "tutor": { "id": "...", "name": { "first": "...", "last": "...", "other": null },
+///              "subjects": ["ExtendedMathematics"], "tutoring_days": ["Tue"],
+///              "available_times": { "Tue": ["5:30 PM"] } },
+///   "students": [{
+///     "id": "mary-jane",
+///     "name": { "first": "Mary", "last": "Jane", "other": null },
+///     "subject": "ExtendedMathematics",
+///     "tags": ["WASSCE 2026"],
+///     "phone": "024 123 4567",
+///     "email": "parent@example.com",
+///     "tabled_sessions": [{ "day": "Tue", "time": "5:30 PM", "duration_minutes": 60 }],
+///     "sessions": [{ "at": "2026-01-06T17:30:00+00:00", "status": "Completed",
+///                    "notes": "Vectors" },
+///                  { "at": "2026-01-13T17:30:00+00:00", "status": "CancelledByTutor" }],
+///     "location": "Home visit",
+///     "session_locations": [{ "at": "2026-01-06T17:30:00+00:00", "location": "Online" }],
+///     "travel_km": 12.0,
+///     "session_distances": [{ "at": "2026-01-08T17:30:00+00:00", "km": 20.5 }],
+///     "session_ratings": [{ "at": "2026-01-06T17:30:00+00:00", "engagement": 4 }],
+///     "payment_data": { "payment_type": "PerSession", "amount": 150.0 },
+///     "rate_changes": [{ "from": "2026-01-01", "previous": 120.0 }],
+///     "tution_start_date": "2025-09-01T00:00:00+00:00",
+///     "tution_end_date": null
+///   }],
+///   "payments": [{ "student_id": "mary-jane", "paid_on": "2026-02-10", "amount": 150.0,
+///                  "invoice_number": 1 }],
+///   "invoices": [{ "number": 1, "student_id": "mary-jane", "year": 2026, "month": 1,
+///                  "amount": 750.0, "sent_on": "2026-02-01", "void": false }],
+///   "charges": [{ "student_id": "mary-jane", "student_name": "Mary Jane",
+///                 "session_at": "2026-02-03T17:30:00+00:00", "reason": "NoShow",
+///                 "amount": 50.0, "invoiced": false }]
+/// }
+/// ```
+///
+/// Times are RFC 3339, dates are `YYYY-MM-DD` and weekdays are
+/// three-letter English names. A session's `status` is `Completed`,
+/// `MissedByStudent`, `CancelledByTutor` or `Rescheduled`. `money_format`,
+/// `payment_instructions`, `blackout_dates`, `locations`, `rates`,
+/// `waitlist`, `invoices`, `charges`, a session's `notes` and `links`, a student's `tags`,
+/// `phone`, `email`, `location`, `session_locations`, `travel_km`,
+/// `session_distances`, `session_ratings` and `rate_changes`, a monthly
+/// `payment_data`'s `min_sessions` and a payment's `invoice_number` may be
+/// left out.
+/// Older files listing held sessions as bare times under `actual_sessions`,
+/// with `cancelled_sessions` beside them, still read.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Interchange {
+    pub format: String,
+    pub version: u32,
+    pub exported_on: NaiveDate,
+    #[serde(default)]
+    pub money_format: MoneyFormat,
+    #[serde(default)]
+    pub payment_instructions: String,
+    #[serde(default)]
+    pub blackout_dates: Vec<NaiveDate>,
+    #[serde(default)]
+    pub locations: Vec<Location>,
+    #[serde(default)]
+    pub rates: Vec<SubjectRate>,
+    #[serde(default)]
+    pub waitlist: Vec<Prospect>,
+    pub tutor: Tutor,
+    pub students: Vec<Student>,
+    pub payments: Vec<Payment>,
+    #[serde(default)]
+    pub invoices: Vec<Invoice>,
+    #[serde(default)]
+    pub charges: Vec<Charge>,
+}
+
+/// Just enough of a file to tell whether the rest can be read.
+#[derive(Deserialize)]
+struct Header {
+    format: String,
+    version: u32,
+}
+
+impl Interchange {
+    pub fn new(domain: &Domain, ledger: &Ledger) -> Self {
+        Self {
+            format: INTERCHANGE_FORMAT.to_string(),
+            version: INTERCHANGE_VERSION,
+            exported_on: domain.clock.today(),
+            money_format: domain.money_format,
+            payment_instructions: domain.payment_instructions.clone(),
+            blackout_dates: domain.blackout_dates.clone(),
+            locations: domain.locations.clone(),
+            rates: domain.rates.clone(),
+            waitlist: domain.waitlist.clone(),
+            tutor: domain.tutor.clone(),
+            students: domain.students.clone(),
+            payments: ledger.payments().to_vec(),
+            invoices: ledger.invoices().to_vec(),
+            charges: ledger.charges().to_vec(),
+        }
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("interchange data always serializes")
+    }
+
+    /// Checks the format and version before reading the rest, so an old or
+    /// foreign file gets a clear error rather than a missing-field one.
+    pub fn from_json(json: &str) -> Result<Self, InterchangeError> {
+        let header: Header =
+            serde_json::from_str(json).map_err(|e| InterchangeError::Invalid(e.to_string()))?;
+        if header.format != INTERCHANGE_FORMAT {
+            return Err(InterchangeError::WrongFormat);
+        }
+        if header.version != INTERCHANGE_VERSION {
+            return Err(InterchangeError::UnsupportedVersion(header.version));
+        }
+
+        let interchange: Self =
+            serde_json::from_str(json).map_err(|e| InterchangeError::Invalid(e.to_string()))?;
+        interchange.check()?;
+
+        Ok(interchange)
+    }
+
+    /// Turns away files that would leave two students or two invoices
+    /// answering to the same id.
+    fn check(&self) -> Result<(), InterchangeError> {
+        let mut student_ids = HashSet::new();
+        if let Some(student) = self
+            .students
+            .iter()
+            .find(|student| !student_ids.insert(student.id.as_str()))
+        {
+            return Err(InterchangeError::Invalid(format!(
+                "student id \"{}\" is used twice",
+                student.id
+            )));
+        }
+
+        let mut numbers = HashSet::new();
+        if let Some(invoice) = self
+            .invoices
+            .iter()
+            .find(|invoice| !numbers.insert(invoice.number))
+        {
+            return Err(InterchangeError::Invalid(format!(
+                "invoice {} is listed twice",
+                invoice.reference()
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// What importing would lose or leave loose, for the tutor to agree
+    /// to before their data is replaced. Empty when nothing is at stake.
+    pub fn conflicts(&self, domain: &Domain, ledger: &Ledger) -> Vec<String> {
+        let mut conflicts = Vec::new();
+        if self.tutor.id != domain.tutor.id {
+            conflicts.push(format!(
+                "The file was exported by {}, not {}",
+                self.tutor.name.full_name(),
+                domain.tutor.name.full_name()
+            ));
+        }
+
+        let missing: Vec<String> = domain
+            .students
+            .iter()
+            .filter(|student| !self.students.iter().any(|kept| kept.id == student.id))
+            .map(|student| student.name.full_name())
+            .collect();
+        if !missing.is_empty() {
+            conflicts.push(format!(
+                "{} students here are not in the file and will be removed: {}",
+                missing.len(),
+                missing.join(", ")
+            ));
+        }
+
+        let sessions_lost: usize = domain
+            .students
+            .iter()
+            .filter_map(|student| {
+                let kept = self.students.iter().find(|kept| kept.id == student.id)?;
+                Some(
+                    student
+                        .sessions
+                        .iter()
+                        .filter(|record| !kept.sessions.iter().any(|other| other.at == record.at))
+                        .count(),
+                )
+            })
+            .sum();
+        match sessions_lost {
+            0 => (),
+            1 => conflicts.push("1 session marked here is not in the file".to_string()),
+            count => conflicts.push(format!(
+                "{} sessions marked here are not in the file",
+                count
+            )),
+        }
+
+        let payments_lost = ledger
+            .payments()
+            .iter()
+            .filter(|payment| !self.payments.contains(payment))
+            .count();
+        match payments_lost {
+            0 => (),
+            1 => conflicts.push("1 payment recorded here is not in the file".to_string()),
+            count => conflicts.push(format!(
+                "{} payments recorded here are not in the file",
+                count
+            )),
+        }
+
+        let unknown = self
+            .payments
+            .iter()
+            .map(|payment| &payment.student_id)
+            .chain(self.invoices.iter().map(|invoice| &invoice.student_id))
+            .filter(|student_id| {
+                !self
+                    .students
+                    .iter()
+                    .any(|student| student.id == **student_id)
+            })
+            .count();
+        match unknown {
+            0 => (),
+            1 => conflicts.push(
+                "1 payment or invoice in the file is for a student it does not list".to_string(),
+            ),
+            count => conflicts.push(format!(
+                "{} payments or invoices in the file are for students it does not list",
+                count
+            )),
+        }
+
+        conflicts
+    }
+
+    /// The imported domain, running on `clock` like the one it replaces,
+    /// and its ledger.
+    pub fn into_domain(self, clock: Clock) -> (Domain, Ledger) {
+        let mut domain = Domain {
+            tutor: self.tutor,
+            students: self.students,
+            money_format: self.money_format,
+            payment_instructions: self.payment_instructions,
+            blackout_dates: self.blackout_dates,
+            locations: self.locations,
+            rates: self.rates,
+            waitlist: self.waitlist,
+            // Taken again from the imported sessions.
+            monthly_snapshots: Vec::new(),
+            trash: Vec::new(),
+            clock,
+        };
+        domain.upgrade_sessions();
+
+        (
+            domain,
+            Ledger::imported(self.charges, self.payments, self.invoices),
+        )
+    }
+}
+
+pub fn interchange_file_name() -> String {
+    format!("tutor-mgr-{}.json", Local::now().format("%Y-%m-%d"))
+}
+
+pub async fn read_interchange(path: PathBuf) -> Result<Interchange, InterchangeError> {
+    let json =
+        std::fs::read_to_string(path).map_err(|e| InterchangeError::ReadFailed(e.to_string()))?;
+
+    Interchange::from_json(&json)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::SessionRecord;
+    use crate::domain::fixtures::{date, sample_domain};
+
+    #[test]
+    fn exports_read_back_into_the_same_domain_and_ledger() {
+        let domain = sample_domain();
+        let mut ledger = Ledger::default();
+        let number = ledger
+            .draft_invoice(&domain.students[0], &[], 2026, 1)
+            .unwrap();
+        ledger.send_invoice(number, date(2026, 2, 1));
+        ledger.record_payment(Payment {
+            student_id: domain.students[0].id.clone(),
+            paid_on: date(2026, 2, 10),
+            amount: 150.0,
+            invoice_number: None,
+        });
+
+        let json = Interchange::new(&domain, &ledger).to_json();
+        let (imported, imported_ledger) = Interchange::from_json(&json)
+            .unwrap()
+            .into_domain(domain.clock);
+
+        assert_eq!(imported, domain);
+        assert_eq!(imported_ledger.payments(), ledger.payments());
+        assert_eq!(imported_ledger.invoices(), ledger.invoices());
+        assert_eq!(imported_ledger.charges(), ledger.charges());
+    }
+
+    #[test]
+    fn other_formats_and_versions_are_turned_away() {
+        let json = Interchange::new(&sample_domain(), &Ledger::default()).to_json();
+
+        assert!(matches!(
+            Interchange::from_json(&json.replace("\"version\": 1", "\"version\": 2")),
+            Err(InterchangeError::UnsupportedVersion(2))
+        ));
+        assert!(matches!(
+            Interchange::from_json(r#"{ "format": "ledger", "version": 1 }"#),
+            Err(InterchangeError::WrongFormat)
+        ));
+        assert!(matches!(
+            Interchange::from_json("[]"),
+            Err(InterchangeError::Invalid(_))
+        ));
+
+        let mut twice = Interchange::new(&sample_domain(), &Ledger::default());
+        twice.students.push(twice.students[0].clone());
+        assert!(matches!(
+            Interchange::from_json(&twice.to_json()),
+            Err(InterchangeError::Invalid(_))
+        ));
+    }
+
+    #[test]
+    fn importing_warns_about_what_would_be_lost() {
+        let domain = sample_domain();
+        let mut ledger = Ledger::default();
+        let file = Interchange::new(&domain, &ledger);
+        assert_eq!(file.conflicts(&domain, &ledger), Vec::<String>::new());
+
+        let mut newer = domain.clone();
+        newer.students[0]
+            .sessions
+            .push(SessionRecord::completed(Local::now()));
+        ledger.record_payment(Payment {
+            student_id: domain.students[0].id.clone(),
+            paid_on: date(2026, 2, 10),
+            amount: 150.0,
+            invoice_number: None,
+        });
+        let mut older = file.clone();
+        let dropped = older.students.remove(1);
+
+        let conflicts = older.conflicts(&newer, &ledger);
+        assert_eq!(conflicts.len(), 3);
+        assert!(conflicts[0].ends_with(&dropped.name.full_name()));
+        assert_eq!(conflicts[1], "1 session marked here is not in the file");
+        assert_eq!(conflicts[2], "1 payment recorded here is not in the file");
+    }
+}
@@ -0,0 +1,455 @@
+use chrono::{Datelike, NaiveDate};
+use iced::advanced::graphics::core::font;
+use iced::widget::{Column, button, checkbox, column, pick_list, row, text};
+use iced::{Center, Element, Font, Length, Task};
+use std::path::PathBuf;
+use std::rc::Rc;
+
+use crate::billing::{Invoice, InvoiceStatus, Ledger};
+use crate::domain::{Domain, Student, month_label, previous_month};
+use crate::export::{ExportError, text_pdf, write_export};
+use crate::fee_note::{fee_note, invoice_lines};
+use crate::money::MoneyFormat;
+use crate::shell::{CopyRequest, Effect};
+use crate::ui_components::{copy_button, global_content_container, page_header};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum InvoiceFilter {
+    #[default]
+    All,
+    /// Sent or overdue, so still waiting on money.
+    Open,
+    Status(InvoiceStatus),
+}
+
+impl InvoiceFilter {
+    fn all() -> Vec<InvoiceFilter> {
+        [InvoiceFilter::All, InvoiceFilter::Open]
+            .into_iter()
+            .chain(InvoiceStatus::ALL.map(InvoiceFilter::Status))
+            .collect()
+    }
+
+    fn matches(&self, status: InvoiceStatus) -> bool {
+        match self {
+            InvoiceFilter::All => true,
+            InvoiceFilter::Open => matches!(status, InvoiceStatus::Sent | InvoiceStatus::Overdue),
+            InvoiceFilter::Status(wanted) => status == *wanted,
+        }
+    }
+}
+
+impl std::fmt::Display for InvoiceFilter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InvoiceFilter::All => write!(f, "All invoices"),
+            InvoiceFilter::Open => write!(f, "Open"),
+            InvoiceFilter::Status(status) => write!(f, "{}", status),
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct InvoicesState {
+    domain: Option<Rc<Domain>>,
+    filter: InvoiceFilter,
+    /// Copied fee notes list each session's recording links.
+    include_links: bool,
+    status_message: Option<String>,
+}
+
+impl InvoicesState {
+    pub fn attach_domain(&mut self, domain: Rc<Domain>) {
+        self.domain = Some(domain);
+    }
+
+    fn today(&self) -> Option<NaiveDate> {
+        self.domain.as_ref().map(|domain| domain.clock.today())
+    }
+
+    fn money(&self) -> MoneyFormat {
+        self.domain
+            .as_ref()
+            .map(|domain| domain.money_format)
+            .unwrap_or_default()
+    }
+
+    fn student_name(&self, student_id: &str) -> String {
+        self.domain
+            .as_ref()
+            .and_then(|domain| domain.students.iter().find(|s| s.id == student_id))
+            .map(|student| student.name.full_name())
+            .unwrap_or_else(|| student_id.to_string())
+    }
+}
+
+#[derive(Clone, Debug)]
+pub enum Msg {
+    FilterSelected(InvoiceFilter),
+    IncludeLinksToggled(bool),
+    DraftLastMonth,
+    SendInvoice(u32),
+    VoidInvoice(u32),
+    CopyFeeNote(u32),
+    ExportPdf(u32),
+    Exported(Result<PathBuf, ExportError>),
+    /// Carried out by the app, e.g. copying with a toast.
+    Effect(Effect),
+}
+
+/// Invoices are kept in the dashboard's ledger beside the payments they
+/// are matched with, so the ledger is passed in.
+pub fn update(state: &mut InvoicesState, msg: Msg, ledger: &mut Ledger) -> Task<Msg> {
+    match msg {
+        Msg::FilterSelected(filter) => {
+            state.filter = filter;
+            Task::none()
+        }
+        Msg::IncludeLinksToggled(include_links) => {
+            state.include_links = include_links;
+            Task::none()
+        }
+        Msg::DraftLastMonth => {
+            let Some(domain) = &state.domain else {
+                return Task::none();
+            };
+
+            let today = domain.clock.today();
+            let (year, month) = previous_month(today.year(), today.month());
+            let drafted = domain
+                .students
+                .iter()
+                .filter_map(|student| ledger.draft_invoice(student, &domain.locations, year, month))
+                .count();
+
+            state.status_message = Some(format!(
+                "Drafted {} invoices for {}",
+                drafted,
+                month_label(year, month)
+            ));
+            Task::none()
+        }
+        Msg::SendInvoice(number) => {
+            if let Some(today) = state.today() {
+                ledger.send_invoice(number, today);
+            }
+            Task::none()
+        }
+        Msg::VoidInvoice(number) => {
+            ledger.void_invoice(number);
+            Task::none()
+        }
+        Msg::CopyFeeNote(number) => {
+            let Some(domain) = &state.domain else {
+                return Task::none();
+            };
+            let Some((invoice, student)) = invoice_and_student(domain, ledger, number) else {
+                return Task::none();
+            };
+
+            let note = fee_note(
+                student,
+                invoice,
+                ledger,
+                &domain.locations,
+                &domain.money_format,
+                &domain.payment_instructions,
+                state.include_links,
+            );
+            let what = format!("fee note for {}", invoice.reference());
+            Task::done(Msg::Effect(Effect::Copy(CopyRequest::new(note, what))))
+        }
+        Msg::ExportPdf(number) => {
+            let Some(domain) = &state.domain else {
+                return Task::none();
+            };
+            let Some((invoice, student)) = invoice_and_student(domain, ledger, number) else {
+                return Task::none();
+            };
+
+            let lines = invoice_lines(
+                student,
+                invoice,
+                ledger,
+                &domain.locations,
+                &domain.money_format,
+                &domain.payment_instructions,
+                &domain.tutor.name,
+            );
+            let file_name = format!("{}.pdf", invoice.reference().to_lowercase());
+            Task::perform(write_export(file_name, text_pdf(&lines)), Msg::Exported)
+        }
+        Msg::Exported(result) => {
+            state.status_message = Some(match result {
+                Ok(path) => format!("Exported to {}", path.display()),
+                Err(e) => e.to_string(),
+            });
+            Task::none()
+        }
+        Msg::Effect(_) => Task::none(),
+    }
+}
+
+fn invoice_and_student<'a>(
+    domain: &'a Domain,
+    ledger: &'a Ledger,
+    number: u32,
+) -> Option<(&'a Invoice, &'a Student)> {
+    let invoice = ledger
+        .invoices()
+        .iter()
+        .find(|invoice| invoice.number == number)?;
+    let student = domain
+        .students
+        .iter()
+        .find(|s| s.id == invoice.student_id)?;
+
+    Some((invoice, student))
+}
+
+/// One line of the invoice list, ready to show.
+#[derive(Debug, Clone, PartialEq)]
+pub struct InvoiceRow {
+    pub number: u32,
+    pub reference: String,
+    pub student: String,
+    pub month: String,
+    pub amount: String,
+    pub paid: String,
+    pub due: String,
+    pub status: InvoiceStatus,
+}
+
+/// Invoices passing `filter`, newest first, and what is still owed on
+/// the sent ones among them.
+pub fn invoice_rows(
+    state: &InvoicesState,
+    ledger: &Ledger,
+    filter: InvoiceFilter,
+    today: NaiveDate,
+) -> (Vec<InvoiceRow>, f32) {
+    let money = state.money();
+    let mut outstanding = 0.0;
+
+    let rows = ledger
+        .invoices()
+        .iter()
+        .rev()
+        .map(|invoice| (invoice, ledger.invoice_status(invoice, today)))
+        .filter(|(_, status)| filter.matches(*status))
+        .map(|(invoice, status)| {
+            let paid = ledger.paid_towards(invoice.number);
+            if matches!(status, InvoiceStatus::Sent | InvoiceStatus::Overdue) {
+                outstanding += invoice.amount - paid;
+            }
+
+            invoice_row(state, invoice, status, paid, &money)
+        })
+        .collect();
+
+    (rows, outstanding)
+}
+
+fn invoice_row(
+    state: &InvoicesState,
+    invoice: &Invoice,
+    status: InvoiceStatus,
+    paid: f32,
+    money: &MoneyFormat,
+) -> InvoiceRow {
+    InvoiceRow {
+        number: invoice.number,
+        reference: invoice.reference(),
+        student: state.student_name(&invoice.student_id),
+        month: month_label(invoice.year, invoice.month),
+        amount: money.format(invoice.amount),
+        paid: money.format(paid),
+        due: invoice
+            .due_on()
+            .map(|due_on| due_on.format("%-d %b %Y").to_string())
+            .unwrap_or_else(|| "--".to_string()),
+        status,
+    }
+}
+
+pub fn view<'a>(state: &'a InvoicesState, ledger: &Ledger) -> Element<'a, Msg> {
+    let Some(today) = state.today() else {
+        return column![page_header("Invoices")].into();
+    };
+
+    let (rows, outstanding) = invoice_rows(state, ledger, state.filter, today);
+    let (year, month) = previous_month(today.year(), today.month());
+
+    let toolbar = row![
+        pick_list(
+            InvoiceFilter::all(),
+            Some(state.filter),
+            Msg::FilterSelected
+        )
+        .text_size(12),
+        button(text(format!("Draft invoices for {}", month_label(year, month))).size(12))
+            .style(button::secondary)
+            .on_press(Msg::DraftLastMonth),
+        checkbox(state.include_links)
+            .label("Recording links in notes")
+            .text_size(12)
+            .on_toggle(Msg::IncludeLinksToggled),
+        row![
+            text(format!("Outstanding {}", state.money().format(outstanding))).size(12),
+            copy_button().on_press(Msg::Effect(Effect::Copy(CopyRequest::new(
+                state.money().format(outstanding),
+                "outstanding total"
+            )))),
+        ]
+        .spacing(4)
+        .align_y(Center),
+    ]
+    .spacing(20)
+    .align_y(Center);
+
+    let header = row![
+        heading("Invoice", 90.0),
+        heading("Student", 160.0),
+        heading("Month", 70.0),
+        heading("Amount", 110.0),
+        heading("Paid", 110.0),
+        heading("Due", 100.0),
+        heading("Status", 70.0),
+    ]
+    .spacing(10);
+
+    let list = Column::new()
+        .extend(rows.into_iter().map(view_invoice_row))
+        .spacing(6);
+
+    let mut content = column![toolbar, header, list].spacing(16);
+    if let Some(message) = &state.status_message {
+        content = content.push(text(message.clone()).size(12));
+    }
+
+    let content = global_content_container(content)
+        .width(Length::Fill)
+        .height(Length::Fill);
+
+    column![page_header("Invoices"), content].into()
+}
+
+fn view_invoice_row<'a>(row: InvoiceRow) -> Element<'a, Msg> {
+    let copy_note = button(text("Copy note").size(12))
+        .style(button::text)
+        .on_press(Msg::CopyFeeNote(row.number));
+    let export_pdf = button(text("PDF").size(12))
+        .style(button::text)
+        .on_press(Msg::ExportPdf(row.number));
+    let actions = match row.status {
+        InvoiceStatus::Draft => row![
+            copy_note,
+            export_pdf,
+            button(text("Send").size(12))
+                .style(button::text)
+                .on_press(Msg::SendInvoice(row.number)),
+            button(text("Void").size(12))
+                .style(button::text)
+                .on_press(Msg::VoidInvoice(row.number)),
+        ],
+        InvoiceStatus::Sent | InvoiceStatus::Overdue => row![
+            copy_note,
+            export_pdf,
+            button(text("Void").size(12))
+                .style(button::text)
+                .on_press(Msg::VoidInvoice(row.number))
+        ],
+        InvoiceStatus::Paid => row![copy_note, export_pdf],
+        InvoiceStatus::Void => row![],
+    };
+    let copy_total = Msg::Effect(Effect::Copy(CopyRequest::new(
+        format!("{}: {}", row.reference, row.amount),
+        "invoice total",
+    )));
+
+    row![
+        cell(row.reference, 90.0),
+        cell(row.student, 160.0),
+        cell(row.month, 70.0),
+        row![cell(row.amount, 86.0), copy_button().on_press(copy_total),]
+            .width(Length::Fixed(110.0))
+            .align_y(Center),
+        cell(row.paid, 110.0),
+        cell(row.due, 100.0),
+        cell(row.status.to_string(), 70.0),
+        actions.spacing(4),
+    ]
+    .spacing(10)
+    .align_y(Center)
+    .into()
+}
+
+fn heading<'a>(label: &'a str, width: f32) -> iced::widget::Text<'a> {
+    text(label).size(12).width(Length::Fixed(width)).font(Font {
+        weight: font::Weight::Medium,
+        ..Default::default()
+    })
+}
+
+fn cell<'a>(value: String, width: f32) -> iced::widget::Text<'a> {
+    text(value).size(12).width(Length::Fixed(width))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::billing::Payment;
+    use crate::domain::fixtures::{date, sample_domain};
+
+    fn statuses(
+        state: &InvoicesState,
+        ledger: &Ledger,
+        filter: InvoiceFilter,
+        today: NaiveDate,
+    ) -> Vec<(String, InvoiceStatus)> {
+        invoice_rows(state, ledger, filter, today)
+            .0
+            .into_iter()
+            .map(|row| (row.reference, row.status))
+            .collect()
+    }
+
+    #[test]
+    fn invoices_are_numbered_in_order_and_settled_by_linked_payments() {
+        let domain = Rc::new(sample_domain());
+        let mut state = InvoicesState::default();
+        state.attach_domain(Rc::clone(&domain));
+        let mut ledger = Ledger::default();
+
+        let _ = update(&mut state, Msg::DraftLastMonth, &mut ledger);
+        let _ = update(&mut state, Msg::DraftLastMonth, &mut ledger);
+        let numbers: Vec<u32> = ledger.invoices().iter().map(|i| i.number).collect();
+        assert_eq!(numbers, vec![1, 2]);
+
+        let _ = update(&mut state, Msg::VoidInvoice(2), &mut ledger);
+        let _ = update(&mut state, Msg::SendInvoice(1), &mut ledger);
+        ledger.record_payment(Payment {
+            student_id: ledger.invoices()[0].student_id.clone(),
+            paid_on: date(2026, 2, 12),
+            amount: ledger.invoices()[0].amount,
+            invoice_number: None,
+        });
+        let redrafted = ledger.draft_invoice(&domain.students[1], &[], 2026, 1);
+        assert_eq!(redrafted, Some(3));
+
+        assert_eq!(
+            statuses(&state, &ledger, InvoiceFilter::All, date(2026, 2, 12)),
+            vec![
+                ("INV-0003".to_string(), InvoiceStatus::Draft),
+                ("INV-0002".to_string(), InvoiceStatus::Void),
+                ("INV-0001".to_string(), InvoiceStatus::Paid),
+            ]
+        );
+
+        let _ = update(&mut state, Msg::SendInvoice(3), &mut ledger);
+        assert_eq!(
+            statuses(&state, &ledger, InvoiceFilter::Open, date(2026, 3, 1)),
+            vec![("INV-0003".to_string(), InvoiceStatus::Overdue)]
+        );
+    }
+}
@@ -0,0 +1,368 @@
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Aggregate {
+    Actual,
+    Potential,
+    Lost,
+    Scheduled,
+    Completed,
+    Students,
+}
+
+impl Aggregate {
+    pub const ALL: [Aggregate; 6] = [
+        Aggregate::Actual,
+        Aggregate::Potential,
+        Aggregate::Lost,
+        Aggregate::Scheduled,
+        Aggregate::Completed,
+        Aggregate::Students,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Aggregate::Actual => "actual",
+            Aggregate::Potential => "potential",
+            Aggregate::Lost => "lost",
+            Aggregate::Scheduled => "scheduled",
+            Aggregate::Completed => "completed",
+            Aggregate::Students => "students",
+        }
+    }
+
+    fn from_name(name: &str) -> Option<Self> {
+        Aggregate::ALL
+            .into_iter()
+            .find(|aggregate| aggregate.name() == name.to_lowercase())
+    }
+}
+
+/// Current-month values that custom metrics can refer to by name.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct KpiInputs {
+    pub actual: f32,
+    pub potential: f32,
+    pub lost: f32,
+    pub scheduled: f32,
+    pub completed: f32,
+    pub students: f32,
+}
+
+impl KpiInputs {
+    fn value(&self, aggregate: Aggregate) -> f32 {
+        match aggregate {
+            Aggregate::Actual => self.actual,
+            Aggregate::Potential => self.potential,
+            Aggregate::Lost => self.lost,
+            Aggregate::Scheduled => self.scheduled,
+            Aggregate::Completed => self.completed,
+            Aggregate::Students => self.students,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum KpiError {
+    UnexpectedCharacter(char),
+    UnknownName(String),
+    UnexpectedToken(String),
+    UnexpectedEnd,
+    DivisionByZero,
+}
+
+impl std::fmt::Display for KpiError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            KpiError::UnexpectedCharacter(c) => write!(f, "Unexpected character '{}'", c),
+            KpiError::UnknownName(name) => {
+                let known: Vec<&str> = Aggregate::ALL.iter().map(|a| a.name()).collect();
+                write!(f, "Unknown value '{}', use one of: {}", name, known.join(", "))
+            }
+            KpiError::UnexpectedToken(token) => write!(f, "Unexpected '{}'", token),
+            KpiError::UnexpectedEnd => write!(f, "Expression ends too early"),
+            KpiError::DivisionByZero => write!(f, "Division by zero"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Operator {
+    Add,
+    Subtract,
+    Multiply,
+    Divide,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Number(f32),
+    Name(String),
+    Operator(Operator),
+    OpenParen,
+    CloseParen,
+}
+
+impl std::fmt::Display for Token {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Token::Number(n) => write!(f, "{}", n),
+            Token::Name(name) => write!(f, "{}", name),
+            Token::Operator(Operator::Add) => write!(f, "+"),
+            Token::Operator(Operator::Subtract) => write!(f, "-"),
+            Token::Operator(Operator::Multiply) => write!(f, "*"),
+            Token::Operator(Operator::Divide) => write!(f, "/"),
+            Token::OpenParen => write!(f, "("),
+            Token::CloseParen => write!(f, ")"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Expr {
+    Number(f32),
+    Aggregate(Aggregate),
+    Negate(Box<Expr>),
+    Binary(Box<Expr>, Operator, Box<Expr>),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct KpiExpression {
+    source: String,
+    expr: Expr,
+}
+
+impl KpiExpression {
+    pub fn parse(source: &str) -> Result<Self, KpiError> {
+        let tokens = tokenize(source)?;
+        let mut parser = Parser { tokens, position: 0 };
+        let expr = parser.expression()?;
+
+        if let Some(token) = parser.peek() {
+            return Err(KpiError::UnexpectedToken(token.to_string()));
+        }
+
+        Ok(Self {
+            source: source.trim().to_string(),
+            expr,
+        })
+    }
+
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+
+    pub fn evaluate(&self, inputs: &KpiInputs) -> Result<f32, KpiError> {
+        evaluate(&self.expr, inputs)
+    }
+}
+
+fn evaluate(expr: &Expr, inputs: &KpiInputs) -> Result<f32, KpiError> {
+    match expr {
+        Expr::Number(n) => Ok(*n),
+        Expr::Aggregate(aggregate) => Ok(inputs.value(*aggregate)),
+        Expr::Negate(inner) => Ok(-evaluate(inner, inputs)?),
+        Expr::Binary(lhs, op, rhs) => {
+            let lhs = evaluate(lhs, inputs)?;
+            let rhs = evaluate(rhs, inputs)?;
+
+            match op {
+                Operator::Add => Ok(lhs + rhs),
+                Operator::Subtract => Ok(lhs - rhs),
+                Operator::Multiply => Ok(lhs * rhs),
+                Operator::Divide if rhs == 0.0 => Err(KpiError::DivisionByZero),
+                Operator::Divide => Ok(lhs / rhs),
+            }
+        }
+    }
+}
+
+fn tokenize(source: &str) -> Result<Vec<Token>, KpiError> {
+    let mut tokens = Vec::new();
+    let mut chars = source.chars().peekable();
+
+    while let Some(&c) = chars.peek() {
+        match c {
+            c if c.is_whitespace() => {
+                chars.next();
+            }
+            '+' | '-' | '*' | '/' => {
+                chars.next();
+                tokens.push(Token::Operator(match c {
+                    '+' => Operator::Add,
+                    '-' => Operator::Subtract,
+                    '*' => Operator::Multiply,
+                    _ => Operator::Divide,
+                }));
+            }
+            '(' => {
+                chars.next();
+                tokens.push(Token::OpenParen);
+            }
+            ')' => {
+                chars.next();
+                tokens.push(Token::CloseParen);
+            }
+            c if c.is_ascii_digit() || c == '.' => {
+                let mut number = String::new();
+                while let Some(&d) = chars.peek().filter(|d| d.is_ascii_digit() || **d == '.') {
+                    number.push(d);
+                    chars.next();
+                }
+                let value = number
+                    .parse::<f32>()
+                    .map_err(|_| KpiError::UnexpectedToken(number.clone()))?;
+                tokens.push(Token::Number(value));
+            }
+            c if c.is_alphabetic() || c == '_' => {
+                let mut name = String::new();
+                while let Some(&d) = chars.peek().filter(|d| d.is_alphanumeric() || **d == '_') {
+                    name.push(d);
+                    chars.next();
+                }
+                tokens.push(Token::Name(name));
+            }
+            other => return Err(KpiError::UnexpectedCharacter(other)),
+        }
+    }
+
+    Ok(tokens)
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    position: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.position)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.position).cloned();
+        self.position += 1;
+        token
+    }
+
+    fn expression(&mut self) -> Result<Expr, KpiError> {
+        let mut lhs = self.term()?;
+
+        while let Some(Token::Operator(op @ (Operator::Add | Operator::Subtract))) = self.peek() {
+            let op = *op;
+            self.next();
+            lhs = Expr::Binary(Box::new(lhs), op, Box::new(self.term()?));
+        }
+
+        Ok(lhs)
+    }
+
+    fn term(&mut self) -> Result<Expr, KpiError> {
+        let mut lhs = self.factor()?;
+
+        while let Some(Token::Operator(op @ (Operator::Multiply | Operator::Divide))) = self.peek()
+        {
+            let op = *op;
+            self.next();
+            lhs = Expr::Binary(Box::new(lhs), op, Box::new(self.factor()?));
+        }
+
+        Ok(lhs)
+    }
+
+    fn factor(&mut self) -> Result<Expr, KpiError> {
+        match self.next() {
+            Some(Token::Number(n)) => Ok(Expr::Number(n)),
+            Some(Token::Name(name)) => Aggregate::from_name(&name)
+                .map(Expr::Aggregate)
+                .ok_or(KpiError::UnknownName(name)),
+            Some(Token::Operator(Operator::Subtract)) => {
+                Ok(Expr::Negate(Box::new(self.factor()?)))
+            }
+            Some(Token::OpenParen) => {
+                let inner = self.expression()?;
+                match self.next() {
+                    Some(Token::CloseParen) => Ok(inner),
+                    Some(token) => Err(KpiError::UnexpectedToken(token.to_string())),
+                    None => Err(KpiError::UnexpectedEnd),
+                }
+            }
+            Some(token) => Err(KpiError::UnexpectedToken(token.to_string())),
+            None => Err(KpiError::UnexpectedEnd),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn inputs() -> KpiInputs {
+        KpiInputs {
+            actual: 750.0,
+            potential: 1000.0,
+            lost: 250.0,
+            scheduled: 8.0,
+            completed: 6.0,
+            students: 2.0,
+        }
+    }
+
+    fn value(source: &str) -> Result<f32, KpiError> {
+        KpiExpression::parse(source)?.evaluate(&inputs())
+    }
+
+    #[test]
+    fn names_and_numbers_are_read_whatever_the_case_and_spacing() {
+        let expression = KpiExpression::parse("  Actual / POTENTIAL * 100 ").unwrap();
+
+        assert_eq!(expression.source(), "Actual / POTENTIAL * 100");
+        assert_eq!(expression.evaluate(&inputs()), Ok(75.0));
+        assert_eq!(value("2.5"), Ok(2.5));
+        assert_eq!(value("-lost"), Ok(-250.0));
+    }
+
+    #[test]
+    fn multiplication_and_division_bind_tighter_than_addition() {
+        assert_eq!(value("1 + 2 * 3"), Ok(7.0));
+        assert_eq!(value("(1 + 2) * 3"), Ok(9.0));
+        assert_eq!(value("potential - actual - lost"), Ok(0.0));
+        assert_eq!(value("scheduled / students / 2"), Ok(2.0));
+        assert_eq!(value("--completed"), Ok(6.0));
+    }
+
+    #[test]
+    fn dividing_by_zero_is_an_error() {
+        assert_eq!(value("actual / 0"), Err(KpiError::DivisionByZero));
+        assert_eq!(
+            value("actual / (completed - 6)"),
+            Err(KpiError::DivisionByZero)
+        );
+    }
+
+    #[test]
+    fn unknown_names_are_reported_by_name() {
+        let error = KpiExpression::parse("actual / revenue").unwrap_err();
+
+        assert_eq!(error, KpiError::UnknownName("revenue".to_string()));
+        assert!(error.to_string().contains("actual, potential, lost"));
+    }
+
+    #[test]
+    fn malformed_expressions_are_rejected() {
+        let parse = |source| KpiExpression::parse(source).unwrap_err();
+
+        assert_eq!(parse(""), KpiError::UnexpectedEnd);
+        assert_eq!(parse("actual +"), KpiError::UnexpectedEnd);
+        assert_eq!(parse("(actual"), KpiError::UnexpectedEnd);
+        assert_eq!(parse("actual)"), KpiError::UnexpectedToken(")".to_string()));
+        assert_eq!(
+            parse("actual lost"),
+            KpiError::UnexpectedToken("lost".to_string())
+        );
+        assert_eq!(parse("* 2"), KpiError::UnexpectedToken("*".to_string()));
+        assert_eq!(
+            parse("1.2.3"),
+            KpiError::UnexpectedToken("1.2.3".to_string())
+        );
+        assert_eq!(parse("actual % 2"), KpiError::UnexpectedCharacter('%'));
+    }
+}
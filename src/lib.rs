@@ -0,0 +1,34 @@
+pub mod app;
+pub mod billing;
+pub mod calendar;
+pub mod compare;
+pub mod config;
+pub mod contact;
+pub mod dashboard;
+pub mod data_dirs;
+pub mod domain;
+pub mod export;
+pub mod fee_note;
+pub mod ical;
+pub mod icons;
+pub mod interchange;
+pub mod invoices;
+pub mod kpi;
+pub mod money;
+pub mod notifications;
+pub mod quote;
+pub mod rate_adjustment;
+pub mod reconciliation;
+pub mod reminders;
+pub mod report_plugins;
+pub mod reports;
+pub mod routes;
+pub mod search;
+pub mod settings;
+pub mod shell;
+pub mod store;
+pub mod student_detail;
+pub mod students;
+pub mod theme;
+pub mod ui_components;
+pub mod usage;
@@ -1,20 +1,12 @@
-pub mod dashboard;
-pub mod domain;
-pub mod icons;
-pub mod shell;
-pub mod students;
-pub mod ui_components;
-
-mod app;
-
 use iced::Size;
 
-use crate::app::App;
+use tutor_mgr::app::App;
 
 fn main() -> iced::Result {
     iced::application(App::new, App::update, App::view)
         .title(App::title)
         .subscription(App::subscription)
+        .theme(App::theme)
         .window(iced::window::Settings {
             size: Size::new(1200.0, 800.0),
             maximized: false,
@@ -0,0 +1,152 @@
+use serde::{Deserialize, Serialize};
+
+/// How amounts of money are rounded and shown. Every card, chart, report
+/// and export goes through this so they agree on precision.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MoneyFormat {
+    /// Digits after the decimal point, from 0 to `MAX_DECIMALS`.
+    pub decimals: u8,
+    /// Groups whole units in threes, as in `1,200`.
+    pub thousands_separator: bool,
+    #[serde(default)]
+    pub currency: Currency,
+}
+
+impl MoneyFormat {
+    pub const MAX_DECIMALS: u8 = 2;
+
+    /// Rounds half away from zero to the configured number of decimals.
+    pub fn round(&self, amount: f32) -> f32 {
+        let scale = 10f32.powi(self.decimals() as i32);
+        (amount * scale).round() / scale
+    }
+
+    /// The amount with its currency, e.g. `GHS 1,200.00`.
+    pub fn format(&self, amount: f32) -> String {
+        format!("{} {}", self.currency, self.amount(amount))
+    }
+
+    /// The amount without its currency, e.g. `1,200.00`.
+    pub fn amount(&self, amount: f32) -> String {
+        let plain = self.plain(amount);
+        if !self.thousands_separator {
+            return plain;
+        }
+
+        let (sign, unsigned) = match plain.strip_prefix('-') {
+            Some(unsigned) => ("-", unsigned),
+            None => ("", plain.as_str()),
+        };
+        let (whole, fraction) = match unsigned.split_once('.') {
+            Some((whole, fraction)) => (whole, Some(fraction)),
+            None => (unsigned, None),
+        };
+
+        let mut grouped = String::with_capacity(plain.len() + whole.len() / 3);
+        for (index, digit) in whole.chars().enumerate() {
+            if index > 0 && (whole.len() - index).is_multiple_of(3) {
+                grouped.push(',');
+            }
+            grouped.push(digit);
+        }
+
+        match fraction {
+            Some(fraction) => format!("{}{}.{}", sign, grouped, fraction),
+            None => format!("{}{}", sign, grouped),
+        }
+    }
+
+    /// The rounded amount with no currency or separators, for exports that
+    /// spreadsheets should read as numbers.
+    pub fn plain(&self, amount: f32) -> String {
+        let rounded = self.round(amount);
+        // Keeps amounts that round to zero from showing as `-0.00`.
+        let rounded = if rounded == 0.0 { 0.0 } else { rounded };
+
+        format!("{:.*}", self.decimals() as usize, rounded)
+    }
+
+    fn decimals(&self) -> u8 {
+        self.decimals.min(Self::MAX_DECIMALS)
+    }
+}
+
+impl Default for MoneyFormat {
+    fn default() -> Self {
+        Self {
+            decimals: Self::MAX_DECIMALS,
+            thousands_separator: true,
+            currency: Currency::default(),
+        }
+    }
+}
+
+/// A three-letter currency code such as `GHS`, kept inline so
+/// `MoneyFormat` stays `Copy`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct Currency([u8; 3]);
+
+impl Currency {
+    /// Takes any three letters, in either case.
+    pub fn parse(code: &str) -> Option<Self> {
+        let code: [u8; 3] = code.trim().as_bytes().try_into().ok()?;
+        code.iter()
+            .all(u8::is_ascii_alphabetic)
+            .then(|| Self(code.map(|letter| letter.to_ascii_uppercase())))
+    }
+
+    pub fn as_str(&self) -> &str {
+        std::str::from_utf8(&self.0).expect("currency codes are ASCII letters")
+    }
+}
+
+impl Default for Currency {
+    fn default() -> Self {
+        Self(*b"GHS")
+    }
+}
+
+impl std::fmt::Display for Currency {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl TryFrom<String> for Currency {
+    type Error = String;
+
+    fn try_from(code: String) -> Result<Self, Self::Error> {
+        Self::parse(&code).ok_or_else(|| format!("{:?} is not a three-letter currency code", code))
+    }
+}
+
+impl From<Currency> for String {
+    fn from(currency: Currency) -> Self {
+        currency.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn amounts_are_rounded_and_grouped_as_configured() {
+        let money = MoneyFormat::default();
+        assert_eq!(money.format(150.0), "GHS 150.00");
+        assert_eq!(money.format(1234567.5), "GHS 1,234,567.50");
+        assert_eq!(money.format(-1200.0), "GHS -1,200.00");
+        assert_eq!(money.format(-0.001), "GHS 0.00");
+        assert_eq!(money.plain(1200.0), "1200.00");
+
+        let whole = MoneyFormat {
+            decimals: 0,
+            thousands_separator: false,
+            currency: Currency::parse("usd").unwrap(),
+        };
+        assert_eq!(whole.format(1299.5), "USD 1300");
+        assert_eq!(whole.round(149.4), 149.0);
+        assert_eq!(Currency::parse("GH₵"), None);
+    }
+}
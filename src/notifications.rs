@@ -0,0 +1,254 @@
+use chrono::{DateTime, Duration, Local};
+use std::collections::{HashMap, HashSet};
+use std::process::Command;
+
+use crate::domain::{Student, is_active_on, scheduled_sessions_on};
+
+/// How many minutes ahead of a session its notification shows, unless
+/// configured.
+pub const DEFAULT_NOTICE_MINUTES: i64 = 15;
+/// Longest notice the config accepts, a day.
+pub const MAX_NOTICE_MINUTES: i64 = 24 * 60;
+const APP_NAME: &str = "Tutor Manager";
+
+#[derive(Debug, Clone)]
+pub enum NotificationError {
+    /// Nothing on this computer shows desktop notifications.
+    Unavailable(String),
+}
+
+impl std::fmt::Display for NotificationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            NotificationError::Unavailable(reason) => {
+                write!(f, "Desktop notifications are not available: {}", reason)
+            }
+        }
+    }
+}
+
+/// When a student's sessions are announced on the desktop.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum StudentNotices {
+    /// As far ahead as set for everyone.
+    #[default]
+    AsSet,
+    Minutes(i64),
+    Off,
+}
+
+impl StudentNotices {
+    pub const CHOICES: [StudentNotices; 6] = [
+        StudentNotices::AsSet,
+        StudentNotices::Minutes(5),
+        StudentNotices::Minutes(10),
+        StudentNotices::Minutes(30),
+        StudentNotices::Minutes(60),
+        StudentNotices::Off,
+    ];
+}
+
+impl std::fmt::Display for StudentNotices {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            StudentNotices::AsSet => write!(f, "As set above"),
+            StudentNotices::Minutes(minutes) => write!(f, "{} minutes before", minutes),
+            StudentNotices::Off => write!(f, "Off"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SessionNotice {
+    pub student_id: String,
+    pub student_name: String,
+    pub session_at: DateTime<Local>,
+}
+
+impl SessionNotice {
+    pub fn body(&self, now: DateTime<Local>) -> String {
+        format!(
+            "{}'s session starts at {} (in {} minutes)",
+            self.student_name,
+            self.session_at.format("%-I:%M %p"),
+            (self.session_at - now).num_minutes().max(0)
+        )
+    }
+}
+
+/// How far ahead sessions are announced, for everyone and per student id,
+/// and the sessions already announced so none shows twice.
+#[derive(Debug, Clone)]
+pub struct NotificationBook {
+    lead: Duration,
+    students: HashMap<String, StudentNotices>,
+    shown: HashSet<(String, DateTime<Local>)>,
+}
+
+impl Default for NotificationBook {
+    fn default() -> Self {
+        Self {
+            lead: Duration::minutes(DEFAULT_NOTICE_MINUTES),
+            students: HashMap::new(),
+            shown: HashSet::new(),
+        }
+    }
+}
+
+impl NotificationBook {
+    pub fn set_lead_minutes(&mut self, minutes: i64) {
+        self.lead = Duration::minutes(minutes);
+    }
+
+    pub fn lead_minutes(&self) -> i64 {
+        self.lead.num_minutes()
+    }
+
+    pub fn student(&self, student_id: &str) -> StudentNotices {
+        self.students.get(student_id).copied().unwrap_or_default()
+    }
+
+    pub fn set_student(&mut self, student_id: &str, notices: StudentNotices) {
+        self.students.insert(student_id.to_string(), notices);
+    }
+
+    /// Replaces every student's setting, e.g. with those read from the
+    /// config. Students left out go back to `AsSet`.
+    pub fn set_students(&mut self, students: HashMap<String, StudentNotices>) {
+        self.students = students;
+    }
+
+    /// The next session of each student that starts within their notice
+    /// and has not been announced yet.
+    pub fn due(&self, students: &[Student], now: DateTime<Local>) -> Vec<SessionNotice> {
+        let today = now.date_naive();
+        students
+            .iter()
+            .filter(|student| is_active_on(student, today))
+            .filter_map(|student| {
+                let lead = match self.student(&student.id) {
+                    StudentNotices::AsSet => self.lead,
+                    StudentNotices::Minutes(minutes) => Duration::minutes(minutes),
+                    StudentNotices::Off => return None,
+                };
+                let session_at = today
+                    .iter_days()
+                    .take(2)
+                    .flat_map(|date| scheduled_sessions_on(student, date))
+                    .find(|session_at| *session_at > now)?;
+                let shown = self.shown.contains(&(student.id.clone(), session_at));
+                (session_at - now <= lead && !shown).then(|| SessionNotice {
+                    student_id: student.id.clone(),
+                    student_name: student.name.full_name(),
+                    session_at,
+                })
+            })
+            .collect()
+    }
+
+    /// Keeps the session from being announced again, whether or not the
+    /// notification could be shown.
+    pub fn mark_shown(&mut self, notice: &SessionNotice) {
+        self.shown
+            .insert((notice.student_id.clone(), notice.session_at));
+    }
+}
+
+/// Hands each notice to the system's notifier: `notify-send` on Linux and
+/// the BSDs, AppleScript on macOS and a toast on Windows. The notifier is
+/// started but not waited on, so only a missing one is reported.
+pub async fn show_notices(
+    notices: Vec<SessionNotice>,
+    now: DateTime<Local>,
+) -> Vec<(SessionNotice, Result<(), NotificationError>)> {
+    notices
+        .into_iter()
+        .map(|notice| {
+            let result = show_notification("Upcoming session", &notice.body(now));
+            (notice, result)
+        })
+        .collect()
+}
+
+fn show_notification(title: &str, body: &str) -> Result<(), NotificationError> {
+    let mut child = notifier_command(title, body)
+        .spawn()
+        .map_err(|e| NotificationError::Unavailable(e.to_string()))?;
+
+    // Reaped off the UI thread so the finished notifier does not linger.
+    std::thread::spawn(move || child.wait());
+    Ok(())
+}
+
+#[cfg(target_os = "macos")]
+fn notifier_command(title: &str, body: &str) -> Command {
+    let quoted = |value: &str| format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""));
+    let mut command = Command::new("osascript");
+    command.arg("-e").arg(format!(
+        "display notification {} with title {}",
+        quoted(body),
+        quoted(&format!("{}: {}", APP_NAME, title))
+    ));
+    command
+}
+
+#[cfg(target_os = "windows")]
+fn notifier_command(title: &str, body: &str) -> Command {
+    // Shown under PowerShell's own app id, as toasts need a registered one.
+    const POWERSHELL_APP_ID: &str =
+        "{1AC14E77-02E7-4E5D-B744-2EB1AE5198B7}\\WindowsPowerShell\\v1.0\\powershell.exe";
+    let quoted = |value: &str| format!("'{}'", value.replace('\'', "''"));
+    let mut command = Command::new("powershell");
+    command.args(["-NoProfile", "-Command"]).arg(format!(
+        "$manager = [Windows.UI.Notifications.ToastNotificationManager, Windows.UI.Notifications, ContentType = WindowsRuntime]; \
+         $toast = $manager::GetTemplateContent([Windows.UI.Notifications.ToastTemplateType]::ToastText02); \
+         $lines = $toast.GetElementsByTagName('text'); \
+         $lines.Item(0).AppendChild($toast.CreateTextNode({})) > $null; \
+         $lines.Item(1).AppendChild($toast.CreateTextNode({})) > $null; \
+         $manager::CreateToastNotifier({}).Show([Windows.UI.Notifications.ToastNotification]::new($toast))",
+        quoted(&format!("{}: {}", APP_NAME, title)),
+        quoted(body),
+        quoted(POWERSHELL_APP_ID)
+    ));
+    command
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+fn notifier_command(title: &str, body: &str) -> Command {
+    let mut command = Command::new("notify-send");
+    command.args(["--app-name", APP_NAME, title, body]);
+    command
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::fixtures::tue_thu_student;
+    use chrono::TimeZone;
+
+    #[test]
+    fn the_next_session_is_announced_once_within_its_notice() {
+        // Tuesday 3 February 2026; the fixture's sessions start at 5 PM.
+        let at = |hour, minute| Local.with_ymd_and_hms(2026, 2, 3, hour, minute, 0).unwrap();
+        let students = [tue_thu_student(&[])];
+        let mut book = NotificationBook::default();
+
+        assert!(book.due(&students, at(16, 30)).is_empty());
+        let due = book.due(&students, at(16, 50));
+        assert_eq!(due.len(), 1);
+        assert_eq!(due[0].session_at, at(17, 0));
+        assert_eq!(
+            due[0].body(at(16, 50)),
+            "Test Student's session starts at 5:00 PM (in 10 minutes)"
+        );
+
+        book.mark_shown(&due[0]);
+        assert!(book.due(&students, at(16, 55)).is_empty());
+
+        book.set_student(&students[0].id, StudentNotices::Minutes(60));
+        let thursday = Local.with_ymd_and_hms(2026, 2, 5, 16, 30, 0).unwrap();
+        assert_eq!(book.due(&students, thursday).len(), 1);
+        book.set_student(&students[0].id, StudentNotices::Off);
+        assert!(book.due(&students, thursday).is_empty());
+    }
+}
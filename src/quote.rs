@@ -0,0 +1,131 @@
+use crate::domain::{Location, PersonalName, SubjectRate, TutorSubject};
+use crate::money::MoneyFormat;
+
+/// Months in a term, as the teaching hours report splits the year.
+pub const MONTHS_PER_TERM: u32 = 4;
+
+/// What a prospective student asked about.
+#[derive(Debug, Clone, PartialEq)]
+pub struct QuoteRequest {
+    pub subject: TutorSubject,
+    pub sessions_per_week: u32,
+    pub duration_minutes: u32,
+    pub location: Option<Location>,
+}
+
+/// Fees for a [`QuoteRequest`] at the configured hourly rate, plus the
+/// location's surcharge on every session.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Quote {
+    pub request: QuoteRequest,
+    pub per_session: f32,
+}
+
+impl Quote {
+    /// `None` when no rate is set for the subject.
+    pub fn compute(rates: &[SubjectRate], request: QuoteRequest) -> Option<Self> {
+        let rate = rates.iter().find(|rate| rate.subject == request.subject)?;
+        let surcharge = request
+            .location
+            .as_ref()
+            .map(|location| location.surcharge)
+            .unwrap_or(0.0);
+
+        Some(Self {
+            per_session: rate.per_hour * request.duration_minutes as f32 / 60.0 + surcharge,
+            request,
+        })
+    }
+
+    /// An average month of 52 / 12 weeks.
+    pub fn monthly(&self) -> f32 {
+        self.per_session * self.request.sessions_per_week as f32 * 52.0 / 12.0
+    }
+
+    pub fn term(&self) -> f32 {
+        self.monthly() * MONTHS_PER_TERM as f32
+    }
+
+    /// The quote as text to send to a parent, or print.
+    pub fn to_lines(&self, tutor: &PersonalName, money: &MoneyFormat) -> Vec<String> {
+        let mut lines = vec![
+            format!("Fee quote from {}", tutor.full_name()),
+            String::new(),
+            format!("Subject: {}", self.request.subject),
+            format!(
+                "Sessions: {} a week, {} minutes each",
+                self.request.sessions_per_week, self.request.duration_minutes
+            ),
+        ];
+        if let Some(location) = &self.request.location {
+            lines.push(format!("Where: {}", location.name));
+        }
+        lines.extend([
+            String::new(),
+            format!("Per session: {}", money.format(self.per_session)),
+            format!("Per month: {}", money.format(self.monthly())),
+            format!(
+                "Per term ({} months): {}",
+                MONTHS_PER_TERM,
+                money.format(self.term())
+            ),
+            String::new(),
+            "Monthly and term fees assume every session goes ahead. Only sessions held are charged."
+                .to_string(),
+        ]);
+
+        lines
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn quotes_scale_the_hourly_rate_and_add_the_surcharge() {
+        let rates = [SubjectRate {
+            subject: TutorSubject::new("Statistics"),
+            per_hour: 120.0,
+        }];
+        let request = QuoteRequest {
+            subject: TutorSubject::new("Statistics"),
+            sessions_per_week: 3,
+            duration_minutes: 90,
+            location: Some(Location {
+                name: "Home visit".to_string(),
+                surcharge: 20.0,
+                home_visit: true,
+            }),
+        };
+
+        let quote = Quote::compute(&rates, request.clone()).unwrap();
+        assert_eq!(quote.per_session, 200.0);
+        assert_eq!(quote.monthly(), 2600.0);
+        assert_eq!(quote.term(), 10400.0);
+
+        let tutor = PersonalName {
+            first: "Ama".to_string(),
+            last: "Mensah".to_string(),
+            other: None,
+        };
+        assert_eq!(
+            quote.to_lines(&tutor, &MoneyFormat::default())[..7],
+            [
+                "Fee quote from Ama Mensah",
+                "",
+                "Subject: Statistics",
+                "Sessions: 3 a week, 90 minutes each",
+                "Where: Home visit",
+                "",
+                "Per session: GHS 200.00",
+            ]
+        );
+
+        let other = QuoteRequest {
+            subject: TutorSubject::new("Additional Mathematics"),
+            ..request
+        };
+        assert_eq!(Quote::compute(&rates, other), None);
+    }
+}
@@ -0,0 +1,102 @@
+use chrono::NaiveDate;
+
+use crate::domain::{PaymentType, PersonalName, Student};
+use crate::money::MoneyFormat;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum AdjustmentKind {
+    #[default]
+    Percentage,
+    Flat,
+}
+
+impl AdjustmentKind {
+    pub const ALL: [AdjustmentKind; 2] = [AdjustmentKind::Percentage, AdjustmentKind::Flat];
+}
+
+impl std::fmt::Display for AdjustmentKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AdjustmentKind::Percentage => write!(f, "By a percentage"),
+            AdjustmentKind::Flat => write!(f, "By a flat amount"),
+        }
+    }
+}
+
+/// How far to move each selected student's rate.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RateAdjustment {
+    Percentage(f32),
+    Flat(f32),
+}
+
+impl RateAdjustment {
+    /// The new rate, rounded as amounts are shown and never below zero.
+    pub fn apply(&self, rate: f32, money: &MoneyFormat) -> f32 {
+        let raised = match self {
+            RateAdjustment::Percentage(percent) => rate * (1.0 + percent / 100.0),
+            RateAdjustment::Flat(amount) => rate + amount,
+        };
+
+        money.round(raised.max(0.0))
+    }
+}
+
+/// A message telling a student's parents about their new rate.
+pub fn rate_notice(
+    student: &Student,
+    tutor: &PersonalName,
+    new_rate: f32,
+    from: NaiveDate,
+    money: &MoneyFormat,
+) -> String {
+    let per = match student.payment_data.payment_type {
+        PaymentType::PerSession => "session",
+        PaymentType::Monthly => "month",
+    };
+
+    format!(
+        "Dear parent of {},\n\n\
+         From {}, fees for {}'s {} lessons will change from {} to {} per {}. \
+         Thank you for your continued support.\n\n\
+         {}",
+        student.name.full_name(),
+        from.format("%-d %B %Y"),
+        student.name.first,
+        student.subject,
+        money.format(student.payment_data.amount),
+        money.format(new_rate),
+        per,
+        tutor.full_name()
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::fixtures::{date, sample_domain};
+
+    #[test]
+    fn adjustments_round_to_the_money_format() {
+        let money = MoneyFormat::default();
+
+        assert_eq!(RateAdjustment::Percentage(7.5).apply(150.0, &money), 161.25);
+        assert_eq!(RateAdjustment::Flat(20.0).apply(150.0, &money), 170.0);
+        assert_eq!(RateAdjustment::Flat(-200.0).apply(150.0, &money), 0.0);
+
+        let domain = sample_domain();
+        assert_eq!(
+            rate_notice(
+                &domain.students[0],
+                &domain.tutor.name,
+                165.0,
+                date(2026, 3, 1),
+                &money
+            ),
+            "Dear parent of Mary Jane,\n\n\
+             From 1 March 2026, fees for Mary's Extended Mathematics lessons will change \
+             from GHS 150.00 to GHS 165.00 per session. Thank you for your continued support.\n\n\
+             Test Tutor"
+        );
+    }
+}
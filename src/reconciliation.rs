@@ -0,0 +1,465 @@
+use chrono::NaiveDate;
+use iced::advanced::graphics::core::font;
+use iced::widget::{Column, button, column, row, text, text_input};
+use iced::{Center, Color, Element, Font, Length, Task, Theme};
+use std::path::PathBuf;
+use std::rc::Rc;
+
+use crate::billing::{Ledger, Payment};
+use crate::domain::Domain;
+use crate::money::MoneyFormat;
+use crate::theme;
+use crate::ui_components::{global_content_container, page_header};
+
+/// A statement line and a payment pair up when the amounts agree and the
+/// dates are at most this many days apart, allowing for clearing delays.
+const MATCH_WINDOW_DAYS: i64 = 3;
+
+const DATE_FORMATS: [&str; 4] = ["%Y-%m-%d", "%d/%m/%Y", "%d-%m-%Y", "%d %b %Y"];
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum StatementError {
+    ReadFailed(String),
+    MissingColumn(&'static str),
+    InvalidLine { line: usize, reason: String },
+}
+
+impl std::fmt::Display for StatementError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            StatementError::ReadFailed(reason) => {
+                write!(f, "Could not read the statement: {}", reason)
+            }
+            StatementError::MissingColumn(column) => {
+                write!(f, "The statement has no {} column", column)
+            }
+            StatementError::InvalidLine { line, reason } => {
+                write!(f, "Line {} of the statement: {}", line, reason)
+            }
+        }
+    }
+}
+
+/// One transaction from a bank or mobile money statement. Money in is
+/// positive, money out negative.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StatementLine {
+    pub date: NaiveDate,
+    pub description: String,
+    pub amount: f32,
+}
+
+/// Where each column lives, found from the header row since banks and
+/// MoMo providers name and order them differently.
+struct Columns {
+    date: usize,
+    description: Option<usize>,
+    amount: Option<usize>,
+    credit: Option<usize>,
+    debit: Option<usize>,
+}
+
+impl Columns {
+    fn from_header(header: &[String]) -> Result<Self, StatementError> {
+        let find = |names: &[&str]| {
+            header.iter().position(|column| {
+                let column = column.trim().to_lowercase();
+                names.iter().any(|name| column.contains(name))
+            })
+        };
+
+        let columns = Columns {
+            date: find(&["date"]).ok_or(StatementError::MissingColumn("date"))?,
+            description: find(&["description", "narration", "details", "reference"]),
+            amount: find(&["amount"]),
+            credit: find(&["credit", "money in", "paid in"]),
+            debit: find(&["debit", "money out", "withdrawn"]),
+        };
+
+        if columns.amount.is_none() && columns.credit.is_none() {
+            return Err(StatementError::MissingColumn("amount or credit"));
+        }
+
+        Ok(columns)
+    }
+}
+
+pub fn parse_statement(csv: &str) -> Result<Vec<StatementLine>, StatementError> {
+    let mut rows = csv
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty());
+
+    let Some((_, header)) = rows.next() else {
+        return Ok(Vec::new());
+    };
+    let columns = Columns::from_header(&csv_fields(header))?;
+
+    rows.map(|(index, line)| {
+        let fields = csv_fields(line);
+        let invalid = |reason: String| StatementError::InvalidLine {
+            line: index + 1,
+            reason,
+        };
+        let field = |column: Option<usize>| {
+            column
+                .and_then(|column| fields.get(column))
+                .map(|field| field.trim())
+                .unwrap_or_default()
+        };
+
+        let date = parse_date(field(Some(columns.date)))
+            .ok_or_else(|| invalid(format!("unrecognised date {:?}", field(Some(columns.date)))))?;
+
+        let amount = match (columns.credit, columns.debit) {
+            (Some(_), _) if !field(columns.credit).is_empty() => {
+                parse_amount(field(columns.credit))
+            }
+            (_, Some(_)) if !field(columns.debit).is_empty() => {
+                parse_amount(field(columns.debit)).map(|amount| -amount.abs())
+            }
+            _ => parse_amount(field(columns.amount)),
+        }
+        .ok_or_else(|| invalid("no amount".to_string()))?;
+
+        Ok(StatementLine {
+            date,
+            description: field(columns.description).to_string(),
+            amount,
+        })
+    })
+    .collect()
+}
+
+fn parse_date(value: &str) -> Option<NaiveDate> {
+    DATE_FORMATS
+        .iter()
+        .find_map(|format| NaiveDate::parse_from_str(value, format).ok())
+}
+
+/// Reads amounts like `GHS 1,200.00`, ignoring currency and separators.
+fn parse_amount(value: &str) -> Option<f32> {
+    let digits: String = value
+        .chars()
+        .filter(|c| c.is_ascii_digit() || *c == '.' || *c == '-')
+        .collect();
+
+    digits.parse().ok()
+}
+
+/// Splits a CSV line, honouring quoted fields as written by `csv_field`.
+fn csv_fields(line: &str) -> Vec<String> {
+    let mut fields = vec![String::new()];
+    let mut quoted = false;
+    let mut chars = line.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        let field = fields.last_mut().unwrap();
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            '"' => quoted = !quoted,
+            ',' if !quoted => fields.push(String::new()),
+            _ => field.push(c),
+        }
+    }
+
+    fields
+}
+
+pub async fn read_statement(path: PathBuf) -> Result<Vec<StatementLine>, StatementError> {
+    let csv =
+        std::fs::read_to_string(&path).map_err(|e| StatementError::ReadFailed(e.to_string()))?;
+
+    parse_statement(&csv)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Reconciled<'a> {
+    pub line: &'a StatementLine,
+    pub payment: Option<&'a Payment>,
+}
+
+/// Pairs each incoming statement line with at most one recorded payment of
+/// the same amount, taking the closest date. Lines left without a payment
+/// are income that was never recorded and needs following up.
+pub fn reconcile<'a>(lines: &'a [StatementLine], payments: &'a [Payment]) -> Vec<Reconciled<'a>> {
+    let mut used = vec![false; payments.len()];
+
+    lines
+        .iter()
+        .filter(|line| line.amount > 0.0)
+        .map(|line| {
+            let closest = payments
+                .iter()
+                .enumerate()
+                .filter(|(index, payment)| {
+                    !used[*index]
+                        && (payment.amount - line.amount).abs() < 0.005
+                        && (payment.paid_on - line.date).num_days().abs() <= MATCH_WINDOW_DAYS
+                })
+                .min_by_key(|(_, payment)| (payment.paid_on - line.date).num_days().abs())
+                .map(|(index, _)| index);
+
+            if let Some(index) = closest {
+                used[index] = true;
+            }
+
+            Reconciled {
+                line,
+                payment: closest.map(|index| &payments[index]),
+            }
+        })
+        .collect()
+}
+
+#[derive(Default)]
+pub struct ReconciliationState {
+    domain: Option<Rc<Domain>>,
+    statement_path: String,
+    statement: Vec<StatementLine>,
+    loading: bool,
+    error: Option<String>,
+}
+
+impl ReconciliationState {
+    pub fn attach_domain(&mut self, domain: Rc<Domain>) {
+        self.domain = Some(domain);
+    }
+
+    fn student_name(&self, student_id: &str) -> String {
+        self.domain
+            .as_ref()
+            .and_then(|domain| domain.students.iter().find(|s| s.id == student_id))
+            .map(|student| student.name.full_name())
+            .unwrap_or_else(|| student_id.to_string())
+    }
+
+    fn money(&self) -> MoneyFormat {
+        self.domain
+            .as_ref()
+            .map(|domain| domain.money_format)
+            .unwrap_or_default()
+    }
+}
+
+#[derive(Clone, Debug)]
+pub enum Msg {
+    StatementPathChanged(String),
+    ImportStatement,
+    StatementImported(Result<Vec<StatementLine>, StatementError>),
+}
+
+pub fn update(state: &mut ReconciliationState, msg: Msg) -> Task<Msg> {
+    match msg {
+        Msg::StatementPathChanged(path) => {
+            state.statement_path = path;
+            state.error = None;
+            Task::none()
+        }
+        Msg::ImportStatement => {
+            let path = state.statement_path.trim();
+            if path.is_empty() || state.loading {
+                return Task::none();
+            }
+
+            state.loading = true;
+            Task::perform(read_statement(PathBuf::from(path)), Msg::StatementImported)
+        }
+        Msg::StatementImported(result) => {
+            state.loading = false;
+            match result {
+                Ok(statement) => {
+                    state.statement = statement;
+                    state.error = None;
+                }
+                Err(e) => state.error = Some(e.to_string()),
+            }
+            Task::none()
+        }
+    }
+}
+
+/// Payments are recorded on the dashboard, so the ledger is passed in to
+/// match against whatever has been recorded so far.
+pub fn view<'a>(state: &'a ReconciliationState, ledger: &'a Ledger) -> Element<'a, Msg> {
+    let import = row![
+        text_input(
+            "Path to a bank or MoMo statement (.csv)",
+            &state.statement_path
+        )
+        .on_input(Msg::StatementPathChanged)
+        .on_submit(Msg::ImportStatement)
+        .size(14)
+        .width(Length::Fixed(420.0)),
+        button(
+            text(if state.loading {
+                "Importing..."
+            } else {
+                "Import"
+            })
+            .size(14)
+        )
+        .on_press(Msg::ImportStatement),
+    ]
+    .spacing(10)
+    .align_y(Center);
+
+    let mut content = column![import].spacing(24);
+    if let Some(error) = &state.error {
+        content = content.push(coloured_text(error.clone(), theme::DANGER));
+    }
+
+    let money = state.money();
+    let reconciled = reconcile(&state.statement, ledger.payments());
+    let (matched, unmatched): (Vec<_>, Vec<_>) = reconciled
+        .into_iter()
+        .partition(|reconciled| reconciled.payment.is_some());
+
+    let follow_up = Column::new()
+        .extend(unmatched.iter().map(|reconciled| {
+            coloured_text(
+                format!("{} · Not recorded", describe(reconciled.line, &money)),
+                theme::WARNING,
+            )
+            .into()
+        }))
+        .spacing(4);
+
+    let paired = Column::new()
+        .extend(matched.iter().filter_map(|reconciled| {
+            let payment = reconciled.payment?;
+            Some(
+                text(format!(
+                    "{} · {} paid {}",
+                    describe(reconciled.line, &money),
+                    state.student_name(&payment.student_id),
+                    payment.paid_on.format("%-d %b")
+                ))
+                .size(12)
+                .into(),
+            )
+        }))
+        .spacing(4);
+
+    if !state.statement.is_empty() {
+        content = content
+            .push(
+                column![
+                    section_title(format!("Needs follow-up ({})", unmatched.len())),
+                    follow_up
+                ]
+                .spacing(12),
+            )
+            .push(
+                column![
+                    section_title(format!("Matched ({})", matched.len())),
+                    paired
+                ]
+                .spacing(12),
+            );
+    }
+
+    let content = global_content_container(content)
+        .width(Length::Fill)
+        .height(Length::Fill);
+
+    column![page_header("Reconciliation"), content].into()
+}
+
+fn describe(line: &StatementLine, money: &MoneyFormat) -> String {
+    format!(
+        "{} · {} · {}",
+        line.date.format("%-d %b %Y"),
+        line.description,
+        money.format(line.amount)
+    )
+}
+
+fn section_title<'a>(title: String) -> iced::widget::Text<'a> {
+    text(title).size(14).font(Font {
+        weight: font::Weight::Medium,
+        ..Default::default()
+    })
+}
+
+fn coloured_text<'a>(message: String, color: Color) -> iced::widget::Text<'a> {
+    text(message)
+        .size(12)
+        .style(move |_theme: &Theme| text::Style { color: Some(color) })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::fixtures::date;
+
+    fn payment(student_id: &str, paid_on: NaiveDate, amount: f32) -> Payment {
+        Payment {
+            student_id: student_id.to_string(),
+            paid_on,
+            amount,
+            invoice_number: None,
+        }
+    }
+
+    #[test]
+    fn parses_statements_with_amount_or_credit_columns() {
+        let momo = "Date,Details,Amount\n\
+                    03/02/2026,\"Transfer from Jane, M.\",\"GHS 1,200.00\"\n\
+                    04/02/2026,Airtime,-10\n";
+        assert_eq!(
+            parse_statement(momo).unwrap(),
+            vec![
+                StatementLine {
+                    date: date(2026, 2, 3),
+                    description: "Transfer from Jane, M.".to_string(),
+                    amount: 1200.0,
+                },
+                StatementLine {
+                    date: date(2026, 2, 4),
+                    description: "Airtime".to_string(),
+                    amount: -10.0,
+                },
+            ]
+        );
+
+        let bank = "Transaction Date,Narration,Debit,Credit\n2026-02-05,Rent,500,\n";
+        assert_eq!(parse_statement(bank).unwrap()[0].amount, -500.0);
+
+        assert_eq!(
+            parse_statement("Narration,Amount\nRent,5\n"),
+            Err(StatementError::MissingColumn("date"))
+        );
+    }
+
+    #[test]
+    fn matches_payments_by_amount_near_the_statement_date() {
+        let line = |day: u32, amount: f32| StatementLine {
+            date: date(2026, 2, day),
+            description: String::new(),
+            amount,
+        };
+        let lines = vec![
+            line(3, 150.0),
+            line(4, 150.0),
+            line(10, 600.0),
+            line(11, -20.0),
+        ];
+        let payments = vec![
+            payment("mary-jane", date(2026, 2, 4), 150.0),
+            payment("peter-parker", date(2026, 2, 1), 600.0),
+        ];
+
+        let reconciled = reconcile(&lines, &payments);
+        let matched: Vec<_> = reconciled
+            .iter()
+            .map(|reconciled| reconciled.payment.map(|p| p.student_id.as_str()))
+            .collect();
+
+        // The one 150 payment goes to the first line; the 600 is too far off.
+        assert_eq!(matched, vec![Some("mary-jane"), None, None]);
+    }
+}
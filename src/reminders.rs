@@ -0,0 +1,525 @@
+use chrono::{DateTime, Duration, Local, NaiveDate};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+use crate::domain::{DomainError, Student, is_active_on, scheduled_sessions_on, write_replacing};
+
+/// Kept beside the database, so sent reminders are not sent again after a
+/// restart.
+const REMINDER_LOG_FILE: &str = "reminders.json";
+
+/// How far ahead of a session its reminder goes out, unless configured.
+pub const DEFAULT_LEAD_HOURS: i64 = 24;
+const MAX_ATTEMPTS: u32 = 3;
+/// Failed reminders wait this long times the attempts so far before retrying.
+const RETRY_BACKOFF_MINUTES: i64 = 15;
+/// How long snoozing holds a reminder back.
+pub const SNOOZE_HOURS: i64 = 6;
+
+#[derive(Debug, Clone)]
+pub enum ReminderError {
+    SendFailed(String),
+}
+
+impl std::fmt::Display for ReminderError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ReminderError::SendFailed(reason) => write!(f, "Reminder not sent: {}", reason),
+        }
+    }
+}
+
+/// Whether a student asked for session reminders, which go to the email
+/// in their details.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ReminderContact {
+    pub opted_in: bool,
+    /// No reminders for sessions up to and including this day, e.g. over
+    /// an exam break.
+    pub muted_until: Option<NaiveDate>,
+}
+
+impl ReminderContact {
+    fn is_muted_for(&self, session_at: DateTime<Local>) -> bool {
+        self.muted_until
+            .is_some_and(|muted_until| session_at.date_naive() <= muted_until)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Reminder {
+    pub student_id: String,
+    pub student_name: String,
+    pub email: String,
+    pub session_at: DateTime<Local>,
+}
+
+impl Reminder {
+    pub fn subject(&self) -> String {
+        format!(
+            "Reminder: tutoring session {}",
+            self.session_at.format("%a %d %b, %-I:%M %p")
+        )
+    }
+
+    pub fn body(&self) -> String {
+        format!(
+            "Hello,\n\nThis is a reminder that {} has a tutoring session on {}.\n\nSee you then!",
+            self.student_name,
+            self.session_at.format("%A %d %B at %-I:%M %p")
+        )
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum DeliveryStatus {
+    /// Saved as an `.eml` file in the outbox. The tutor still has to send
+    /// it from their mail app.
+    #[serde(alias = "Sent")]
+    WrittenToOutbox,
+    Failed(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReminderLogEntry {
+    pub student_id: String,
+    pub student_name: String,
+    pub session_at: DateTime<Local>,
+    pub attempts: u32,
+    pub last_attempt: DateTime<Local>,
+    pub status: DeliveryStatus,
+}
+
+impl ReminderLogEntry {
+    fn can_retry(&self, now: DateTime<Local>) -> bool {
+        match self.status {
+            DeliveryStatus::WrittenToOutbox => false,
+            DeliveryStatus::Failed(_) => {
+                let backoff = Duration::minutes(RETRY_BACKOFF_MINUTES * self.attempts as i64);
+                self.attempts < MAX_ATTEMPTS && now >= self.last_attempt + backoff
+            }
+        }
+    }
+}
+
+/// Reminder opt-ins per student id, plus a log of every reminder sent or
+/// tried, which is what keeps a session from being reminded twice.
+#[derive(Debug, Clone)]
+pub struct ReminderBook {
+    lead: Duration,
+    contacts: HashMap<String, ReminderContact>,
+    log: Vec<ReminderLogEntry>,
+    /// Held-back reminders by student id and session, with when they may go.
+    snoozed: HashMap<(String, DateTime<Local>), DateTime<Local>>,
+}
+
+impl Default for ReminderBook {
+    fn default() -> Self {
+        Self {
+            lead: Duration::hours(DEFAULT_LEAD_HOURS),
+            contacts: HashMap::new(),
+            log: Vec::new(),
+            snoozed: HashMap::new(),
+        }
+    }
+}
+
+impl ReminderBook {
+    pub fn set_lead_hours(&mut self, hours: i64) {
+        self.lead = Duration::hours(hours);
+    }
+
+    pub fn lead_hours(&self) -> i64 {
+        self.lead.num_hours()
+    }
+
+    /// When the reminder goes out unless snoozed.
+    pub fn send_at(&self, reminder: &Reminder) -> DateTime<Local> {
+        reminder.session_at - self.lead
+    }
+
+    pub fn contact(&self, student_id: &str) -> ReminderContact {
+        self.contacts.get(student_id).cloned().unwrap_or_default()
+    }
+
+    pub fn set_contact(&mut self, student_id: &str, contact: ReminderContact) {
+        self.contacts.insert(student_id.to_string(), contact);
+    }
+
+    /// Mutes each student in `mutes` up to their day and unmutes the rest,
+    /// e.g. as read from the config.
+    pub fn set_mutes(&mut self, mutes: &HashMap<String, NaiveDate>) {
+        for (student_id, contact) in &mut self.contacts {
+            contact.muted_until = mutes.get(student_id).copied();
+        }
+        for (student_id, muted_until) in mutes {
+            self.contacts
+                .entry(student_id.clone())
+                .or_default()
+                .muted_until = Some(*muted_until);
+        }
+    }
+
+    /// Opts in each student in `opted_in` and out the rest, e.g. as read
+    /// from the config.
+    pub fn set_opt_ins(&mut self, opted_in: &HashSet<String>) {
+        for (student_id, contact) in &mut self.contacts {
+            contact.opted_in = opted_in.contains(student_id);
+        }
+        for student_id in opted_in {
+            self.contacts
+                .entry(student_id.clone())
+                .or_default()
+                .opted_in = true;
+        }
+    }
+
+    pub fn set_snoozed(&mut self, snoozed: HashMap<(String, DateTime<Local>), DateTime<Local>>) {
+        self.snoozed = snoozed;
+    }
+
+    /// Most recent attempts first.
+    pub fn log(&self) -> impl DoubleEndedIterator<Item = &ReminderLogEntry> {
+        self.log.iter().rev()
+    }
+
+    /// Replaces the log with one read back from disk.
+    pub fn set_log(&mut self, log: Vec<ReminderLogEntry>) {
+        self.log = log;
+    }
+
+    /// Reminders for sessions starting within the lead time that have not
+    /// been sent yet, including failed ones whose retry is due. Muted
+    /// students and snoozed reminders are left out.
+    pub fn due(&self, students: &[Student], now: DateTime<Local>) -> Vec<Reminder> {
+        self.upcoming(students, now, now + self.lead)
+            .into_iter()
+            .filter(|reminder| {
+                self.snoozed_until(reminder)
+                    .is_none_or(|snoozed_until| now >= snoozed_until)
+            })
+            .filter(|reminder| {
+                self.entry_for(reminder)
+                    .is_none_or(|entry| entry.can_retry(now))
+            })
+            .collect()
+    }
+
+    /// Reminders still to go out for sessions starting after `now` and no
+    /// later than `until`, snoozed ones included.
+    pub fn upcoming(
+        &self,
+        students: &[Student],
+        now: DateTime<Local>,
+        until: DateTime<Local>,
+    ) -> Vec<Reminder> {
+        students
+            .iter()
+            .filter_map(|student| {
+                let contact = self.contacts.get(&student.id)?;
+                let email = student.email.as_deref().map(str::trim)?;
+                (contact.opted_in && !email.is_empty()).then_some((student, contact, email))
+            })
+            .flat_map(|(student, contact, email)| {
+                sessions_between(student, now, until)
+                    .into_iter()
+                    .filter(|session_at| is_active_on(student, session_at.date_naive()))
+                    .filter(|session_at| !contact.is_muted_for(*session_at))
+                    .map(move |session_at| Reminder {
+                        student_id: student.id.clone(),
+                        student_name: student.name.full_name(),
+                        email: email.to_string(),
+                        session_at,
+                    })
+            })
+            .filter(|reminder| {
+                self.entry_for(reminder)
+                    .is_none_or(|entry| entry.status != DeliveryStatus::WrittenToOutbox)
+            })
+            .collect()
+    }
+
+    /// Holds the reminder back for `SNOOZE_HOURS` from when it would go
+    /// out, or from `now` if that has passed.
+    pub fn snooze(&mut self, reminder: &Reminder, now: DateTime<Local>) {
+        let from = self
+            .snoozed_until(reminder)
+            .unwrap_or(self.send_at(reminder));
+        self.snoozed.insert(
+            (reminder.student_id.clone(), reminder.session_at),
+            from.max(now) + Duration::hours(SNOOZE_HOURS),
+        );
+    }
+
+    pub fn snoozed_until(&self, reminder: &Reminder) -> Option<DateTime<Local>> {
+        self.snoozed
+            .get(&(reminder.student_id.clone(), reminder.session_at))
+            .copied()
+    }
+
+    pub fn record(
+        &mut self,
+        reminder: &Reminder,
+        result: Result<(), ReminderError>,
+        now: DateTime<Local>,
+    ) {
+        let status = match result {
+            Ok(()) => DeliveryStatus::WrittenToOutbox,
+            Err(e) => DeliveryStatus::Failed(e.to_string()),
+        };
+
+        let index = self.log.iter().position(|entry| {
+            entry.student_id == reminder.student_id && entry.session_at == reminder.session_at
+        });
+
+        match index {
+            Some(index) => {
+                let mut entry = self.log.remove(index);
+                entry.attempts += 1;
+                entry.last_attempt = now;
+                entry.status = status;
+                self.log.push(entry);
+            }
+            None => self.log.push(ReminderLogEntry {
+                student_id: reminder.student_id.clone(),
+                student_name: reminder.student_name.clone(),
+                session_at: reminder.session_at,
+                attempts: 1,
+                last_attempt: now,
+                status,
+            }),
+        }
+    }
+
+    fn entry_for(&self, reminder: &Reminder) -> Option<&ReminderLogEntry> {
+        self.log.iter().find(|entry| {
+            entry.student_id == reminder.student_id && entry.session_at == reminder.session_at
+        })
+    }
+}
+
+/// Tabled sessions starting after `from` and no later than `until`.
+fn sessions_between(
+    student: &Student,
+    from: DateTime<Local>,
+    until: DateTime<Local>,
+) -> Vec<DateTime<Local>> {
+    from.date_naive()
+        .iter_days()
+        .take_while(|date| *date <= until.date_naive())
+        .flat_map(|date| scheduled_sessions_on(student, date))
+        .filter(|session_at| *session_at > from && *session_at <= until)
+        .collect()
+}
+
+/// Where the reminder log goes for the domain saved at `database`.
+pub fn log_path(database: &Path) -> PathBuf {
+    database.with_file_name(REMINDER_LOG_FILE)
+}
+
+/// Oldest first. A missing file reads as an empty log.
+pub async fn load_log(path: PathBuf) -> Result<Vec<ReminderLogEntry>, DomainError> {
+    match std::fs::read_to_string(&path) {
+        Ok(json) => serde_json::from_str(&json).map_err(|e| DomainError::Corrupt(e.to_string())),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Vec::new()),
+        Err(e) => Err(DomainError::ReadFailed(e.to_string())),
+    }
+}
+
+pub async fn save_log(log: Vec<ReminderLogEntry>, path: PathBuf) -> Result<(), DomainError> {
+    let json =
+        serde_json::to_string_pretty(&log).map_err(|e| DomainError::WriteFailed(e.to_string()))?;
+
+    write_replacing(&path, &json).map_err(|e| DomainError::WriteFailed(e.to_string()))
+}
+
+pub trait Mailer {
+    fn send(&self, reminder: &Reminder) -> Result<(), ReminderError>;
+}
+
+/// Writes each reminder as an `.eml` file for the user to send from
+/// their own mail client. Nothing is emailed from the app itself.
+#[derive(Debug, Clone)]
+pub struct OutboxMailer {
+    pub dir: PathBuf,
+}
+
+impl Mailer for OutboxMailer {
+    fn send(&self, reminder: &Reminder) -> Result<(), ReminderError> {
+        let file_name = format!(
+            "{}-{}.eml",
+            reminder.session_at.format("%Y%m%d-%H%M"),
+            reminder.student_id
+        );
+        let message = format!(
+            "To: {}\r\nSubject: {}\r\nContent-Type: text/plain; charset=utf-8\r\n\r\n{}\r\n",
+            reminder.email,
+            reminder.subject(),
+            reminder.body()
+        );
+
+        std::fs::create_dir_all(&self.dir)
+            .and_then(|_| std::fs::write(self.dir.join(file_name), message))
+            .map_err(|e| ReminderError::SendFailed(e.to_string()))
+    }
+}
+
+pub async fn send_reminders(
+    mailer: impl Mailer,
+    reminders: Vec<Reminder>,
+) -> Vec<(Reminder, Result<(), ReminderError>)> {
+    reminders
+        .into_iter()
+        .map(|reminder| {
+            let result = mailer.send(&reminder);
+            (reminder, result)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::fixtures::tue_thu_student;
+    use chrono::TimeZone;
+    use iced::futures::executor::block_on;
+
+    fn at(day: u32, hour: u32, minute: u32) -> DateTime<Local> {
+        Local
+            .with_ymd_and_hms(2026, 2, day, hour, minute, 0)
+            .unwrap()
+    }
+
+    fn opted_in_book() -> ReminderBook {
+        let mut book = ReminderBook::default();
+        book.set_contact(
+            "test-student",
+            ReminderContact {
+                opted_in: true,
+                muted_until: None,
+            },
+        );
+        book
+    }
+
+    fn student_with_email() -> Student {
+        Student {
+            email: Some(" parent@example.com ".to_string()),
+            ..tue_thu_student(&[])
+        }
+    }
+
+    #[test]
+    fn reminds_opted_in_students_a_day_ahead() {
+        let students = vec![student_with_email()];
+        let book = opted_in_book();
+
+        // Monday 2 February, 6 PM: Tuesday's 5 PM session is within a day.
+        let due = book.due(&students, at(2, 18, 0));
+        assert_eq!(due.len(), 1);
+        assert_eq!(due[0].session_at, at(3, 17, 0));
+
+        // Monday 2 February, 4 PM: still more than a day away.
+        assert!(book.due(&students, at(2, 16, 0)).is_empty());
+        assert!(
+            ReminderBook::default()
+                .due(&students, at(2, 18, 0))
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn failed_reminders_retry_with_backoff_until_the_limit() {
+        let students = vec![student_with_email()];
+        let mut book = opted_in_book();
+        let failure = || Err(ReminderError::SendFailed("offline".to_string()));
+
+        let reminder = book.due(&students, at(2, 18, 0)).remove(0);
+        book.record(&reminder, failure(), at(2, 18, 0));
+
+        assert!(book.due(&students, at(2, 18, 10)).is_empty());
+        assert_eq!(book.due(&students, at(2, 18, 15)), vec![reminder.clone()]);
+
+        book.record(&reminder, failure(), at(2, 18, 15));
+        book.record(&reminder, failure(), at(2, 18, 45));
+        assert!(book.due(&students, at(2, 23, 0)).is_empty());
+        assert_eq!(book.log().count(), 1);
+        assert_eq!(book.log().next().unwrap().attempts, MAX_ATTEMPTS);
+    }
+
+    #[test]
+    fn sent_reminders_are_not_repeated() {
+        let students = vec![student_with_email()];
+        let mut book = opted_in_book();
+
+        let reminder = book.due(&students, at(2, 18, 0)).remove(0);
+        book.record(&reminder, Ok(()), at(2, 18, 0));
+
+        assert!(book.due(&students, at(2, 20, 0)).is_empty());
+    }
+
+    #[test]
+    fn snoozed_reminders_wait_and_muted_students_are_skipped() {
+        let students = vec![student_with_email()];
+        let mut book = opted_in_book();
+
+        let reminder = book.due(&students, at(2, 18, 0)).remove(0);
+        book.snooze(&reminder, at(2, 18, 0));
+        assert_eq!(book.snoozed_until(&reminder), Some(at(3, 0, 0)));
+        assert!(book.due(&students, at(2, 23, 0)).is_empty());
+        assert_eq!(book.due(&students, at(3, 0, 0)), vec![reminder]);
+
+        let mut contact = book.contact("test-student");
+        contact.muted_until = Some(at(5, 0, 0).date_naive());
+        book.set_contact("test-student", contact);
+        assert!(book.due(&students, at(3, 0, 0)).is_empty());
+        assert!(
+            book.upcoming(&students, at(2, 18, 0), at(6, 18, 0))
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn reminders_go_to_the_students_email_while_enrolled() {
+        let book = opted_in_book();
+
+        let due = book.due(&[student_with_email()], at(2, 18, 0));
+        assert_eq!(due[0].email, "parent@example.com");
+        assert!(book.due(&[tue_thu_student(&[])], at(2, 18, 0)).is_empty());
+
+        let finished = Student {
+            tution_end_date: Some(at(3, 0, 0)),
+            ..student_with_email()
+        };
+        assert!(
+            book.upcoming(&[finished], at(2, 18, 0), at(6, 18, 0))
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn written_reminders_stay_written_after_the_log_is_saved_and_read_back() {
+        let students = vec![student_with_email()];
+        let mut book = opted_in_book();
+        let reminder = book.due(&students, at(2, 18, 0)).remove(0);
+        book.record(&reminder, Ok(()), at(2, 18, 0));
+
+        let dir = std::env::temp_dir().join(format!("tutor-mgr-reminders-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = log_path(&dir.join("tutor-mgr.db"));
+        let log = book.log().rev().cloned().collect();
+        block_on(save_log(log, path.clone())).unwrap();
+
+        let mut reloaded = opted_in_book();
+        reloaded.set_log(block_on(load_log(path)).unwrap());
+        assert!(reloaded.due(&students, at(2, 20, 0)).is_empty());
+        assert_eq!(reloaded.log().count(), 1);
+    }
+
+    #[test]
+    fn logs_from_before_the_outbox_rename_still_read() {
+        let status: DeliveryStatus = serde_json::from_str("\"Sent\"").unwrap();
+        assert_eq!(status, DeliveryStatus::WrittenToOutbox);
+    }
+}
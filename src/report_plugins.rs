@@ -0,0 +1,307 @@
+use serde_json::Value;
+use std::path::{Path, PathBuf};
+use toml_edit::{DocumentMut, Item};
+
+/// Lists in the exported data a report can run over.
+const SOURCES: [&str; 3] = ["students", "payments", "invoices"];
+
+#[derive(Debug, Clone)]
+pub enum PluginError {
+    ReadFailed(String),
+    Invalid { file: String, reason: String },
+}
+
+impl std::fmt::Display for PluginError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PluginError::ReadFailed(reason) => {
+                write!(f, "Could not read the custom reports: {}", reason)
+            }
+            PluginError::Invalid { file, reason } => write!(f, "{} is not valid: {}", file, reason),
+        }
+    }
+}
+
+/// A report someone wrote for themselves, kept as a `.toml` file in the
+/// plugins folder. It runs over the same JSON as a data export:
+///
+/// ```toml
+/// title = "Payments this month"
+/// source = "payments"        # "students", "payments" or "invoices"
+/// month_field = "paid_on"    # keeps rows dated in the month shown
+/// row = "{paid_on}  {student_id}  {amount}"
+/// header = "Payments · {month}"
+/// footer = "{count} payments, {sum:amount} in total"
+///
+/// [where]
+/// student_id = "mary-jane"
+/// ```
+///
+/// Only `title`, `source` and `row` are needed. Rows fill `{field}` from
+/// the item, reaching into objects with dots like `{name.first}`. The
+/// header and footer take `{title}`, `{month}`, `{count}` and
+/// `{sum:field}`. Unknown fields are left as written.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReportPlugin {
+    pub file_name: String,
+    pub title: String,
+    pub source: String,
+    pub month_field: Option<String>,
+    /// Fields that must equal the given text for a row to be kept.
+    pub filters: Vec<(String, String)>,
+    pub header: Option<String>,
+    pub row: String,
+    pub footer: Option<String>,
+}
+
+impl ReportPlugin {
+    pub fn parse(file_name: &str, toml: &str) -> Result<Self, PluginError> {
+        let invalid = |reason: String| PluginError::Invalid {
+            file: file_name.to_string(),
+            reason,
+        };
+        let document: DocumentMut = toml
+            .parse()
+            .map_err(|e: toml_edit::TomlError| invalid(e.message().to_string()))?;
+        let text = |key: &str| {
+            document
+                .get(key)
+                .map(|item: &Item| {
+                    item.as_str()
+                        .map(str::to_string)
+                        .ok_or_else(|| invalid(format!("{} should be text in quotes", key)))
+                })
+                .transpose()
+        };
+        let required = |key: &str| text(key)?.ok_or_else(|| invalid(format!("{} is missing", key)));
+
+        let source = required("source")?;
+        if !SOURCES.contains(&source.as_str()) {
+            return Err(invalid(
+                "source should be \"students\", \"payments\" or \"invoices\"".to_string(),
+            ));
+        }
+
+        let filters = match document.get("where") {
+            None => Vec::new(),
+            Some(item) => item
+                .as_table_like()
+                .ok_or_else(|| invalid("where should be a table".to_string()))?
+                .iter()
+                .map(|(field, value)| {
+                    value
+                        .as_str()
+                        .map(|value| (field.to_string(), value.to_string()))
+                        .ok_or_else(|| invalid(format!("where.{} should be text in quotes", field)))
+                })
+                .collect::<Result<_, _>>()?,
+        };
+
+        Ok(Self {
+            file_name: file_name.to_string(),
+            title: required("title")?,
+            source,
+            month_field: text("month_field")?,
+            filters,
+            header: text("header")?,
+            row: required("row")?,
+            footer: text("footer")?,
+        })
+    }
+
+    /// The report's lines over `data`, an export as JSON, for the month
+    /// shown on the Reports screen.
+    pub fn render(&self, data: &Value, year: i32, month: u32) -> Vec<String> {
+        let month_prefix = format!("{}-{:02}", year, month);
+        let rows: Vec<&Value> = data
+            .get(&self.source)
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+            .filter(|item| {
+                self.month_field.as_ref().is_none_or(|field| {
+                    lookup(item, field)
+                        .and_then(Value::as_str)
+                        .is_some_and(|date| date.starts_with(&month_prefix))
+                })
+            })
+            .filter(|item| {
+                self.filters.iter().all(|(field, expected)| {
+                    lookup(item, field).is_some_and(|value| display(value) == *expected)
+                })
+            })
+            .collect();
+
+        let month_title = chrono::NaiveDate::from_ymd_opt(year, month, 1)
+            .map(|date| date.format("%B %Y").to_string())
+            .unwrap_or_default();
+        let summary = |name: &str| match name {
+            "title" => Some(self.title.clone()),
+            "month" => Some(month_title.clone()),
+            "count" => Some(rows.len().to_string()),
+            _ => name.strip_prefix("sum:").map(|field| {
+                number(
+                    rows.iter()
+                        .filter_map(|item| lookup(item, field).and_then(Value::as_f64))
+                        .sum(),
+                )
+            }),
+        };
+
+        let mut lines = Vec::new();
+        lines.extend(self.header.as_deref().map(|header| fill(header, summary)));
+        lines.extend(
+            rows.iter()
+                .map(|item| fill(&self.row, |field| lookup(item, field).map(display))),
+        );
+        lines.extend(self.footer.as_deref().map(|footer| fill(footer, summary)));
+
+        lines
+    }
+}
+
+fn lookup<'a>(item: &'a Value, path: &str) -> Option<&'a Value> {
+    path.split('.').try_fold(item, |value, key| value.get(key))
+}
+
+fn display(value: &Value) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::Bool(flag) => if *flag { "yes" } else { "no" }.to_string(),
+        Value::Number(n) => n.as_f64().map(number).unwrap_or_else(|| n.to_string()),
+        Value::String(s) => s.clone(),
+        Value::Array(items) => items.iter().map(display).collect::<Vec<_>>().join(", "),
+        Value::Object(_) => value.to_string(),
+    }
+}
+
+fn number(n: f64) -> String {
+    if n.fract() == 0.0 {
+        format!("{}", n as i64)
+    } else {
+        format!("{:.2}", n)
+    }
+}
+
+/// Replaces each `{name}` in `template` with `value(name)`.
+fn fill(template: &str, value: impl Fn(&str) -> Option<String>) -> String {
+    let mut filled = String::new();
+    let mut rest = template;
+
+    while let Some(start) = rest.find('{') {
+        let Some(length) = rest[start..].find('}') else {
+            break;
+        };
+        let name = &rest[start + 1..start + length];
+        filled.push_str(&rest[..start]);
+        match value(name) {
+            Some(value) => filled.push_str(&value),
+            None => filled.push_str(&rest[start..=start + length]),
+        }
+        rest = &rest[start + length + 1..];
+    }
+    filled.push_str(rest);
+
+    filled
+}
+
+/// Every `.toml` file in `dir`, in name order. A missing folder simply has
+/// no reports, and a broken file does not hide the others.
+pub fn load_report_plugins(dir: &Path) -> Vec<Result<ReportPlugin, PluginError>> {
+    let entries = match std::fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Vec::new(),
+        Err(e) => return vec![Err(PluginError::ReadFailed(e.to_string()))],
+    };
+
+    let mut paths: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| {
+            path.extension()
+                .is_some_and(|extension| extension == "toml")
+        })
+        .collect();
+    paths.sort();
+
+    paths
+        .iter()
+        .map(|path| {
+            let file_name = path
+                .file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+                .unwrap_or_default();
+            std::fs::read_to_string(path)
+                .map_err(|e| PluginError::ReadFailed(e.to_string()))
+                .and_then(|toml| ReportPlugin::parse(&file_name, &toml))
+        })
+        .collect()
+}
+
+pub async fn read_report_plugins(dir: PathBuf) -> Vec<Result<ReportPlugin, PluginError>> {
+    load_report_plugins(&dir)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::billing::{Ledger, Payment};
+    use crate::domain::fixtures::{date, sample_domain};
+    use crate::interchange::Interchange;
+
+    #[test]
+    fn plugins_run_over_the_exported_data() {
+        let domain = sample_domain();
+        let student_id = domain.students[0].id.clone();
+        let mut ledger = Ledger::default();
+        for (paid_on, amount) in [(date(2026, 1, 30), 100.0), (date(2026, 2, 10), 150.5)] {
+            ledger.record_payment(Payment {
+                student_id: student_id.clone(),
+                paid_on,
+                amount,
+                invoice_number: None,
+            });
+        }
+        let data = serde_json::to_value(Interchange::new(&domain, &ledger)).unwrap();
+
+        let plugin = ReportPlugin::parse(
+            "payments.toml",
+            &format!(
+                "title = \"Payments\"\n\
+                 source = \"payments\"\n\
+                 month_field = \"paid_on\"\n\
+                 header = \"{{title}} · {{month}}\"\n\
+                 row = \"{{paid_on}} {{amount}} {{unknown}}\"\n\
+                 footer = \"{{count}} paid, {{sum:amount}} in total\"\n\
+                 [where]\n\
+                 student_id = \"{}\"\n",
+                student_id
+            ),
+        )
+        .unwrap();
+
+        assert_eq!(
+            plugin.render(&data, 2026, 2),
+            [
+                "Payments · February 2026",
+                "2026-02-10 150.50 {unknown}",
+                "1 paid, 150.50 in total",
+            ]
+        );
+
+        for invalid in [
+            "title = \"No source\"\nrow = \"{id}\"",
+            "title = \"T\"\nsource = \"expenses\"\nrow = \"{id}\"",
+            "title = \"T\"\nsource = \"students\"",
+            "title = \"T\"\nsource = \"students\"\nrow = \"{id}\"\nwhere = 3",
+        ] {
+            assert!(
+                matches!(
+                    ReportPlugin::parse("x.toml", invalid),
+                    Err(PluginError::Invalid { .. })
+                ),
+                "{}",
+                invalid
+            );
+        }
+    }
+}
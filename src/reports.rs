@@ -0,0 +1,894 @@
+use chrono::{DateTime, Datelike, Local, NaiveDate};
+use iced::advanced::graphics::core::font;
+use iced::widget::{Column, button, column, combo_box, pick_list, row, text};
+use iced::{Center, Element, Font, Length, Task};
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::rc::Rc;
+
+use crate::billing::{ChargeReason, Ledger};
+use crate::domain::{
+    Domain, Location, PersonalName, Student, TutorSubject, completed_sessions_in,
+    compute_monthly_completed_sessions, compute_monthly_sum, monthly_surcharges, next_month,
+    previous_month, session_distance, session_location, session_minutes,
+};
+use crate::export::{ExportError, csv_line, text_pdf, write_export};
+use crate::interchange::Interchange;
+use crate::money::MoneyFormat;
+use crate::report_plugins::{PluginError, ReportPlugin, read_report_plugins};
+use crate::ui_components::{global_content_container, page_header, searchable_pick_list};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EntryKind {
+    Income,
+    Expense,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct StatementEntry {
+    pub label: String,
+    pub kind: EntryKind,
+    pub amount: f32,
+}
+
+/// Profit and loss for one month. Income is what was earned in the month,
+/// whether or not it has been paid yet.
+#[derive(Debug, Clone, PartialEq)]
+pub struct IncomeStatement {
+    pub year: i32,
+    pub month: u32,
+    pub entries: Vec<StatementEntry>,
+}
+
+impl IncomeStatement {
+    /// Commissions, discounts, refunds and expenses are not recorded
+    /// anywhere yet, so only earned income shows until they are.
+    pub fn compute(
+        students: &[Student],
+        locations: &[Location],
+        ledger: &Ledger,
+        year: i32,
+        month: u32,
+    ) -> Self {
+        let session_fees = students
+            .iter()
+            .map(|student| {
+                compute_monthly_sum(student, month, year, compute_monthly_completed_sessions)
+            })
+            .fold(0.0, |total, amount| total + amount);
+        let surcharges = students
+            .iter()
+            .map(|student| monthly_surcharges(student, locations, month, year))
+            .fold(0.0, |total, amount| total + amount);
+        let no_show_fees = ledger
+            .charges_in(month, year)
+            .filter(|charge| charge.reason == ChargeReason::NoShow)
+            .fold(0.0, |total, charge| total + charge.amount);
+
+        Self {
+            year,
+            month,
+            entries: vec![
+                StatementEntry {
+                    label: "Session fees".to_string(),
+                    kind: EntryKind::Income,
+                    amount: session_fees,
+                },
+                StatementEntry {
+                    label: "Location surcharges".to_string(),
+                    kind: EntryKind::Income,
+                    amount: surcharges,
+                },
+                StatementEntry {
+                    label: ChargeReason::NoShow.to_string(),
+                    kind: EntryKind::Income,
+                    amount: no_show_fees,
+                },
+            ],
+        }
+    }
+
+    pub fn total(&self, kind: EntryKind) -> f32 {
+        self.entries
+            .iter()
+            .filter(|entry| entry.kind == kind)
+            .fold(0.0, |total, entry| total + entry.amount)
+    }
+
+    pub fn net(&self) -> f32 {
+        self.total(EntryKind::Income) - self.total(EntryKind::Expense)
+    }
+
+    fn title(&self) -> String {
+        NaiveDate::from_ymd_opt(self.year, self.month, 1)
+            .map(|date| date.format("%B %Y").to_string())
+            .unwrap_or_default()
+    }
+
+    pub fn to_csv(&self, money: &MoneyFormat) -> String {
+        let amount = |value: f32| money.plain(value);
+        let mut lines = vec![csv_line(["Item", "Type", "Amount"])];
+
+        for entry in &self.entries {
+            let kind = match entry.kind {
+                EntryKind::Income => "Income",
+                EntryKind::Expense => "Expense",
+            };
+            lines.push(csv_line([
+                entry.label.as_str(),
+                kind,
+                &amount(entry.amount),
+            ]));
+        }
+        lines.push(csv_line(["Net income", "", &amount(self.net())]));
+
+        lines.join("\n") + "\n"
+    }
+}
+
+/// How teaching hours are grouped. Terms split the calendar year into
+/// thirds: January to April, May to August and September to December.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum HoursPeriod {
+    #[default]
+    Month,
+    Term,
+}
+
+impl HoursPeriod {
+    const ALL: [HoursPeriod; 2] = [HoursPeriod::Month, HoursPeriod::Term];
+
+    fn index_of(&self, month: u32) -> u32 {
+        match self {
+            HoursPeriod::Month => month,
+            HoursPeriod::Term => (month - 1) / 4 + 1,
+        }
+    }
+
+    fn label(&self, year: i32, index: u32) -> String {
+        match self {
+            HoursPeriod::Month => NaiveDate::from_ymd_opt(year, index, 1)
+                .map(|date| date.format("%B").to_string())
+                .unwrap_or_default(),
+            HoursPeriod::Term => format!("Term {}", index),
+        }
+    }
+}
+
+impl std::fmt::Display for HoursPeriod {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            HoursPeriod::Month => write!(f, "By month"),
+            HoursPeriod::Term => write!(f, "By term"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TeachingHoursRow {
+    pub period: String,
+    pub subject: TutorSubject,
+    pub sessions: usize,
+    pub minutes: u32,
+}
+
+impl TeachingHoursRow {
+    pub fn hours(&self) -> f32 {
+        self.minutes as f32 / 60.0
+    }
+}
+
+/// Attended sessions in a year, totalled per period and subject, with
+/// each session as long as the tabled slot it was held in.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TeachingHoursReport {
+    pub year: i32,
+    pub period: HoursPeriod,
+    pub rows: Vec<TeachingHoursRow>,
+}
+
+impl TeachingHoursReport {
+    pub fn compute(students: &[Student], year: i32, period: HoursPeriod) -> Self {
+        let mut totals: BTreeMap<(u32, String), TeachingHoursRow> = BTreeMap::new();
+
+        for student in students {
+            for at in student.held_sessions().filter(|at| at.year() == year) {
+                let index = period.index_of(at.month());
+                let subject = student.subject_at(at);
+                let row = totals
+                    .entry((index, subject.to_string()))
+                    .or_insert_with(|| TeachingHoursRow {
+                        period: period.label(year, index),
+                        subject: subject.clone(),
+                        sessions: 0,
+                        minutes: 0,
+                    });
+                row.sessions += 1;
+                row.minutes += session_minutes(student, at);
+            }
+        }
+
+        Self {
+            year,
+            period,
+            rows: totals.into_values().collect(),
+        }
+    }
+
+    pub fn total_hours(&self) -> f32 {
+        self.rows.iter().map(|row| row.minutes).sum::<u32>() as f32 / 60.0
+    }
+
+    /// The report as printable lines ending in a signature block, for
+    /// certification or visa paperwork.
+    pub fn to_lines(&self, tutor: &PersonalName) -> Vec<String> {
+        let mut lines = vec![
+            format!("Teaching hours record - {}", self.year),
+            format!("Tutor: {}", tutor.full_name()),
+            String::new(),
+            format!(
+                "{:<12} {:<24} {:>8} {:>8}",
+                "Period", "Subject", "Sessions", "Hours"
+            ),
+        ];
+
+        for row in &self.rows {
+            lines.push(format!(
+                "{:<12} {:<24} {:>8} {:>8.1}",
+                row.period,
+                row.subject.to_string(),
+                row.sessions,
+                row.hours()
+            ));
+        }
+
+        let sessions: usize = self.rows.iter().map(|row| row.sessions).sum();
+        lines.extend([
+            format!(
+                "{:<12} {:<24} {:>8} {:>8.1}",
+                "Total",
+                "",
+                sessions,
+                self.total_hours()
+            ),
+            String::new(),
+            "I confirm that the hours above are a true record of my teaching.".to_string(),
+            String::new(),
+            String::new(),
+            "Signature: ______________________   Date: ______________".to_string(),
+            tutor.full_name(),
+        ]);
+
+        lines
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct MileageRow {
+    pub at: DateTime<Local>,
+    pub student: String,
+    pub location: String,
+    pub km: f32,
+}
+
+/// Travel to home visits held in one month, for expense claims and tax.
+/// Visits with no distance logged show 0 km so they stand out.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MileageReport {
+    pub year: i32,
+    pub month: u32,
+    pub rows: Vec<MileageRow>,
+}
+
+impl MileageReport {
+    pub fn compute(students: &[Student], locations: &[Location], year: i32, month: u32) -> Self {
+        let mut rows: Vec<MileageRow> = students
+            .iter()
+            .flat_map(|student| {
+                completed_sessions_in(student, month, year)
+                    .into_iter()
+                    .filter_map(move |at| {
+                        let km = session_distance(student, locations, at)?;
+                        Some(MileageRow {
+                            at,
+                            student: student.name.full_name(),
+                            location: session_location(student, at)
+                                .unwrap_or_default()
+                                .to_string(),
+                            km,
+                        })
+                    })
+            })
+            .collect();
+        rows.sort_by_key(|row| row.at);
+
+        Self { year, month, rows }
+    }
+
+    pub fn total_km(&self) -> f32 {
+        self.rows.iter().map(|row| row.km).sum()
+    }
+
+    pub fn to_csv(&self) -> String {
+        let mut lines = vec![csv_line(["Date", "Student", "Location", "Km"])];
+
+        for row in &self.rows {
+            lines.push(csv_line([
+                row.at.format("%Y-%m-%d").to_string().as_str(),
+                &row.student,
+                &row.location,
+                &format!("{:.1}", row.km),
+            ]));
+        }
+        lines.push(csv_line([
+            "Total",
+            "",
+            "",
+            &format!("{:.1}", self.total_km()),
+        ]));
+
+        lines.join("\n") + "\n"
+    }
+}
+
+pub struct ReportsState {
+    domain: Option<Rc<Domain>>,
+    year: i32,
+    month: u32,
+    hours_period: HoursPeriod,
+    status_message: Option<String>,
+    plugin_dir: Option<PathBuf>,
+    plugins: Vec<ReportPlugin>,
+    /// Files in the plugins folder that could not be read.
+    plugin_errors: Vec<String>,
+    selected_plugin: Option<usize>,
+    plugin_options: combo_box::State<PluginChoice>,
+}
+
+/// A custom report as offered in the report picker.
+#[derive(Debug, Clone, PartialEq)]
+struct PluginChoice {
+    index: usize,
+    title: String,
+}
+
+impl std::fmt::Display for PluginChoice {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.title)
+    }
+}
+
+impl Default for ReportsState {
+    fn default() -> Self {
+        Self {
+            domain: None,
+            year: 1970,
+            month: 1,
+            hours_period: HoursPeriod::default(),
+            status_message: None,
+            plugin_dir: None,
+            plugins: Vec::new(),
+            plugin_errors: Vec::new(),
+            selected_plugin: None,
+            plugin_options: combo_box::State::default(),
+        }
+    }
+}
+
+impl ReportsState {
+    pub fn attach_domain(&mut self, domain: Rc<Domain>) {
+        let today = domain.clock.today();
+        (self.year, self.month) = (today.year(), today.month());
+        self.domain = Some(domain);
+    }
+
+    /// Picks up a new revision of the domain, keeping the month shown.
+    pub fn refresh(&mut self, domain: Rc<Domain>) {
+        self.domain = Some(domain);
+    }
+
+    fn money(&self) -> MoneyFormat {
+        self.domain
+            .as_ref()
+            .map(|domain| domain.money_format)
+            .unwrap_or_default()
+    }
+
+    pub fn month(&self) -> (i32, u32) {
+        (self.year, self.month)
+    }
+
+    pub fn show_month(&mut self, year: i32, month: u32) {
+        (self.year, self.month) = (year, month);
+    }
+
+    /// Reads the custom reports kept in `dir`, again on every reload.
+    pub fn load_plugins(&mut self, dir: PathBuf) -> Task<Msg> {
+        self.plugin_dir = Some(dir.clone());
+        Task::perform(read_report_plugins(dir), Msg::PluginsLoaded)
+    }
+
+    /// The selected custom report over everything the app knows.
+    fn plugin_lines(&self, ledger: &Ledger) -> Option<(&ReportPlugin, Vec<String>)> {
+        let plugin = self.plugins.get(self.selected_plugin?)?;
+        let domain = self.domain.as_ref()?;
+        let data = serde_json::to_value(Interchange::new(domain, ledger)).ok()?;
+
+        Some((plugin, plugin.render(&data, self.year, self.month)))
+    }
+
+    fn income_statement(&self, ledger: &Ledger) -> IncomeStatement {
+        let (students, locations) = self
+            .domain
+            .as_ref()
+            .map(|domain| (domain.students.as_slice(), domain.locations.as_slice()))
+            .unwrap_or_default();
+
+        IncomeStatement::compute(students, locations, ledger, self.year, self.month)
+    }
+
+    fn mileage(&self) -> MileageReport {
+        let (students, locations) = self
+            .domain
+            .as_ref()
+            .map(|domain| (domain.students.as_slice(), domain.locations.as_slice()))
+            .unwrap_or_default();
+
+        MileageReport::compute(students, locations, self.year, self.month)
+    }
+
+    fn teaching_hours(&self) -> TeachingHoursReport {
+        let students = self
+            .domain
+            .as_ref()
+            .map(|domain| domain.students.as_slice())
+            .unwrap_or_default();
+
+        TeachingHoursReport::compute(students, self.year, self.hours_period)
+    }
+}
+
+#[derive(Clone, Debug)]
+pub enum Msg {
+    PreviousMonth,
+    NextMonth,
+    ExportIncomeStatement,
+    HoursPeriodSelected(HoursPeriod),
+    ExportTeachingHours,
+    ExportMileage,
+    Exported(Result<PathBuf, ExportError>),
+    ReloadPlugins,
+    PluginsLoaded(Vec<Result<ReportPlugin, PluginError>>),
+    PluginSelected(usize),
+    ExportPlugin,
+}
+
+/// Charges live in the dashboard's ledger, so it is passed in alongside.
+pub fn update(state: &mut ReportsState, msg: Msg, ledger: &Ledger) -> Task<Msg> {
+    match msg {
+        Msg::PreviousMonth => {
+            (state.year, state.month) = previous_month(state.year, state.month);
+            Task::none()
+        }
+        Msg::NextMonth => {
+            (state.year, state.month) = next_month(state.year, state.month);
+            Task::none()
+        }
+        Msg::ExportIncomeStatement => {
+            let statement = state.income_statement(ledger);
+            let file_name = format!(
+                "income-statement-{}-{:02}.csv",
+                statement.year, statement.month
+            );
+
+            Task::perform(
+                write_export(file_name, statement.to_csv(&state.money())),
+                Msg::Exported,
+            )
+        }
+        Msg::HoursPeriodSelected(period) => {
+            state.hours_period = period;
+            Task::none()
+        }
+        Msg::ExportTeachingHours => {
+            let Some(domain) = &state.domain else {
+                return Task::none();
+            };
+
+            let report = state.teaching_hours();
+            let pdf = text_pdf(&report.to_lines(&domain.tutor.name));
+            let file_name = format!("teaching-hours-{}.pdf", report.year);
+
+            Task::perform(write_export(file_name, pdf), Msg::Exported)
+        }
+        Msg::ReloadPlugins => match state.plugin_dir.clone() {
+            Some(dir) => state.load_plugins(dir),
+            None => Task::none(),
+        },
+        Msg::PluginsLoaded(results) => {
+            let selected = state
+                .selected_plugin
+                .and_then(|index| state.plugins.get(index))
+                .map(|plugin| plugin.file_name.clone());
+            (state.plugins, state.plugin_errors) = (Vec::new(), Vec::new());
+            for result in results {
+                match result {
+                    Ok(plugin) => state.plugins.push(plugin),
+                    Err(e) => state.plugin_errors.push(e.to_string()),
+                }
+            }
+            state.selected_plugin = selected.and_then(|file_name| {
+                state
+                    .plugins
+                    .iter()
+                    .position(|plugin| plugin.file_name == file_name)
+            });
+            state.plugin_options = combo_box::State::new(
+                state
+                    .plugins
+                    .iter()
+                    .enumerate()
+                    .map(|(index, plugin)| PluginChoice {
+                        index,
+                        title: plugin.title.clone(),
+                    })
+                    .collect(),
+            );
+            Task::none()
+        }
+        Msg::PluginSelected(index) => {
+            state.selected_plugin = Some(index);
+            Task::none()
+        }
+        Msg::ExportPlugin => {
+            let Some((plugin, lines)) = state.plugin_lines(ledger) else {
+                return Task::none();
+            };
+            let stem = plugin.file_name.trim_end_matches(".toml");
+            let file_name = format!("{}-{}-{:02}.txt", stem, state.year, state.month);
+
+            Task::perform(
+                write_export(file_name, lines.join("\n") + "\n"),
+                Msg::Exported,
+            )
+        }
+        Msg::ExportMileage => {
+            let report = state.mileage();
+            let file_name = format!("mileage-{}-{:02}.csv", report.year, report.month);
+
+            Task::perform(write_export(file_name, report.to_csv()), Msg::Exported)
+        }
+        Msg::Exported(result) => {
+            state.status_message = Some(match result {
+                Ok(path) => format!("Exported to {}", path.display()),
+                Err(e) => e.to_string(),
+            });
+            Task::none()
+        }
+    }
+}
+
+pub fn view<'a>(state: &'a ReportsState, ledger: &Ledger) -> Element<'a, Msg> {
+    let content = global_content_container(
+        column![
+            view_income_statement(state, ledger),
+            view_mileage(state),
+            view_teaching_hours(state)
+        ]
+        .push(view_custom_reports(state, ledger))
+        .spacing(40)
+        .width(Length::Fill),
+    )
+    .width(Length::Fill)
+    .height(Length::Fill);
+
+    column![page_header("Reports"), content].into()
+}
+
+fn section_title<'a>(title: String) -> iced::widget::Text<'a> {
+    text(title).size(14).font(Font {
+        weight: font::Weight::Medium,
+        ..Default::default()
+    })
+}
+
+fn amount_row<'a>(label: String, amount: f32, money: &MoneyFormat) -> Element<'a, Msg> {
+    row![
+        text(label).size(12).width(Length::Fixed(200.0)),
+        text(money.format(amount)).size(12),
+    ]
+    .spacing(10)
+    .into()
+}
+
+fn view_income_statement<'a>(state: &'a ReportsState, ledger: &Ledger) -> Element<'a, Msg> {
+    let statement = state.income_statement(ledger);
+    let money = state.money();
+
+    let header = row![
+        section_title(format!("Income statement · {}", statement.title())),
+        button(text("Previous").size(12))
+            .style(button::text)
+            .on_press(Msg::PreviousMonth),
+        button(text("Next").size(12))
+            .style(button::text)
+            .on_press(Msg::NextMonth),
+        button(text("Export CSV").size(12))
+            .style(button::secondary)
+            .on_press(Msg::ExportIncomeStatement),
+    ]
+    .spacing(20)
+    .align_y(Center);
+
+    let lines_of = |kind: EntryKind| {
+        Column::new()
+            .extend(
+                statement
+                    .entries
+                    .iter()
+                    .filter(|entry| entry.kind == kind)
+                    .map(|entry| amount_row(entry.label.clone(), entry.amount, &money)),
+            )
+            .spacing(4)
+    };
+
+    let mut section = column![
+        header,
+        lines_of(EntryKind::Income),
+        amount_row(
+            "Total income".to_string(),
+            statement.total(EntryKind::Income),
+            &money
+        ),
+        lines_of(EntryKind::Expense),
+        amount_row(
+            "Total expenses".to_string(),
+            statement.total(EntryKind::Expense),
+            &money
+        ),
+        amount_row("Net income".to_string(), statement.net(), &money),
+    ]
+    .spacing(12);
+
+    if let Some(message) = &state.status_message {
+        section = section.push(text(message.clone()).size(12));
+    }
+
+    section.into()
+}
+
+fn view_mileage(state: &ReportsState) -> Element<'_, Msg> {
+    let report = state.mileage();
+
+    let header = row![
+        section_title("Mileage".to_string()),
+        button(text("Export CSV").size(12))
+            .style(button::secondary)
+            .on_press(Msg::ExportMileage),
+    ]
+    .spacing(20)
+    .align_y(Center);
+
+    let rows = Column::new()
+        .extend(report.rows.iter().map(|row| {
+            row![
+                text(row.at.format("%a %-d %b").to_string())
+                    .size(12)
+                    .width(Length::Fixed(100.0)),
+                text(row.student.clone())
+                    .size(12)
+                    .width(Length::Fixed(180.0)),
+                text(row.location.clone())
+                    .size(12)
+                    .width(Length::Fixed(100.0)),
+                text(format!("{:.1} km", row.km)).size(12),
+            ]
+            .spacing(10)
+            .into()
+        }))
+        .spacing(4);
+
+    column![
+        header,
+        rows,
+        text(format!(
+            "{} home visits, {:.1} km",
+            report.rows.len(),
+            report.total_km()
+        ))
+        .size(12)
+    ]
+    .spacing(12)
+    .into()
+}
+
+fn view_teaching_hours(state: &ReportsState) -> Element<'_, Msg> {
+    let report = state.teaching_hours();
+
+    let header = row![
+        section_title(format!("Teaching hours · {}", report.year)),
+        pick_list(
+            HoursPeriod::ALL,
+            Some(state.hours_period),
+            Msg::HoursPeriodSelected
+        )
+        .text_size(12),
+        button(text("Export PDF").size(12))
+            .style(button::secondary)
+            .on_press(Msg::ExportTeachingHours),
+    ]
+    .spacing(20)
+    .align_y(Center);
+
+    let rows = Column::new()
+        .extend(report.rows.iter().map(|row| {
+            row![
+                text(row.period.clone())
+                    .size(12)
+                    .width(Length::Fixed(100.0)),
+                text(row.subject.to_string())
+                    .size(12)
+                    .width(Length::Fixed(180.0)),
+                text(format!("{} sessions", row.sessions))
+                    .size(12)
+                    .width(Length::Fixed(100.0)),
+                text(format!("{:.1} h", row.hours())).size(12),
+            ]
+            .spacing(10)
+            .into()
+        }))
+        .spacing(4);
+
+    column![
+        header,
+        rows,
+        text(format!("Total {:.1} h", report.total_hours())).size(12)
+    ]
+    .spacing(12)
+    .into()
+}
+
+/// Hidden until the plugins folder holds at least one file.
+fn view_custom_reports<'a>(state: &'a ReportsState, ledger: &Ledger) -> Option<Element<'a, Msg>> {
+    if state.plugins.is_empty() && state.plugin_errors.is_empty() {
+        return None;
+    }
+
+    let mut header = row![
+        section_title("Custom reports".to_string()),
+        button(text("Reload").size(12))
+            .style(button::text)
+            .on_press(Msg::ReloadPlugins),
+    ]
+    .spacing(20)
+    .align_y(Center);
+    if state.selected_plugin.is_some() {
+        header = header.push(
+            button(text("Export").size(12))
+                .style(button::secondary)
+                .on_press(Msg::ExportPlugin),
+        );
+    }
+
+    let selected = state
+        .selected_plugin
+        .and_then(|index| state.plugin_options.options().get(index).cloned());
+    let menu = searchable_pick_list(
+        &state.plugin_options,
+        "Find a custom report",
+        selected.as_ref(),
+        |choice| Msg::PluginSelected(choice.index),
+    )
+    .width(Length::Fixed(300.0));
+
+    let mut section = column![header, menu].spacing(12);
+    if let Some((_, lines)) = state.plugin_lines(ledger) {
+        section = section.push(
+            Column::new()
+                .extend(
+                    lines
+                        .into_iter()
+                        .map(|line| text(line).size(12).font(Font::MONOSPACE).into()),
+                )
+                .spacing(2),
+        );
+    }
+    section = section.extend(
+        state
+            .plugin_errors
+            .iter()
+            .map(|error| text(error.clone()).size(12).into()),
+    );
+
+    Some(section.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::billing::Charge;
+    use crate::domain::fixtures::sample_domain;
+    use crate::domain::{SessionDistance, SessionLocation};
+    use chrono::{Local, TimeZone};
+
+    #[test]
+    fn income_statement_adds_session_surcharge_and_no_show_fees() {
+        let mut domain = sample_domain();
+        domain.students[0].location = Some("Home visit".to_string());
+        domain.locations.push(Location {
+            name: "Home visit".to_string(),
+            surcharge: 20.0,
+            home_visit: true,
+        });
+        let mut ledger = Ledger::default();
+        ledger.add(Charge {
+            student_id: "mary-jane".to_string(),
+            student_name: "Mary Jane".to_string(),
+            session_at: Local.with_ymd_and_hms(2026, 1, 13, 17, 30, 0).unwrap(),
+            reason: ChargeReason::NoShow,
+            amount: 50.0,
+            invoiced: true,
+        });
+
+        let statement =
+            IncomeStatement::compute(&domain.students, &domain.locations, &ledger, 2026, 1);
+
+        assert_eq!(statement.title(), "January 2026");
+        assert_eq!(
+            statement.to_csv(&MoneyFormat::default()),
+            "Item,Type,Amount\n\
+             Session fees,Income,1350.00\n\
+             Location surcharges,Income,100.00\n\
+             No-show fee,Income,50.00\n\
+             Net income,,1500.00\n"
+        );
+    }
+
+    #[test]
+    fn mileage_lists_home_visits_with_their_distance() {
+        let mut domain = sample_domain();
+        domain.locations = vec![
+            Location {
+                name: "Home visit".to_string(),
+                surcharge: 0.0,
+                home_visit: true,
+            },
+            Location {
+                name: "Online".to_string(),
+                surcharge: 0.0,
+                home_visit: false,
+            },
+        ];
+        let mary = &mut domain.students[0];
+        mary.location = Some("Home visit".to_string());
+        mary.travel_km = Some(12.0);
+        mary.session_distances.push(SessionDistance {
+            at: Local.with_ymd_and_hms(2026, 2, 5, 17, 0, 0).unwrap(),
+            km: 20.5,
+        });
+        mary.session_locations.push(SessionLocation {
+            at: Local.with_ymd_and_hms(2026, 2, 10, 17, 0, 0).unwrap(),
+            location: "Online".to_string(),
+        });
+
+        let report = MileageReport::compute(&domain.students, &domain.locations, 2026, 2);
+
+        assert_eq!(
+            report.to_csv(),
+            "Date,Student,Location,Km\n\
+             2026-02-03,Mary Jane,Home visit,12.0\n\
+             2026-02-05,Mary Jane,Home visit,20.5\n\
+             Total,,,32.5\n"
+        );
+    }
+
+    #[test]
+    fn teaching_hours_group_attended_sessions_by_term_and_subject() {
+        let domain = sample_domain();
+
+        let report = TeachingHoursReport::compute(&domain.students, 2026, HoursPeriod::Term);
+        let lines = report.to_lines(&domain.tutor.name);
+
+        insta::assert_snapshot!(lines.join("\n"));
+    }
+}
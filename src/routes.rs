@@ -0,0 +1,193 @@
+use std::path::{Path, PathBuf};
+
+use crate::shell::SideMenuItem;
+
+/// Prefix that turns a route into a link, e.g. for pasting into notes.
+pub const LINK_SCHEME: &str = "tutor-mgr://";
+/// Holds the route that was open when the app last closed.
+const LAST_ROUTE_FILE: &str = "last-route";
+
+#[derive(Debug, Clone)]
+pub enum RouteError {
+    SaveFailed(String),
+}
+
+impl std::fmt::Display for RouteError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RouteError::SaveFailed(reason) => write!(f, "Could not save the open view: {}", reason),
+        }
+    }
+}
+
+/// A screen and what it has open, written like `students/<id>`,
+/// `students/<id>/details` or `reports?month=2025-11`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Route {
+    Dashboard,
+    Students(Option<String>),
+    /// One student's page, with their full history.
+    StudentDetail(String),
+    /// Students side by side, picked on the page.
+    Compare,
+    Calendar,
+    Reconciliation,
+    Invoices,
+    Reports(Option<(i32, u32)>),
+    Settings,
+}
+
+impl Route {
+    /// Reads a route with or without the link scheme in front.
+    pub fn parse(link: &str) -> Option<Route> {
+        let link = link.trim();
+        let link = link.strip_prefix(LINK_SCHEME).unwrap_or(link);
+        let (path, query) = link.split_once('?').unwrap_or((link, ""));
+        let mut segments = path.trim_matches('/').split('/');
+
+        let route = match (segments.next()?, segments.next()) {
+            ("dashboard", None) => Route::Dashboard,
+            ("students", None) => Route::Students(None),
+            ("students", Some(id)) if !id.is_empty() => match segments.next() {
+                None => Route::Students(Some(id.to_string())),
+                Some("details") => Route::StudentDetail(id.to_string()),
+                Some(_) => return None,
+            },
+            ("compare", None) => Route::Compare,
+            ("calendar", None) => Route::Calendar,
+            ("reconciliation", None) => Route::Reconciliation,
+            ("invoices", None) => Route::Invoices,
+            ("reports", None) => Route::Reports(query_month(query)),
+            ("settings", None) => Route::Settings,
+            _ => return None,
+        };
+
+        segments.next().is_none().then_some(route)
+    }
+
+    pub fn link(&self) -> String {
+        format!("{}{}", LINK_SCHEME, self)
+    }
+
+    pub fn menu_item(&self) -> SideMenuItem {
+        match self {
+            Route::Dashboard => SideMenuItem::Dashboard,
+            Route::Students(_) | Route::StudentDetail(_) | Route::Compare => {
+                SideMenuItem::StudentManager
+            }
+            Route::Calendar => SideMenuItem::Calendar,
+            Route::Reconciliation => SideMenuItem::Reconciliation,
+            Route::Invoices => SideMenuItem::Invoices,
+            Route::Reports(_) => SideMenuItem::Reports,
+            Route::Settings => SideMenuItem::Settings,
+        }
+    }
+}
+
+impl From<SideMenuItem> for Route {
+    fn from(item: SideMenuItem) -> Self {
+        match item {
+            SideMenuItem::Dashboard | SideMenuItem::Logout => Route::Dashboard,
+            SideMenuItem::StudentManager => Route::Students(None),
+            SideMenuItem::Calendar => Route::Calendar,
+            SideMenuItem::Reconciliation => Route::Reconciliation,
+            SideMenuItem::Invoices => Route::Invoices,
+            SideMenuItem::Reports => Route::Reports(None),
+            SideMenuItem::Settings => Route::Settings,
+        }
+    }
+}
+
+impl std::fmt::Display for Route {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Route::Dashboard => write!(f, "dashboard"),
+            Route::Students(None) => write!(f, "students"),
+            Route::Students(Some(id)) => write!(f, "students/{}", id),
+            Route::StudentDetail(id) => write!(f, "students/{}/details", id),
+            Route::Compare => write!(f, "compare"),
+            Route::Calendar => write!(f, "calendar"),
+            Route::Reconciliation => write!(f, "reconciliation"),
+            Route::Invoices => write!(f, "invoices"),
+            Route::Reports(None) => write!(f, "reports"),
+            Route::Reports(Some((year, month))) => {
+                write!(f, "reports?month={}-{:02}", year, month)
+            }
+            Route::Settings => write!(f, "settings"),
+        }
+    }
+}
+
+/// The `month=YYYY-MM` parameter, if present and a real month.
+fn query_month(query: &str) -> Option<(i32, u32)> {
+    let value = query
+        .split('&')
+        .find_map(|param| param.strip_prefix("month="))?;
+    let (year, month) = value.split_once('-')?;
+    let (year, month) = (year.parse().ok()?, month.parse().ok()?);
+
+    (1..=12).contains(&month).then_some((year, month))
+}
+
+/// A link passed on the command line, such as one clicked in a notes app.
+pub fn route_from_args(mut args: impl Iterator<Item = String>) -> Option<Route> {
+    args.find(|arg| arg.starts_with(LINK_SCHEME))
+        .and_then(|link| Route::parse(&link))
+}
+
+pub fn load_last_route(dir: &Path) -> Option<Route> {
+    std::fs::read_to_string(dir.join(LAST_ROUTE_FILE))
+        .ok()
+        .and_then(|route| Route::parse(&route))
+}
+
+pub async fn save_last_route(dir: PathBuf, route: Route) -> Result<(), RouteError> {
+    std::fs::write(dir.join(LAST_ROUTE_FILE), route.to_string())
+        .map_err(|e| RouteError::SaveFailed(e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn routes_survive_a_round_trip_through_their_links() {
+        let routes = [
+            Route::Dashboard,
+            Route::Students(None),
+            Route::Students(Some("mary-jane".to_string())),
+            Route::StudentDetail("mary-jane".to_string()),
+            Route::Compare,
+            Route::Calendar,
+            Route::Reconciliation,
+            Route::Invoices,
+            Route::Reports(None),
+            Route::Reports(Some((2025, 11))),
+            Route::Settings,
+        ];
+
+        for route in routes {
+            assert_eq!(Route::parse(&route.link()), Some(route.clone()));
+            assert_eq!(Route::parse(&route.to_string()), Some(route));
+        }
+    }
+
+    #[test]
+    fn unknown_paths_and_months_are_rejected() {
+        assert_eq!(Route::parse("tutor-mgr://timesheets"), None);
+        assert_eq!(Route::parse("students/mary-jane/notes"), None);
+        assert_eq!(Route::parse("students/mary-jane/details/notes"), None);
+        assert_eq!(
+            Route::parse("reports?month=2025-13"),
+            Some(Route::Reports(None))
+        );
+        assert_eq!(
+            route_from_args(
+                ["--portable", "tutor-mgr://settings"]
+                    .map(String::from)
+                    .into_iter()
+            ),
+            Some(Route::Settings)
+        );
+    }
+}
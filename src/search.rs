@@ -0,0 +1,247 @@
+use crate::billing::Payment;
+use crate::domain::{Student, weekly_schedule};
+use crate::money::MoneyFormat;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum SearchGroup {
+    Students,
+    Sessions,
+    Payments,
+}
+
+impl std::fmt::Display for SearchGroup {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SearchGroup::Students => write!(f, "Students"),
+            SearchGroup::Sessions => write!(f, "Sessions"),
+            SearchGroup::Payments => write!(f, "Payments"),
+        }
+    }
+}
+
+/// Where a result leads, by student id.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SearchTarget {
+    Student(String),
+    Payment(String),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SearchResult {
+    pub group: SearchGroup,
+    pub title: String,
+    pub detail: String,
+    pub target: SearchTarget,
+}
+
+/// Case-insensitive search over students by name, tag or any of their
+/// subjects, tabled sessions, session notes and recorded payments,
+/// ordered by group. Payments match on student, date and amount.
+pub fn search(
+    students: &[Student],
+    payments: &[Payment],
+    query: &str,
+    money: &MoneyFormat,
+) -> Vec<SearchResult> {
+    let query = query.trim().to_lowercase();
+    if query.is_empty() {
+        return Vec::new();
+    }
+    let matches = |haystack: &str| haystack.to_lowercase().contains(&query);
+
+    let student_results = students
+        .iter()
+        .filter(|student| {
+            matches(&format!(
+                "{} {} {}",
+                student.name.full_name(),
+                student.subject_names(),
+                student.tags.join(" ")
+            ))
+        })
+        .map(|student| SearchResult {
+            group: SearchGroup::Students,
+            title: student.name.full_name(),
+            detail: student.subject_names(),
+            target: SearchTarget::Student(student.id.clone()),
+        });
+
+    let session_results = students.iter().flat_map(|student| {
+        weekly_schedule(student)
+            .into_iter()
+            .map(move |slot| (student, slot.to_string()))
+            .filter(|(student, slot)| matches(&format!("{} {}", slot, student.name.full_name())))
+            .map(|(student, slot)| SearchResult {
+                group: SearchGroup::Sessions,
+                title: slot,
+                detail: student.name.full_name(),
+                target: SearchTarget::Student(student.id.clone()),
+            })
+    });
+
+    let note_results = students.iter().flat_map(|student| {
+        student
+            .sessions
+            .iter()
+            .filter(|record| !record.notes.trim().is_empty() && matches(&record.notes))
+            .map(move |record| SearchResult {
+                group: SearchGroup::Sessions,
+                title: format!(
+                    "{} with {}",
+                    record.at.format("%-d %b %Y"),
+                    student.name.full_name()
+                ),
+                detail: record.notes.trim().to_string(),
+                target: SearchTarget::Student(student.id.clone()),
+            })
+    });
+
+    let payment_results = payments.iter().filter_map(|payment| {
+        let name = students
+            .iter()
+            .find(|student| student.id == payment.student_id)
+            .map(|student| student.name.full_name())
+            .unwrap_or_else(|| payment.student_id.clone());
+        let amount = money.format(payment.amount);
+        let paid_on = payment.paid_on.format("%-d %b %Y").to_string();
+
+        // The plain amount lets `1200` find `GHS 1,200.00`.
+        let haystack = format!(
+            "{} {} {} {}",
+            name,
+            amount,
+            money.plain(payment.amount),
+            paid_on
+        );
+        matches(&haystack).then(|| SearchResult {
+            group: SearchGroup::Payments,
+            title: format!("{} from {}", amount, name),
+            detail: paid_on,
+            target: SearchTarget::Payment(payment.student_id.clone()),
+        })
+    });
+
+    let mut results: Vec<SearchResult> = student_results
+        .chain(session_results)
+        .chain(note_results)
+        .chain(payment_results)
+        .collect();
+    results.sort_by_key(|result| result.group);
+
+    results
+}
+
+/// Whether every word of `query` is found in some word of `haystack`,
+/// either whole or with letters missing, so "ext maths" finds
+/// "Extended Mathematics" and "mry" finds "Mary". Ignores case.
+pub fn loosely_matches(haystack: &str, query: &str) -> bool {
+    let haystack = haystack.to_lowercase();
+    let words: Vec<&str> = haystack.split_whitespace().collect();
+
+    query.to_lowercase().split_whitespace().all(|term| {
+        words
+            .iter()
+            .any(|word| word.contains(term) || is_subsequence(term, word))
+    })
+}
+
+/// `term`'s letters appear in `word` in order, starting with its first.
+fn is_subsequence(term: &str, word: &str) -> bool {
+    if term.chars().next() != word.chars().next() {
+        return false;
+    }
+
+    let mut letters = word.chars();
+    term.chars()
+        .all(|wanted| letters.any(|letter| letter == wanted))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::fixtures::{date, tue_thu_student};
+    use crate::domain::{Enrolment, TutorSubject};
+
+    #[test]
+    fn finds_students_sessions_and_payments_by_any_of_their_text() {
+        let students = vec![tue_thu_student(&[])];
+        let payments = vec![Payment {
+            student_id: students[0].id.clone(),
+            paid_on: date(2026, 2, 10),
+            amount: 150.0,
+            invoice_number: None,
+        }];
+
+        let groups = |query: &str| {
+            search(&students, &payments, query, &MoneyFormat::default())
+                .into_iter()
+                .map(|result| result.group)
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            groups("test student"),
+            vec![
+                SearchGroup::Students,
+                SearchGroup::Sessions,
+                SearchGroup::Sessions,
+                SearchGroup::Payments
+            ]
+        );
+        assert_eq!(groups("THU 5"), vec![SearchGroup::Sessions]);
+        assert_eq!(groups("10 feb"), vec![SearchGroup::Payments]);
+        assert!(groups("  ").is_empty());
+    }
+
+    #[test]
+    fn finds_students_by_tag_and_by_any_subject() {
+        let mut student = tue_thu_student(&[]);
+        student.tags = vec!["Exam year".to_string()];
+        student.other_subjects = vec![Enrolment {
+            subject: TutorSubject::new("Physics"),
+            tabled_sessions: Vec::new(),
+            payment_data: student.payment_data.clone(),
+        }];
+        let students = vec![student];
+        let found = |query: &str| search(&students, &[], query, &MoneyFormat::default());
+
+        let by_tag = found("exam year");
+        assert_eq!(by_tag.len(), 1);
+        assert_eq!(by_tag[0].group, SearchGroup::Students);
+        assert_eq!(by_tag[0].detail, "Extended Mathematics, Physics");
+
+        let by_subject = found("physics");
+        assert_eq!(by_subject.len(), 1);
+        assert_eq!(by_subject[0].title, "Test Student");
+    }
+
+    #[test]
+    fn finds_sessions_by_their_notes() {
+        let mut student = tue_thu_student(&[(2026, 2, 3), (2026, 2, 5)]);
+        student.sessions[0].notes = "Covered vectors".to_string();
+        let students = vec![student];
+
+        let results = search(&students, &[], "vectors", &MoneyFormat::default());
+        assert_eq!(
+            results,
+            [SearchResult {
+                group: SearchGroup::Sessions,
+                title: "3 Feb 2026 with Test Student".to_string(),
+                detail: "Covered vectors".to_string(),
+                target: SearchTarget::Student(students[0].id.clone()),
+            }]
+        );
+    }
+
+    #[test]
+    fn loose_matches_allow_missing_letters_in_each_word() {
+        let haystack = "Mary Jane Extended Mathematics";
+
+        assert!(loosely_matches(haystack, "jane"));
+        assert!(loosely_matches(haystack, "ext maths"));
+        assert!(loosely_matches(haystack, "MRY"));
+        assert!(loosely_matches(haystack, ""));
+        assert!(!loosely_matches(haystack, "yram"));
+        assert!(!loosely_matches(haystack, "mary statistics"));
+    }
+}
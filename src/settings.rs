@@ -0,0 +1,1605 @@
+use chrono::{DateTime, Days, Local, NaiveDate};
+use iced::advanced::graphics::core::font;
+use iced::futures::channel::mpsc;
+use iced::futures::{SinkExt, Stream};
+use iced::widget::{
+    Column, button, checkbox, column, pick_list, progress_bar, row, text, text_input,
+};
+use iced::{Center, Element, Font, Length, Subscription, Task, Theme, stream};
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+use std::rc::Rc;
+use std::time::Duration;
+
+use crate::billing::Ledger;
+use crate::config::MAX_LEAD_HOURS;
+use crate::data_dirs::{DataDirError, DataDirs, open_folder};
+use crate::domain::{
+    Clock, Domain, DomainError, Location, MonthlySummary, SubjectRate, TRASH_RETENTION_DAYS,
+    TutorSubject, month_label,
+};
+use crate::export::{ExportError, write_export};
+use crate::interchange::{Interchange, InterchangeError, interchange_file_name, read_interchange};
+use crate::money::MoneyFormat;
+use crate::notifications::{
+    MAX_NOTICE_MINUTES, NotificationBook, NotificationError, SessionNotice, StudentNotices,
+    show_notices,
+};
+use crate::reminders::{
+    DeliveryStatus, OutboxMailer, Reminder, ReminderBook, ReminderError, ReminderLogEntry,
+    SNOOZE_HOURS, load_log, log_path, save_log, send_reminders,
+};
+use crate::shell::{CopyRequest, Effect};
+use crate::theme;
+use crate::ui_components::{copy_button, global_content_container, number_stepper, page_header};
+use crate::usage::UsageStats;
+
+/// How often due reminders are looked for.
+const REMINDER_CHECK_INTERVAL: Duration = Duration::from_secs(60);
+/// How often sessions about to start are looked for.
+const NOTICE_CHECK_INTERVAL: Duration = Duration::from_secs(30);
+/// How far ahead upcoming reminders are listed.
+const UPCOMING_REMINDER_DAYS: i64 = 7;
+/// Weeks of session logging shown under Usage.
+const USAGE_WEEKS: usize = 8;
+/// Actions listed as most used.
+const USAGE_TOP_ACTIONS: usize = 5;
+
+/// A choice of how long to mute a student's reminders.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct MuteWeeks(u64);
+
+impl MuteWeeks {
+    const ALL: [MuteWeeks; 4] = [MuteWeeks(1), MuteWeeks(2), MuteWeeks(4), MuteWeeks(8)];
+}
+
+impl std::fmt::Display for MuteWeeks {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.0 {
+            1 => write!(f, "1 week"),
+            weeks => write!(f, "{} weeks", weeks),
+        }
+    }
+}
+
+pub struct SettingsState {
+    domain: Option<Rc<Domain>>,
+    data_dirs: Result<DataDirs, DataDirError>,
+    data_folder_error: Option<String>,
+    reminders: ReminderBook,
+    sending_reminders: bool,
+    /// Nothing is sent until the log is read back, so no session is
+    /// reminded twice.
+    reminder_log_loaded: bool,
+    /// Why the reminder log could not be read or saved.
+    reminder_log_error: Option<String>,
+    notices: NotificationBook,
+    showing_notices: bool,
+    /// Why the last desktop notification could not be shown.
+    notice_error: Option<String>,
+    import_path: String,
+    importing: bool,
+    /// A file read in but held back until the tutor agrees to what it
+    /// would replace.
+    pending_import: Option<(Box<Interchange>, Vec<String>)>,
+    transfer_message: Option<String>,
+    /// Months done and months in all, while analytics are recomputed.
+    recomputing: Option<(usize, usize)>,
+    snapshot_message: Option<String>,
+    /// Payment instructions as typed, saved to the domain on request.
+    payment_instructions: String,
+    blackout_input: String,
+    blackout_error: Option<String>,
+    location_name: String,
+    location_surcharge: String,
+    location_home_visit: bool,
+    location_error: Option<String>,
+    subject_input: String,
+    subject_error: Option<String>,
+    rate_subject: Option<TutorSubject>,
+    rate_input: String,
+    rate_error: Option<String>,
+}
+
+impl SettingsState {
+    pub fn new(data_dirs: Result<DataDirs, DataDirError>) -> Self {
+        Self {
+            domain: None,
+            data_dirs,
+            data_folder_error: None,
+            reminders: ReminderBook::default(),
+            sending_reminders: false,
+            reminder_log_loaded: false,
+            reminder_log_error: None,
+            notices: NotificationBook::default(),
+            showing_notices: false,
+            notice_error: None,
+            import_path: String::new(),
+            importing: false,
+            pending_import: None,
+            transfer_message: None,
+            recomputing: None,
+            snapshot_message: None,
+            payment_instructions: String::new(),
+            blackout_input: String::new(),
+            blackout_error: None,
+            location_name: String::new(),
+            location_surcharge: String::new(),
+            location_home_visit: false,
+            location_error: None,
+            subject_input: String::new(),
+            subject_error: None,
+            rate_subject: None,
+            rate_input: String::new(),
+            rate_error: None,
+        }
+    }
+
+    /// Reads back the reminders sent in earlier runs, from beside the
+    /// database.
+    pub fn load_reminder_log(&self) -> Task<Msg> {
+        match &self.data_dirs {
+            Ok(data_dirs) => Task::perform(
+                load_log(log_path(&data_dirs.database)),
+                Msg::ReminderLogLoaded,
+            ),
+            Err(_) => Task::none(),
+        }
+    }
+
+    /// The domain's clock, or the system's until one is attached.
+    pub fn clock(&self) -> Clock {
+        self.domain
+            .as_ref()
+            .map(|domain| domain.clock)
+            .unwrap_or_default()
+    }
+
+    /// Keeps unsaved payment instructions unless the saved ones changed.
+    pub fn attach_domain(&mut self, domain: Rc<Domain>) {
+        let saved = self
+            .domain
+            .as_ref()
+            .map(|previous| previous.payment_instructions.as_str());
+        if saved != Some(domain.payment_instructions.as_str()) {
+            self.payment_instructions = domain.payment_instructions.clone();
+        }
+        self.domain = Some(domain);
+    }
+
+    pub fn set_reminder_lead_hours(&mut self, hours: i64) {
+        self.reminders.set_lead_hours(hours);
+    }
+
+    pub fn set_notice_minutes(&mut self, minutes: i64) {
+        self.notices.set_lead_minutes(minutes);
+    }
+
+    pub fn set_student_notices(&mut self, students: HashMap<String, StudentNotices>) {
+        self.notices.set_students(students);
+    }
+
+    pub fn set_reminder_contacts(
+        &mut self,
+        opted_in: &HashSet<String>,
+        mutes: &HashMap<String, NaiveDate>,
+    ) {
+        self.reminders.set_opt_ins(opted_in);
+        self.reminders.set_mutes(mutes);
+    }
+
+    pub fn set_snoozed_reminders(
+        &mut self,
+        snoozed: HashMap<(String, DateTime<Local>), DateTime<Local>>,
+    ) {
+        self.reminders.set_snoozed(snoozed);
+    }
+
+    pub fn snoozed_until(&self, reminder: &Reminder) -> Option<DateTime<Local>> {
+        self.reminders.snoozed_until(reminder)
+    }
+
+    fn money_format(&self) -> MoneyFormat {
+        self.domain
+            .as_ref()
+            .map(|domain| domain.money_format)
+            .unwrap_or_default()
+    }
+}
+
+#[derive(Clone, Debug)]
+pub enum Msg {
+    OpenDataFolder,
+    DataFolderOpened(Result<(), DataDirError>),
+    ReminderOptInToggled(String, bool),
+    /// Mutes the student's reminders up to the day given, or unmutes them.
+    ReminderMuted(String, Option<NaiveDate>),
+    /// Hours ahead reminders go out, which the app also saves to the config.
+    ReminderLeadChanged(i64),
+    SnoozeReminder(Reminder),
+    CheckReminders,
+    RemindersSent(Vec<(Reminder, Result<(), ReminderError>)>),
+    ReminderLogLoaded(Result<Vec<ReminderLogEntry>, DomainError>),
+    ReminderLogSaved(Result<(), DomainError>),
+    OpenOutbox,
+    /// Minutes ahead desktop notifications show, which the app also saves
+    /// to the config.
+    NoticeMinutesChanged(i64),
+    StudentNoticesChanged(String, StudentNotices),
+    CheckNotices,
+    NoticesShown(Vec<(SessionNotice, Result<(), NotificationError>)>),
+    /// Applied by the app through the domain store, which brings it back
+    /// here and to every other screen.
+    MoneyFormatChanged(MoneyFormat),
+    PaymentInstructionsEdited(String),
+    /// Applied by the app through the domain store.
+    SavePaymentInstructions(String),
+    BlackoutInputChanged(String),
+    AddBlackoutDate,
+    /// The full new list, applied by the app through the domain store.
+    BlackoutDatesChanged(Vec<NaiveDate>),
+    LocationNameChanged(String),
+    LocationSurchargeChanged(String),
+    LocationHomeVisitToggled(bool),
+    AddLocation,
+    /// The full new list, applied by the app through the domain store.
+    LocationsChanged(Vec<Location>),
+    SubjectInputChanged(String),
+    AddSubject,
+    /// The full new list, applied by the app through the domain store.
+    SubjectsChanged(Vec<TutorSubject>),
+    RateSubjectSelected(TutorSubject),
+    RateInputChanged(String),
+    SetRate,
+    /// The full new list, applied by the app through the domain store.
+    RatesChanged(Vec<SubjectRate>),
+    ExportAllData,
+    DataExported(Result<PathBuf, ExportError>),
+    ImportPathChanged(String),
+    ImportData,
+    DataImported(Result<Box<Interchange>, InterchangeError>),
+    ConfirmImport,
+    CancelImport,
+    /// The app swaps the imported data in before this arrives here.
+    ImportConfirmed(Box<Interchange>),
+    /// Works every month that has ended out again from its sessions, in the
+    /// background, as after an import.
+    RecomputeAnalytics,
+    RecomputeProgressed {
+        done: usize,
+        total: usize,
+    },
+    /// Applied by the app through the domain store.
+    SnapshotsTaken(Vec<MonthlySummary>),
+    /// Applied by the app through the domain store, by place in the trash.
+    RestoreTrashed(usize),
+    DiscardTrashed(usize),
+    /// Applied by the app to the ledger, by place in its trash.
+    RestorePayment(usize),
+    DiscardPayment(usize),
+    /// Carried out by the app, which keeps the usage statistics.
+    ClearUsage,
+    /// Carried out by the app, e.g. copying with a toast.
+    Effect(Effect),
+}
+
+/// Payments live in the dashboard's ledger, so it is passed in for exports.
+pub fn update(state: &mut SettingsState, msg: Msg, ledger: &Ledger) -> Task<Msg> {
+    match msg {
+        Msg::OpenDataFolder => match &state.data_dirs {
+            Ok(data_dirs) => {
+                Task::perform(open_folder(data_dirs.root.clone()), Msg::DataFolderOpened)
+            }
+            Err(_) => Task::none(),
+        },
+        Msg::DataFolderOpened(result) => {
+            state.data_folder_error = result.err().map(|e| e.to_string());
+            Task::none()
+        }
+        Msg::ReminderOptInToggled(student_id, opted_in) => {
+            let mut contact = state.reminders.contact(&student_id);
+            contact.opted_in = opted_in;
+            state.reminders.set_contact(&student_id, contact);
+            Task::none()
+        }
+        Msg::ReminderMuted(student_id, muted_until) => {
+            let mut contact = state.reminders.contact(&student_id);
+            contact.muted_until = muted_until;
+            state.reminders.set_contact(&student_id, contact);
+            Task::none()
+        }
+        Msg::ReminderLeadChanged(hours) => {
+            state.reminders.set_lead_hours(hours);
+            Task::none()
+        }
+        Msg::SnoozeReminder(reminder) => {
+            state.reminders.snooze(&reminder, state.clock().now());
+            Task::none()
+        }
+        Msg::CheckReminders => {
+            let (Some(domain), Ok(data_dirs)) = (&state.domain, &state.data_dirs) else {
+                return Task::none();
+            };
+            if state.sending_reminders || !state.reminder_log_loaded {
+                return Task::none();
+            }
+
+            let due = state.reminders.due(&domain.students, domain.clock.now());
+            if due.is_empty() {
+                return Task::none();
+            }
+
+            state.sending_reminders = true;
+            let mailer = OutboxMailer {
+                dir: data_dirs.outbox.clone(),
+            };
+            Task::perform(send_reminders(mailer, due), Msg::RemindersSent)
+        }
+        Msg::RemindersSent(results) => {
+            let now = state.clock().now();
+            for (reminder, result) in results {
+                state.reminders.record(&reminder, result, now);
+            }
+            state.sending_reminders = false;
+            let Ok(data_dirs) = &state.data_dirs else {
+                return Task::none();
+            };
+            let log = state.reminders.log().rev().cloned().collect();
+            Task::perform(
+                save_log(log, log_path(&data_dirs.database)),
+                Msg::ReminderLogSaved,
+            )
+        }
+        Msg::ReminderLogLoaded(Ok(log)) => {
+            state.reminders.set_log(log);
+            state.reminder_log_loaded = true;
+            state.reminder_log_error = None;
+            Task::none()
+        }
+        Msg::ReminderLogLoaded(Err(e)) | Msg::ReminderLogSaved(Err(e)) => {
+            state.reminder_log_error = Some(e.to_string());
+            Task::none()
+        }
+        Msg::ReminderLogSaved(Ok(())) => {
+            state.reminder_log_error = None;
+            Task::none()
+        }
+        Msg::OpenOutbox => match &state.data_dirs {
+            Ok(data_dirs) => {
+                Task::perform(open_folder(data_dirs.outbox.clone()), Msg::DataFolderOpened)
+            }
+            Err(_) => Task::none(),
+        },
+        Msg::NoticeMinutesChanged(minutes) => {
+            state.notices.set_lead_minutes(minutes);
+            Task::none()
+        }
+        Msg::StudentNoticesChanged(student_id, notices) => {
+            state.notices.set_student(&student_id, notices);
+            Task::none()
+        }
+        Msg::CheckNotices => {
+            let Some(domain) = &state.domain else {
+                return Task::none();
+            };
+            if state.showing_notices {
+                return Task::none();
+            }
+
+            let now = domain.clock.now();
+            let due = state.notices.due(&domain.students, now);
+            if due.is_empty() {
+                return Task::none();
+            }
+
+            state.showing_notices = true;
+            Task::perform(show_notices(due, now), Msg::NoticesShown)
+        }
+        Msg::NoticesShown(results) => {
+            state.showing_notices = false;
+            state.notice_error = None;
+            for (notice, result) in results {
+                state.notices.mark_shown(&notice);
+                if let Err(e) = result {
+                    state.notice_error = Some(e.to_string());
+                }
+            }
+            Task::none()
+        }
+        Msg::MoneyFormatChanged(_) => Task::none(),
+        Msg::PaymentInstructionsEdited(instructions) => {
+            state.payment_instructions = instructions;
+            Task::none()
+        }
+        Msg::SavePaymentInstructions(_)
+        | Msg::RestoreTrashed(_)
+        | Msg::DiscardTrashed(_)
+        | Msg::RestorePayment(_)
+        | Msg::DiscardPayment(_)
+        | Msg::ClearUsage
+        | Msg::Effect(_) => Task::none(),
+        Msg::BlackoutInputChanged(input) => {
+            state.blackout_input = input;
+            state.blackout_error = None;
+            Task::none()
+        }
+        Msg::AddBlackoutDate => {
+            let Some(domain) = &state.domain else {
+                return Task::none();
+            };
+            let Ok(date) = NaiveDate::parse_from_str(state.blackout_input.trim(), "%Y-%m-%d")
+            else {
+                state.blackout_error = Some("Enter the date as YYYY-MM-DD".to_string());
+                return Task::none();
+            };
+
+            let mut blackout_dates = domain.blackout_dates.clone();
+            if !blackout_dates.contains(&date) {
+                blackout_dates.push(date);
+                blackout_dates.sort();
+            }
+            state.blackout_input.clear();
+            Task::done(Msg::BlackoutDatesChanged(blackout_dates))
+        }
+        Msg::BlackoutDatesChanged(_) => Task::none(),
+        Msg::LocationNameChanged(name) => {
+            state.location_name = name;
+            state.location_error = None;
+            Task::none()
+        }
+        Msg::LocationSurchargeChanged(surcharge) => {
+            state.location_surcharge = surcharge;
+            state.location_error = None;
+            Task::none()
+        }
+        Msg::LocationHomeVisitToggled(home_visit) => {
+            state.location_home_visit = home_visit;
+            Task::none()
+        }
+        Msg::AddLocation => {
+            let Some(domain) = &state.domain else {
+                return Task::none();
+            };
+            let name = state.location_name.trim();
+            if name.is_empty() {
+                state.location_error = Some("Enter a name, e.g. Home visit".to_string());
+                return Task::none();
+            }
+            let surcharge = match state.location_surcharge.trim() {
+                "" => 0.0,
+                surcharge => match surcharge.parse::<f32>() {
+                    Ok(surcharge) if surcharge >= 0.0 => surcharge,
+                    _ => {
+                        state.location_error =
+                            Some("Enter the surcharge per session, e.g. 30".to_string());
+                        return Task::none();
+                    }
+                },
+            };
+
+            // Adding a name that exists updates it.
+            let location = Location {
+                name: name.to_string(),
+                surcharge,
+                home_visit: state.location_home_visit,
+            };
+            let mut locations = domain.locations.clone();
+            match locations.iter_mut().find(|existing| existing.name == name) {
+                Some(existing) => *existing = location,
+                None => locations.push(location),
+            }
+            state.location_name.clear();
+            state.location_surcharge.clear();
+            state.location_home_visit = false;
+            Task::done(Msg::LocationsChanged(locations))
+        }
+        Msg::LocationsChanged(_) => Task::none(),
+        Msg::SubjectInputChanged(input) => {
+            state.subject_input = input;
+            state.subject_error = None;
+            Task::none()
+        }
+        Msg::AddSubject => {
+            let Some(domain) = &state.domain else {
+                return Task::none();
+            };
+            let name = state.subject_input.trim();
+            if name.is_empty() {
+                state.subject_error = Some("Enter the subject's name".to_string());
+                return Task::none();
+            }
+            if domain
+                .tutor
+                .subjects
+                .iter()
+                .any(|subject| subject.name().eq_ignore_ascii_case(name))
+            {
+                state.subject_error = Some(format!("{} is already listed", name));
+                return Task::none();
+            }
+
+            let mut subjects = domain.tutor.subjects.clone();
+            subjects.push(TutorSubject::new(name));
+            state.subject_input.clear();
+            Task::done(Msg::SubjectsChanged(subjects))
+        }
+        Msg::SubjectsChanged(_) => Task::none(),
+        Msg::RateSubjectSelected(subject) => {
+            state.rate_subject = Some(subject);
+            state.rate_error = None;
+            Task::none()
+        }
+        Msg::RateInputChanged(input) => {
+            state.rate_input = input;
+            state.rate_error = None;
+            Task::none()
+        }
+        Msg::SetRate => {
+            let Some(domain) = &state.domain else {
+                return Task::none();
+            };
+            let Some(subject) = state.rate_subject.clone() else {
+                state.rate_error = Some("Choose a subject".to_string());
+                return Task::none();
+            };
+            let Some(per_hour) = state
+                .rate_input
+                .trim()
+                .parse::<f32>()
+                .ok()
+                .filter(|rate| *rate > 0.0)
+            else {
+                state.rate_error = Some("Enter the rate per hour, e.g. 150".to_string());
+                return Task::none();
+            };
+
+            let mut rates: Vec<SubjectRate> = domain
+                .rates
+                .iter()
+                .filter(|rate| rate.subject != subject)
+                .cloned()
+                .collect();
+            rates.push(SubjectRate { subject, per_hour });
+            state.rate_input.clear();
+            Task::done(Msg::RatesChanged(rates))
+        }
+        Msg::RatesChanged(_) => Task::none(),
+        Msg::ExportAllData => {
+            let Some(domain) = &state.domain else {
+                return Task::none();
+            };
+
+            let json = Interchange::new(domain, ledger).to_json();
+            Task::perform(
+                write_export(interchange_file_name(), json),
+                Msg::DataExported,
+            )
+        }
+        Msg::DataExported(result) => {
+            state.transfer_message = Some(match result {
+                Ok(path) => format!("Exported to {}", path.display()),
+                Err(e) => e.to_string(),
+            });
+            Task::none()
+        }
+        Msg::ImportPathChanged(path) => {
+            state.import_path = path;
+            state.pending_import = None;
+            state.transfer_message = None;
+            Task::none()
+        }
+        Msg::ImportData => {
+            let path = state.import_path.trim();
+            if path.is_empty() || state.importing {
+                return Task::none();
+            }
+
+            state.importing = true;
+            Task::perform(read_interchange(PathBuf::from(path)), |result| {
+                Msg::DataImported(result.map(Box::new))
+            })
+        }
+        Msg::DataImported(Ok(interchange)) => {
+            state.importing = false;
+            let Some(domain) = &state.domain else {
+                return Task::none();
+            };
+
+            let conflicts = interchange.conflicts(domain, ledger);
+            if conflicts.is_empty() {
+                Task::done(Msg::ImportConfirmed(interchange))
+            } else {
+                state.pending_import = Some((interchange, conflicts));
+                Task::none()
+            }
+        }
+        Msg::DataImported(Err(e)) => {
+            state.importing = false;
+            state.transfer_message = Some(e.to_string());
+            Task::none()
+        }
+        Msg::ConfirmImport => match state.pending_import.take() {
+            Some((interchange, _)) => Task::done(Msg::ImportConfirmed(interchange)),
+            None => Task::none(),
+        },
+        Msg::CancelImport => {
+            state.pending_import = None;
+            Task::none()
+        }
+        Msg::ImportConfirmed(interchange) => {
+            let counted = |count: usize, one: &str, many: &str| match count {
+                1 => format!("1 {}", one),
+                count => format!("{} {}", count, many),
+            };
+            state.transfer_message = Some(format!(
+                "Imported {} and {}",
+                counted(interchange.students.len(), "student", "students"),
+                counted(interchange.payments.len(), "payment", "payments")
+            ));
+            Task::done(Msg::RecomputeAnalytics)
+        }
+        Msg::RecomputeAnalytics => {
+            let Some(domain) = &state.domain else {
+                return Task::none();
+            };
+            if state.recomputing.is_some() {
+                return Task::none();
+            }
+
+            let months = domain.closed_months();
+            state.recomputing = Some((0, months.len()));
+            state.snapshot_message = None;
+            Task::stream(recompute_analytics(Domain::clone(domain), months))
+        }
+        Msg::RecomputeProgressed { done, total } => {
+            state.recomputing = Some((done, total));
+            Task::none()
+        }
+        Msg::SnapshotsTaken(summaries) => {
+            state.recomputing = None;
+            state.snapshot_message = Some(match summaries.as_slice() {
+                [] => "No month has ended yet".to_string(),
+                [only] => format!("Retook {}", month_label(only.year, only.month)),
+                [first, .., last] => format!(
+                    "Retook {} months, {} to {}",
+                    summaries.len(),
+                    month_label(first.year, first.month),
+                    month_label(last.year, last.month)
+                ),
+            });
+            Task::none()
+        }
+    }
+}
+
+/// Retakes the totals of `months` one at a time so the progress bar moves
+/// and the window stays responsive over a long history.
+fn recompute_analytics(domain: Domain, months: Vec<(i32, u32)>) -> impl Stream<Item = Msg> {
+    stream::channel(0, async move |mut output: mpsc::Sender<Msg>| {
+        let total = months.len();
+        let mut summaries = Vec::with_capacity(total);
+        for (done, &(year, month)) in months.iter().enumerate() {
+            summaries.push(domain.live_summary(year, month));
+            let _ = output
+                .send(Msg::RecomputeProgressed {
+                    done: done + 1,
+                    total,
+                })
+                .await;
+        }
+        let _ = output.send(Msg::SnapshotsTaken(summaries)).await;
+    })
+}
+
+pub fn subscription(state: &SettingsState) -> Subscription<Msg> {
+    if state.domain.is_some() {
+        Subscription::batch([
+            iced::time::every(REMINDER_CHECK_INTERVAL).map(|_| Msg::CheckReminders),
+            iced::time::every(NOTICE_CHECK_INTERVAL).map(|_| Msg::CheckNotices),
+        ])
+    } else {
+        Subscription::none()
+    }
+}
+
+pub fn view<'a>(
+    state: &'a SettingsState,
+    ledger: &'a Ledger,
+    usage: &'a UsageStats,
+) -> Element<'a, Msg> {
+    let content = global_content_container(
+        column![
+            view_data_section(state),
+            view_transfer_section(state),
+            view_snapshot_section(state),
+            view_money_section(state),
+            view_fee_note_section(state),
+            view_blackout_section(state),
+            view_locations_section(state),
+            view_subjects_section(state),
+            view_rates_section(state),
+            view_reminders_section(state),
+            view_notices_section(state),
+            view_trash_section(state, ledger),
+            view_usage_section(state, usage)
+        ]
+        .spacing(40),
+    )
+    .width(Length::Fill)
+    .height(Length::Fill);
+
+    column![page_header("Settings"), content].into()
+}
+
+fn section_title<'a>(title: &'a str) -> iced::widget::Text<'a> {
+    text(title).size(14).font(Font {
+        weight: font::Weight::Medium,
+        ..Default::default()
+    })
+}
+
+fn view_data_section(state: &SettingsState) -> Element<'_, Msg> {
+    let body: Element<'_, Msg> = match &state.data_dirs {
+        Ok(data_dirs) => {
+            let paths = Column::new()
+                .extend(data_dirs.entries().into_iter().map(|(label, path)| {
+                    row![
+                        text(label).size(12).width(Length::Fixed(90.0)),
+                        text(path.display().to_string()).size(12),
+                    ]
+                    .spacing(10)
+                    .into()
+                }))
+                .spacing(6);
+
+            let open_button =
+                button(text("Open data folder").size(14)).on_press(Msg::OpenDataFolder);
+
+            let location = if data_dirs.portable {
+                "Portable mode: data is kept in a folder next to the app"
+            } else {
+                "Data is kept in this computer's app data folder"
+            };
+
+            column![
+                text(location).size(12),
+                paths,
+                row![open_button].align_y(Center)
+            ]
+            .spacing(12)
+            .into()
+        }
+        Err(e) => error_text(e.to_string()).into(),
+    };
+
+    let mut section = column![section_title("Data"), body].spacing(12);
+    if let Some(error) = &state.data_folder_error {
+        section = section.push(error_text(error.clone()));
+    }
+
+    section.into()
+}
+
+fn view_transfer_section(state: &SettingsState) -> Element<'_, Msg> {
+    let hint = text(
+        "Export everything as a versioned JSON file for backups, moving to \
+         another computer or other tools. Importing a file replaces the data \
+         held now, after warning about anything that would be lost.",
+    )
+    .size(12);
+
+    let controls = row![
+        button(text("Export all data").size(14)).on_press(Msg::ExportAllData),
+        text_input("Path to a Tutor Manager .json file", &state.import_path)
+            .on_input(Msg::ImportPathChanged)
+            .on_submit(Msg::ImportData)
+            .size(14)
+            .width(Length::Fixed(360.0)),
+        button(
+            text(if state.importing {
+                "Importing..."
+            } else {
+                "Import"
+            })
+            .size(14)
+        )
+        .on_press(Msg::ImportData),
+    ]
+    .spacing(10)
+    .align_y(Center);
+
+    let mut section = column![section_title("Import and export"), hint, controls].spacing(12);
+    if let Some((_, conflicts)) = &state.pending_import {
+        section = section.push(
+            column![text("Importing this file would replace data held here:").size(12)]
+                .extend(
+                    conflicts
+                        .iter()
+                        .map(|conflict| error_text(format!("• {}", conflict)).into()),
+                )
+                .push(
+                    row![
+                        button(text("Cancel").size(14))
+                            .style(button::secondary)
+                            .on_press(Msg::CancelImport),
+                        button(text("Replace my data").size(14))
+                            .style(button::danger)
+                            .on_press(Msg::ConfirmImport),
+                    ]
+                    .spacing(10),
+                )
+                .spacing(6),
+        );
+    }
+    if let Some(message) = &state.transfer_message {
+        section = section.push(text(message.clone()).size(12));
+    }
+
+    section.into()
+}
+
+fn view_snapshot_section(state: &SettingsState) -> Element<'_, Msg> {
+    let recorded = match state
+        .domain
+        .as_ref()
+        .map(|domain| domain.monthly_snapshots.as_slice())
+    {
+        Some([.., latest]) => format!(
+            "Totals are recorded as each month ends, most recently for {}. \
+             Trends compare against these, so recompute them after correcting \
+             sessions from past months. Imports recompute them on their own.",
+            month_label(latest.year, latest.month)
+        ),
+        _ => "Totals are recorded as each month ends. None have been recorded yet.".to_string(),
+    };
+
+    let recompute = button(text("Recompute analytics").size(14)).on_press_maybe(
+        state
+            .recomputing
+            .is_none()
+            .then_some(Msg::RecomputeAnalytics),
+    );
+
+    let mut section = column![
+        section_title("Monthly totals"),
+        text(recorded).size(12),
+        row![recompute]
+    ]
+    .spacing(12);
+    if let Some((done, total)) = state.recomputing {
+        section = section.push(
+            column![
+                progress_bar(0.0..=total.max(1) as f32, done as f32)
+                    .length(Length::Fixed(320.0))
+                    .girth(6),
+                text(format!("Recomputing {} of {} months...", done, total)).size(12),
+            ]
+            .spacing(6),
+        );
+    }
+    if let Some(message) = &state.snapshot_message {
+        section = section.push(text(message.clone()).size(12));
+    }
+
+    section.into()
+}
+
+fn view_money_section(state: &SettingsState) -> Element<'_, Msg> {
+    let money = state.money_format();
+    let decimals: Vec<u8> = (0..=MoneyFormat::MAX_DECIMALS).collect();
+
+    let controls = row![
+        text("Decimal places").size(12),
+        pick_list(decimals, Some(money.decimals), move |decimals| {
+            Msg::MoneyFormatChanged(MoneyFormat { decimals, ..money })
+        })
+        .text_size(12),
+        checkbox(money.thousands_separator)
+            .label("Thousands separators")
+            .text_size(12)
+            .on_toggle(move |thousands_separator| {
+                Msg::MoneyFormatChanged(MoneyFormat {
+                    thousands_separator,
+                    ..money
+                })
+            }),
+    ]
+    .spacing(10)
+    .align_y(Center);
+
+    column![
+        section_title("Money"),
+        text(format!(
+            "Amounts are rounded and shown like this everywhere: {}",
+            money.format(1234.5)
+        ))
+        .size(12),
+        controls
+    ]
+    .spacing(12)
+    .into()
+}
+
+fn view_fee_note_section(state: &SettingsState) -> Element<'_, Msg> {
+    let hint = text(
+        "Fee notes copied from the Invoices screen end with these instructions, \
+         e.g. a mobile money number or bank account.",
+    )
+    .size(12);
+
+    let saved = state
+        .domain
+        .as_ref()
+        .is_some_and(|domain| domain.payment_instructions == state.payment_instructions.trim());
+    let save = Msg::SavePaymentInstructions(state.payment_instructions.clone());
+
+    let controls = row![
+        text_input("How students should pay", &state.payment_instructions)
+            .on_input(Msg::PaymentInstructionsEdited)
+            .on_submit(save.clone())
+            .size(14)
+            .width(Length::Fixed(360.0)),
+        button(text("Save").size(14)).on_press_maybe((!saved).then_some(save)),
+    ]
+    .spacing(10)
+    .align_y(Center);
+
+    column![section_title("Fee notes"), hint, controls]
+        .spacing(12)
+        .into()
+}
+
+fn view_blackout_section(state: &SettingsState) -> Element<'_, Msg> {
+    let hint = text(
+        "Holidays and other days off. The dashboard leaves them out of the \
+         sessions still to come this month.",
+    )
+    .size(12);
+
+    let blackout_dates = state
+        .domain
+        .as_ref()
+        .map(|domain| domain.blackout_dates.as_slice())
+        .unwrap_or_default();
+
+    let controls = row![
+        text_input("YYYY-MM-DD", &state.blackout_input)
+            .on_input(Msg::BlackoutInputChanged)
+            .on_submit(Msg::AddBlackoutDate)
+            .size(14)
+            .width(Length::Fixed(160.0)),
+        button(text("Add day off").size(14)).on_press(Msg::AddBlackoutDate),
+    ]
+    .spacing(10)
+    .align_y(Center);
+
+    let dates = Column::new()
+        .extend(blackout_dates.iter().map(|date| {
+            let remaining: Vec<NaiveDate> = blackout_dates
+                .iter()
+                .copied()
+                .filter(|other| other != date)
+                .collect();
+
+            row![
+                text(date.format("%a %-d %b %Y").to_string())
+                    .size(12)
+                    .width(Length::Fixed(120.0)),
+                button(text("Remove").size(12))
+                    .style(button::text)
+                    .on_press(Msg::BlackoutDatesChanged(remaining)),
+            ]
+            .spacing(10)
+            .align_y(Center)
+            .into()
+        }))
+        .spacing(4);
+
+    let mut section = column![section_title("Days off"), hint, controls].spacing(12);
+    if let Some(error) = &state.blackout_error {
+        section = section.push(text(error.clone()).size(12));
+    }
+
+    section.push(dates).into()
+}
+
+fn view_locations_section(state: &SettingsState) -> Element<'_, Msg> {
+    let hint = text(
+        "Where sessions are held. A surcharge is added to the fee of every \
+         session held there, on invoices and in reports. Travel to home \
+         visits is logged for the mileage report.",
+    )
+    .size(12);
+
+    let money = state.money_format();
+    let locations = state
+        .domain
+        .as_ref()
+        .map(|domain| domain.locations.as_slice())
+        .unwrap_or_default();
+
+    let controls = row![
+        text_input("Home visit", &state.location_name)
+            .on_input(Msg::LocationNameChanged)
+            .on_submit(Msg::AddLocation)
+            .size(14)
+            .width(Length::Fixed(160.0)),
+        text_input("Surcharge", &state.location_surcharge)
+            .on_input(Msg::LocationSurchargeChanged)
+            .on_submit(Msg::AddLocation)
+            .size(14)
+            .width(Length::Fixed(100.0)),
+        checkbox(state.location_home_visit)
+            .label("Home visit")
+            .text_size(12)
+            .on_toggle(Msg::LocationHomeVisitToggled),
+        button(text("Add location").size(14)).on_press(Msg::AddLocation),
+    ]
+    .spacing(10)
+    .align_y(Center);
+
+    let list = Column::new()
+        .extend(locations.iter().map(|location| {
+            let remaining: Vec<Location> = locations
+                .iter()
+                .filter(|other| other.name != location.name)
+                .cloned()
+                .collect();
+
+            row![
+                text(location.name.clone())
+                    .size(12)
+                    .width(Length::Fixed(160.0)),
+                text(format!("+{} per session", money.format(location.surcharge)))
+                    .size(12)
+                    .width(Length::Fixed(160.0)),
+                text(if location.home_visit {
+                    "Home visit"
+                } else {
+                    ""
+                })
+                .size(12)
+                .width(Length::Fixed(80.0)),
+                button(text("Remove").size(12))
+                    .style(button::text)
+                    .on_press(Msg::LocationsChanged(remaining)),
+            ]
+            .spacing(10)
+            .align_y(Center)
+            .into()
+        }))
+        .spacing(4);
+
+    let mut section = column![section_title("Locations"), hint, controls].spacing(12);
+    if let Some(error) = &state.location_error {
+        section = section.push(text(error.clone()).size(12));
+    }
+
+    section.push(list).into()
+}
+
+fn view_subjects_section(state: &SettingsState) -> Element<'_, Msg> {
+    let hint = text(
+        "Subjects you teach, offered when adding students, quoting and setting rates. \
+         A subject someone is still taking cannot be removed.",
+    )
+    .size(12);
+
+    let controls = row![
+        text_input("Subject", &state.subject_input)
+            .on_input(Msg::SubjectInputChanged)
+            .on_submit(Msg::AddSubject)
+            .size(14)
+            .width(Length::Fixed(220.0)),
+        button(text("Add subject").size(14)).on_press(Msg::AddSubject),
+    ]
+    .spacing(10)
+    .align_y(Center);
+
+    let list = Column::new().spacing(4);
+    let list = match &state.domain {
+        Some(domain) => list.extend(domain.tutor.subjects.iter().map(|subject| {
+            let taken = domain
+                .students
+                .iter()
+                .any(|student| student.subjects().any(|taken| taken == subject));
+            let remaining: Vec<TutorSubject> = domain
+                .tutor
+                .subjects
+                .iter()
+                .filter(|other| *other != subject)
+                .cloned()
+                .collect();
+
+            row![
+                text(subject.to_string())
+                    .size(12)
+                    .width(Length::Fixed(220.0)),
+                button(text("Remove").size(12))
+                    .style(button::text)
+                    .on_press_maybe((!taken).then_some(Msg::SubjectsChanged(remaining))),
+            ]
+            .spacing(10)
+            .align_y(Center)
+            .into()
+        })),
+        None => list,
+    };
+
+    let mut section = column![section_title("Subjects"), hint, controls].spacing(12);
+    if let Some(error) = &state.subject_error {
+        section = section.push(text(error.clone()).size(12));
+    }
+
+    section.push(list).into()
+}
+
+fn view_rates_section(state: &SettingsState) -> Element<'_, Msg> {
+    let hint = text("Hourly rates used when quoting fees to new students.").size(12);
+
+    let money = state.money_format();
+    let (subjects, rates) = state
+        .domain
+        .as_ref()
+        .map(|domain| (domain.tutor.subjects.clone(), domain.rates.as_slice()))
+        .unwrap_or_default();
+
+    let controls = row![
+        pick_list(
+            subjects,
+            state.rate_subject.clone(),
+            Msg::RateSubjectSelected
+        )
+        .placeholder("Subject")
+        .text_size(14)
+        .width(Length::Fixed(220.0)),
+        text_input("Per hour", &state.rate_input)
+            .on_input(Msg::RateInputChanged)
+            .on_submit(Msg::SetRate)
+            .size(14)
+            .width(Length::Fixed(100.0)),
+        button(text("Set rate").size(14)).on_press(Msg::SetRate),
+    ]
+    .spacing(10)
+    .align_y(Center);
+
+    let list = Column::new()
+        .extend(rates.iter().map(|rate| {
+            let remaining: Vec<SubjectRate> = rates
+                .iter()
+                .filter(|other| other.subject != rate.subject)
+                .cloned()
+                .collect();
+
+            row![
+                text(rate.subject.to_string())
+                    .size(12)
+                    .width(Length::Fixed(220.0)),
+                text(format!("{} per hour", money.format(rate.per_hour)))
+                    .size(12)
+                    .width(Length::Fixed(160.0)),
+                button(text("Remove").size(12))
+                    .style(button::text)
+                    .on_press(Msg::RatesChanged(remaining)),
+            ]
+            .spacing(10)
+            .align_y(Center)
+            .into()
+        }))
+        .spacing(4);
+
+    let mut section = column![section_title("Rates"), hint, controls].spacing(12);
+    if let Some(error) = &state.rate_error {
+        section = section.push(text(error.clone()).size(12));
+    }
+
+    section.push(list).into()
+}
+
+fn view_trash_section<'a>(state: &'a SettingsState, ledger: &'a Ledger) -> Element<'a, Msg> {
+    let hint = text(format!(
+        "Deleted students, sessions and payments are kept here for {} days \
+         before they are gone for good.",
+        TRASH_RETENTION_DAYS
+    ))
+    .size(12);
+
+    let money = state.money_format();
+    let trash = state
+        .domain
+        .as_ref()
+        .map(|domain| domain.trash.as_slice())
+        .unwrap_or_default();
+    let names = |student_id: &str| {
+        state
+            .domain
+            .as_ref()
+            .and_then(|domain| domain.students.iter().find(|s| s.id == student_id))
+            .map(|student| student.name.full_name())
+            .unwrap_or_else(|| student_id.to_string())
+    };
+
+    let items = trash
+        .iter()
+        .enumerate()
+        .map(|(index, trashed)| {
+            view_trashed(
+                trashed.item.to_string(),
+                trashed.expires_on(),
+                Msg::RestoreTrashed(index),
+                Msg::DiscardTrashed(index),
+            )
+        })
+        .chain(
+            ledger
+                .trashed_payments()
+                .iter()
+                .enumerate()
+                .map(|(index, trashed)| {
+                    let payment = &trashed.item;
+                    view_trashed(
+                        format!(
+                            "{} paid by {} on {}",
+                            money.format(payment.amount),
+                            names(&payment.student_id),
+                            payment.paid_on.format("%-d %b %Y")
+                        ),
+                        trashed.expires_on(),
+                        Msg::RestorePayment(index),
+                        Msg::DiscardPayment(index),
+                    )
+                }),
+        )
+        .collect::<Vec<_>>();
+
+    let body: Element<'_, Msg> = if items.is_empty() {
+        text("The trash is empty").size(12).into()
+    } else {
+        Column::with_children(items).spacing(4).into()
+    };
+
+    column![section_title("Trash"), hint, body]
+        .spacing(12)
+        .into()
+}
+
+fn view_trashed<'a>(
+    label: String,
+    expires_on: NaiveDate,
+    restore: Msg,
+    discard: Msg,
+) -> Element<'a, Msg> {
+    row![
+        text(label).size(12).width(Length::Fill),
+        text(format!("Kept until {}", expires_on.format("%-d %b %Y"))).size(12),
+        button(text("Restore").size(12))
+            .style(button::text)
+            .on_press(restore),
+        button(text("Delete now").size(12))
+            .style(button::text)
+            .on_press(discard),
+    ]
+    .spacing(10)
+    .align_y(Center)
+    .into()
+}
+
+fn view_usage_section<'a>(state: &'a SettingsState, usage: &'a UsageStats) -> Element<'a, Msg> {
+    let hint = text(
+        "How you keep your records, for your own reflection. These figures stay \
+         in the data folder on this computer and are never sent anywhere.",
+    )
+    .size(12);
+
+    let today = state.clock().today();
+    let weeks = usage.logged_per_week(today, USAGE_WEEKS);
+    let busiest = weeks
+        .iter()
+        .map(|week| week.logged)
+        .max()
+        .unwrap_or(0)
+        .max(1);
+    let per_week = Column::new()
+        .push(text("Sessions logged per week").size(12))
+        .extend(weeks.iter().map(|week| {
+            row![
+                text(week.week.format("%-d %b").to_string())
+                    .size(12)
+                    .width(Length::Fixed(60.0)),
+                progress_bar(0.0..=busiest as f32, week.logged as f32)
+                    .length(Length::Fixed(200.0))
+                    .girth(8),
+                text(format!(
+                    "{} logged, {} on the day",
+                    week.logged, week.same_day
+                ))
+                .size(12),
+            ]
+            .spacing(10)
+            .align_y(Center)
+            .into()
+        }))
+        .spacing(4);
+
+    let most_used = usage.most_used(USAGE_TOP_ACTIONS);
+    let actions: Element<'_, Msg> = if most_used.is_empty() {
+        text("Nothing recorded yet").size(12).into()
+    } else {
+        Column::new()
+            .push(text("Most used").size(12))
+            .extend(most_used.into_iter().map(|(action, times)| {
+                row![
+                    text(action).size(12).width(Length::Fixed(260.0)),
+                    text(times.to_string()).size(12),
+                ]
+                .spacing(10)
+                .into()
+            }))
+            .spacing(4)
+            .into()
+    };
+
+    column![
+        section_title("Usage"),
+        hint,
+        per_week,
+        actions,
+        row![button(text("Clear usage statistics").size(14)).on_press(Msg::ClearUsage)]
+    ]
+    .spacing(12)
+    .into()
+}
+
+fn view_reminders_section(state: &SettingsState) -> Element<'_, Msg> {
+    let lead_hours = state.reminders.lead_hours();
+    let lead = match lead_hours {
+        1 => "an hour".to_string(),
+        24 => "a day".to_string(),
+        hours => format!("{} hours", hours),
+    };
+    let hint = text(format!(
+        "A reminder is written {} before each session of opted-in students. \
+         The app does not send email: reminders are saved as .eml files in \
+         the outbox folder, and you must send each one yourself from your \
+         mail app.",
+        lead
+    ))
+    .size(12);
+    let open_outbox = button(text("Open outbox folder").size(12))
+        .on_press_maybe(state.data_dirs.is_ok().then_some(Msg::OpenOutbox));
+    let lead_stepper = row![
+        text("Write reminders").size(12),
+        number_stepper(
+            u32::try_from(lead_hours).unwrap_or(1),
+            1..=MAX_LEAD_HOURS as u32,
+            1,
+            |hours| Msg::ReminderLeadChanged(i64::from(hours))
+        ),
+        text("hours before a session").size(12),
+    ]
+    .spacing(10)
+    .align_y(Center);
+
+    let students = state
+        .domain
+        .as_ref()
+        .map(|domain| domain.students.as_slice())
+        .unwrap_or_default();
+    let now = state.clock().now();
+    let today = state.clock().today();
+
+    let contacts = Column::new()
+        .extend(students.iter().map(|student| {
+            let contact = state.reminders.contact(&student.id);
+            let opt_in_id = student.id.clone();
+            let email = student
+                .email
+                .as_deref()
+                .map(str::trim)
+                .filter(|email| !email.is_empty());
+            let copy_contact = email.map(|email| {
+                Msg::Effect(Effect::Copy(CopyRequest::new(
+                    format!("{} <{}>", student.name.full_name(), email),
+                    "contact",
+                )))
+            });
+            let email_text = match email {
+                Some(email) => text(email),
+                None => text("No email in the student's details").color(theme::MUTED),
+            };
+
+            row![
+                text(student.name.full_name())
+                    .size(12)
+                    .width(Length::Fixed(160.0)),
+                email_text.size(12).width(Length::Fixed(240.0)),
+                copy_button().on_press_maybe(copy_contact),
+                checkbox(contact.opted_in)
+                    .label("Save reminders")
+                    .text_size(12)
+                    .on_toggle(move |opted_in| {
+                        Msg::ReminderOptInToggled(opt_in_id.clone(), opted_in)
+                    }),
+                view_mute_control(&student.id, contact.muted_until, today),
+            ]
+            .spacing(10)
+            .align_y(Center)
+            .into()
+        }))
+        .spacing(6);
+
+    let log = Column::new()
+        .extend(state.reminders.log().take(10).map(|entry| {
+            let status = match &entry.status {
+                DeliveryStatus::WrittenToOutbox => "In the outbox, send it yourself".to_string(),
+                DeliveryStatus::Failed(reason) => {
+                    format!("Not saved after {} attempts: {}", entry.attempts, reason)
+                }
+            };
+
+            text(format!(
+                "{} · {} · {}",
+                entry.student_name,
+                entry.session_at.format("%a %-d %b %-I:%M %p"),
+                status
+            ))
+            .size(12)
+            .into()
+        }))
+        .spacing(4);
+
+    let upcoming = Column::new()
+        .extend(
+            state
+                .reminders
+                .upcoming(
+                    students,
+                    now,
+                    now + chrono::Duration::days(UPCOMING_REMINDER_DAYS),
+                )
+                .into_iter()
+                .map(|reminder| {
+                    let goes_out = match state.reminders.snoozed_until(&reminder) {
+                        Some(snoozed_until) => format!(
+                            "snoozed until {}",
+                            snoozed_until.format("%a %-d %b %-I:%M %p")
+                        ),
+                        None => format!(
+                            "written to the outbox {}",
+                            state
+                                .reminders
+                                .send_at(&reminder)
+                                .format("%a %-d %b %-I:%M %p")
+                        ),
+                    };
+
+                    row![
+                        text(format!(
+                            "{} · {} · {}",
+                            reminder.student_name,
+                            reminder.session_at.format("%a %-d %b %-I:%M %p"),
+                            goes_out
+                        ))
+                        .size(12),
+                        button(text(format!("Snooze {}h", SNOOZE_HOURS)).size(12))
+                            .style(button::text)
+                            .on_press(Msg::SnoozeReminder(reminder)),
+                    ]
+                    .spacing(10)
+                    .align_y(Center)
+                    .into()
+                }),
+        )
+        .spacing(4);
+
+    let mut section = column![
+        section_title("Session reminders (outbox only)"),
+        hint,
+        open_outbox,
+        lead_stepper,
+        contacts,
+        section_title("Upcoming"),
+        upcoming,
+        section_title("Outbox log"),
+        log
+    ]
+    .spacing(12);
+    if let Some(error) = &state.reminder_log_error {
+        section = section.push(error_text(error.clone()));
+    }
+
+    section.into()
+}
+
+fn view_notices_section(state: &SettingsState) -> Element<'_, Msg> {
+    let hint = text(
+        "A desktop notification shows before each session, as far ahead as set \
+         here or for the student below, while the app is open.",
+    )
+    .size(12);
+    let lead_stepper = row![
+        text("Notify").size(12),
+        number_stepper(
+            u32::try_from(state.notices.lead_minutes()).unwrap_or(1),
+            1..=MAX_NOTICE_MINUTES as u32,
+            5,
+            |minutes| Msg::NoticeMinutesChanged(i64::from(minutes))
+        ),
+        text("minutes before a session").size(12),
+    ]
+    .spacing(10)
+    .align_y(Center);
+
+    let students = state
+        .domain
+        .as_ref()
+        .map(|domain| domain.students.as_slice())
+        .unwrap_or_default();
+    let per_student = Column::new()
+        .extend(students.iter().map(|student| {
+            let student_id = student.id.clone();
+            row![
+                text(student.name.full_name())
+                    .size(12)
+                    .width(Length::Fixed(160.0)),
+                pick_list(
+                    StudentNotices::CHOICES,
+                    Some(state.notices.student(&student.id)),
+                    move |notices| Msg::StudentNoticesChanged(student_id.clone(), notices),
+                )
+                .text_size(12),
+            ]
+            .spacing(10)
+            .align_y(Center)
+            .into()
+        }))
+        .spacing(6);
+
+    let mut section = column![
+        section_title("Desktop notifications"),
+        hint,
+        lead_stepper,
+        per_student
+    ]
+    .spacing(12);
+    if let Some(error) = &state.notice_error {
+        section = section.push(error_text(error.clone()));
+    }
+
+    section.into()
+}
+
+fn error_text<'a>(message: String) -> iced::widget::Text<'a> {
+    text(message).size(12).style(|_theme: &Theme| text::Style {
+        color: Some(theme::DANGER),
+    })
+}
+
+fn view_mute_control<'a>(
+    student_id: &str,
+    muted_until: Option<NaiveDate>,
+    today: NaiveDate,
+) -> Element<'a, Msg> {
+    let student_id = student_id.to_string();
+
+    match muted_until.filter(|muted_until| *muted_until >= today) {
+        Some(muted_until) => row![
+            text(format!("Muted until {}", muted_until.format("%-d %b"))).size(12),
+            button(text("Unmute").size(12))
+                .style(button::text)
+                .on_press(Msg::ReminderMuted(student_id, None)),
+        ]
+        .spacing(6)
+        .align_y(Center)
+        .into(),
+        None => pick_list(MuteWeeks::ALL, None::<MuteWeeks>, move |weeks| {
+            Msg::ReminderMuted(
+                student_id.clone(),
+                today.checked_add_days(Days::new(weeks.0 * 7)),
+            )
+        })
+        .placeholder("Mute for...")
+        .text_size(12)
+        .into(),
+    }
+}
@@ -1,14 +1,59 @@
+use chrono::{DateTime, Local};
+use iced::clipboard;
+use iced::keyboard::{self, Key};
+use iced::widget::operation::focus;
 use iced::window::frames;
-use std::time::Instant;
+use std::time::{Duration, Instant};
 
 use lilt::{Animated, Easing};
 
 use iced::advanced::graphics::core::font;
 use iced::mouse::Interaction;
-use iced::widget::{Container, column, container, mouse_area, row, svg, text};
+use iced::widget::{
+    Column, Container, button, column, container, mouse_area, row, space, stack, svg, text,
+    text_input,
+};
 use iced::{Background, Border, Center, Color, Element, Font, Length, Subscription, Task, Theme};
 
+use crate::domain::Clock;
 use crate::icons;
+use crate::money::MoneyFormat;
+use crate::routes::Route;
+use crate::search::{SearchResult, SearchTarget};
+
+const SEARCH_INPUT_ID: &str = "global-search";
+/// Results listed per group before the rest are left out.
+const SEARCH_RESULTS_PER_GROUP: usize = 5;
+/// How long a confirmation toast stays up.
+const TOAST_DURATION: Duration = Duration::from_secs(3);
+
+/// Text to put on the clipboard and a few words naming it for the
+/// confirmation, e.g. `fee note`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CopyRequest {
+    pub text: String,
+    pub what: String,
+}
+
+impl CopyRequest {
+    pub fn new(text: impl Into<String>, what: impl Into<String>) -> Self {
+        Self {
+            text: text.into(),
+            what: what.into(),
+        }
+    }
+}
+
+/// Something a screen asks the app to do beyond its own state.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Effect {
+    /// Puts text on the clipboard and confirms it with a toast.
+    Copy(CopyRequest),
+    /// Opens another screen, or something within one.
+    Navigate(Route),
+    /// Opens a link, such as a session recording, in the browser.
+    Open(String),
+}
 
 pub struct ShellState {
     pub current_screen: Screen,
@@ -19,6 +64,16 @@ pub struct ShellState {
     pub animated_menu_width_change: Animated<bool, Instant>,
     pub animated_menu_item_height_change: Animated<bool, Instant>,
     pub show_menu_text: bool,
+
+    pub search_open: bool,
+    pub search_query: String,
+
+    /// Every route visited, for going back and forward.
+    history: Vec<Route>,
+    history_position: usize,
+
+    /// A short confirmation and when it went up.
+    toast: Option<(String, Instant)>,
 }
 
 impl Default for ShellState {
@@ -36,14 +91,114 @@ impl Default for ShellState {
                 .duration(200.)
                 .easing(Easing::EaseInOut),
             show_menu_text: false,
+
+            search_open: false,
+            search_query: String::new(),
+
+            history: vec![Route::Dashboard],
+            history_position: 0,
+
+            toast: None,
+        }
+    }
+}
+
+impl ShellState {
+    pub fn route(&self) -> &Route {
+        &self.history[self.history_position]
+    }
+
+    /// Goes to `route`, dropping anything that was ahead in the history.
+    pub fn open(&mut self, route: Route) {
+        if *self.route() != route {
+            self.history.truncate(self.history_position + 1);
+            self.history.push(route);
+            self.history_position += 1;
+        }
+        self.show_route();
+    }
+
+    /// Updates what the current screen has open without adding a step to
+    /// the history, e.g. when the reports month changes.
+    pub fn replace_route(&mut self, route: Route) {
+        self.history[self.history_position] = route;
+    }
+
+    pub fn can_go_back(&self) -> bool {
+        self.history_position > 0
+    }
+
+    pub fn can_go_forward(&self) -> bool {
+        self.history_position + 1 < self.history.len()
+    }
+
+    /// Carries out an effect a screen asked for.
+    pub fn run<T>(&mut self, effect: Effect) -> Task<T> {
+        match effect {
+            Effect::Copy(request) => self.copy(request),
+            Effect::Navigate(route) => {
+                self.open(route);
+                Task::none()
+            }
+            Effect::Open(link) => {
+                if let Err(e) = open::that_detached(&link) {
+                    self.notify(format!("Could not open the link: {}", e));
+                }
+                Task::none()
+            }
         }
     }
+
+    /// Puts the text on the clipboard and confirms it with a toast.
+    pub fn copy<T>(&mut self, request: CopyRequest) -> Task<T> {
+        self.notify(format!("Copied {}", request.what));
+        clipboard::write(request.text)
+    }
+
+    /// Shows `message` in a toast for a few seconds.
+    pub fn notify(&mut self, message: String) {
+        self.toast = Some((message, Instant::now()));
+    }
+
+    pub fn toast(&self) -> Option<&str> {
+        self.toast.as_ref().map(|(message, _)| message.as_str())
+    }
+
+    fn show_route(&mut self) {
+        let item = self.route().menu_item();
+        self.selected_menu_item = item;
+        self.current_screen = match self.route() {
+            Route::StudentDetail(_) => Screen::StudentDetail,
+            Route::Compare => Screen::Compare,
+            _ => item.into(),
+        };
+    }
+}
+
+/// Figures shown in the header strip on every screen.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct QuickStats {
+    pub todays_sessions: usize,
+    pub unpaid_balance: f32,
+    pub money: MoneyFormat,
+    /// When the next session starts and who it is with.
+    pub next_session: Option<(DateTime<Local>, String)>,
+    /// What the countdown to the next session is measured from.
+    pub clock: Clock,
 }
 
 #[derive(Debug)]
 pub enum Screen {
     Dashboard,
     StudentManager,
+    /// Reached from a student card rather than the menu.
+    StudentDetail,
+    /// Reached from the Student Manager.
+    Compare,
+    Calendar,
+    Reconciliation,
+    Invoices,
+    Reports,
     Settings,
     Logout,
 }
@@ -52,6 +207,10 @@ pub enum Screen {
 pub enum SideMenuItem {
     Dashboard,
     StudentManager,
+    Calendar,
+    Reconciliation,
+    Invoices,
+    Reports,
     Settings,
     Logout,
 }
@@ -61,6 +220,10 @@ impl Into<Screen> for SideMenuItem {
         match self {
             SideMenuItem::Dashboard => Screen::Dashboard,
             SideMenuItem::StudentManager => Screen::StudentManager,
+            SideMenuItem::Calendar => Screen::Calendar,
+            SideMenuItem::Reconciliation => Screen::Reconciliation,
+            SideMenuItem::Invoices => Screen::Invoices,
+            SideMenuItem::Reports => Screen::Reports,
             SideMenuItem::Settings => Screen::Settings,
             SideMenuItem::Logout => Screen::Logout,
         }
@@ -73,14 +236,53 @@ pub enum Msg {
     MenuItemHovered(Option<SideMenuItem>),
     SideMenuHovered(bool),
     Tick,
+    Back,
+    Forward,
+    CopyLink,
+    ToggleSearch,
+    CloseSearch,
+    SearchQueryChanged(String),
+    OpenSearchResult(SearchTarget),
 }
 
-pub fn update(state: &mut ShellState, msg: Msg) {
+pub fn update(state: &mut ShellState, msg: Msg) -> Task<Msg> {
     match msg {
         Msg::NavigateTo(item) => {
+            state.open(item.into());
             state.selected_menu_item = item;
             state.current_screen = item.into();
         }
+        Msg::Back => {
+            if state.can_go_back() {
+                state.history_position -= 1;
+                state.show_route();
+            }
+        }
+        Msg::Forward => {
+            if state.can_go_forward() {
+                state.history_position += 1;
+                state.show_route();
+            }
+        }
+        Msg::CopyLink => {
+            let link = state.route().link();
+            return state.copy(CopyRequest::new(link, "link"));
+        }
+        Msg::ToggleSearch => {
+            state.search_open = !state.search_open;
+            if state.search_open {
+                return focus(SEARCH_INPUT_ID);
+            }
+        }
+        Msg::CloseSearch => state.search_open = false,
+        Msg::SearchQueryChanged(query) => state.search_query = query,
+        Msg::OpenSearchResult(target) => {
+            state.search_open = false;
+            state.open(match target {
+                SearchTarget::Student(student_id) => Route::Students(Some(student_id)),
+                SearchTarget::Payment(_) => Route::Dashboard,
+            });
+        }
         Msg::SideMenuHovered(is_hovered) => {
             let now = Instant::now();
             state.animated_menu_width_change.transition(is_hovered, now);
@@ -90,20 +292,179 @@ pub fn update(state: &mut ShellState, msg: Msg) {
         Msg::MenuItemHovered(is_hovered_opt) => {
             state.hovered_menu_item = is_hovered_opt;
         }
-        Msg::Tick => (),
+        Msg::Tick => {
+            if state
+                .toast
+                .as_ref()
+                .is_some_and(|(_, shown_at)| shown_at.elapsed() >= TOAST_DURATION)
+            {
+                state.toast = None;
+            }
+        }
     }
+
+    Task::none()
 }
 
 pub fn view<'a, Message: 'a>(
     state: &'a ShellState,
+    quick_stats: QuickStats,
+    search_results: Vec<SearchResult>,
     content: Element<'a, Message>,
-    map_msg: impl Fn(Msg) -> Message + 'a,
+    map_msg: impl Fn(Msg) -> Message + Clone + 'a,
 ) -> Element<'a, Message> {
-    row![view_side_menu(state).map(map_msg), container(content)]
+    let mut main = column![view_quick_stats(state, quick_stats).map(map_msg.clone())];
+    if state.search_open {
+        main = main.push(view_search(state, search_results).map(map_msg.clone()));
+    }
+
+    let mut main_with_toast = stack![main.push(container(content))];
+    if let Some(message) = state.toast() {
+        main_with_toast = main_with_toast.push(view_toast(message));
+    }
+
+    row![view_side_menu(state).map(map_msg), main_with_toast]
         // .spacing(20)
         .into()
 }
 
+fn view_toast<'a, Message: 'a>(message: &'a str) -> Element<'a, Message> {
+    let toast = container(text(message).size(13).style(|_: &Theme| text::Style {
+        color: Some(Color::WHITE),
+    }))
+    .padding([10, 16])
+    .style(|_: &Theme| container::Style {
+        background: Some(Background::Color(Color::from_rgba(0.1, 0.1, 0.1, 0.9))),
+        border: Border {
+            radius: 8.0.into(),
+            ..Default::default()
+        },
+        ..Default::default()
+    });
+
+    container(toast)
+        .width(Length::Fill)
+        .height(Length::Fill)
+        .align_x(Center)
+        .align_y(iced::alignment::Vertical::Bottom)
+        .padding(24)
+        .into()
+}
+
+fn view_search(state: &ShellState, results: Vec<SearchResult>) -> Element<'_, Msg> {
+    let input = text_input(
+        "Search students, sessions and payments",
+        &state.search_query,
+    )
+    .id(SEARCH_INPUT_ID)
+    .on_input(Msg::SearchQueryChanged)
+    .size(14);
+
+    let mut groups = Column::new().spacing(10);
+    for group in results.chunk_by(|a, b| a.group == b.group) {
+        let heading = text(format!("{} ({})", group[0].group, group.len())).size(12);
+        let entries =
+            Column::new().extend(group.iter().take(SEARCH_RESULTS_PER_GROUP).map(|result| {
+                button(
+                    row![
+                        text(result.title.clone()).size(13),
+                        text(result.detail.clone()).size(12)
+                    ]
+                    .spacing(10)
+                    .align_y(Center),
+                )
+                .style(button::text)
+                .padding([2, 8])
+                .on_press(Msg::OpenSearchResult(result.target.clone()))
+                .into()
+            }));
+
+        groups = groups.push(column![heading, entries].spacing(2));
+    }
+
+    let body: Element<'_, Msg> = if results.is_empty() && !state.search_query.trim().is_empty() {
+        text("No matches").size(12).into()
+    } else {
+        groups.into()
+    };
+
+    container(column![input, body].spacing(10))
+        .width(Length::Fill)
+        .padding([10, 20])
+        .style(container::rounded_box)
+        .into()
+}
+
+fn view_quick_stats<'a>(state: &ShellState, stats: QuickStats) -> Element<'a, Msg> {
+    let strip_button = |label: String| {
+        button(text(label).size(12))
+            .style(button::text)
+            .padding([2, 8])
+    };
+    let stat =
+        |label: String, item: SideMenuItem| strip_button(label).on_press(Msg::NavigateTo(item));
+
+    let todays_sessions = match stats.todays_sessions {
+        1 => "1 session today".to_string(),
+        count => format!("{} sessions today", count),
+    };
+    let next_session = match stats.next_session {
+        Some((at, student)) => format!(
+            "Next: {} {}",
+            student,
+            format_countdown(stats.clock.now(), at)
+        ),
+        None => "No sessions this week".to_string(),
+    };
+
+    container(
+        row![
+            strip_button("←".to_string()).on_press_maybe(state.can_go_back().then_some(Msg::Back)),
+            strip_button("→".to_string())
+                .on_press_maybe(state.can_go_forward().then_some(Msg::Forward)),
+            stat(todays_sessions, SideMenuItem::Dashboard),
+            stat(
+                format!("{} unpaid", stats.money.format(stats.unpaid_balance)),
+                SideMenuItem::Dashboard
+            ),
+            stat(next_session, SideMenuItem::StudentManager),
+            space::horizontal(),
+            strip_button("Copy link".to_string()).on_press(Msg::CopyLink),
+            strip_button("Search (Ctrl+/)".to_string()).on_press(Msg::ToggleSearch),
+        ]
+        .spacing(12)
+        .align_y(Center),
+    )
+    .width(Length::Fill)
+    .padding([4, 20])
+    .style(|theme: &Theme| {
+        let palette = theme.extended_palette();
+
+        container::Style {
+            background: Some(palette.background.weak.color.into()),
+            border: Border {
+                color: palette.background.strong.color,
+                width: 1.0,
+                radius: 0.0.into(),
+            },
+            ..Default::default()
+        }
+    })
+    .into()
+}
+
+/// Time until `at`, e.g. "in 2h 15m", rounded down to the minute.
+fn format_countdown(now: DateTime<Local>, at: DateTime<Local>) -> String {
+    let minutes = (at - now).num_minutes().max(0);
+    let (days, hours, minutes) = (minutes / 1440, minutes / 60 % 24, minutes % 60);
+
+    match (days, hours) {
+        (0, 0) => format!("in {}m", minutes),
+        (0, _) => format!("in {}h {}m", hours, minutes),
+        _ => format!("in {}d {}h", days, hours),
+    }
+}
+
 fn view_side_menu<'a>(state: &'a ShellState) -> Element<'a, Msg> {
     let now = Instant::now();
 
@@ -126,6 +487,34 @@ fn view_side_menu<'a>(state: &'a ShellState) -> Element<'a, Msg> {
                         state,
                         now
                     ),
+                    menu_item(
+                        "Calendar",
+                        icons::schedule(),
+                        SideMenuItem::Calendar,
+                        state,
+                        now
+                    ),
+                    menu_item(
+                        "Reconciliation",
+                        icons::payments(),
+                        SideMenuItem::Reconciliation,
+                        state,
+                        now
+                    ),
+                    menu_item(
+                        "Invoices",
+                        icons::wallet(),
+                        SideMenuItem::Invoices,
+                        state,
+                        now
+                    ),
+                    menu_item(
+                        "Reports",
+                        icons::calendar(),
+                        SideMenuItem::Reports,
+                        state,
+                        now
+                    ),
                 ]
                 .spacing(5),
                 container(
@@ -301,9 +690,101 @@ fn menu_item_container<'a>(
 
 pub fn subscription(state: &ShellState) -> Subscription<Msg> {
     let now = Instant::now();
-    if state.animated_menu_width_change.in_progress(now) {
+    let animation = if state.animated_menu_width_change.in_progress(now) {
         frames().map(|_| Msg::Tick)
     } else {
         Subscription::none()
+    };
+
+    let toast = if state.toast.is_some() {
+        iced::time::every(Duration::from_millis(500)).map(|_| Msg::Tick)
+    } else {
+        Subscription::none()
+    };
+
+    Subscription::batch([animation, toast, keyboard::listen().filter_map(shortcut)])
+}
+
+/// Ctrl+/ (Cmd+/ on macOS) toggles search and Escape closes it.
+/// Alt+Left and Alt+Right go back and forward.
+fn shortcut(event: keyboard::Event) -> Option<Msg> {
+    let keyboard::Event::KeyPressed { key, modifiers, .. } = event else {
+        return None;
+    };
+
+    match key.as_ref() {
+        Key::Character("/") if modifiers.command() => Some(Msg::ToggleSearch),
+        Key::Named(keyboard::key::Named::Escape) => Some(Msg::CloseSearch),
+        Key::Named(keyboard::key::Named::ArrowLeft) if modifiers.alt() => Some(Msg::Back),
+        Key::Named(keyboard::key::Named::ArrowRight) if modifiers.alt() => Some(Msg::Forward),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn countdown_shows_the_two_largest_units() {
+        let now = Local.with_ymd_and_hms(2026, 2, 12, 9, 0, 0).unwrap();
+        let after = |minutes| now + chrono::Duration::minutes(minutes);
+
+        assert_eq!(format_countdown(now, after(45)), "in 45m");
+        assert_eq!(format_countdown(now, after(135)), "in 2h 15m");
+        assert_eq!(format_countdown(now, after(27 * 60 + 5)), "in 1d 3h");
+    }
+    #[test]
+    fn history_goes_back_and_forward_and_drops_the_future_on_a_new_visit() {
+        let mut state = ShellState::default();
+        let _ = update(&mut state, Msg::NavigateTo(SideMenuItem::Reports));
+        let _ = update(
+            &mut state,
+            Msg::OpenSearchResult(SearchTarget::Student("mary-jane".to_string())),
+        );
+
+        let _ = update(&mut state, Msg::Back);
+        assert_eq!(*state.route(), Route::Reports(None));
+        assert!(matches!(state.current_screen, Screen::Reports));
+
+        let _ = update(&mut state, Msg::Forward);
+        assert_eq!(
+            *state.route(),
+            Route::Students(Some("mary-jane".to_string()))
+        );
+
+        let _ = update(&mut state, Msg::Back);
+        let _ = update(&mut state, Msg::NavigateTo(SideMenuItem::Settings));
+        assert!(!state.can_go_forward());
+        assert_eq!(
+            state.history,
+            vec![Route::Dashboard, Route::Reports(None), Route::Settings]
+        );
+    }
+
+    #[test]
+    fn copying_shows_a_toast_until_it_times_out() {
+        let mut state = ShellState::default();
+        let _: Task<Msg> = state.copy(CopyRequest::new("GHS 150.00", "invoice total"));
+        let _ = update(&mut state, Msg::Tick);
+        assert_eq!(state.toast(), Some("Copied invoice total"));
+
+        if let Some((_, shown_at)) = &mut state.toast {
+            *shown_at -= TOAST_DURATION;
+        }
+        let _ = update(&mut state, Msg::Tick);
+        assert_eq!(state.toast(), None);
+    }
+
+    #[test]
+    fn navigate_effects_open_the_route_like_a_link() {
+        let mut state = ShellState::default();
+        let student = Route::Students(Some("mary-jane".to_string()));
+        let _: Task<Msg> = state.run(Effect::Navigate(student.clone()));
+
+        assert_eq!(*state.route(), student);
+        assert!(matches!(state.current_screen, Screen::StudentManager));
+        assert!(state.can_go_back());
     }
 }
@@ -0,0 +1,881 @@
+use chrono::{DateTime, Datelike, Local, NaiveDate};
+use std::rc::Rc;
+
+use crate::domain::{
+    Domain, Location, MonthlySummary, Prospect, RateChange, SessionDistance, SessionLocation,
+    SessionRating, SessionRecord, SessionStatus, Student, SubjectRate, TrashItem, Trashed,
+    TutorSubject,
+};
+use crate::money::MoneyFormat;
+
+/// A change to the domain. Screens hand these to the store instead of
+/// editing their own copies, so every screen sees the same data.
+#[derive(Debug, Clone)]
+pub enum DomainEdit {
+    /// Adds a new student, with any sessions backfilled for them. Ignored
+    /// if a student with the same id is already there.
+    StudentAdded(Student),
+    /// Replaces the student with the same id.
+    StudentUpdated(Student),
+    /// Moves the student, with everything logged for them, to the trash.
+    /// Payments in the ledger are kept.
+    StudentRemoved(String),
+    /// Marks how the session starting at `at` went, replacing any earlier
+    /// mark, or clears the mark with `None`, trashing it with its notes.
+    SessionMarked {
+        student_id: String,
+        at: DateTime<Local>,
+        status: Option<SessionStatus>,
+    },
+    /// Moves the session at `at` to `to` just this once, or back to its
+    /// tabled time with `None`.
+    SessionRescheduled {
+        student_id: String,
+        at: DateTime<Local>,
+        to: Option<DateTime<Local>>,
+    },
+    /// Replaces the notes on the marked session at `at`.
+    SessionNoted {
+        student_id: String,
+        at: DateTime<Local>,
+        notes: String,
+    },
+    /// Replaces the recording or whiteboard links on the marked session
+    /// at `at`.
+    SessionLinked {
+        student_id: String,
+        at: DateTime<Local>,
+        links: Vec<String>,
+    },
+    /// Records where the held session at `at` took place and how far was
+    /// travelled for it. With `usual` both become the student's usual.
+    SessionLocated {
+        student_id: String,
+        at: DateTime<Local>,
+        location: String,
+        distance_km: Option<f32>,
+        usual: bool,
+    },
+    /// Moves each listed student to their new rate from the start of the
+    /// month `from`, keeping the old rate for the months before.
+    RatesAdjusted {
+        from: NaiveDate,
+        rates: Vec<(String, f32)>,
+    },
+    /// Rates engagement in the held session at `at`, replacing any earlier
+    /// rating for it.
+    SessionRated {
+        student_id: String,
+        at: DateTime<Local>,
+        engagement: u8,
+    },
+    /// Records that the student confirmed they will come to the session
+    /// at `at`, or takes that back. Attendance is marked separately.
+    SessionConfirmed {
+        student_id: String,
+        at: DateTime<Local>,
+        confirmed: bool,
+    },
+    MoneyFormatChanged(MoneyFormat),
+    PaymentInstructionsChanged(String),
+    BlackoutDatesChanged(Vec<NaiveDate>),
+    LocationsChanged(Vec<Location>),
+    SubjectsChanged(Vec<TutorSubject>),
+    RatesChanged(Vec<SubjectRate>),
+    WaitlistChanged(Vec<Prospect>),
+    /// Records freshly taken month totals, replacing older snapshots of
+    /// the same months.
+    MonthsSnapshotted(Vec<MonthlySummary>),
+    /// Puts the item at this index in the trash back where it came from.
+    TrashRestored(usize),
+    /// Deletes the item at this index in the trash for good.
+    TrashDiscarded(usize),
+    /// Lets go of everything kept in the trash past its retention.
+    TrashPurged,
+    /// Replaces everything with imported data, keeping the current clock.
+    Imported(Domain),
+}
+
+impl DomainEdit {
+    /// What the tutor did, for their usage statistics. `None` for edits
+    /// the app makes on its own.
+    pub fn action(&self) -> Option<&'static str> {
+        Some(match self {
+            DomainEdit::StudentAdded(_) => "Added a student",
+            DomainEdit::StudentUpdated(_) => "Saved a student",
+            DomainEdit::StudentRemoved(_) => "Deleted a student",
+            DomainEdit::SessionMarked {
+                status: Some(_), ..
+            } => "Marked a session",
+            DomainEdit::SessionMarked { status: None, .. } => "Cleared a session mark",
+            DomainEdit::SessionRescheduled { .. } => "Moved a session",
+            DomainEdit::SessionNoted { .. } => "Noted a session",
+            DomainEdit::SessionLinked { .. } => "Linked a recording",
+            DomainEdit::SessionLocated { .. } => "Recorded where a session was held",
+            DomainEdit::SessionRated { .. } => "Rated a session",
+            DomainEdit::SessionConfirmed {
+                confirmed: true, ..
+            } => "Confirmed a session",
+            DomainEdit::SessionConfirmed {
+                confirmed: false, ..
+            } => "Took back a session confirmation",
+            DomainEdit::RatesAdjusted { .. } => "Adjusted rates",
+            DomainEdit::MoneyFormatChanged(_) => "Changed the money format",
+            DomainEdit::PaymentInstructionsChanged(_) => "Edited payment instructions",
+            DomainEdit::BlackoutDatesChanged(_) => "Changed days off",
+            DomainEdit::LocationsChanged(_) => "Changed locations",
+            DomainEdit::SubjectsChanged(_) => "Changed subjects",
+            DomainEdit::RatesChanged(_) => "Changed subject rates",
+            DomainEdit::WaitlistChanged(_) => "Changed the waitlist",
+            DomainEdit::TrashRestored(_) => "Restored from the trash",
+            DomainEdit::TrashDiscarded(_) => "Deleted from the trash",
+            DomainEdit::Imported(_) => "Imported data",
+            DomainEdit::MonthsSnapshotted(_) | DomainEdit::TrashPurged => return None,
+        })
+    }
+
+    /// Whether applying this to `domain` could change the totals of the
+    /// month, leaving its snapshot out of date.
+    fn touches_month(&self, domain: &Domain, year: i32, month: u32) -> bool {
+        let within = |at: &DateTime<Local>| (at.year(), at.month()) == (year, month);
+        match self {
+            DomainEdit::StudentAdded(_)
+            | DomainEdit::StudentUpdated(_)
+            | DomainEdit::StudentRemoved(_) => true,
+            DomainEdit::SessionMarked { at, .. } => within(at),
+            DomainEdit::SessionRescheduled { student_id, at, to } => {
+                let from = domain
+                    .students
+                    .iter()
+                    .find(|student| student.id == *student_id)
+                    .map(|student| {
+                        let from = student.moved_from(*at).unwrap_or(*at);
+                        let moved = student.reschedules.iter().find(|moved| moved.from == from);
+                        (from, moved.map(|moved| moved.to))
+                    });
+
+                within(at)
+                    || to.as_ref().is_some_and(within)
+                    || from.is_some_and(|(from, moved_to)| {
+                        within(&from) || moved_to.as_ref().is_some_and(within)
+                    })
+            }
+            DomainEdit::RatesAdjusted { from, .. } => (year, month) >= (from.year(), from.month()),
+            DomainEdit::TrashRestored(index) => match domain.trash.get(*index) {
+                Some(Trashed {
+                    item: TrashItem::Session { record, .. },
+                    ..
+                }) => within(&record.at),
+                Some(_) => true,
+                None => false,
+            },
+            _ => false,
+        }
+    }
+}
+
+/// The one copy of the domain. Screens hold cheap `Rc` handles to it and
+/// are given a fresh one whenever an edit lands, tracked by `revision`.
+#[derive(Debug)]
+pub struct DomainStore {
+    domain: Rc<Domain>,
+    revision: u64,
+}
+
+impl DomainStore {
+    pub fn new(domain: Domain) -> Self {
+        Self {
+            domain: Rc::new(domain),
+            revision: 0,
+        }
+    }
+
+    pub fn domain(&self) -> Rc<Domain> {
+        Rc::clone(&self.domain)
+    }
+
+    /// Goes up by one with every edit applied.
+    pub fn revision(&self) -> u64 {
+        self.revision
+    }
+
+    /// Applies `edit`, copying the domain first if screens still hold the
+    /// previous revision. Returns whether anything changed.
+    pub fn apply(&mut self, edit: DomainEdit) -> bool {
+        let stale = self
+            .domain
+            .monthly_snapshots
+            .iter()
+            .map(|snapshot| (snapshot.year, snapshot.month))
+            .filter(|&(year, month)| edit.touches_month(&self.domain, year, month))
+            .collect::<Vec<_>>();
+
+        match edit {
+            DomainEdit::StudentAdded(mut student) => {
+                if self
+                    .domain
+                    .students
+                    .iter()
+                    .any(|existing| existing.id == student.id)
+                {
+                    return false;
+                }
+
+                student.sessions.sort_by_key(|record| record.at);
+                Rc::make_mut(&mut self.domain).students.push(student);
+            }
+            DomainEdit::StudentUpdated(student) => {
+                let Some(index) = self
+                    .domain
+                    .students
+                    .iter()
+                    .position(|existing| existing.id == student.id && *existing != student)
+                else {
+                    return false;
+                };
+
+                Rc::make_mut(&mut self.domain).students[index] = student;
+            }
+            DomainEdit::StudentRemoved(student_id) => {
+                let Some(index) = self
+                    .domain
+                    .students
+                    .iter()
+                    .position(|student| student.id == student_id)
+                else {
+                    return false;
+                };
+
+                let today = self.domain.clock.today();
+                let domain = Rc::make_mut(&mut self.domain);
+                let student = domain.students.remove(index);
+                domain
+                    .trash
+                    .push(Trashed::new(TrashItem::Student(Box::new(student)), today));
+            }
+            DomainEdit::SessionMarked {
+                student_id,
+                at,
+                status,
+            } => {
+                let Some(index) = self.domain.students.iter().position(|student| {
+                    student.id == student_id && student.session_status(at) != status
+                }) else {
+                    return false;
+                };
+
+                let today = self.domain.clock.today();
+                let domain = Rc::make_mut(&mut self.domain);
+                let student = &mut domain.students[index];
+                let sessions = &mut student.sessions;
+                match status {
+                    Some(status) => match sessions.iter_mut().find(|record| record.at == at) {
+                        Some(record) => record.status = status,
+                        None => sessions.push(SessionRecord {
+                            at,
+                            status,
+                            notes: String::new(),
+                            links: Vec::new(),
+                        }),
+                    },
+                    None => {
+                        let removed = sessions.iter().position(|record| record.at == at);
+                        if let Some(position) = removed {
+                            let record = sessions.remove(position);
+                            let item = TrashItem::Session {
+                                student_id: student.id.clone(),
+                                student_name: student.name.full_name(),
+                                record,
+                            };
+                            domain.trash.push(Trashed::new(item, today));
+                        }
+                    }
+                }
+            }
+            DomainEdit::SessionRescheduled { student_id, at, to } => {
+                let Some(index) = self
+                    .domain
+                    .students
+                    .iter()
+                    .position(|student| student.id == student_id)
+                else {
+                    return false;
+                };
+                let mut student = self.domain.students[index].clone();
+                student.reschedule(at, to);
+                if student == self.domain.students[index] {
+                    return false;
+                }
+
+                Rc::make_mut(&mut self.domain).students[index] = student;
+            }
+            DomainEdit::SessionNoted {
+                student_id,
+                at,
+                notes,
+            } => {
+                let Some(index) = self
+                    .domain
+                    .students
+                    .iter()
+                    .position(|student| student.id == student_id)
+                else {
+                    return false;
+                };
+                let Some(position) = self.domain.students[index]
+                    .sessions
+                    .iter()
+                    .position(|record| record.at == at && record.notes != notes)
+                else {
+                    return false;
+                };
+
+                Rc::make_mut(&mut self.domain).students[index].sessions[position].notes = notes;
+            }
+            DomainEdit::SessionLinked {
+                student_id,
+                at,
+                links,
+            } => {
+                let Some(index) = self
+                    .domain
+                    .students
+                    .iter()
+                    .position(|student| student.id == student_id)
+                else {
+                    return false;
+                };
+                let Some(position) = self.domain.students[index]
+                    .sessions
+                    .iter()
+                    .position(|record| record.at == at && record.links != links)
+                else {
+                    return false;
+                };
+
+                Rc::make_mut(&mut self.domain).students[index].sessions[position].links = links;
+            }
+            DomainEdit::SessionLocated {
+                student_id,
+                at,
+                location,
+                distance_km,
+                usual,
+            } => {
+                let Some(index) = self
+                    .domain
+                    .students
+                    .iter()
+                    .position(|student| student.id == student_id)
+                else {
+                    return false;
+                };
+
+                let mut student = self.domain.students[index].clone();
+                if usual {
+                    student.location = Some(location.clone());
+                    student.travel_km = distance_km.or(student.travel_km);
+                }
+                student.session_locations.retain(|session| session.at != at);
+                if student.location.as_ref() != Some(&location) {
+                    student
+                        .session_locations
+                        .push(SessionLocation { at, location });
+                }
+                student.session_distances.retain(|session| session.at != at);
+                if let Some(km) = distance_km.filter(|km| Some(*km) != student.travel_km) {
+                    student.session_distances.push(SessionDistance { at, km });
+                }
+                if student == self.domain.students[index] {
+                    return false;
+                }
+
+                Rc::make_mut(&mut self.domain).students[index] = student;
+            }
+            DomainEdit::SessionRated {
+                student_id,
+                at,
+                engagement,
+            } => {
+                let rating = SessionRating { at, engagement };
+                let Some(index) = self.domain.students.iter().position(|student| {
+                    student.id == student_id && !student.session_ratings.contains(&rating)
+                }) else {
+                    return false;
+                };
+
+                let ratings = &mut Rc::make_mut(&mut self.domain).students[index].session_ratings;
+                ratings.retain(|rating| rating.at != at);
+                ratings.push(rating);
+            }
+            DomainEdit::SessionConfirmed {
+                student_id,
+                at,
+                confirmed,
+            } => {
+                let Some(index) = self.domain.students.iter().position(|student| {
+                    student.id == student_id
+                        && student.confirmed_sessions.contains(&at) != confirmed
+                }) else {
+                    return false;
+                };
+
+                // Confirmations only matter until the day is over.
+                let today = self.domain.clock.today();
+                let confirmations =
+                    &mut Rc::make_mut(&mut self.domain).students[index].confirmed_sessions;
+                confirmations.retain(|session| *session != at && session.date_naive() >= today);
+                if confirmed {
+                    confirmations.push(at);
+                    confirmations.sort();
+                }
+            }
+            DomainEdit::RatesAdjusted { from, rates } => {
+                let mut students = self.domain.students.clone();
+                let mut changed = false;
+                for student in &mut students {
+                    let Some((_, rate)) = rates.iter().find(|(id, _)| *id == student.id) else {
+                        continue;
+                    };
+                    if *rate == student.payment_data.amount {
+                        continue;
+                    }
+
+                    student.rate_changes.push(RateChange {
+                        from,
+                        previous: student.payment_data.amount,
+                    });
+                    student.rate_changes.sort_by_key(|change| change.from);
+                    student.payment_data.amount = *rate;
+                    changed = true;
+                }
+                if !changed {
+                    return false;
+                }
+
+                Rc::make_mut(&mut self.domain).students = students;
+            }
+            DomainEdit::MoneyFormatChanged(money_format) => {
+                if self.domain.money_format == money_format {
+                    return false;
+                }
+
+                Rc::make_mut(&mut self.domain).money_format = money_format;
+            }
+            DomainEdit::PaymentInstructionsChanged(instructions) => {
+                if self.domain.payment_instructions == instructions {
+                    return false;
+                }
+
+                Rc::make_mut(&mut self.domain).payment_instructions = instructions;
+            }
+            DomainEdit::BlackoutDatesChanged(blackout_dates) => {
+                if self.domain.blackout_dates == blackout_dates {
+                    return false;
+                }
+
+                Rc::make_mut(&mut self.domain).blackout_dates = blackout_dates;
+            }
+            DomainEdit::LocationsChanged(locations) => {
+                if self.domain.locations == locations {
+                    return false;
+                }
+
+                Rc::make_mut(&mut self.domain).locations = locations;
+            }
+            DomainEdit::SubjectsChanged(subjects) => {
+                if self.domain.tutor.subjects == subjects {
+                    return false;
+                }
+
+                Rc::make_mut(&mut self.domain).tutor.subjects = subjects;
+            }
+            DomainEdit::RatesChanged(rates) => {
+                if self.domain.rates == rates {
+                    return false;
+                }
+
+                Rc::make_mut(&mut self.domain).rates = rates;
+            }
+            DomainEdit::WaitlistChanged(waitlist) => {
+                if self.domain.waitlist == waitlist {
+                    return false;
+                }
+
+                Rc::make_mut(&mut self.domain).waitlist = waitlist;
+            }
+            DomainEdit::MonthsSnapshotted(summaries) => {
+                let mut snapshots = self.domain.monthly_snapshots.clone();
+                for summary in summaries {
+                    snapshots.retain(|snapshot| {
+                        (snapshot.year, snapshot.month) != (summary.year, summary.month)
+                    });
+                    snapshots.push(summary);
+                }
+                snapshots.sort_by_key(|snapshot| (snapshot.year, snapshot.month));
+                if self.domain.monthly_snapshots == snapshots {
+                    return false;
+                }
+
+                Rc::make_mut(&mut self.domain).monthly_snapshots = snapshots;
+            }
+            DomainEdit::TrashRestored(index) => {
+                let Some(trashed) = self.domain.trash.get(index) else {
+                    return false;
+                };
+                let restorable = match &trashed.item {
+                    TrashItem::Student(student) => !self
+                        .domain
+                        .students
+                        .iter()
+                        .any(|other| other.id == student.id),
+                    TrashItem::Session {
+                        student_id, record, ..
+                    } => self.domain.students.iter().any(|student| {
+                        student.id == *student_id && student.session_status(record.at).is_none()
+                    }),
+                };
+                if !restorable {
+                    return false;
+                }
+
+                let domain = Rc::make_mut(&mut self.domain);
+                match domain.trash.remove(index).item {
+                    TrashItem::Student(student) => domain.students.push(*student),
+                    TrashItem::Session {
+                        student_id, record, ..
+                    } => {
+                        if let Some(student) = domain
+                            .students
+                            .iter_mut()
+                            .find(|student| student.id == student_id)
+                        {
+                            student.sessions.push(record);
+                            student.sessions.sort_by_key(|record| record.at);
+                        }
+                    }
+                }
+            }
+            DomainEdit::TrashDiscarded(index) => {
+                if index >= self.domain.trash.len() {
+                    return false;
+                }
+
+                Rc::make_mut(&mut self.domain).trash.remove(index);
+            }
+            DomainEdit::TrashPurged => {
+                let today = self.domain.clock.today();
+                if !self
+                    .domain
+                    .trash
+                    .iter()
+                    .any(|trashed| trashed.is_expired(today))
+                {
+                    return false;
+                }
+
+                Rc::make_mut(&mut self.domain)
+                    .trash
+                    .retain(|trashed| !trashed.is_expired(today));
+            }
+            DomainEdit::Imported(domain) => {
+                let domain = Domain {
+                    clock: self.domain.clock,
+                    ..domain
+                };
+                if *self.domain == domain {
+                    return false;
+                }
+
+                self.domain = Rc::new(domain);
+            }
+        }
+
+        // Snapshots of months the edit reached are dropped, so their totals
+        // are worked out live until the next snapshot is taken.
+        if !stale.is_empty() {
+            Rc::make_mut(&mut self.domain)
+                .monthly_snapshots
+                .retain(|snapshot| !stale.contains(&(snapshot.year, snapshot.month)));
+        }
+
+        self.revision += 1;
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::fixtures::{date, sample_domain};
+    use crate::domain::{Clock, rate_in, recent_engagement};
+    use chrono::TimeZone;
+    use iced::futures::executor::block_on;
+
+    #[test]
+    fn edits_reach_new_handles_and_leave_old_ones_alone() {
+        let mut store = DomainStore::new(sample_domain());
+        let before = store.domain();
+
+        let mut student = before.students[0].clone();
+        student.payment_data.amount = 999.0;
+        assert!(store.apply(DomainEdit::StudentUpdated(student.clone())));
+        assert!(!store.apply(DomainEdit::StudentUpdated(student)));
+
+        assert_eq!(store.revision(), 1);
+        assert_eq!(store.domain().students[0].payment_data.amount, 999.0);
+        assert_ne!(before.students[0].payment_data.amount, 999.0);
+    }
+
+    #[test]
+    fn an_added_student_arrives_once_with_their_sessions() {
+        let mut store = DomainStore::new(sample_domain());
+        let mut student = store.domain().students[0].clone();
+        student.id = "new-student".to_string();
+        student.sessions = vec![SessionRecord {
+            at: Local.with_ymd_and_hms(2026, 2, 10, 17, 30, 0).unwrap(),
+            status: SessionStatus::Completed,
+            notes: String::new(),
+            links: Vec::new(),
+        }];
+        let count = store.domain().students.len();
+
+        assert!(store.apply(DomainEdit::StudentAdded(student.clone())));
+        assert!(!store.apply(DomainEdit::StudentAdded(student.clone())));
+        assert_eq!(store.revision(), 1);
+        assert_eq!(store.domain().students.len(), count + 1);
+        assert_eq!(store.domain().students.last(), Some(&student));
+    }
+
+    #[test]
+    fn a_logged_session_is_still_there_after_saving_and_reloading() {
+        let dir = std::env::temp_dir().join("tutor-mgr-logged-session");
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("tutor-mgr.db");
+        let mut store = DomainStore::new(sample_domain());
+        let student_id = store.domain().students[0].id.clone();
+        let at = Local.with_ymd_and_hms(2026, 2, 10, 17, 30, 0).unwrap();
+
+        assert!(store.apply(DomainEdit::SessionMarked {
+            student_id,
+            at,
+            status: Some(SessionStatus::Completed),
+        }));
+        block_on(Domain::clone(&store.domain()).save_state_to_db(path.clone())).unwrap();
+
+        let reloaded = block_on(Domain::load_state_from_db(Some(path))).unwrap();
+        assert!(
+            reloaded.students[0]
+                .sessions
+                .iter()
+                .any(|record| record.at == at && record.status == SessionStatus::Completed)
+        );
+    }
+
+    #[test]
+    fn removed_students_wait_in_the_trash_until_it_is_purged() {
+        let mut domain = sample_domain();
+        domain.clock = Clock::Fixed(date(2026, 3, 1));
+        let mut store = DomainStore::new(domain.clone());
+        let student_id = store.domain().students[0].id.clone();
+
+        assert!(store.apply(DomainEdit::StudentRemoved(student_id.clone())));
+        assert!(!store.apply(DomainEdit::StudentRemoved(student_id.clone())));
+        assert!(
+            store
+                .domain()
+                .students
+                .iter()
+                .all(|student| student.id != student_id)
+        );
+        assert_eq!(store.domain().trash[0].expires_on(), date(2026, 3, 31));
+
+        assert!(store.apply(DomainEdit::TrashRestored(0)));
+        assert!(store.domain().trash.is_empty());
+        assert!(
+            store
+                .domain()
+                .students
+                .iter()
+                .any(|student| student.id == student_id)
+        );
+
+        assert!(store.apply(DomainEdit::StudentRemoved(student_id.clone())));
+        assert!(!store.apply(DomainEdit::TrashPurged));
+        let kept = store.domain().trash.clone();
+        store = DomainStore::new(Domain {
+            clock: Clock::Fixed(date(2026, 3, 31)),
+            trash: kept,
+            ..domain
+        });
+        assert!(store.apply(DomainEdit::TrashPurged));
+        assert!(store.domain().trash.is_empty());
+    }
+
+    #[test]
+    fn a_session_is_marked_once_and_can_be_taken_back() {
+        let mut store = DomainStore::new(sample_domain());
+        let student_id = store.domain().students[0].id.clone();
+        let at = Local.with_ymd_and_hms(2026, 2, 12, 17, 0, 0).unwrap();
+        let mark = |status| DomainEdit::SessionMarked {
+            student_id: student_id.clone(),
+            at,
+            status,
+        };
+        let marked = |store: &DomainStore| {
+            store.domain().students[0]
+                .sessions
+                .iter()
+                .filter(|record| record.at == at)
+                .map(|record| (record.status, record.notes.clone()))
+                .collect::<Vec<_>>()
+        };
+
+        assert!(store.apply(mark(Some(SessionStatus::Completed))));
+        assert!(!store.apply(mark(Some(SessionStatus::Completed))));
+        assert!(store.apply(DomainEdit::SessionNoted {
+            student_id: student_id.clone(),
+            at,
+            notes: "Covered vectors".to_string(),
+        }));
+        assert!(store.apply(mark(Some(SessionStatus::MissedByStudent))));
+        assert_eq!(
+            marked(&store),
+            [(
+                SessionStatus::MissedByStudent,
+                "Covered vectors".to_string()
+            )]
+        );
+
+        assert!(store.apply(mark(None)));
+        assert_eq!(marked(&store), []);
+    }
+
+    #[test]
+    fn a_session_keeps_only_its_latest_rating() {
+        let mut store = DomainStore::new(sample_domain());
+        let student_id = store.domain().students[0].id.clone();
+        let at = Local.with_ymd_and_hms(2026, 2, 10, 17, 0, 0).unwrap();
+        let rate = |engagement| DomainEdit::SessionRated {
+            student_id: student_id.clone(),
+            at,
+            engagement,
+        };
+
+        assert!(store.apply(rate(3)));
+        assert!(!store.apply(rate(3)));
+        assert!(store.apply(rate(5)));
+        assert_eq!(
+            store.domain().students[0].session_ratings,
+            [SessionRating { at, engagement: 5 }]
+        );
+        assert_eq!(recent_engagement(&store.domain().students[0], 10), [5]);
+    }
+
+    #[test]
+    fn a_confirmed_session_is_kept_apart_from_attendance() {
+        let mut domain = sample_domain();
+        domain.clock = Clock::Fixed(date(2026, 2, 12));
+        let mut store = DomainStore::new(domain);
+        let student_id = store.domain().students[0].id.clone();
+        let at = Local.with_ymd_and_hms(2026, 2, 12, 17, 30, 0).unwrap();
+        let confirm = |confirmed| DomainEdit::SessionConfirmed {
+            student_id: student_id.clone(),
+            at,
+            confirmed,
+        };
+        let sessions = store.domain().students[0].sessions.clone();
+
+        assert!(store.apply(confirm(true)));
+        assert!(!store.apply(confirm(true)));
+        let student = &store.domain().students[0];
+        assert_eq!(student.confirmed_sessions, [at]);
+        assert_eq!(student.session_status(at), None);
+        assert_eq!(student.sessions, sessions);
+
+        assert!(store.apply(confirm(false)));
+        assert!(store.domain().students[0].confirmed_sessions.is_empty());
+        assert!(store.domain().trash.is_empty());
+    }
+
+    #[test]
+    fn adjusted_rates_keep_the_old_rate_for_earlier_months() {
+        let mut store = DomainStore::new(sample_domain());
+        let student_id = store.domain().students[0].id.clone();
+        let adjust = |rate| DomainEdit::RatesAdjusted {
+            from: date(2026, 3, 1),
+            rates: vec![(student_id.clone(), rate)],
+        };
+
+        assert!(!store.apply(adjust(150.0)));
+        assert!(store.apply(adjust(165.0)));
+        let student = &store.domain().students[0];
+        assert_eq!(student.payment_data.amount, 165.0);
+        assert_eq!(rate_in(student, 2026, 2), 150.0);
+        assert_eq!(rate_in(student, 2026, 3), 165.0);
+        assert_eq!(store.domain().students[1].rate_changes, []);
+    }
+
+    #[test]
+    fn sessions_away_from_the_usual_location_are_kept_apart() {
+        let mut store = DomainStore::new(sample_domain());
+        let student_id = store.domain().students[0].id.clone();
+        let at = Local.with_ymd_and_hms(2026, 2, 12, 17, 30, 0).unwrap();
+        let locate = |location: &str, usual| DomainEdit::SessionLocated {
+            student_id: student_id.clone(),
+            at,
+            location: location.to_string(),
+            distance_km: None,
+            usual,
+        };
+
+        assert!(store.apply(locate("Home visit", true)));
+        assert!(!store.apply(locate("Home visit", false)));
+        let student = &store.domain().students[0];
+        assert_eq!(student.location.as_deref(), Some("Home visit"));
+        assert!(student.session_locations.is_empty());
+
+        assert!(store.apply(locate("Online", false)));
+        let student = &store.domain().students[0];
+        assert_eq!(student.location.as_deref(), Some("Home visit"));
+        assert_eq!(
+            student.session_locations,
+            [SessionLocation {
+                at,
+                location: "Online".to_string()
+            }]
+        );
+    }
+
+    #[test]
+    fn snapshots_of_edited_months_give_way_until_retaken() {
+        let mut store = DomainStore::new(sample_domain());
+        let months = store.domain().unsnapshotted_months();
+        assert_eq!(months.first(), Some(&(2025, 9)));
+        assert_eq!(months.last(), Some(&(2026, 1)));
+
+        let taken = store.domain().snapshot_months(&months);
+        assert!(store.apply(DomainEdit::MonthsSnapshotted(taken.clone())));
+        assert!(!store.apply(DomainEdit::MonthsSnapshotted(taken)));
+        assert!(store.domain().unsnapshotted_months().is_empty());
+
+        let student_id = store.domain().students[0].id.clone();
+        store.apply(DomainEdit::SessionMarked {
+            student_id,
+            at: Local.with_ymd_and_hms(2026, 1, 27, 17, 0, 0).unwrap(),
+            status: Some(SessionStatus::Completed),
+        });
+        let domain = store.domain();
+        assert_eq!(domain.monthly_summary(2026, 1).completed_sessions, 9);
+        assert_eq!(domain.live_summary(2026, 1).completed_sessions, 9);
+        assert_eq!(domain.unsnapshotted_months(), [(2026, 1)]);
+
+        let retaken = domain.snapshot_months(&domain.unsnapshotted_months());
+        assert!(store.apply(DomainEdit::MonthsSnapshotted(retaken)));
+        assert_eq!(
+            store.domain().monthly_summary(2026, 1).completed_sessions,
+            9
+        );
+        assert_eq!(store.domain().monthly_snapshots.len(), months.len());
+    }
+}
@@ -0,0 +1,790 @@
+use chrono::{Datelike, NaiveDate, NaiveDateTime, NaiveTime};
+use iced::advanced::graphics::core::font;
+use iced::widget::{Column, button, column, progress_bar, row, scrollable, text};
+use iced::{Center, Color, Element, Font, Length, Theme};
+use std::rc::Rc;
+
+use crate::billing::{Ledger, Payment};
+use crate::domain::{
+    Domain, MAX_ENGAGEMENT, PaymentType, SessionStatus, Student, attendance_rate, month_label,
+    session_location, student_months,
+};
+use crate::money::MoneyFormat;
+use crate::routes::Route;
+use crate::shell::Effect;
+use crate::theme;
+use crate::ui_components::{global_content_container, page_header};
+
+/// Timeline entries shown at first, and added each time the page is
+/// scrolled near its end.
+const TIMELINE_PAGE: usize = 30;
+/// How far down, from 0 to 1, the page is scrolled before more of the
+/// timeline is shown.
+const LOAD_MORE_AT: f32 = 0.9;
+
+pub struct StudentDetailState {
+    domain: Option<Rc<Domain>>,
+    student_id: Option<String>,
+    /// How many timeline entries are shown, newest first.
+    timeline_shown: usize,
+    /// Whether the page was last seen scrolled to its end, so each arrival
+    /// there shows one more page and no more.
+    at_end: bool,
+}
+
+impl Default for StudentDetailState {
+    fn default() -> Self {
+        Self {
+            domain: None,
+            student_id: None,
+            timeline_shown: TIMELINE_PAGE,
+            at_end: false,
+        }
+    }
+}
+
+impl StudentDetailState {
+    pub fn attach_domain(&mut self, domain: Rc<Domain>) {
+        self.domain = Some(domain);
+    }
+
+    pub fn show_student(&mut self, student_id: &str) {
+        if self.student_id.as_deref() != Some(student_id) {
+            self.timeline_shown = TIMELINE_PAGE;
+            self.at_end = false;
+        }
+        self.student_id = Some(student_id.to_string());
+    }
+
+    /// Shows another page of the timeline when the page has just reached
+    /// its end, never past the `total` entries there are.
+    fn scrolled(&mut self, at_end: bool, total: usize) {
+        if at_end && !self.at_end {
+            self.timeline_shown = (self.timeline_shown + TIMELINE_PAGE)
+                .min(total)
+                .max(self.timeline_shown);
+        }
+        self.at_end = at_end;
+    }
+
+    fn student(&self) -> Option<(&Domain, &Student)> {
+        let domain = self.domain.as_deref()?;
+        let student_id = self.student_id.as_deref()?;
+        let student = domain.students.iter().find(|s| s.id == student_id)?;
+
+        Some((domain, student))
+    }
+}
+
+#[derive(Clone, Debug)]
+pub enum Msg {
+    /// Moved to the ledger's trash by the app.
+    DeletePayment(Payment),
+    /// Shows more of the timeline once the page nears its end.
+    Scrolled(scrollable::Viewport),
+    /// Carried out by the app, e.g. going back to the Student Manager.
+    Effect(Effect),
+}
+
+pub fn update(state: &mut StudentDetailState, msg: Msg, ledger: &Ledger) {
+    match msg {
+        Msg::Scrolled(viewport) => {
+            let total = state.student().map_or(0, |(domain, student)| {
+                timeline(student, ledger, &domain.money_format).len()
+            });
+            state.scrolled(viewport.relative_offset().y >= LOAD_MORE_AT, total);
+        }
+        Msg::DeletePayment(_) | Msg::Effect(_) => {}
+    }
+}
+
+/// Everything the detail page lists for one student, ready to show.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StudentDetail {
+    pub name: String,
+    /// Subject and rate, e.g. "Extended Mathematics · GHS 150.00 per session".
+    pub summary: String,
+    /// Share of sessions held since the student started.
+    pub attendance_rate: Option<f32>,
+    pub earned: String,
+    pub paid: String,
+    pub year_to_date: YearToDate,
+    /// Newest first, as are the lists below.
+    pub months: Vec<MonthRow>,
+    pub sessions: Vec<SessionRow>,
+    pub payments: Vec<PaymentRow>,
+    pub notes: Vec<NoteRow>,
+    pub timeline: Vec<TimelineEntry>,
+}
+
+/// Totals from the start of this year to today.
+#[derive(Debug, Clone, PartialEq)]
+pub struct YearToDate {
+    pub year: i32,
+    pub held: i32,
+    pub earned: String,
+    pub paid: String,
+    /// Earned less paid, so positive while money is owed.
+    pub balance: String,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct MonthRow {
+    pub month: String,
+    pub held: i32,
+    pub scheduled: i32,
+    pub attendance_rate: Option<f32>,
+    pub earned: String,
+    pub paid: String,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SessionRow {
+    pub when: String,
+    pub status: SessionStatus,
+    pub location: Option<String>,
+    pub engagement: Option<u8>,
+    /// Recordings or whiteboard exports, opened in the browser.
+    pub links: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PaymentRow {
+    pub paid_on: String,
+    pub amount: String,
+    /// The invoice it paid towards, e.g. `INV-0007`.
+    pub invoice: Option<String>,
+    pub payment: Payment,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct NoteRow {
+    pub when: String,
+    pub notes: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TimelineKind {
+    Session(SessionStatus),
+    Payment,
+    RateChange,
+    Note,
+    /// An engagement rating given after a session.
+    Assessment,
+}
+
+/// One thing that happened in the student's time with the tutor.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TimelineEntry {
+    /// Payments and rate changes fall at the start of their day.
+    pub at: NaiveDateTime,
+    pub kind: TimelineKind,
+    pub what: String,
+}
+
+pub fn student_detail(
+    student: &Student,
+    ledger: &Ledger,
+    money: &MoneyFormat,
+    today: NaiveDate,
+) -> StudentDetail {
+    let months = student_months(student, today);
+    let earned: f32 = months.iter().map(|month| month.earned).sum();
+
+    let mut sessions: Vec<_> = student.sessions.iter().collect();
+    sessions.sort_by_key(|record| std::cmp::Reverse(record.at));
+
+    let payments: Vec<_> = ledger
+        .payments()
+        .iter()
+        .rev()
+        .filter(|payment| payment.student_id == student.id)
+        .collect();
+    let paid: f32 = payments.iter().map(|payment| payment.amount).sum();
+    let paid_in = |year: i32, month: Option<u32>| -> f32 {
+        payments
+            .iter()
+            .filter(|payment| payment.paid_on.year() == year)
+            .filter(|payment| month.is_none_or(|month| payment.paid_on.month() == month))
+            .map(|payment| payment.amount)
+            .sum()
+    };
+    let this_year = months.iter().filter(|month| month.year == today.year());
+    let earned_this_year: f32 = this_year.clone().map(|month| month.earned).sum();
+    let paid_this_year = paid_in(today.year(), None);
+
+    StudentDetail {
+        name: student.name.full_name(),
+        summary: format!(
+            "{} · {} per {}",
+            student.subject_names(),
+            money.format(student.payment_data.amount),
+            match student.payment_data.payment_type {
+                PaymentType::PerSession => "session",
+                PaymentType::Monthly => "month",
+            }
+        ),
+        attendance_rate: attendance_rate(student, student.tution_start_date.date_naive(), today),
+        earned: money.format(earned),
+        paid: money.format(paid),
+        year_to_date: YearToDate {
+            year: today.year(),
+            held: this_year.map(|month| month.held).sum(),
+            earned: money.format(earned_this_year),
+            paid: money.format(paid_this_year),
+            balance: money.format(earned_this_year - paid_this_year),
+        },
+        months: months
+            .iter()
+            .rev()
+            .map(|month| MonthRow {
+                month: month_label(month.year, month.month),
+                held: month.held,
+                scheduled: month.scheduled,
+                attendance_rate: month.attendance_rate,
+                earned: money.format(month.earned),
+                paid: money.format(paid_in(month.year, Some(month.month))),
+            })
+            .collect(),
+        sessions: sessions
+            .iter()
+            .map(|record| SessionRow {
+                when: record.at.format("%a %-d %b %Y, %-I:%M %p").to_string(),
+                status: record.status,
+                location: session_location(student, record.at).map(str::to_string),
+                engagement: student
+                    .session_ratings
+                    .iter()
+                    .find(|rating| rating.at == record.at)
+                    .map(|rating| rating.engagement),
+                links: record.links.clone(),
+            })
+            .collect(),
+        payments: payments
+            .iter()
+            .map(|payment| PaymentRow {
+                paid_on: payment.paid_on.format("%-d %b %Y").to_string(),
+                amount: money.format(payment.amount),
+                invoice: payment.invoice_number.and_then(|number| {
+                    ledger
+                        .invoices()
+                        .iter()
+                        .find(|invoice| invoice.number == number)
+                        .map(|invoice| invoice.reference())
+                }),
+                payment: (*payment).clone(),
+            })
+            .collect(),
+        notes: sessions
+            .iter()
+            .filter(|record| !record.notes.trim().is_empty())
+            .map(|record| NoteRow {
+                when: record.at.format("%-d %b %Y").to_string(),
+                notes: record.notes.trim().to_string(),
+            })
+            .collect(),
+        timeline: timeline(student, ledger, money),
+    }
+}
+
+/// Sessions, payments, rate changes, notes and engagement ratings, newest
+/// first. A session's note and rating come just after it.
+pub fn timeline(student: &Student, ledger: &Ledger, money: &MoneyFormat) -> Vec<TimelineEntry> {
+    let start_of = |date: NaiveDate| date.and_time(NaiveTime::MIN);
+    let mut entries = Vec::new();
+
+    for record in &student.sessions {
+        let mut what = vec![record.status.to_string()];
+        what.extend(session_location(student, record.at).map(str::to_string));
+        entries.push(TimelineEntry {
+            at: record.at.naive_local(),
+            kind: TimelineKind::Session(record.status),
+            what: what.join(" · "),
+        });
+        if !record.notes.trim().is_empty() {
+            entries.push(TimelineEntry {
+                at: record.at.naive_local(),
+                kind: TimelineKind::Note,
+                what: record.notes.trim().to_string(),
+            });
+        }
+    }
+    entries.extend(student.session_ratings.iter().map(|rating| TimelineEntry {
+        at: rating.at.naive_local(),
+        kind: TimelineKind::Assessment,
+        what: format!("Engagement {}/{}", rating.engagement, MAX_ENGAGEMENT),
+    }));
+    entries.extend(
+        ledger
+            .payments()
+            .iter()
+            .filter(|payment| payment.student_id == student.id)
+            .map(|payment| TimelineEntry {
+                at: start_of(payment.paid_on),
+                kind: TimelineKind::Payment,
+                what: format!("Paid {}", money.format(payment.amount)),
+            }),
+    );
+
+    // Each change's new rate is what the next one changed from.
+    let mut changes: Vec<_> = student.rate_changes.iter().collect();
+    changes.sort_by_key(|change| change.from);
+    let new_rates = changes
+        .iter()
+        .skip(1)
+        .map(|change| change.previous)
+        .chain([student.payment_data.amount]);
+    entries.extend(
+        changes
+            .iter()
+            .zip(new_rates)
+            .map(|(change, rate)| TimelineEntry {
+                at: start_of(change.from),
+                kind: TimelineKind::RateChange,
+                what: format!(
+                    "Rate changed from {} to {}",
+                    money.format(change.previous),
+                    money.format(rate)
+                ),
+            }),
+    );
+
+    entries.sort_by_key(|entry| std::cmp::Reverse(entry.at));
+    entries
+}
+
+pub fn view<'a>(state: &'a StudentDetailState, ledger: &Ledger) -> Element<'a, Msg> {
+    let back = button(text("← Student Manager").size(12))
+        .style(button::text)
+        .on_press(Msg::Effect(Effect::Navigate(Route::Students(None))));
+
+    let Some((domain, student)) = state.student() else {
+        return column![
+            page_header("Student Details"),
+            global_content_container(column![back, muted("This student could not be found.")]),
+        ]
+        .into();
+    };
+
+    let detail = student_detail(student, ledger, &domain.money_format, domain.clock.today());
+
+    let title = column![
+        text(detail.name).size(20).font(Font {
+            weight: font::Weight::Medium,
+            ..Default::default()
+        }),
+        muted(detail.summary),
+    ]
+    .spacing(4);
+
+    let totals = row![
+        total("Attendance", percentage(detail.attendance_rate)),
+        total("Earned", detail.earned),
+        total("Paid", detail.paid),
+    ]
+    .spacing(40);
+
+    let ytd = detail.year_to_date;
+    let year_to_date = row![
+        total("Sessions held", ytd.held.to_string()),
+        total("Earned", ytd.earned),
+        total("Paid", ytd.paid),
+        total("Balance", ytd.balance),
+    ]
+    .spacing(40);
+
+    let months = Column::new()
+        .push(
+            row![
+                heading("Month", 80.0),
+                heading("Held", 70.0),
+                heading("Attendance", 200.0),
+                heading("Earned", 110.0),
+                heading("Paid", 110.0),
+            ]
+            .spacing(10),
+        )
+        .extend(detail.months.into_iter().map(view_month))
+        .spacing(6);
+
+    let sessions = detail.sessions.into_iter().map(view_session).collect();
+    let payments = detail.payments.into_iter().map(view_payment).collect();
+    let notes = detail
+        .notes
+        .into_iter()
+        .map(|note| {
+            column![muted(note.when), text(note.notes).size(12)]
+                .spacing(2)
+                .into()
+        })
+        .collect();
+    let timeline_total = detail.timeline.len();
+    let timeline = detail
+        .timeline
+        .into_iter()
+        .take(state.timeline_shown)
+        .map(view_timeline_entry)
+        .collect();
+    let more = (timeline_total > state.timeline_shown).then(|| {
+        muted(format!(
+            "Showing {} of {} · scroll for more",
+            state.timeline_shown, timeline_total
+        ))
+    });
+
+    let content = column![
+        back,
+        title,
+        totals,
+        section(format!("{} to date", ytd.year), year_to_date),
+        section("Month by month", months),
+        row![
+            section("Sessions", list(sessions, 6, "No sessions marked yet."))
+                .width(Length::FillPortion(3)),
+            section("Payments", list(payments, 6, "No payments recorded yet."))
+                .width(Length::FillPortion(2)),
+        ]
+        .spacing(40),
+        section("Notes", list(notes, 10, "No session notes yet.")),
+        section("Timeline", list(timeline, 6, "Nothing has happened yet.")),
+    ]
+    .push(more)
+    .spacing(24);
+
+    let content = global_content_container(scrollable(content).on_scroll(Msg::Scrolled))
+        .width(Length::Fill)
+        .height(Length::Fill);
+
+    column![page_header("Student Details"), content].into()
+}
+
+fn view_month<'a>(month: MonthRow) -> Element<'a, Msg> {
+    row![
+        cell(month.month, 80.0),
+        cell(format!("{} of {}", month.held, month.scheduled), 70.0),
+        row![
+            progress_bar(0.0..=1.0, month.attendance_rate.unwrap_or(0.0))
+                .length(Length::Fixed(140.0))
+                .girth(8),
+            cell(percentage(month.attendance_rate), 50.0),
+        ]
+        .spacing(10)
+        .width(Length::Fixed(200.0))
+        .align_y(Center),
+        cell(month.earned, 110.0),
+        cell(month.paid, 110.0),
+    ]
+    .spacing(10)
+    .align_y(Center)
+    .into()
+}
+
+fn view_session<'a>(session: SessionRow) -> Element<'a, Msg> {
+    let color = match session.status {
+        SessionStatus::Completed => theme::SUCCESS,
+        SessionStatus::MissedByStudent => theme::WARNING,
+        SessionStatus::CancelledByTutor | SessionStatus::Rescheduled => theme::MUTED,
+    };
+    let mut details = vec![session.status.to_string()];
+    details.extend(session.location);
+    details.extend(
+        session
+            .engagement
+            .map(|engagement| format!("engagement {}/5", engagement)),
+    );
+
+    row![
+        cell(session.when, 190.0),
+        coloured(details.join(" · "), color),
+    ]
+    .extend(session.links.into_iter().enumerate().map(|(index, link)| {
+        button(text(format!("Link {}", index + 1)).size(12))
+            .style(button::text)
+            .padding([0, 4])
+            .on_press(Msg::Effect(Effect::Open(link)))
+            .into()
+    }))
+    .spacing(10)
+    .align_y(Center)
+    .into()
+}
+
+fn view_timeline_entry<'a>(entry: TimelineEntry) -> Element<'a, Msg> {
+    let (label, color) = match entry.kind {
+        TimelineKind::Session(SessionStatus::Completed) => ("Session", theme::SUCCESS),
+        TimelineKind::Session(SessionStatus::MissedByStudent) => ("Session", theme::WARNING),
+        TimelineKind::Session(_) => ("Session", theme::MUTED),
+        TimelineKind::Payment => ("Payment", theme::SUCCESS),
+        TimelineKind::RateChange => ("Rate", theme::ACCENT),
+        TimelineKind::Note => ("Note", theme::MUTED),
+        TimelineKind::Assessment => ("Assessment", theme::ACCENT),
+    };
+    let when = if entry.at.time() == NaiveTime::MIN {
+        entry.at.format("%a %-d %b %Y").to_string()
+    } else {
+        entry.at.format("%a %-d %b %Y, %-I:%M %p").to_string()
+    };
+
+    row![
+        cell(when, 190.0),
+        coloured("●", color),
+        cell(label.to_string(), 80.0),
+        text(entry.what).size(12),
+    ]
+    .spacing(10)
+    .align_y(Center)
+    .into()
+}
+
+fn view_payment<'a>(payment: PaymentRow) -> Element<'a, Msg> {
+    row![
+        cell(payment.paid_on, 100.0),
+        cell(payment.amount, 110.0),
+        muted(payment.invoice.unwrap_or_default()).width(Length::Fill),
+        button(text("Delete").size(12))
+            .style(button::text)
+            .padding([0, 4])
+            .on_press(Msg::DeletePayment(payment.payment)),
+    ]
+    .spacing(10)
+    .align_y(Center)
+    .into()
+}
+
+fn section<'a>(
+    title: impl text::IntoFragment<'a>,
+    content: impl Into<Element<'a, Msg>>,
+) -> Column<'a, Msg> {
+    column![
+        text(title).size(14).font(Font {
+            weight: font::Weight::Medium,
+            ..Default::default()
+        }),
+        content.into(),
+    ]
+    .spacing(12)
+}
+
+/// `rows` one under the other, or `empty` when there are none.
+fn list<'a>(rows: Vec<Element<'a, Msg>>, spacing: u32, empty: &'a str) -> Element<'a, Msg> {
+    if rows.is_empty() {
+        muted(empty).into()
+    } else {
+        Column::with_children(rows).spacing(spacing).into()
+    }
+}
+
+fn total<'a>(label: &'a str, value: String) -> Column<'a, Msg> {
+    column![
+        muted(label),
+        text(value).size(18).font(Font {
+            weight: font::Weight::Medium,
+            ..Default::default()
+        }),
+    ]
+    .spacing(2)
+}
+
+fn percentage(rate: Option<f32>) -> String {
+    rate.map(|rate| format!("{:.0}%", rate * 100.0))
+        .unwrap_or_else(|| "--".to_string())
+}
+
+fn heading<'a>(label: &'a str, width: f32) -> iced::widget::Text<'a> {
+    text(label).size(12).width(Length::Fixed(width)).font(Font {
+        weight: font::Weight::Medium,
+        ..Default::default()
+    })
+}
+
+fn cell<'a>(value: String, width: f32) -> iced::widget::Text<'a> {
+    text(value).size(12).width(Length::Fixed(width))
+}
+
+fn muted<'a>(value: impl text::IntoFragment<'a>) -> iced::widget::Text<'a> {
+    coloured(value, theme::MUTED)
+}
+
+fn coloured<'a>(value: impl text::IntoFragment<'a>, color: Color) -> iced::widget::Text<'a> {
+    text(value)
+        .size(12)
+        .style(move |_theme: &Theme| text::Style { color: Some(color) })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::billing::Payment;
+    use crate::domain::fixtures::{date, sample_domain};
+    use crate::domain::{RateChange, SessionRating, SessionRecord};
+    use chrono::{Local, TimeZone};
+
+    #[test]
+    fn detail_lists_months_sessions_payments_and_notes_newest_first() {
+        let mut mary = sample_domain().students.remove(0);
+        mary.tution_start_date = Local.with_ymd_and_hms(2026, 1, 1, 0, 0, 0).unwrap();
+        mary.sessions.push(SessionRecord {
+            at: Local.with_ymd_and_hms(2026, 1, 22, 17, 30, 0).unwrap(),
+            status: SessionStatus::MissedByStudent,
+            notes: "Away for a school trip".to_string(),
+            links: vec!["https://example.com/recording".to_string()],
+        });
+        let mut ledger = Ledger::default();
+        ledger.record_payment(Payment {
+            student_id: mary.id.clone(),
+            paid_on: date(2026, 2, 2),
+            amount: 750.0,
+            invoice_number: None,
+        });
+        ledger.record_payment(Payment {
+            student_id: "peter-parker".to_string(),
+            paid_on: date(2026, 2, 3),
+            amount: 600.0,
+            invoice_number: None,
+        });
+
+        let detail = student_detail(&mary, &ledger, &MoneyFormat::default(), date(2026, 2, 12));
+
+        assert_eq!(
+            detail
+                .months
+                .iter()
+                .map(|month| (month.month.as_str(), month.held, month.scheduled))
+                .collect::<Vec<_>>(),
+            vec![("Feb '26", 3, 4), ("Jan '26", 5, 9)]
+        );
+        assert_eq!(detail.earned, MoneyFormat::default().format(1200.0));
+        assert_eq!(
+            detail.year_to_date,
+            YearToDate {
+                year: 2026,
+                held: 8,
+                earned: MoneyFormat::default().format(1200.0),
+                paid: MoneyFormat::default().format(750.0),
+                balance: MoneyFormat::default().format(450.0),
+            }
+        );
+        assert_eq!(detail.months[0].paid, MoneyFormat::default().format(750.0));
+        assert_eq!(detail.months[1].paid, MoneyFormat::default().format(0.0));
+        assert_eq!(detail.sessions.len(), 9);
+        assert_eq!(detail.sessions[0].when, "Tue 10 Feb 2026, 5:00 PM");
+        assert_eq!(detail.sessions[3].status, SessionStatus::MissedByStudent);
+        assert_eq!(detail.sessions[3].links, ["https://example.com/recording"]);
+        assert_eq!(
+            detail.payments,
+            vec![PaymentRow {
+                paid_on: "2 Feb 2026".to_string(),
+                amount: MoneyFormat::default().format(750.0),
+                invoice: None,
+                payment: ledger.payments()[0].clone(),
+            }]
+        );
+        assert_eq!(
+            detail.notes,
+            vec![NoteRow {
+                when: "22 Jan 2026".to_string(),
+                notes: "Away for a school trip".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn timeline_interleaves_everything_newest_first() {
+        let mut mary = sample_domain().students.remove(0);
+        mary.sessions.push(SessionRecord {
+            at: Local.with_ymd_and_hms(2026, 1, 22, 17, 30, 0).unwrap(),
+            status: SessionStatus::Completed,
+            notes: "Covered fractions".to_string(),
+            links: Vec::new(),
+        });
+        mary.session_ratings.push(SessionRating {
+            at: Local.with_ymd_and_hms(2026, 1, 22, 17, 30, 0).unwrap(),
+            engagement: 4,
+        });
+        mary.payment_data.amount = 165.0;
+        mary.rate_changes = vec![
+            RateChange {
+                from: date(2026, 2, 1),
+                previous: 150.0,
+            },
+            RateChange {
+                from: date(2025, 9, 1),
+                previous: 140.0,
+            },
+        ];
+        let mut ledger = Ledger::default();
+        ledger.record_payment(Payment {
+            student_id: mary.id.clone(),
+            paid_on: date(2026, 1, 23),
+            amount: 750.0,
+            invoice_number: None,
+        });
+        let money = MoneyFormat::default();
+
+        let timeline = timeline(&mary, &ledger, &money);
+        let around_the_note: Vec<_> = timeline
+            .iter()
+            .filter(|entry| {
+                entry.at.date() >= date(2026, 1, 22) && entry.at.date() <= date(2026, 2, 1)
+            })
+            .map(|entry| (entry.kind, entry.what.as_str()))
+            .collect();
+
+        let rate_changed = format!(
+            "Rate changed from {} to {}",
+            money.format(150.0),
+            money.format(165.0)
+        );
+        let paid = format!("Paid {}", money.format(750.0));
+        assert_eq!(
+            around_the_note,
+            [
+                (TimelineKind::RateChange, rate_changed.as_str()),
+                (TimelineKind::Payment, paid.as_str()),
+                (TimelineKind::Session(SessionStatus::Completed), "Completed"),
+                (TimelineKind::Note, "Covered fractions"),
+                (TimelineKind::Assessment, "Engagement 4/5"),
+            ]
+        );
+        assert!(timeline.windows(2).all(|pair| pair[0].at >= pair[1].at));
+        assert_eq!(
+            timeline.last().unwrap().what,
+            format!(
+                "Rate changed from {} to {}",
+                money.format(140.0),
+                money.format(150.0)
+            )
+        );
+    }
+
+    #[test]
+    fn the_timeline_starts_from_its_first_page_for_each_student() {
+        let mut state = StudentDetailState::default();
+        state.show_student("mary");
+        state.timeline_shown = 3 * TIMELINE_PAGE;
+        state.show_student("mary");
+        assert_eq!(state.timeline_shown, 3 * TIMELINE_PAGE);
+
+        state.show_student("peter");
+        assert_eq!(state.timeline_shown, TIMELINE_PAGE);
+    }
+
+    #[test]
+    fn the_timeline_grows_a_page_per_arrival_at_the_end_up_to_its_length() {
+        let mut state = StudentDetailState::default();
+        let total = 2 * TIMELINE_PAGE + 5;
+
+        state.scrolled(true, total);
+        state.scrolled(true, total);
+        state.scrolled(true, total);
+        assert_eq!(state.timeline_shown, 2 * TIMELINE_PAGE);
+
+        state.scrolled(false, total);
+        state.scrolled(true, total);
+        assert_eq!(state.timeline_shown, total);
+
+        state.scrolled(false, total);
+        state.scrolled(true, total);
+        assert_eq!(state.timeline_shown, total);
+    }
+}
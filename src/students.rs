@@ -1,28 +1,67 @@
-use chrono::{Datelike, Local, Weekday};
+use chrono::{DateTime, Datelike, Local, NaiveDate, NaiveTime, TimeZone, Weekday};
 use iced::advanced::graphics::core::font;
 use iced::mouse::Interaction;
 use iced::widget::{
-    Column, Row, Stack, button, center, column, container, mouse_area, opaque,
-    operation::focus_next, pick_list, row, space, stack, svg, text, text_input,
+    Column, Row, Stack, button, center, checkbox, column, combo_box, container, mouse_area, opaque,
+    operation::focus_next, pick_list, row, scrollable, space, stack, svg, table, text, text_input,
+    tooltip,
 };
+use iced::window::frames;
 use iced::{
-    Alignment, Background, Border, Center, Color, Element, Font, Length, Padding, Shadow, Task,
-    Theme, Vector,
+    Alignment, Background, Border, Center, Color, Element, Font, Length, Padding, Subscription,
+    Task, Theme,
 };
+use lilt::{Animated, Easing};
+use std::collections::HashSet;
+use std::ops::RangeInclusive;
+use std::path::PathBuf;
 use std::rc::Rc;
+use std::time::Instant;
 
+use crate::billing::{Ledger, Payment};
+use crate::contact::{ContactAction, ContactError, international_number, is_email, open_contact};
 use crate::domain::{
-    Domain, SessionData, Student, Tutor, TutorSubject, compute_monthly_completed_sessions,
-    compute_monthly_sum, get_next_session,
+    ChurnRisk, Clock, DEFAULT_SESSION_MINUTES, Domain, Enrolment, Location, MAX_ENGAGEMENT,
+    PaymentData, PaymentType, PersonalName, Prospect, RiskLevel, SessionData, SessionRecord,
+    SessionStatus, SessionTiming, Student, SubjectRate, Tutor, TutorSubject, WaitlistMatch,
+    WeeklySlot, attendance_rate, available_slots, compute_churn_risk,
+    compute_monthly_completed_sessions, compute_monthly_sum, get_month_date_range,
+    get_next_session, has_left, is_active_on, month_label, next_month, parse_session_time,
+    previous_month, recent_engagement, scheduled_sessions_on, sessions_in_month, suggested_session,
+    upcoming_session, waitlist_matches, weekly_schedule,
 };
+use crate::export::{ExportError, csv_line, text_pdf, write_export};
 use crate::icons;
-use crate::ui_components::{global_content_container, page_header, ui_button};
+use crate::money::{Currency, MoneyFormat};
+use crate::quote::{Quote, QuoteRequest};
+use crate::rate_adjustment::{AdjustmentKind, RateAdjustment, rate_notice};
+use crate::routes::Route;
+use crate::search::loosely_matches;
+use crate::shell::{CopyRequest, Effect};
+use crate::theme;
+use crate::ui_components::{
+    copy_button, global_content_container, icon_button, number_stepper, page_header, progress_ring,
+    searchable_pick_list, session_countdown, tag_input, ui_button,
+};
+
+/// Most weekly slots a student can be booked into.
+const MAX_TIME_SLOTS: usize = 3;
+/// Lengths a session can be set to, moved in quarter hours.
+const SESSION_MINUTES: RangeInclusive<u32> = 15..=240;
+const SESSION_MINUTES_STEP: u32 = 15;
+/// Sessions a week a quote can be worked out for.
+const SESSIONS_PER_WEEK: RangeInclusive<u32> = 1..=7;
+/// Most sessions a monthly fee can be set to cover, one a day.
+const MAX_MIN_SESSIONS: u32 = 31;
+/// Shown for a student with no session tabled or moved ahead.
+const NO_NEXT_SESSION: &str = "None scheduled";
 
 #[derive(Clone, Debug)]
 pub struct TimeSlot {
     pub id: usize,
     pub selected_day: Option<DaySelection>,
     pub selected_time: Option<TimeSelection>,
+    pub duration_minutes: u32,
 }
 
 impl TimeSlot {
@@ -31,17 +70,119 @@ impl TimeSlot {
             id,
             selected_day: None,
             selected_time: None,
+            duration_minutes: DEFAULT_SESSION_MINUTES,
+        }
+    }
+}
+
+/// A further subject in the form, booked into one weekly slot.
+#[derive(Clone, Debug)]
+pub struct OtherSubjectDraft {
+    pub subject: Option<TutorSubject>,
+    pub rate: String,
+    pub slot: TimeSlot,
+    /// Slots after the first from a saved student, kept as they were.
+    pub more_slots: Vec<SessionData>,
+}
+
+impl OtherSubjectDraft {
+    fn load(enrolment: &Enrolment) -> Self {
+        let mut slot = TimeSlot::new(0);
+        if let Some(first) = enrolment.tabled_sessions.first() {
+            slot.selected_day = Some(DaySelection::Day(first.day));
+            slot.selected_time = Some(TimeSelection::Time(first.time.clone()));
+            slot.duration_minutes = first.duration_minutes;
+        }
+
+        Self {
+            subject: Some(enrolment.subject.clone()),
+            rate: enrolment.payment_data.amount.to_string(),
+            slot,
+            more_slots: enrolment.tabled_sessions.iter().skip(1).cloned().collect(),
         }
     }
+
+    fn enrolment(&self, payment_type: PaymentType) -> Result<Enrolment, String> {
+        let subject = self
+            .subject
+            .clone()
+            .ok_or_else(|| "Choose each further subject".to_string())?;
+        let amount = self
+            .rate
+            .trim()
+            .parse::<f32>()
+            .ok()
+            .filter(|rate| *rate > 0.0)
+            .ok_or_else(|| format!("Enter a rate for {}", subject))?;
+        let (Some(DaySelection::Day(day)), Some(TimeSelection::Time(time))) =
+            (&self.slot.selected_day, &self.slot.selected_time)
+        else {
+            return Err(format!("Pick a day and time for {}", subject));
+        };
+
+        let first = SessionData {
+            day: *day,
+            time: time.clone(),
+            duration_minutes: self.slot.duration_minutes,
+        };
+        Ok(Enrolment {
+            subject,
+            tabled_sessions: std::iter::once(first)
+                .chain(self.more_slots.iter().cloned())
+                .collect(),
+            payment_data: PaymentData {
+                payment_type,
+                amount,
+                min_sessions: None,
+            },
+        })
+    }
 }
 
 pub struct StudentManagerState {
     pub search_query: String,
     pub show_add_student_modal: bool,
     pub hovered_student_card: Option<usize>,
+    pub sort_by: StudentSort,
+    pub sort_descending: bool,
+    pub student_view: StudentView,
+    pub visible_columns: Vec<StudentSort>,
+    pub status_message: Option<String>,
+    pub inline_edit: Option<InlineEdit>,
+    pub session_log: Option<SessionLog>,
+    pub payment_entry: Option<PaymentEntry>,
+    pub removal: Option<StudentRemoval>,
+    pub show_archived: bool,
+    /// The month the cards and table add up sessions and fees for, or
+    /// `None` for the current one.
+    pub metrics_month: Option<(i32, u32)>,
+    /// Only students with every one of these tags are listed.
+    pub tag_filter: Vec<String>,
+    pub tag_filter_draft: String,
+    /// Every tag in use, for suggestions.
+    pub known_tags: Vec<String>,
+    pub quote_form: Option<QuoteForm>,
+    pub waitlist_form: Option<WaitlistForm>,
+    pub rate_wizard: Option<RateWizard>,
+    pub card_density: CardDensity,
     pub tutor: Option<Tutor>,
     pub students: Option<Vec<Student>>,
+    pub locations: Vec<Location>,
+    pub rates: Vec<SubjectRate>,
+    pub waitlist: Vec<Prospect>,
+    pub waitlist_matches: Vec<WaitlistMatch>,
+    /// The app's ledger as of its last change, for overdue invoices.
+    pub ledger: Ledger,
+    pub clock: Clock,
+    pub money_format: MoneyFormat,
     pub modal_state: AddStudentModal,
+    pub subject_options: combo_box::State<TutorSubject>,
+    /// Students still taught, by name, for logging a session quickly.
+    pub student_options: combo_box::State<StudentChoice>,
+    /// Fills the attendance rings on the cards when the screen opens.
+    pub rings_filled: Animated<bool, Instant>,
+    /// What countdowns count from, moved on by the clock tick.
+    pub now: DateTime<Local>,
 }
 
 impl StudentManagerState {
@@ -49,9 +190,53 @@ impl StudentManagerState {
         self.search_query.clear();
         self.show_add_student_modal = false;
         self.hovered_student_card = None;
+        self.modal_state.clear();
+        self.rings_filled
+            .transition_instantaneous(false, Instant::now());
+        self.rings_filled.transition(true, Instant::now());
+        self.refresh(domain);
+    }
+
+    /// Picks up a new revision of the domain, keeping what the user has
+    /// open on this screen.
+    pub fn refresh(&mut self, domain: Rc<Domain>) {
         self.tutor = Some(domain.tutor.clone());
         self.students = Some(domain.students.clone());
-        self.modal_state.clear();
+        self.locations = domain.locations.clone();
+        self.rates = domain.rates.clone();
+        self.waitlist = domain.waitlist.clone();
+        self.waitlist_matches = waitlist_matches(&domain);
+        self.clock = domain.clock;
+        self.now = self.clock.now();
+        self.money_format = domain.money_format;
+        self.subject_options = combo_box::State::new(domain.tutor.subjects.clone());
+        self.student_options =
+            combo_box::State::new(student_choices(&domain.students, self.clock.today()));
+        self.known_tags = known_tags(&domain.students);
+    }
+
+    pub fn attach_ledger(&mut self, ledger: &Ledger) {
+        self.ledger = ledger.clone();
+    }
+
+    /// Narrows the list down to one student's card, as when opened from
+    /// search or the table.
+    pub fn focus_student(&mut self, student_id: &str) {
+        if let Some(student) = self
+            .students
+            .iter()
+            .flatten()
+            .find(|student| student.id == student_id)
+        {
+            self.search_query = student.name.full_name();
+            self.student_view = StudentView::Cards;
+        }
+    }
+
+    /// The year and month the figures are shown for.
+    pub fn shown_month(&self) -> (i32, u32) {
+        let today = self.clock.today();
+        self.metrics_month.unwrap_or((today.year(), today.month()))
     }
 
     pub fn empty() -> Self {
@@ -59,9 +244,455 @@ impl StudentManagerState {
             search_query: String::new(),
             show_add_student_modal: false,
             hovered_student_card: None,
+            sort_by: StudentSort::default(),
+            sort_descending: false,
+            student_view: StudentView::default(),
+            visible_columns: StudentSort::ALL.to_vec(),
+            status_message: None,
+            inline_edit: None,
+            session_log: None,
+            payment_entry: None,
+            removal: None,
+            show_archived: false,
+            metrics_month: None,
+            tag_filter: Vec::new(),
+            tag_filter_draft: String::new(),
+            known_tags: Vec::new(),
+            quote_form: None,
+            waitlist_form: None,
+            rate_wizard: None,
+            card_density: CardDensity::default(),
             tutor: None,
             students: None,
+            locations: Vec::new(),
+            rates: Vec::new(),
+            waitlist: Vec::new(),
+            waitlist_matches: Vec::new(),
+            ledger: Ledger::default(),
+            clock: Clock::default(),
+            money_format: MoneyFormat::default(),
             modal_state: AddStudentModal::default(),
+            subject_options: combo_box::State::default(),
+            student_options: combo_box::State::default(),
+            rings_filled: Animated::new(false).duration(600.).easing(Easing::EaseOut),
+            now: Clock::default().now(),
+        }
+    }
+}
+
+/// A student as offered in a searchable pick list.
+#[derive(Clone, Debug, PartialEq)]
+pub struct StudentChoice {
+    pub student_id: String,
+    pub name: String,
+}
+
+impl std::fmt::Display for StudentChoice {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name)
+    }
+}
+
+/// Every tag given to a student, sorted, each once.
+fn known_tags(students: &[Student]) -> Vec<String> {
+    let mut tags: Vec<String> = students
+        .iter()
+        .flat_map(|student| student.tags.iter().cloned())
+        .collect();
+    tags.sort_by_key(|tag| tag.to_lowercase());
+    tags.dedup_by(|a, b| a.eq_ignore_ascii_case(b));
+
+    tags
+}
+
+/// Adds `tag` trimmed, unless it is blank or already there in any case.
+fn add_tag(tags: &mut Vec<String>, tag: &str) {
+    let tag = tag.trim();
+    if !tag.is_empty()
+        && !tags
+            .iter()
+            .any(|existing| existing.eq_ignore_ascii_case(tag))
+    {
+        tags.push(tag.to_string());
+    }
+}
+
+/// Tags typed as a comma-separated list, each once and none blank.
+fn parse_tags(list: &str) -> Vec<String> {
+    let mut tags = Vec::new();
+    for tag in list.split(',') {
+        add_tag(&mut tags, tag);
+    }
+
+    tags
+}
+
+/// Students not archived by `today`, by name.
+fn student_choices(students: &[Student], today: NaiveDate) -> Vec<StudentChoice> {
+    let mut choices: Vec<StudentChoice> = students
+        .iter()
+        .filter(|student| !has_left(student, today))
+        .map(|student| StudentChoice {
+            student_id: student.id.clone(),
+            name: student.name.full_name(),
+        })
+        .collect();
+    choices.sort_by_key(|choice| choice.name.to_lowercase());
+
+    choices
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum StudentView {
+    #[default]
+    Cards,
+    Table,
+}
+
+impl StudentView {
+    pub const ALL: [StudentView; 2] = [StudentView::Cards, StudentView::Table];
+}
+
+impl std::fmt::Display for StudentView {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            StudentView::Cards => write!(f, "Card view"),
+            StudentView::Table => write!(f, "Table view"),
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum StudentSort {
+    #[default]
+    Name,
+    Subject,
+    Tags,
+    Rate,
+    NextSession,
+    CompletedSessions,
+    Accrued,
+    /// What the student owes today, from the ledger.
+    Balance,
+    ChurnRisk,
+}
+
+impl StudentSort {
+    pub const ALL: [StudentSort; 9] = [
+        StudentSort::Name,
+        StudentSort::Subject,
+        StudentSort::Tags,
+        StudentSort::Rate,
+        StudentSort::NextSession,
+        StudentSort::CompletedSessions,
+        StudentSort::Accrued,
+        StudentSort::Balance,
+        StudentSort::ChurnRisk,
+    ];
+
+    fn label(&self) -> &'static str {
+        match self {
+            StudentSort::Name => "Name",
+            StudentSort::Subject => "Subject",
+            StudentSort::Tags => "Tags",
+            StudentSort::Rate => "Rate",
+            StudentSort::NextSession => "Next session",
+            StudentSort::CompletedSessions => "Sessions this month",
+            StudentSort::Accrued => "Accrued",
+            StudentSort::Balance => "Balance",
+            StudentSort::ChurnRisk => "Churn risk",
+        }
+    }
+
+    fn descending_by_default(&self) -> bool {
+        matches!(
+            self,
+            StudentSort::Rate
+                | StudentSort::CompletedSessions
+                | StudentSort::Accrued
+                | StudentSort::Balance
+                | StudentSort::ChurnRisk
+        )
+    }
+}
+
+impl std::fmt::Display for StudentSort {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Sort by {}", self.label().to_lowercase())
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum InlineField {
+    Rate,
+    Subject,
+    /// Typed as a comma-separated list.
+    Tags,
+}
+
+#[derive(Clone, Debug)]
+pub struct InlineEdit {
+    pub student_id: String,
+    pub field: InlineField,
+    pub value: String,
+    pub error: Option<String>,
+}
+
+/// A session being logged from a student card, as typed.
+#[derive(Clone, Debug)]
+pub struct SessionLog {
+    pub student_id: String,
+    pub student_name: String,
+    /// In `YYYY-MM-DD` form.
+    pub date: String,
+    /// In the same form as tabled times, e.g. "5:30 PM".
+    pub time: String,
+    /// Where it was held, starting from where the student usually meets.
+    pub location: Option<String>,
+    /// Distance travelled in km, as typed, asked for home visits only.
+    pub distance: String,
+    /// Makes `location` and `distance` the student's usual ones.
+    pub usual: bool,
+    /// From 1 to 5, left out when not rated.
+    pub engagement: Option<u8>,
+    pub notes: String,
+    /// Links to the recording or whiteboard export, separated by spaces.
+    pub links: String,
+    pub error: Option<String>,
+}
+
+impl SessionLog {
+    fn start_time(&self) -> Result<DateTime<Local>, String> {
+        let date = NaiveDate::parse_from_str(self.date.trim(), "%Y-%m-%d")
+            .map_err(|_| "Enter the date as YYYY-MM-DD".to_string())?;
+        let time = parse_session_time(&self.time)
+            .ok_or_else(|| "Enter the time like 5:30 PM".to_string())?;
+
+        Local
+            .from_local_datetime(&date.and_time(time))
+            .earliest()
+            .ok_or_else(|| "That time does not exist on this date".to_string())
+    }
+
+    fn distance_km(&self) -> Result<Option<f32>, String> {
+        match self.distance.trim() {
+            "" => Ok(None),
+            distance => distance
+                .parse::<f32>()
+                .ok()
+                .filter(|km| *km >= 0.0)
+                .map(Some)
+                .ok_or_else(|| "Enter the distance in km, e.g. 12.5".to_string()),
+        }
+    }
+
+    fn links(&self) -> Result<Vec<String>, String> {
+        self.links
+            .split([' ', ',', '\n'])
+            .filter(|link| !link.is_empty())
+            .map(|link| {
+                (link.starts_with("https://") || link.starts_with("http://"))
+                    .then(|| link.to_string())
+                    .ok_or_else(|| "Links start with https://".to_string())
+            })
+            .collect()
+    }
+}
+
+/// A payment being recorded from a student card, as typed.
+#[derive(Clone, Debug)]
+pub struct PaymentEntry {
+    pub student_id: String,
+    pub student_name: String,
+    pub amount: String,
+    pub error: Option<String>,
+}
+
+/// A student about to be archived or deleted, waiting for the tutor to
+/// confirm which.
+#[derive(Clone, Debug)]
+pub struct StudentRemoval {
+    pub student_id: String,
+    pub student_name: String,
+    /// Already archived, so the dialog offers to restore them instead.
+    pub archived: bool,
+}
+
+/// Fees being worked out for a prospective student.
+#[derive(Clone, Debug)]
+pub struct QuoteForm {
+    pub subject: Option<TutorSubject>,
+    pub sessions_per_week: u32,
+    pub duration_minutes: u32,
+    pub location: Option<String>,
+    pub message: Option<String>,
+}
+
+impl QuoteForm {
+    fn quote(&self, rates: &[SubjectRate], locations: &[Location]) -> Result<Quote, String> {
+        let subject = self
+            .subject
+            .clone()
+            .ok_or_else(|| "Choose a subject".to_string())?;
+        let location = self.location.as_ref().and_then(|name| {
+            locations
+                .iter()
+                .find(|location| location.name == *name)
+                .cloned()
+        });
+
+        let request = QuoteRequest {
+            subject: subject.clone(),
+            sessions_per_week: self.sessions_per_week,
+            duration_minutes: self.duration_minutes,
+            location,
+        };
+        Quote::compute(rates, request)
+            .ok_or_else(|| format!("Set an hourly rate for {} in Settings", subject))
+    }
+}
+
+/// A prospective student being added to the waitlist, as entered.
+#[derive(Clone, Debug, Default)]
+pub struct WaitlistForm {
+    pub name: String,
+    pub subject: Option<TutorSubject>,
+    pub slots: Vec<WeeklySlot>,
+    pub error: Option<String>,
+}
+
+impl WaitlistForm {
+    fn prospect(&self, added_on: NaiveDate) -> Result<Prospect, String> {
+        let name = self.name.trim();
+        if name.is_empty() {
+            return Err("Enter their name".to_string());
+        }
+        let subject = self
+            .subject
+            .clone()
+            .ok_or_else(|| "Choose a subject".to_string())?;
+        if self.slots.is_empty() {
+            return Err("Tick at least one slot that suits them".to_string());
+        }
+
+        Ok(Prospect {
+            name: name.to_string(),
+            subject,
+            slots: self.slots.clone(),
+            added_on,
+        })
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum RateWizardStep {
+    /// Picking the students, the change and when it starts.
+    #[default]
+    Choose,
+    /// Checking the new rates and sending the notices.
+    Notify,
+}
+
+/// Raises several students' rates at once, as entered.
+#[derive(Clone, Debug, Default)]
+pub struct RateWizard {
+    pub step: RateWizardStep,
+    pub student_ids: Vec<String>,
+    pub kind: AdjustmentKind,
+    pub amount: String,
+    /// As typed, in `YYYY-MM-DD` form.
+    pub from: String,
+    pub error: Option<String>,
+}
+
+impl RateWizard {
+    fn adjustment(&self) -> Result<(RateAdjustment, NaiveDate), String> {
+        if self.student_ids.is_empty() {
+            return Err("Choose at least one student".to_string());
+        }
+        let amount = self
+            .amount
+            .trim()
+            .parse::<f32>()
+            .ok()
+            .filter(|amount| *amount != 0.0)
+            .ok_or_else(|| match self.kind {
+                AdjustmentKind::Percentage => "Enter the percentage, e.g. 10".to_string(),
+                AdjustmentKind::Flat => "Enter the amount to add, e.g. 20".to_string(),
+            })?;
+        let from = NaiveDate::parse_from_str(self.from.trim(), "%Y-%m-%d")
+            .ok()
+            .filter(|from| from.day() == 1)
+            .ok_or_else(|| "Rates change on the first of a month, e.g. 2026-03-01".to_string())?;
+
+        let adjustment = match self.kind {
+            AdjustmentKind::Percentage => RateAdjustment::Percentage(amount),
+            AdjustmentKind::Flat => RateAdjustment::Flat(amount),
+        };
+        Ok((adjustment, from))
+    }
+
+    /// Each chosen student with their new rate, in list order.
+    fn new_rates<'a>(
+        &self,
+        students: &'a [Student],
+        money: &MoneyFormat,
+    ) -> Vec<(&'a Student, f32)> {
+        let Ok((adjustment, _)) = self.adjustment() else {
+            return Vec::new();
+        };
+
+        students
+            .iter()
+            .filter(|student| self.student_ids.contains(&student.id))
+            .map(|student| {
+                (
+                    student,
+                    adjustment.apply(student.payment_data.amount, money),
+                )
+            })
+            .collect()
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum CardDensity {
+    Compact,
+    Comfortable,
+    #[default]
+    Detailed,
+}
+
+impl CardDensity {
+    pub const ALL: [CardDensity; 3] = [
+        CardDensity::Compact,
+        CardDensity::Comfortable,
+        CardDensity::Detailed,
+    ];
+
+    fn card_size(&self) -> (f32, f32) {
+        match self {
+            CardDensity::Compact => (260.0, 300.0),
+            CardDensity::Comfortable => (280.0, 420.0),
+            CardDensity::Detailed => (300.0, 500.0),
+        }
+    }
+
+    fn info_spacing(&self) -> f32 {
+        match self {
+            CardDensity::Compact => 15.0,
+            CardDensity::Comfortable => 25.0,
+            CardDensity::Detailed => 40.0,
+        }
+    }
+}
+
+impl std::fmt::Display for CardDensity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CardDensity::Compact => write!(f, "Compact"),
+            CardDensity::Comfortable => write!(f, "Comfortable"),
+            CardDensity::Detailed => write!(f, "Detailed"),
         }
     }
 }
@@ -74,6 +705,19 @@ pub struct AddStudentModal {
     pub validation_errors: Option<ValidatedStudent>,
     pub time_slots: Vec<TimeSlot>,
     pub next_slot_id: usize,
+    /// The slot being dragged by its handle, if any.
+    pub dragged_slot: Option<usize>,
+    /// Past sessions since the start date the tutor ticked as held.
+    pub backfill_held: HashSet<DateTime<Local>>,
+    /// The past sessions the tutor last confirmed, which must still match
+    /// what the start date and slots give for the student to be saved.
+    pub backfill_confirmed: Option<Vec<DateTime<Local>>>,
+    /// The student being edited, or `None` while adding a new one.
+    pub editing: Option<String>,
+    pub tags: Vec<String>,
+    pub tag_draft: String,
+    pub other_subjects: Vec<OtherSubjectDraft>,
+    pub other_subjects_error: Option<String>,
 }
 
 impl AddStudentModal {
@@ -82,14 +726,241 @@ impl AddStudentModal {
         self.selected_subject = None;
         self.time_slots = vec![TimeSlot::new(0)];
         self.next_slot_id = 1;
+        self.dragged_slot = None;
+        self.backfill_held.clear();
+        self.backfill_confirmed = None;
         self.validation_errors = None;
         self.modal_message.clear();
+        self.editing = None;
+        self.tags.clear();
+        self.tag_draft.clear();
+        self.other_subjects.clear();
+        self.other_subjects_error = None;
+    }
+
+    /// Fills the form with a student's details to edit them.
+    fn load(&mut self, student: &Student) {
+        self.clear();
+        self.editing = Some(student.id.clone());
+        self.modal_input = ModalInput {
+            first_name: student.name.first.clone(),
+            last_name: student.name.last.clone(),
+            other_names: student.name.other.clone().unwrap_or_default(),
+            phone: student.phone.clone().unwrap_or_default(),
+            email: student.email.clone().unwrap_or_default(),
+            subject: student.subject.to_string(),
+            pay_rate: student.payment_data.amount.to_string(),
+            min_sessions: student
+                .payment_data
+                .min_sessions
+                .map(|min| min.to_string())
+                .unwrap_or_default(),
+            start_date: student.tution_start_date.format("%Y-%m-%d").to_string(),
+            ..Default::default()
+        };
+        self.selected_subject = Some(student.subject.clone());
+        self.tags = student.tags.clone();
+        self.other_subjects = student
+            .other_subjects
+            .iter()
+            .map(OtherSubjectDraft::load)
+            .collect();
+
+        if !student.tabled_sessions.is_empty() {
+            self.time_slots = student
+                .tabled_sessions
+                .iter()
+                .enumerate()
+                .map(|(id, session)| TimeSlot {
+                    id,
+                    selected_day: Some(DaySelection::Day(session.day)),
+                    selected_time: Some(TimeSelection::Time(session.time.clone())),
+                    duration_minutes: session.duration_minutes,
+                })
+                .collect();
+            self.next_slot_id = self.time_slots.len();
+        }
+    }
+
+    /// The further subjects as entered, billed the same way as the first.
+    fn other_subjects(&self, payment_type: PaymentType) -> Result<Vec<Enrolment>, String> {
+        self.other_subjects
+            .iter()
+            .map(|draft| draft.enrolment(payment_type))
+            .collect()
+    }
+
+    /// `student` with the details in the form, keeping their history.
+    fn edited(&self, student: &Student) -> Student {
+        let input = &self.modal_input;
+        let other_names = input.other_names.trim();
+        let phone = input.phone.trim();
+        let email = input.email.trim();
+        let tabled_sessions = self
+            .time_slots
+            .iter()
+            .filter_map(|slot| {
+                let (Some(DaySelection::Day(day)), Some(TimeSelection::Time(time))) =
+                    (&slot.selected_day, &slot.selected_time)
+                else {
+                    return None;
+                };
+
+                Some(SessionData {
+                    day: *day,
+                    time: time.clone(),
+                    duration_minutes: slot.duration_minutes,
+                })
+            })
+            .collect();
+        let tution_start_date = parse_start_date(&input.start_date)
+            .filter(|date| *date != student.tution_start_date.date_naive())
+            .and_then(|date| {
+                Local
+                    .from_local_datetime(&date.and_time(NaiveTime::MIN))
+                    .earliest()
+            })
+            .unwrap_or(student.tution_start_date);
+
+        let mut edited = student.clone();
+        edited.name = PersonalName {
+            first: input.first_name.trim().to_string(),
+            last: input.last_name.trim().to_string(),
+            other: (!other_names.is_empty()).then(|| other_names.to_string()),
+        };
+        edited.phone = (!phone.is_empty()).then(|| phone.to_string());
+        edited.email = (!email.is_empty()).then(|| email.to_string());
+        edited.subject = self
+            .selected_subject
+            .clone()
+            .unwrap_or_else(|| student.subject.clone());
+        edited.tags = self.tags.clone();
+        edited.payment_data.amount = input.pay_rate.trim().parse().unwrap_or_default();
+        edited.payment_data.min_sessions = input.min_sessions.trim().parse().ok();
+        edited.tabled_sessions = tabled_sessions;
+        edited.other_subjects = self
+            .other_subjects(student.payment_data.payment_type)
+            .unwrap_or_else(|_| student.other_subjects.clone());
+        edited.tution_start_date = tution_start_date;
+        edited
+    }
+
+    /// A new student with the details in the form, starting on the start
+    /// date with the past sessions ticked as held already marked.
+    fn added(&self, id: String, today: NaiveDate) -> Student {
+        let input = &self.modal_input;
+        let start = parse_start_date(&input.start_date).unwrap_or(today);
+        let blank = Student {
+            id,
+            name: PersonalName {
+                first: String::new(),
+                last: String::new(),
+                other: None,
+            },
+            subject: TutorSubject::new(&input.subject),
+            tags: Vec::new(),
+            phone: None,
+            email: None,
+            tabled_sessions: Vec::new(),
+            other_subjects: Vec::new(),
+            reschedules: Vec::new(),
+            sessions: Vec::new(),
+            cancelled_sessions: Vec::new(),
+            location: None,
+            session_locations: Vec::new(),
+            travel_km: None,
+            session_distances: Vec::new(),
+            session_ratings: Vec::new(),
+            confirmed_sessions: Vec::new(),
+            payment_data: PaymentData {
+                payment_type: PaymentType::PerSession,
+                amount: 0.0,
+                min_sessions: None,
+            },
+            rate_changes: Vec::new(),
+            tution_start_date: Local
+                .from_local_datetime(&start.and_time(NaiveTime::MIN))
+                .earliest()
+                .unwrap_or_else(Local::now),
+            tution_end_date: None,
+        };
+
+        let mut added = self.edited(&blank);
+        added.sessions = self
+            .expected_backfill(today)
+            .into_iter()
+            .filter(|at| self.backfill_held.contains(at))
+            .map(SessionRecord::completed)
+            .collect();
+        added
+    }
+
+    /// Sessions the chosen slots give from the start date up to, but not
+    /// including, `today`. Empty while the start date is not a past date.
+    fn expected_backfill(&self, today: NaiveDate) -> Vec<DateTime<Local>> {
+        let Some(start) = parse_start_date(&self.modal_input.start_date) else {
+            return Vec::new();
+        };
+
+        let mut sessions: Vec<DateTime<Local>> = start
+            .iter_days()
+            .take_while(|date| *date < today)
+            .flat_map(|date| {
+                self.time_slots.iter().filter_map(move |slot| {
+                    let (Some(DaySelection::Day(day)), Some(TimeSelection::Time(time))) =
+                        (&slot.selected_day, &slot.selected_time)
+                    else {
+                        return None;
+                    };
+                    let time = parse_session_time(time).filter(|_| *day == date.weekday())?;
+                    Local.from_local_datetime(&date.and_time(time)).earliest()
+                })
+            })
+            .collect();
+
+        sessions.sort();
+        sessions.dedup();
+        sessions
+    }
+
+    /// Whether past sessions are still waiting to be reviewed.
+    fn backfill_pending(&self, today: NaiveDate) -> bool {
+        let expected = self.expected_backfill(today);
+        !expected.is_empty() && self.backfill_confirmed.as_ref() != Some(&expected)
+    }
+
+    /// Moves a slot to where another one sits, shifting the rest along.
+    fn move_slot(&mut self, slot_id: usize, onto_id: usize) {
+        let from = self.time_slots.iter().position(|slot| slot.id == slot_id);
+        let to = self.time_slots.iter().position(|slot| slot.id == onto_id);
+
+        if let (Some(from), Some(to)) = (from, to) {
+            let slot = self.time_slots.remove(from);
+            self.time_slots.insert(to, slot);
+        }
+    }
+
+    /// Copies a slot in just below itself, while there is room.
+    fn duplicate_slot(&mut self, slot_id: usize) {
+        if self.time_slots.len() >= MAX_TIME_SLOTS {
+            return;
+        }
+
+        if let Some(index) = self.time_slots.iter().position(|slot| slot.id == slot_id) {
+            let copy = TimeSlot {
+                id: self.next_slot_id,
+                ..self.time_slots[index].clone()
+            };
+            self.time_slots.insert(index + 1, copy);
+            self.next_slot_id += 1;
+        }
     }
 }
 
 #[derive(Debug, Clone)]
 pub enum StudentError {
-    StudentNotSaved(ModalInput),
+    StudentNotSaved(Box<ModalInput>),
+    StudentNotUpdated(String),
 }
 
 impl std::fmt::Display for StudentError {
@@ -99,26 +970,165 @@ impl std::fmt::Display for StudentError {
                 write!(f, "Student with name {} {} not saved", 
                     modal_input.first_name, modal_input.last_name)
             }
+            StudentError::StudentNotUpdated(student_id) => {
+                write!(f, "Changes to student {} not saved", student_id)
+            }
         }
     }
 }
 
 #[derive(Clone, Debug)]
 pub enum Msg {
+    SearchQueryChanged(String),
     StudentCardHovered(Option<usize>),
+    /// A frame of the rings filling, to redraw.
+    Tick,
+    /// Moves countdowns on. Sent by the app every minute.
+    ClockTicked,
+    SortSelected(StudentSort),
+    SortColumnClicked(StudentSort),
+    StudentViewSelected(StudentView),
+    ColumnToggled(StudentSort, bool),
+    ExportCsv,
+    CsvExported(Result<PathBuf, ExportError>),
+    StartInlineEdit(String, InlineField),
+    InlineEditChanged(String),
+    InlineSubjectSelected(TutorSubject),
+    CommitInlineEdit,
+    CancelInlineEdit,
+    /// Carries the saved student for the app to apply to the domain.
+    StudentUpdated(Result<Box<Student>, StudentError>),
+    StartSessionLog(String),
+    /// Opens the session log for whoever most likely just had a session.
+    StartQuickLog,
+    SessionLogDateChanged(String),
+    SessionLogTimeChanged(String),
+    LogSession,
+    CloseSessionLog,
+    SessionLogLocationSelected(String),
+    SessionLogDistanceChanged(String),
+    SessionLogUsualToggled(bool),
+    /// Picking the selected rating again clears it.
+    SessionLogEngagementSelected(u8),
+    SessionLogNotesChanged(String),
+    SessionLogLinksChanged(String),
+    /// Carries the logged session for the app to apply to the domain.
+    SessionLogged {
+        student_id: String,
+        at: DateTime<Local>,
+        location: Option<String>,
+        distance_km: Option<f32>,
+        usual: bool,
+        engagement: Option<u8>,
+        notes: String,
+        links: Vec<String>,
+    },
+    /// Marks how today's session went from the student card, or clears
+    /// the mark. Applied by the app through the domain store.
+    SessionMarked {
+        student_id: String,
+        at: DateTime<Local>,
+        status: Option<SessionStatus>,
+    },
+    StartPaymentEntry(String),
+    PaymentAmountChanged(String),
+    RecordPayment,
+    ClosePaymentEntry,
+    /// Carries the payment for the app to add to the ledger.
+    PaymentRecorded(Payment),
+    StartQuote,
+    QuoteSubjectSelected(TutorSubject),
+    QuoteSessionsChanged(u32),
+    QuoteDurationChanged(u32),
+    QuoteLocationSelected(String),
+    CopyQuote,
+    ExportQuote,
+    QuoteExported(Result<PathBuf, ExportError>),
+    CloseQuote,
+    StartWaitlist,
+    WaitlistNameChanged(String),
+    WaitlistSubjectSelected(TutorSubject),
+    WaitlistSlotToggled(WeeklySlot, bool),
+    AddProspect,
+    RemoveProspect(usize),
+    /// Carries the new waitlist for the app to apply to the domain.
+    WaitlistChanged(Vec<Prospect>),
+    CloseWaitlist,
+    StartRateWizard,
+    RateWizardStudentToggled(String, bool),
+    RateWizardKindSelected(AdjustmentKind),
+    RateWizardAmountChanged(String),
+    RateWizardFromChanged(String),
+    RateWizardNext,
+    RateWizardBack,
+    CopyRateNotices(Option<String>),
+    ApplyRateWizard,
+    /// Carries the new rates for the app to apply to the domain.
+    RatesAdjusted {
+        from: NaiveDate,
+        rates: Vec<(String, f32)>,
+    },
+    CloseRateWizard,
+    CardDensitySelected(CardDensity),
     ShowAddStudentModal,
     CloseAddStudentModal,
     SubjectSelected(TutorSubject),
+    TagDraftChanged(String),
+    TagAdded(String),
+    TagRemoved(String),
     FirstNameInputChanged(String),
     LastNameInputChanged(String),
     OtherNamesInputChanged(String),
+    PhoneInputChanged(String),
+    EmailInputChanged(String),
     RateInputChanged(String),
+    MinSessionsChanged(String),
     AddStudent,
-    StudentAdded(Result<(), StudentError>),
+    /// Carries the new student for the app to add to the domain.
+    StudentAdded(Result<Box<Student>, StudentError>),
+    StartStudentEdit(String),
+    StartStudentRemoval(String),
+    /// Hands a call, chat or email to the system's app for it.
+    OpenContact(String, ContactAction),
+    ContactOpened(Result<(), ContactError>),
+    ArchiveStudent,
+    RestoreStudent,
+    DeleteStudent,
+    CloseStudentRemoval,
+    /// Carries the deleted student's id for the app to apply to the domain.
+    StudentDeleted(String),
+    ShowArchivedToggled(bool),
+    MetricsPreviousMonth,
+    MetricsNextMonth,
+    /// Goes back to the current month's figures.
+    MetricsThisMonth,
+    TagFilterDraftChanged(String),
+    TagFilterAdded(String),
+    TagFilterRemoved(String),
+    TagFilterCleared,
+    /// Saves the edited student, which the app then applies to the domain.
+    UpdateStudent,
+    AddOtherSubject,
+    RemoveOtherSubject(usize),
+    OtherSubjectSelected(usize, TutorSubject),
+    OtherSubjectRateChanged(usize, String),
+    OtherSubjectDaySelected(usize, DaySelection),
+    OtherSubjectTimeSelected(usize, TimeSelection),
     AddTimeSlot,
     RemoveTimeSlot(usize),
+    DuplicateTimeSlot(usize),
+    TimeSlotDragStarted(usize),
+    TimeSlotDraggedOver(usize),
+    TimeSlotDragEnded,
     TutoringDaySelected(usize, DaySelection),
     TutoringTimeSelected(usize, TimeSelection),
+    TimeSlotDurationChanged(usize, u32),
+    StartDateChanged(String),
+    BackfillSessionToggled(DateTime<Local>, bool),
+    BackfillAllToggled(bool),
+    BackfillConfirmed(bool),
+    /// Carried out by the app, e.g. copying with a toast.
+    Effect(Effect),
 }
 
 #[derive(Clone, PartialEq, Eq, Debug)]
@@ -155,15 +1165,25 @@ pub struct ModalInput {
     pub first_name: String,
     pub last_name: String,
     pub other_names: String,
+    pub phone: String,
+    pub email: String,
     pub subject: String,
     pub pay_rate: String,
+    /// Sessions a month a monthly fee is owed for, blank for every one.
+    pub min_sessions: String,
     pub weekly_schedule: WeeklySchedule,
+    /// As typed, in `YYYY-MM-DD` form.
+    pub start_date: String,
 }
 
 pub fn update(state: &mut StudentManagerState, msg: Msg) -> Task<Msg> {
     match msg {
         Msg::ShowAddStudentModal => {
             state.show_add_student_modal = true;
+            if state.modal_state.modal_input.start_date.is_empty() {
+                state.modal_state.modal_input.start_date =
+                    state.clock.today().format("%Y-%m-%d").to_string();
+            }
             focus_next()
         }
         Msg::CloseAddStudentModal => {
@@ -175,102 +1195,1106 @@ pub fn update(state: &mut StudentManagerState, msg: Msg) -> Task<Msg> {
             state.modal_state.selected_subject = Some(subject);
             Task::none()
         }
-        Msg::StudentCardHovered(card_idx_opt) => {
-            state.hovered_student_card = card_idx_opt;
+        Msg::TagDraftChanged(draft) => {
+            state.modal_state.tag_draft = draft;
             Task::none()
         }
-        Msg::AddTimeSlot => {
-            if state.modal_state.time_slots.len() < 3 {
-                state.modal_state.time_slots.push(
-                    TimeSlot::new(state.modal_state.next_slot_id)
-                );
-                state.modal_state.next_slot_id += 1;
-            }
+        Msg::TagAdded(tag) => {
+            add_tag(&mut state.modal_state.tags, &tag);
+            state.modal_state.tag_draft.clear();
             Task::none()
         }
-        Msg::RemoveTimeSlot(id) => {
-            state.modal_state.time_slots.retain(|slot| slot.id != id);
-            if state.modal_state.time_slots.is_empty() {
-                state.modal_state.time_slots.push(
-                    TimeSlot::new(state.modal_state.next_slot_id)
-                );
-                state.modal_state.next_slot_id += 1;
-            }
+        Msg::TagRemoved(tag) => {
+            state.modal_state.tags.retain(|existing| *existing != tag);
             Task::none()
         }
-        Msg::TutoringDaySelected(slot_id, day) => {
-            if let Some(slot) = state.modal_state.time_slots.iter_mut().find(|s| s.id == slot_id) {
-                slot.selected_day = Some(day);
-                slot.selected_time = None;
-            }
+        Msg::SearchQueryChanged(query) => {
+            state.search_query = query;
+            state.hovered_student_card = None;
             Task::none()
         }
-        Msg::TutoringTimeSelected(slot_id, time) => {
-            if let Some(slot) = state.modal_state.time_slots.iter_mut().find(|s| s.id == slot_id) {
-                slot.selected_time = Some(time);
-            }
+        Msg::StudentCardHovered(card_idx_opt) => {
+            state.hovered_student_card = card_idx_opt;
             Task::none()
         }
-        Msg::FirstNameInputChanged(name) => {
-            state.modal_state.modal_input.first_name = name;
+        Msg::Tick => Task::none(),
+        Msg::ClockTicked => {
+            state.now = state.clock.now();
             Task::none()
         }
-        Msg::LastNameInputChanged(name) => {
-            state.modal_state.modal_input.last_name = name;
+        Msg::SortSelected(sort) => {
+            state.sort_by = sort;
+            state.sort_descending = sort.descending_by_default();
             Task::none()
         }
-        Msg::OtherNamesInputChanged(name) => {
-            state.modal_state.modal_input.other_names = name;
+        Msg::SortColumnClicked(sort) => {
+            if state.sort_by == sort {
+                state.sort_descending = !state.sort_descending;
+            } else {
+                state.sort_by = sort;
+                state.sort_descending = sort.descending_by_default();
+            }
             Task::none()
         }
-        Msg::RateInputChanged(amount) => {
-            state.modal_state.modal_input.pay_rate = amount;
+        Msg::StudentViewSelected(view) => {
+            state.student_view = view;
+            state.hovered_student_card = None;
             Task::none()
         }
-        Msg::AddStudent => {
-            let validated_data = validate_student(
-                state.modal_state.modal_input.clone(),
-                &state.modal_state.time_slots
-            );
-            
-            if validated_data.is_valid() {
-                Task::perform(
-                    add_student(state.modal_state.modal_input.clone()),
-                    Msg::StudentAdded
-                )
-            } else {
-                state.modal_state.validation_errors = Some(validated_data);
-                Task::none()
+        Msg::ColumnToggled(column, is_visible) => {
+            state.visible_columns.retain(|visible| *visible != column);
+            if is_visible {
+                state.visible_columns.push(column);
+                state
+                    .visible_columns
+                    .sort_by_key(|visible| StudentSort::ALL.iter().position(|c| c == visible));
             }
-        }
-        Msg::StudentAdded(result) => {
-            state.modal_state.modal_message = match result {
-                Ok(()) => "Student saved".to_string(),
-                Err(e) => e.to_string(),
-            };
             Task::none()
         }
-    }
-}
+        Msg::ExportCsv => {
+            let Some(students) = &state.students else {
+                return Task::none();
+            };
 
-pub fn view(state: &StudentManagerState) -> Element<'_, Msg> {
-    view_student_manager(state)
-}
+            let today = state.clock.today();
+            let rows = student_rows(state, students);
+            let csv = students_to_csv(&rows, &state.visible_columns, &state.money_format);
+            let file_name = format!("students-{}.csv", today.format("%Y-%m-%d"));
 
-fn view_student_manager(state: &StudentManagerState) -> Element<'_, Msg> {
+            Task::perform(write_export(file_name, csv), Msg::CsvExported)
+        }
+        Msg::StartInlineEdit(student_id, field) => {
+            let value = state
+                .students
+                .iter()
+                .flatten()
+                .find(|student| student.id == student_id)
+                .map(|student| match field {
+                    InlineField::Rate => student.payment_data.amount.to_string(),
+                    InlineField::Subject => student.subject.to_string(),
+                    InlineField::Tags => student.tags.join(", "),
+                })
+                .unwrap_or_default();
+
+            state.inline_edit = Some(InlineEdit {
+                student_id,
+                field,
+                value,
+                error: None,
+            });
+            Task::none()
+        }
+        Msg::InlineEditChanged(value) => {
+            if let Some(edit) = &mut state.inline_edit {
+                edit.value = value;
+                edit.error = None;
+            }
+            Task::none()
+        }
+        Msg::InlineSubjectSelected(subject) => {
+            let Some(edit) = state.inline_edit.take() else {
+                return Task::none();
+            };
+
+            save_inline_change(state, &edit.student_id, |student| student.subject = subject)
+        }
+        Msg::CommitInlineEdit => {
+            let Some(edit) = state.inline_edit.take() else {
+                return Task::none();
+            };
+
+            if edit.field == InlineField::Tags {
+                let tags = parse_tags(&edit.value);
+                return save_inline_change(state, &edit.student_id, |student| student.tags = tags);
+            }
+
+            match validate_number(edit.value.clone()) {
+                (rate, ValidityTag::Safe) => {
+                    let amount = rate.parse::<f32>().unwrap_or_default();
+                    save_inline_change(state, &edit.student_id, |student| {
+                        student.payment_data.amount = amount
+                    })
+                }
+                (_, ValidityTag::Problematic { message, .. }) => {
+                    state.inline_edit = Some(InlineEdit {
+                        error: Some(message),
+                        ..edit
+                    });
+                    Task::none()
+                }
+            }
+        }
+        Msg::CancelInlineEdit => {
+            state.inline_edit = None;
+            Task::none()
+        }
+        Msg::StudentUpdated(result) => {
+            if let Err(e) = result {
+                state.status_message = Some(e.to_string());
+            }
+            Task::none()
+        }
+        Msg::StartSessionLog(student_id) => {
+            let Some(student) = state
+                .students
+                .iter()
+                .flatten()
+                .find(|student| student.id == student_id)
+            else {
+                return Task::none();
+            };
+
+            let suggested = suggested_session(student, state.clock.now());
+            state.session_log = Some(SessionLog {
+                student_id,
+                student_name: student.name.full_name(),
+                date: suggested
+                    .map(|at| at.date_naive())
+                    .unwrap_or_else(|| state.clock.today())
+                    .format("%Y-%m-%d")
+                    .to_string(),
+                time: suggested
+                    .map(|at| at.format("%-I:%M %p").to_string())
+                    .unwrap_or_default(),
+                location: student.location.clone(),
+                distance: student
+                    .travel_km
+                    .map(|km| km.to_string())
+                    .unwrap_or_default(),
+                usual: student.location.is_none(),
+                engagement: None,
+                notes: String::new(),
+                links: String::new(),
+                error: None,
+            });
+            Task::none()
+        }
+        Msg::StartQuickLog => {
+            let now = state.clock.now();
+            let today = state.clock.today();
+            let latest = state
+                .students
+                .iter()
+                .flatten()
+                .filter(|student| !has_left(student, today))
+                .filter_map(|student| Some((suggested_session(student, now)?, student)))
+                .max_by_key(|(at, _)| *at)
+                .map(|(_, student)| student.id.clone())
+                .or_else(|| {
+                    state
+                        .student_options
+                        .options()
+                        .first()
+                        .map(|choice| choice.student_id.clone())
+                });
+
+            match latest {
+                Some(student_id) => update(state, Msg::StartSessionLog(student_id)),
+                None => Task::none(),
+            }
+        }
+        Msg::SessionLogDateChanged(date) => {
+            if let Some(log) = &mut state.session_log {
+                log.date = date;
+                log.error = None;
+            }
+            Task::none()
+        }
+        Msg::SessionLogTimeChanged(time) => {
+            if let Some(log) = &mut state.session_log {
+                log.time = time;
+                log.error = None;
+            }
+            Task::none()
+        }
+        Msg::SessionLogLocationSelected(location) => {
+            if let Some(log) = &mut state.session_log {
+                log.location = Some(location);
+            }
+            Task::none()
+        }
+        Msg::SessionLogDistanceChanged(distance) => {
+            if let Some(log) = &mut state.session_log {
+                log.distance = distance;
+                log.error = None;
+            }
+            Task::none()
+        }
+        Msg::SessionLogUsualToggled(usual) => {
+            if let Some(log) = &mut state.session_log {
+                log.usual = usual;
+            }
+            Task::none()
+        }
+        Msg::SessionLogEngagementSelected(engagement) => {
+            if let Some(log) = &mut state.session_log {
+                log.engagement = (log.engagement != Some(engagement)).then_some(engagement);
+            }
+            Task::none()
+        }
+        Msg::SessionLogNotesChanged(notes) => {
+            if let Some(log) = &mut state.session_log {
+                log.notes = notes;
+            }
+            Task::none()
+        }
+        Msg::SessionLogLinksChanged(links) => {
+            if let Some(log) = &mut state.session_log {
+                log.links = links;
+                log.error = None;
+            }
+            Task::none()
+        }
+        Msg::LogSession => {
+            let Some(log) = &mut state.session_log else {
+                return Task::none();
+            };
+
+            let distance_km = if is_home_visit(&state.locations, log.location.as_deref()) {
+                log.distance_km()
+            } else {
+                Ok(None)
+            };
+
+            match log
+                .start_time()
+                .and_then(|at| Ok((at, distance_km?, log.links()?)))
+            {
+                Ok((at, distance_km, links)) => Task::done(Msg::SessionLogged {
+                    student_id: log.student_id.clone(),
+                    at,
+                    location: log.location.clone(),
+                    distance_km,
+                    usual: log.usual,
+                    engagement: log.engagement,
+                    notes: log.notes.trim().to_string(),
+                    links,
+                }),
+                Err(error) => {
+                    log.error = Some(error);
+                    Task::none()
+                }
+            }
+        }
+        Msg::CloseSessionLog => {
+            state.session_log = None;
+            Task::none()
+        }
+        Msg::SessionLogged { at, .. } => {
+            if let Some(log) = state.session_log.take() {
+                state.status_message = Some(format!(
+                    "Logged {}'s session on {}",
+                    log.student_name,
+                    at.format("%a %-d %b, %-I:%M %p")
+                ));
+            }
+            Task::none()
+        }
+        Msg::StartPaymentEntry(student_id) => {
+            let Some(student) = state
+                .students
+                .iter()
+                .flatten()
+                .find(|student| student.id == student_id)
+            else {
+                return Task::none();
+            };
+
+            state.payment_entry = Some(PaymentEntry {
+                student_id,
+                student_name: student.name.full_name(),
+                amount: String::new(),
+                error: None,
+            });
+            focus_next()
+        }
+        Msg::PaymentAmountChanged(amount) => {
+            if let Some(entry) = &mut state.payment_entry {
+                entry.amount = amount;
+                entry.error = None;
+            }
+            Task::none()
+        }
+        Msg::RecordPayment => {
+            let Some(entry) = &mut state.payment_entry else {
+                return Task::none();
+            };
+
+            match entry.amount.trim().parse::<f32>() {
+                Ok(amount) if amount > 0.0 => Task::done(Msg::PaymentRecorded(Payment {
+                    student_id: entry.student_id.clone(),
+                    paid_on: state.clock.today(),
+                    amount,
+                    invoice_number: None,
+                })),
+                _ => {
+                    entry.error = Some("Enter the amount received, e.g. 150".to_string());
+                    Task::none()
+                }
+            }
+        }
+        Msg::ClosePaymentEntry | Msg::PaymentRecorded(_) => {
+            state.payment_entry = None;
+            Task::none()
+        }
+        Msg::StartQuote => {
+            state.quote_form = Some(QuoteForm {
+                subject: state
+                    .tutor
+                    .as_ref()
+                    .and_then(|tutor| tutor.subjects.first().cloned()),
+                sessions_per_week: 1,
+                duration_minutes: DEFAULT_SESSION_MINUTES,
+                location: None,
+                message: None,
+            });
+            Task::none()
+        }
+        Msg::QuoteSubjectSelected(subject) => {
+            if let Some(form) = &mut state.quote_form {
+                form.subject = Some(subject);
+                form.message = None;
+            }
+            Task::none()
+        }
+        Msg::QuoteSessionsChanged(sessions) => {
+            if let Some(form) = &mut state.quote_form {
+                form.sessions_per_week = sessions;
+                form.message = None;
+            }
+            Task::none()
+        }
+        Msg::QuoteDurationChanged(minutes) => {
+            if let Some(form) = &mut state.quote_form {
+                form.duration_minutes = minutes;
+                form.message = None;
+            }
+            Task::none()
+        }
+        Msg::QuoteLocationSelected(location) => {
+            if let Some(form) = &mut state.quote_form {
+                form.location = Some(location);
+                form.message = None;
+            }
+            Task::none()
+        }
+        Msg::CopyQuote | Msg::ExportQuote => {
+            let (Some(form), Some(tutor)) = (&state.quote_form, &state.tutor) else {
+                return Task::none();
+            };
+            let Ok(quote) = form.quote(&state.rates, &state.locations) else {
+                return Task::none();
+            };
+
+            let lines = quote.to_lines(&tutor.name, &state.money_format);
+            if let Msg::CopyQuote = msg {
+                let request = CopyRequest::new(lines.join("\n"), "fee quote");
+                return Task::done(Msg::Effect(Effect::Copy(request)));
+            }
+            let file_name = format!("quote-{}.pdf", state.clock.today().format("%Y-%m-%d"));
+            Task::perform(
+                write_export(file_name, text_pdf(&lines)),
+                Msg::QuoteExported,
+            )
+        }
+        Msg::QuoteExported(result) => {
+            if let Some(form) = &mut state.quote_form {
+                form.message = Some(match result {
+                    Ok(path) => format!("Exported to {}", path.display()),
+                    Err(e) => e.to_string(),
+                });
+            }
+            Task::none()
+        }
+        Msg::CloseQuote => {
+            state.quote_form = None;
+            Task::none()
+        }
+        Msg::StartWaitlist => {
+            state.waitlist_form = Some(WaitlistForm {
+                subject: state
+                    .tutor
+                    .as_ref()
+                    .and_then(|tutor| tutor.subjects.first().cloned()),
+                ..WaitlistForm::default()
+            });
+            Task::none()
+        }
+        Msg::WaitlistNameChanged(name) => {
+            if let Some(form) = &mut state.waitlist_form {
+                form.name = name;
+                form.error = None;
+            }
+            Task::none()
+        }
+        Msg::WaitlistSubjectSelected(subject) => {
+            if let Some(form) = &mut state.waitlist_form {
+                form.subject = Some(subject);
+                form.error = None;
+            }
+            Task::none()
+        }
+        Msg::WaitlistSlotToggled(slot, wanted) => {
+            if let Some(form) = &mut state.waitlist_form {
+                form.slots.retain(|chosen| *chosen != slot);
+                if wanted {
+                    form.slots.push(slot);
+                }
+                form.error = None;
+            }
+            Task::none()
+        }
+        Msg::AddProspect => {
+            let Some(form) = &mut state.waitlist_form else {
+                return Task::none();
+            };
+
+            match form.prospect(state.clock.today()) {
+                Ok(prospect) => {
+                    let mut waitlist = state.waitlist.clone();
+                    waitlist.push(prospect);
+                    form.name.clear();
+                    form.slots.clear();
+                    Task::done(Msg::WaitlistChanged(waitlist))
+                }
+                Err(error) => {
+                    form.error = Some(error);
+                    Task::none()
+                }
+            }
+        }
+        Msg::RemoveProspect(index) => {
+            let mut waitlist = state.waitlist.clone();
+            if index >= waitlist.len() {
+                return Task::none();
+            }
+            waitlist.remove(index);
+            Task::done(Msg::WaitlistChanged(waitlist))
+        }
+        // Applied by the app, which refreshes this screen.
+        Msg::WaitlistChanged(_) | Msg::SessionMarked { .. } => Task::none(),
+        Msg::CloseWaitlist => {
+            state.waitlist_form = None;
+            Task::none()
+        }
+        Msg::StartRateWizard => {
+            let today = state.clock.today();
+            let (year, month) = next_month(today.year(), today.month());
+            state.rate_wizard = Some(RateWizard {
+                student_ids: state
+                    .students
+                    .iter()
+                    .flatten()
+                    .filter(|student| is_active_on(student, today))
+                    .map(|student| student.id.clone())
+                    .collect(),
+                amount: "10".to_string(),
+                from: format!("{}-{:02}-01", year, month),
+                ..RateWizard::default()
+            });
+            Task::none()
+        }
+        Msg::RateWizardStudentToggled(student_id, chosen) => {
+            if let Some(wizard) = &mut state.rate_wizard {
+                wizard.student_ids.retain(|id| *id != student_id);
+                if chosen {
+                    wizard.student_ids.push(student_id);
+                }
+                wizard.error = None;
+            }
+            Task::none()
+        }
+        Msg::RateWizardKindSelected(kind) => {
+            if let Some(wizard) = &mut state.rate_wizard {
+                wizard.kind = kind;
+                wizard.error = None;
+            }
+            Task::none()
+        }
+        Msg::RateWizardAmountChanged(amount) => {
+            if let Some(wizard) = &mut state.rate_wizard {
+                wizard.amount = amount;
+                wizard.error = None;
+            }
+            Task::none()
+        }
+        Msg::RateWizardFromChanged(from) => {
+            if let Some(wizard) = &mut state.rate_wizard {
+                wizard.from = from;
+                wizard.error = None;
+            }
+            Task::none()
+        }
+        Msg::RateWizardNext => {
+            if let Some(wizard) = &mut state.rate_wizard {
+                match wizard.adjustment() {
+                    Ok(_) => wizard.step = RateWizardStep::Notify,
+                    Err(error) => wizard.error = Some(error),
+                }
+            }
+            Task::none()
+        }
+        Msg::RateWizardBack => {
+            if let Some(wizard) = &mut state.rate_wizard {
+                wizard.step = RateWizardStep::Choose;
+            }
+            Task::none()
+        }
+        Msg::CopyRateNotices(student_id) => {
+            let (Some(wizard), Some(tutor), Some(students)) =
+                (&state.rate_wizard, &state.tutor, &state.students)
+            else {
+                return Task::none();
+            };
+            let Ok((_, from)) = wizard.adjustment() else {
+                return Task::none();
+            };
+
+            let notices: Vec<String> = wizard
+                .new_rates(students, &state.money_format)
+                .into_iter()
+                .filter(|(student, _)| student_id.as_ref().is_none_or(|id| *id == student.id))
+                .map(|(student, rate)| {
+                    rate_notice(student, &tutor.name, rate, from, &state.money_format)
+                })
+                .collect();
+            let what = if notices.len() == 1 {
+                "rate notice"
+            } else {
+                "rate notices"
+            };
+            let request = CopyRequest::new(notices.join("\n\n---\n\n"), what);
+            Task::done(Msg::Effect(Effect::Copy(request)))
+        }
+        Msg::ApplyRateWizard => {
+            let (Some(wizard), Some(students)) = (&state.rate_wizard, &state.students) else {
+                return Task::none();
+            };
+            let Ok((_, from)) = wizard.adjustment() else {
+                return Task::none();
+            };
+
+            let rates = wizard
+                .new_rates(students, &state.money_format)
+                .into_iter()
+                .map(|(student, rate)| (student.id.clone(), rate))
+                .collect();
+            Task::done(Msg::RatesAdjusted { from, rates })
+        }
+        Msg::CloseRateWizard | Msg::RatesAdjusted { .. } => {
+            state.rate_wizard = None;
+            Task::none()
+        }
+        Msg::CsvExported(result) => {
+            state.status_message = Some(match result {
+                Ok(path) => format!("Exported to {}", path.display()),
+                Err(e) => e.to_string(),
+            });
+            Task::none()
+        }
+        Msg::CardDensitySelected(density) => {
+            state.card_density = density;
+            Task::none()
+        }
+        Msg::StartStudentRemoval(student_id) => {
+            let today = state.clock.today();
+            state.removal = state
+                .students
+                .iter()
+                .flatten()
+                .find(|student| student.id == student_id)
+                .map(|student| StudentRemoval {
+                    student_id,
+                    student_name: student.name.full_name(),
+                    archived: has_left(student, today),
+                });
+            Task::none()
+        }
+        Msg::ArchiveStudent => {
+            let Some(removal) = state.removal.take() else {
+                return Task::none();
+            };
+
+            let today = state.clock.today();
+            let Some(end) = Local
+                .from_local_datetime(&today.and_time(NaiveTime::MIN))
+                .earliest()
+            else {
+                return Task::none();
+            };
+            state.status_message = Some(format!("{} archived", removal.student_name));
+            save_inline_change(state, &removal.student_id, |student| {
+                student.tution_end_date = Some(end)
+            })
+        }
+        Msg::RestoreStudent => {
+            let Some(removal) = state.removal.take() else {
+                return Task::none();
+            };
+
+            state.status_message = Some(format!("{} restored", removal.student_name));
+            save_inline_change(state, &removal.student_id, |student| {
+                student.tution_end_date = None
+            })
+        }
+        Msg::DeleteStudent => {
+            let Some(removal) = state.removal.take() else {
+                return Task::none();
+            };
+
+            state.status_message = Some(format!(
+                "{} moved to the trash in Settings",
+                removal.student_name
+            ));
+            Task::done(Msg::StudentDeleted(removal.student_id))
+        }
+        Msg::CloseStudentRemoval | Msg::StudentDeleted(_) => {
+            state.removal = None;
+            Task::none()
+        }
+        Msg::ShowArchivedToggled(show_archived) => {
+            state.show_archived = show_archived;
+            state.hovered_student_card = None;
+            Task::none()
+        }
+        Msg::MetricsPreviousMonth => {
+            let (year, month) = state.shown_month();
+            state.metrics_month = Some(previous_month(year, month));
+            Task::none()
+        }
+        Msg::MetricsNextMonth => {
+            let today = state.clock.today();
+            let (year, month) = state.shown_month();
+            let next = next_month(year, month);
+            state.metrics_month = (next < (today.year(), today.month())).then_some(next);
+            Task::none()
+        }
+        Msg::MetricsThisMonth => {
+            state.metrics_month = None;
+            Task::none()
+        }
+        Msg::TagFilterDraftChanged(draft) => {
+            state.tag_filter_draft = draft;
+            Task::none()
+        }
+        Msg::TagFilterAdded(tag) => {
+            add_tag(&mut state.tag_filter, &tag);
+            state.tag_filter_draft.clear();
+            state.hovered_student_card = None;
+            Task::none()
+        }
+        Msg::TagFilterRemoved(tag) => {
+            state.tag_filter.retain(|existing| *existing != tag);
+            state.hovered_student_card = None;
+            Task::none()
+        }
+        Msg::TagFilterCleared => {
+            state.tag_filter.clear();
+            state.hovered_student_card = None;
+            Task::none()
+        }
+        Msg::AddOtherSubject => {
+            let modal = &mut state.modal_state;
+            modal.other_subjects.push(OtherSubjectDraft {
+                subject: None,
+                rate: modal.modal_input.pay_rate.clone(),
+                slot: TimeSlot::new(0),
+                more_slots: Vec::new(),
+            });
+            modal.other_subjects_error = None;
+            Task::none()
+        }
+        Msg::RemoveOtherSubject(index) => {
+            if index < state.modal_state.other_subjects.len() {
+                state.modal_state.other_subjects.remove(index);
+            }
+            state.modal_state.other_subjects_error = None;
+            Task::none()
+        }
+        Msg::OtherSubjectSelected(index, subject) => {
+            if let Some(draft) = state.modal_state.other_subjects.get_mut(index) {
+                draft.subject = Some(subject);
+            }
+            state.modal_state.other_subjects_error = None;
+            Task::none()
+        }
+        Msg::OtherSubjectRateChanged(index, rate) => {
+            if let Some(draft) = state.modal_state.other_subjects.get_mut(index) {
+                draft.rate = rate;
+            }
+            state.modal_state.other_subjects_error = None;
+            Task::none()
+        }
+        Msg::OtherSubjectDaySelected(index, day) => {
+            if let Some(draft) = state.modal_state.other_subjects.get_mut(index) {
+                draft.slot.selected_day = Some(day);
+                draft.slot.selected_time = None;
+            }
+            state.modal_state.other_subjects_error = None;
+            Task::none()
+        }
+        Msg::OtherSubjectTimeSelected(index, time) => {
+            if let Some(draft) = state.modal_state.other_subjects.get_mut(index) {
+                draft.slot.selected_time = Some(time);
+            }
+            state.modal_state.other_subjects_error = None;
+            Task::none()
+        }
+        Msg::AddTimeSlot => {
+            if state.modal_state.time_slots.len() < MAX_TIME_SLOTS {
+                state.modal_state.time_slots.push(
+                    TimeSlot::new(state.modal_state.next_slot_id)
+                );
+                state.modal_state.next_slot_id += 1;
+            }
+            Task::none()
+        }
+        Msg::RemoveTimeSlot(id) => {
+            state.modal_state.time_slots.retain(|slot| slot.id != id);
+            if state.modal_state.time_slots.is_empty() {
+                state.modal_state.time_slots.push(
+                    TimeSlot::new(state.modal_state.next_slot_id)
+                );
+                state.modal_state.next_slot_id += 1;
+            }
+            Task::none()
+        }
+        Msg::DuplicateTimeSlot(id) => {
+            state.modal_state.duplicate_slot(id);
+            Task::none()
+        }
+        Msg::TimeSlotDragStarted(id) => {
+            state.modal_state.dragged_slot = Some(id);
+            Task::none()
+        }
+        Msg::TimeSlotDraggedOver(onto_id) => {
+            if let Some(id) = state.modal_state.dragged_slot {
+                state.modal_state.move_slot(id, onto_id);
+            }
+            Task::none()
+        }
+        Msg::TimeSlotDragEnded => {
+            state.modal_state.dragged_slot = None;
+            Task::none()
+        }
+        Msg::TutoringDaySelected(slot_id, day) => {
+            if let Some(slot) = state.modal_state.time_slots.iter_mut().find(|s| s.id == slot_id) {
+                slot.selected_day = Some(day);
+                slot.selected_time = None;
+            }
+            Task::none()
+        }
+        Msg::TutoringTimeSelected(slot_id, time) => {
+            if let Some(slot) = state.modal_state.time_slots.iter_mut().find(|s| s.id == slot_id) {
+                slot.selected_time = Some(time);
+            }
+            Task::none()
+        }
+        Msg::TimeSlotDurationChanged(slot_id, minutes) => {
+            if let Some(slot) = state
+                .modal_state
+                .time_slots
+                .iter_mut()
+                .find(|s| s.id == slot_id)
+            {
+                slot.duration_minutes = minutes;
+            }
+            Task::none()
+        }
+        Msg::StartDateChanged(date) => {
+            state.modal_state.modal_input.start_date = date;
+            Task::none()
+        }
+        Msg::BackfillSessionToggled(at, held) => {
+            if held {
+                state.modal_state.backfill_held.insert(at);
+            } else {
+                state.modal_state.backfill_held.remove(&at);
+            }
+            Task::none()
+        }
+        Msg::BackfillAllToggled(held) => {
+            state.modal_state.backfill_held = if held {
+                state
+                    .modal_state
+                    .expected_backfill(state.clock.today())
+                    .into_iter()
+                    .collect()
+            } else {
+                HashSet::new()
+            };
+            Task::none()
+        }
+        Msg::BackfillConfirmed(confirmed) => {
+            state.modal_state.backfill_confirmed =
+                confirmed.then(|| state.modal_state.expected_backfill(state.clock.today()));
+            Task::none()
+        }
+        Msg::Effect(_) => Task::none(),
+        Msg::FirstNameInputChanged(name) => {
+            state.modal_state.modal_input.first_name = name;
+            Task::none()
+        }
+        Msg::LastNameInputChanged(name) => {
+            state.modal_state.modal_input.last_name = name;
+            Task::none()
+        }
+        Msg::OtherNamesInputChanged(name) => {
+            state.modal_state.modal_input.other_names = name;
+            Task::none()
+        }
+        Msg::PhoneInputChanged(phone) => {
+            state.modal_state.modal_input.phone = phone;
+            Task::none()
+        }
+        Msg::EmailInputChanged(email) => {
+            state.modal_state.modal_input.email = email;
+            Task::none()
+        }
+        Msg::RateInputChanged(amount) => {
+            state.modal_state.modal_input.pay_rate = amount;
+            Task::none()
+        }
+        Msg::MinSessionsChanged(sessions) => {
+            state.modal_state.modal_input.min_sessions = sessions;
+            Task::none()
+        }
+        Msg::AddStudent => {
+            let today = state.clock.today();
+            let validated_data = validate_student(
+                state.modal_state.modal_input.clone(),
+                &state.modal_state.time_slots,
+                state.modal_state.backfill_pending(today),
+            );
+
+            if let Err(e) = state.modal_state.other_subjects(PaymentType::PerSession) {
+                state.modal_state.other_subjects_error = Some(e);
+                return Task::none();
+            }
+
+            if validated_data.is_valid() {
+                let id = new_student_id(
+                    &state.modal_state.modal_input,
+                    state.students.iter().flatten(),
+                );
+                let student = state.modal_state.added(id, today);
+
+                Task::perform(add_student(student), Msg::StudentAdded)
+            } else {
+                state.modal_state.validation_errors = Some(validated_data);
+                Task::none()
+            }
+        }
+        Msg::StudentAdded(result) => {
+            let message = match result {
+                Ok(student) => {
+                    state.modal_state.clear();
+                    format!("{} saved", student.name.full_name())
+                }
+                Err(e) => e.to_string(),
+            };
+            state.modal_state.modal_message = message;
+            Task::none()
+        }
+        Msg::OpenContact(student_id, action) => {
+            let link = state
+                .students
+                .iter()
+                .flatten()
+                .find(|student| student.id == student_id)
+                .and_then(|student| action.link(student));
+
+            match link {
+                Some(link) => Task::perform(open_contact(action, link), Msg::ContactOpened),
+                None => Task::none(),
+            }
+        }
+        Msg::ContactOpened(result) => {
+            if let Err(e) = result {
+                state.status_message = Some(e.to_string());
+            }
+            Task::none()
+        }
+        Msg::StartStudentEdit(student_id) => {
+            let Some(student) = state
+                .students
+                .iter()
+                .flatten()
+                .find(|student| student.id == student_id)
+            else {
+                return Task::none();
+            };
+
+            state.modal_state.load(student);
+            state.show_add_student_modal = true;
+            focus_next()
+        }
+        Msg::UpdateStudent => {
+            let Some(student) = state.modal_state.editing.as_ref().and_then(|student_id| {
+                state
+                    .students
+                    .iter()
+                    .flatten()
+                    .find(|student| student.id == *student_id)
+            }) else {
+                return Task::none();
+            };
+
+            // Past sessions are already logged, so there is nothing to
+            // backfill when editing.
+            let validated_data = validate_student(
+                state.modal_state.modal_input.clone(),
+                &state.modal_state.time_slots,
+                false,
+            );
+            if !validated_data.is_valid() {
+                state.modal_state.validation_errors = Some(validated_data);
+                return Task::none();
+            }
+            if let Err(e) = state
+                .modal_state
+                .other_subjects(student.payment_data.payment_type)
+            {
+                state.modal_state.other_subjects_error = Some(e);
+                return Task::none();
+            }
+
+            let student = state.modal_state.edited(student);
+            state.modal_state.clear();
+            state.show_add_student_modal = false;
+            Task::perform(update_student(student), Msg::StudentUpdated)
+        }
+    }
+}
+
+pub fn subscription(state: &StudentManagerState) -> Subscription<Msg> {
+    if state.rings_filled.in_progress(Instant::now()) {
+        frames().map(|_| Msg::Tick)
+    } else {
+        Subscription::none()
+    }
+}
+
+pub fn view(state: &StudentManagerState) -> Element<'_, Msg> {
+    view_student_manager(state)
+}
+
+fn view_student_manager(state: &StudentManagerState) -> Element<'_, Msg> {
     let search_bar = view_search_bar("Search Students", &state.search_query);
     let add_button = create_add_student_button();
-    let action_bar = row![search_bar, add_button].spacing(100);
-    
-    let card_container = container(
-        Row::new()
-            .extend(view_student_manager_card_list(state))
-            .spacing(30)
+    let sort_picker = pick_list(StudentSort::ALL, Some(state.sort_by), Msg::SortSelected);
+    let density_picker = pick_list(
+        CardDensity::ALL,
+        Some(state.card_density),
+        Msg::CardDensitySelected,
     );
+    let view_picker = pick_list(
+        StudentView::ALL,
+        Some(state.student_view),
+        Msg::StudentViewSelected,
+    );
+
+    let archived_toggle = checkbox(state.show_archived)
+        .label("Show archived")
+        .text_size(14)
+        .on_toggle(Msg::ShowArchivedToggled);
+
+    let view_options = match state.student_view {
+        StudentView::Cards => row![view_picker, sort_picker, density_picker, archived_toggle],
+        StudentView::Table => row![
+            view_picker,
+            archived_toggle,
+            button(text("Export CSV").size(14))
+                .style(button::secondary)
+                .on_press(Msg::ExportCsv),
+        ],
+    }
+    .spacing(10)
+    .align_y(Center);
+
+    let quote_button = button(text("Quote fees").size(14))
+        .style(button::secondary)
+        .on_press(Msg::StartQuote);
+    let waitlist_label = if state.waitlist.is_empty() {
+        "Waitlist".to_string()
+    } else {
+        format!("Waitlist ({})", state.waitlist.len())
+    };
+    let waitlist_button = button(text(waitlist_label).size(14))
+        .style(button::secondary)
+        .on_press(Msg::StartWaitlist);
+    let log_button = button(text("Log session").size(14))
+        .style(button::secondary)
+        .on_press(Msg::StartQuickLog);
+    let rates_button = button(text("Raise rates").size(14))
+        .style(button::secondary)
+        .on_press(Msg::StartRateWizard);
+    let compare_button = button(text("Compare").size(14))
+        .style(button::secondary)
+        .on_press(Msg::Effect(Effect::Navigate(Route::Compare)));
+    let action_bar = row![
+        search_bar,
+        view_options,
+        row![
+            log_button,
+            waitlist_button,
+            rates_button,
+            compare_button,
+            quote_button,
+            add_button
+        ]
+        .spacing(20)
+        .align_y(Center)
+    ]
+    .spacing(100)
+    .align_y(Center);
+    let tag_filter = row![
+        text("Tags").size(14),
+        tag_input(
+            &state.tag_filter,
+            &state.tag_filter_draft,
+            &state.known_tags,
+            "Filter by tag",
+            Msg::TagFilterDraftChanged,
+            Msg::TagFilterAdded,
+            Msg::TagFilterRemoved,
+        ),
+    ]
+    .spacing(10);
+
+    let student_list: Element<'_, Msg> = match state.student_view {
+        StudentView::Cards => container(
+            Row::new()
+                .extend(view_student_manager_card_list(state))
+                .spacing(30),
+        )
+        .into(),
+        StudentView::Table => view_student_table(state),
+    };
 
-    let header = page_header("Student Manager");
+    let (year, month) = state.shown_month();
+    let month_selector = row![
+        button(text("Previous").size(12))
+            .style(button::text)
+            .on_press(Msg::MetricsPreviousMonth),
+        text(format!("Figures for {}", month_label(year, month))).size(14),
+        button(text("Next").size(12))
+            .style(button::text)
+            .on_press_maybe(state.metrics_month.map(|_| Msg::MetricsNextMonth)),
+    ]
+    .push(state.metrics_month.map(|_| {
+        button(text("This month").size(12))
+            .style(button::secondary)
+            .on_press(Msg::MetricsThisMonth)
+    }))
+    .spacing(10)
+    .align_y(Center);
+    let header = page_header("Student Manager")
+        .push(month_selector)
+        .spacing(40)
+        .align_y(Center);
     let main_area_content = global_content_container(
-        column![action_bar, card_container].spacing(30)
+        column![column![action_bar, tag_filter].spacing(10), student_list].spacing(30),
     )
     .width(Length::Fill)
     .height(Length::Fill);
@@ -279,6 +2303,26 @@ fn view_student_manager(state: &StudentManagerState) -> Element<'_, Msg> {
 
     if state.show_add_student_modal {
         modal(main_container, modal_content_container(state)).into()
+    } else if let Some(log) = &state.session_log {
+        modal(
+            main_container,
+            view_session_log(log, &state.locations, &state.student_options),
+        )
+        .into()
+    } else if let Some(entry) = &state.payment_entry {
+        modal(
+            main_container,
+            view_payment_entry(entry, state.money_format.currency),
+        )
+        .into()
+    } else if let Some(form) = &state.quote_form {
+        modal(main_container, view_quote_form(form, state)).into()
+    } else if let Some(form) = &state.waitlist_form {
+        modal(main_container, view_waitlist(form, state)).into()
+    } else if let Some(wizard) = &state.rate_wizard {
+        modal(main_container, view_rate_wizard(wizard, state)).into()
+    } else if let Some(removal) = &state.removal {
+        modal(main_container, view_student_removal(removal)).into()
     } else {
         main_container.into()
     }
@@ -291,7 +2335,7 @@ fn create_add_student_button<'a>() -> Element<'a, Msg> {
                 .width(22)
                 .height(22)
                 .style(|_theme: &Theme, _status| svg::Style {
-                    color: Some(Color::from_rgba(0.0, 0.2, 0.9, 0.7)),
+                    color: Some(theme::ACCENT),
                 }),
             text("Add Student")
                 .font(Font {
@@ -299,32 +2343,585 @@ fn create_add_student_button<'a>() -> Element<'a, Msg> {
                     ..Default::default()
                 })
                 .style(|_theme: &Theme| text::Style {
-                    color: Some(Color::from_rgba(0.0, 0.2, 0.9, 0.7)),
+                    color: Some(theme::ACCENT),
+                }),
+        ]
+        .align_y(Center)
+        .spacing(5),
+    )
+    .style(|_theme, _status| button::Style {
+        background: None,
+        ..Default::default()
+    })
+    .on_press(Msg::ShowAddStudentModal)
+    .into()
+}
+
+/// Logs a session for one student, starting from the slot it most likely
+/// was so that usually only "Log" needs pressing.
+fn view_session_log<'a>(
+    log: &'a SessionLog,
+    locations: &[Location],
+    students: &'a combo_box::State<StudentChoice>,
+) -> Element<'a, Msg> {
+    let student = StudentChoice {
+        student_id: log.student_id.clone(),
+        name: log.student_name.clone(),
+    };
+
+    let error = log.error.as_deref().map(|error| {
+        text(error).size(12).style(|_theme: &Theme| text::Style {
+            color: Some(theme::DANGER),
+        })
+    });
+    let location = (!locations.is_empty()).then(|| {
+        let names: Vec<String> = locations
+            .iter()
+            .map(|location| location.name.clone())
+            .collect();
+
+        let distance = is_home_visit(locations, log.location.as_deref()).then(|| {
+            text_input("km", &log.distance)
+                .on_input(Msg::SessionLogDistanceChanged)
+                .on_submit(Msg::LogSession)
+                .width(Length::Fixed(70.0))
+        });
+
+        row![
+            pick_list(names, log.location.clone(), Msg::SessionLogLocationSelected)
+                .placeholder("Where was it held?")
+                .width(Length::Fixed(200.0)),
+        ]
+        .push(distance)
+        .push(
+            checkbox(log.usual)
+                .label("Usual")
+                .text_size(12)
+                .on_toggle(Msg::SessionLogUsualToggled),
+        )
+        .spacing(10)
+        .align_y(Center)
+    });
+
+    container(
+        column![
+            page_header("Log Session").padding([10, 0]),
+            row![
+                text("Session held with").size(14),
+                searchable_pick_list(students, "Student", Some(&student), |choice| {
+                    Msg::StartSessionLog(choice.student_id)
                 }),
+            ]
+            .spacing(10)
+            .align_y(Center),
+            row![
+                text_input("YYYY-MM-DD", &log.date)
+                    .on_input(Msg::SessionLogDateChanged)
+                    .on_submit(Msg::LogSession)
+                    .width(Length::Fixed(140.0)),
+                text_input("5:30 PM", &log.time)
+                    .on_input(Msg::SessionLogTimeChanged)
+                    .on_submit(Msg::LogSession)
+                    .width(Length::Fixed(120.0)),
+            ]
+            .spacing(10),
+        ]
+        .push(location)
+        .push(
+            row![text("Engagement").size(12)]
+                .extend((1..=MAX_ENGAGEMENT).map(|engagement| {
+                    button(text(engagement.to_string()).size(12))
+                        .style(if log.engagement == Some(engagement) {
+                            button::primary
+                        } else {
+                            button::secondary
+                        })
+                        .on_press(Msg::SessionLogEngagementSelected(engagement))
+                        .into()
+                }))
+                .spacing(6)
+                .align_y(Center),
+        )
+        .push(
+            text_input("Notes, e.g. what was covered", &log.notes)
+                .on_input(Msg::SessionLogNotesChanged)
+                .on_submit(Msg::LogSession),
+        )
+        .push(
+            text_input("Recording or whiteboard links, if online", &log.links)
+                .on_input(Msg::SessionLogLinksChanged)
+                .on_submit(Msg::LogSession),
+        )
+        .push(error)
+        .push(
+            row![
+                button(text("Cancel").size(14))
+                    .style(button::secondary)
+                    .on_press(Msg::CloseSessionLog),
+                button(text("Log").size(14)).on_press(Msg::LogSession),
+            ]
+            .spacing(10),
+        )
+        .spacing(16),
+    )
+    .width(400)
+    .padding([10, 30])
+    .style(container::rounded_box)
+    .into()
+}
+
+/// Works out fees while a parent is on the phone, updating as they type.
+fn view_quote_form<'a>(form: &'a QuoteForm, state: &'a StudentManagerState) -> Element<'a, Msg> {
+    let subjects = state
+        .tutor
+        .as_ref()
+        .map(|tutor| tutor.subjects.clone())
+        .unwrap_or_default();
+    let locations: Vec<String> = state
+        .locations
+        .iter()
+        .map(|location| location.name.clone())
+        .collect();
+    let money = &state.money_format;
+
+    let outcome: Element<'a, Msg> = match form.quote(&state.rates, &state.locations) {
+        Ok(quote) => column![
+            text(format!("Per session: {}", money.format(quote.per_session))).size(14),
+            text(format!("Per month: {}", money.format(quote.monthly()))).size(14),
+            text(format!("Per term: {}", money.format(quote.term()))).size(14),
+        ]
+        .spacing(4)
+        .into(),
+        Err(hint) => text(hint).size(12).into(),
+    };
+    let can_share = form.quote(&state.rates, &state.locations).is_ok();
+
+    container(
+        column![
+            page_header("Quote Fees").padding([10, 0]),
+            pick_list(subjects, form.subject.clone(), Msg::QuoteSubjectSelected)
+                .placeholder("Subject")
+                .width(Length::Fill),
+            row![
+                number_stepper(
+                    form.sessions_per_week,
+                    SESSIONS_PER_WEEK,
+                    1,
+                    Msg::QuoteSessionsChanged
+                ),
+                text("a week, of").size(14),
+                number_stepper(
+                    form.duration_minutes,
+                    SESSION_MINUTES,
+                    SESSION_MINUTES_STEP,
+                    Msg::QuoteDurationChanged
+                ),
+                text("minutes").size(14),
+            ]
+            .spacing(10)
+            .align_y(Center),
+        ]
+        .push((!locations.is_empty()).then(|| {
+            pick_list(locations, form.location.clone(), Msg::QuoteLocationSelected)
+                .placeholder("Where")
+                .width(Length::Fill)
+        }))
+        .push(outcome)
+        .push(
+            form.message
+                .as_deref()
+                .map(|message| text(message).size(12)),
+        )
+        .push(
+            row![
+                button(text("Close").size(14))
+                    .style(button::secondary)
+                    .on_press(Msg::CloseQuote),
+                button(text("Copy").size(14)).on_press_maybe(can_share.then_some(Msg::CopyQuote)),
+                button(text("Export PDF").size(14))
+                    .on_press_maybe(can_share.then_some(Msg::ExportQuote)),
+            ]
+            .spacing(10),
+        )
+        .spacing(16),
+    )
+    .width(440)
+    .padding([10, 30])
+    .style(container::rounded_box)
+    .into()
+}
+
+/// Who is waiting for a slot, with the free slots that would suit them,
+/// and a form to add someone new.
+fn view_waitlist<'a>(form: &'a WaitlistForm, state: &'a StudentManagerState) -> Element<'a, Msg> {
+    let (subjects, slots) = state
+        .tutor
+        .as_ref()
+        .map(|tutor| (tutor.subjects.clone(), available_slots(tutor)))
+        .unwrap_or_default();
+
+    let waiting: Element<'a, Msg> = if state.waitlist.is_empty() {
+        text("Nobody is waiting").size(14).into()
+    } else {
+        Column::new()
+            .extend(state.waitlist.iter().enumerate().map(|(index, prospect)| {
+                let wanted = prospect
+                    .slots
+                    .iter()
+                    .map(WeeklySlot::to_string)
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                let free = state
+                    .waitlist_matches
+                    .iter()
+                    .filter(|matched| matched.prospect == prospect.name)
+                    .map(|matched| matched.slot.to_string())
+                    .collect::<Vec<_>>();
+
+                column![
+                    row![
+                        text(&prospect.name).size(14).width(Length::Fill),
+                        text(prospect.subject.to_string()).size(12),
+                        button(text("Remove").size(12))
+                            .style(button::text)
+                            .on_press(Msg::RemoveProspect(index)),
+                    ]
+                    .spacing(10)
+                    .align_y(Center),
+                    text(format!(
+                        "Since {} · wants {}",
+                        prospect.added_on.format("%-d %b"),
+                        wanted
+                    ))
+                    .size(12),
+                ]
+                .push((!free.is_empty()).then(|| {
+                    text(format!("Free now: {}", free.join(", ")))
+                        .size(12)
+                        .style(|_theme: &Theme| text::Style {
+                            color: Some(theme::SUCCESS),
+                        })
+                }))
+                .spacing(2)
+                .into()
+            }))
+            .spacing(10)
+            .into()
+    };
+
+    let slot_choices = Column::new()
+        .extend(slots.into_iter().map(|slot| {
+            let chosen = form.slots.contains(&slot);
+            checkbox(chosen)
+                .label(slot.to_string())
+                .text_size(12)
+                .on_toggle(move |wanted| Msg::WaitlistSlotToggled(slot.clone(), wanted))
+                .into()
+        }))
+        .spacing(4);
+    let error = form.error.as_deref().map(|error| {
+        text(error).size(12).style(|_theme: &Theme| text::Style {
+            color: Some(theme::DANGER),
+        })
+    });
+
+    container(
+        column![
+            page_header("Waitlist").padding([10, 0]),
+            scrollable(waiting).height(Length::Shrink),
+            text("Add someone").size(14),
+            row![
+                text_input("Name", &form.name)
+                    .on_input(Msg::WaitlistNameChanged)
+                    .on_submit(Msg::AddProspect)
+                    .width(Length::Fill),
+                pick_list(subjects, form.subject.clone(), Msg::WaitlistSubjectSelected)
+                    .placeholder("Subject"),
+            ]
+            .spacing(10),
+            slot_choices,
+        ]
+        .push(error)
+        .push(
+            row![
+                button(text("Close").size(14))
+                    .style(button::secondary)
+                    .on_press(Msg::CloseWaitlist),
+                button(text("Add").size(14)).on_press(Msg::AddProspect),
+            ]
+            .spacing(10),
+        )
+        .spacing(16),
+    )
+    .width(440)
+    .padding([10, 30])
+    .style(container::rounded_box)
+    .into()
+}
+
+/// Raises rates for several students, then shows each one's new rate and
+/// a notice to send their parents before anything is saved.
+fn view_rate_wizard<'a>(
+    wizard: &'a RateWizard,
+    state: &'a StudentManagerState,
+) -> Element<'a, Msg> {
+    let students = state.students.as_deref().unwrap_or_default();
+    let money = &state.money_format;
+    let error = wizard.error.as_deref().map(|error| {
+        text(error).size(12).style(|_theme: &Theme| text::Style {
+            color: Some(theme::DANGER),
+        })
+    });
+
+    let body: Element<'a, Msg> = match wizard.step {
+        RateWizardStep::Choose => {
+            let choices = Column::new()
+                .extend(students.iter().map(|student| {
+                    let student_id = student.id.clone();
+                    checkbox(wizard.student_ids.contains(&student.id))
+                        .label(format!(
+                            "{} ({})",
+                            student.name.full_name(),
+                            money.format(student.payment_data.amount)
+                        ))
+                        .text_size(12)
+                        .on_toggle(move |chosen| {
+                            Msg::RateWizardStudentToggled(student_id.clone(), chosen)
+                        })
+                        .into()
+                }))
+                .spacing(4);
+            let unit = match wizard.kind {
+                AdjustmentKind::Percentage => "%".to_string(),
+                AdjustmentKind::Flat => money.currency.to_string(),
+            };
+
+            column![
+                scrollable(choices).height(Length::Fixed(160.0)),
+                row![
+                    pick_list(
+                        AdjustmentKind::ALL,
+                        Some(wizard.kind),
+                        Msg::RateWizardKindSelected
+                    ),
+                    text_input("10", &wizard.amount)
+                        .on_input(Msg::RateWizardAmountChanged)
+                        .on_submit(Msg::RateWizardNext)
+                        .width(Length::Fixed(80.0)),
+                    text(unit).size(14),
+                ]
+                .spacing(10)
+                .align_y(Center),
+                row![
+                    text("From").size(14),
+                    text_input("YYYY-MM-01", &wizard.from)
+                        .on_input(Msg::RateWizardFromChanged)
+                        .on_submit(Msg::RateWizardNext)
+                        .width(Length::Fixed(140.0)),
+                ]
+                .spacing(10)
+                .align_y(Center),
+            ]
+            .push(error)
+            .push(
+                row![
+                    button(text("Cancel").size(14))
+                        .style(button::secondary)
+                        .on_press(Msg::CloseRateWizard),
+                    button(text("Next").size(14)).on_press(Msg::RateWizardNext),
+                ]
+                .spacing(10),
+            )
+            .spacing(16)
+            .into()
+        }
+        RateWizardStep::Notify => {
+            let rows = Column::new()
+                .extend(
+                    wizard
+                        .new_rates(students, money)
+                        .into_iter()
+                        .map(|(student, rate)| {
+                            row![
+                                text(student.name.full_name()).size(14).width(Length::Fill),
+                                text(format!(
+                                    "{} to {}",
+                                    money.format(student.payment_data.amount),
+                                    money.format(rate)
+                                ))
+                                .size(12),
+                                copy_button()
+                                    .on_press(Msg::CopyRateNotices(Some(student.id.clone()))),
+                            ]
+                            .spacing(10)
+                            .align_y(Center)
+                            .into()
+                        }),
+                )
+                .spacing(6);
+
+            column![
+                text(format!("New rates from {}", wizard.from.trim())).size(14),
+                scrollable(rows).height(Length::Fixed(180.0)),
+                text("Copy a notice for each student's parents, or all of them at once.").size(12),
+                row![
+                    button(text("Back").size(14))
+                        .style(button::secondary)
+                        .on_press(Msg::RateWizardBack),
+                    button(text("Copy all notices").size(14))
+                        .style(button::secondary)
+                        .on_press(Msg::CopyRateNotices(None)),
+                    button(text("Apply").size(14)).on_press(Msg::ApplyRateWizard),
+                ]
+                .spacing(10),
+            ]
+            .spacing(16)
+            .into()
+        }
+    };
+
+    container(column![page_header("Raise Rates").padding([10, 0]), body].spacing(16))
+        .width(480)
+        .padding([10, 30])
+        .style(container::rounded_box)
+        .into()
+}
+
+fn is_home_visit(locations: &[Location], name: Option<&str>) -> bool {
+    locations
+        .iter()
+        .any(|location| location.home_visit && Some(location.name.as_str()) == name)
+}
+
+fn view_payment_entry(entry: &PaymentEntry, currency: Currency) -> Element<'_, Msg> {
+    let error = entry.error.as_deref().map(|error| {
+        text(error).size(12).style(|_theme: &Theme| text::Style {
+            color: Some(theme::DANGER),
+        })
+    });
+
+    container(
+        column![
+            page_header("Record Payment").padding([10, 0]),
+            text(format!("Received from {} today", entry.student_name)).size(14),
+            row![
+                text(currency.to_string()).size(14),
+                text_input("0.00", &entry.amount)
+                    .on_input(Msg::PaymentAmountChanged)
+                    .on_submit(Msg::RecordPayment)
+                    .width(Length::Fixed(140.0)),
+            ]
+            .spacing(10)
+            .align_y(Center),
+        ]
+        .push(error)
+        .push(
+            row![
+                button(text("Cancel").size(14))
+                    .style(button::secondary)
+                    .on_press(Msg::ClosePaymentEntry),
+                button(text("Record").size(14)).on_press(Msg::RecordPayment),
+            ]
+            .spacing(10),
+        )
+        .spacing(16),
+    )
+    .width(400)
+    .padding([10, 30])
+    .style(container::rounded_box)
+    .into()
+}
+
+/// Asks whether to archive or delete a student. Archiving is offered first
+/// as it keeps their sessions in the income figures.
+fn view_student_removal(removal: &StudentRemoval) -> Element<'_, Msg> {
+    let (explanation, keep) = if removal.archived {
+        (
+            format!(
+                "{} is archived. Restore them to the active list, or move them with \
+                 all their sessions to the trash.",
+                removal.student_name
+            ),
+            button(text("Restore").size(14)).on_press(Msg::RestoreStudent),
+        )
+    } else {
+        (
+            format!(
+                "Archiving hides {} from the student list from today but keeps their \
+                 sessions, so income figures stay as they are. Deleting moves them \
+                 and every session logged for them to the trash.",
+                removal.student_name
+            ),
+            button(text("Archive").size(14)).on_press(Msg::ArchiveStudent),
+        )
+    };
+
+    container(
+        column![
+            page_header("Remove Student").padding([10, 0]),
+            text(explanation).size(14),
+            row![
+                button(text("Cancel").size(14))
+                    .style(button::secondary)
+                    .on_press(Msg::CloseStudentRemoval),
+                keep,
+                button(text("Delete").size(14))
+                    .style(button::danger)
+                    .on_press(Msg::DeleteStudent),
+            ]
+            .spacing(10),
         ]
-        .align_y(Center)
-        .spacing(5),
+        .spacing(16),
     )
-    .style(|_theme, _status| button::Style {
-        background: None,
-        ..Default::default()
-    })
-    .on_press(Msg::ShowAddStudentModal)
+    .width(400)
+    .padding([10, 30])
+    .style(container::rounded_box)
     .into()
 }
 
 fn modal_content_container(state: &StudentManagerState) -> Element<'_, Msg> {
+    let is_editing = state.modal_state.editing.is_some();
     let basic_info_section = create_basic_info_section(state);
     let schedule_section = create_schedule_section(state);
-    let action_section = create_action_section();
+    let other_subjects_section = create_other_subjects_section(state);
 
-    container(column![
-        page_header("Add New Student").padding([10, 0]),
+    let backfill_section = if is_editing {
+        space().into()
+    } else {
+        create_backfill_section(state)
+    };
+    let action_section = create_action_section(is_editing);
+
+    let form = column![
         basic_info_section,
         schedule_section,
-        action_section,
+        other_subjects_section,
+        backfill_section,
+        action_section
+    ]
+    .width(600);
+
+    container(column![
+        page_header(if is_editing {
+            "Edit Student"
+        } else {
+            "Add New Student"
+        })
+        .padding([10, 0]),
+        row![
+            form,
+            column![
+                view_schedule_preview(&state.modal_state.time_slots),
+                view_monthly_quote(
+                    monthly_quote(&state.modal_state, state.clock.today()),
+                    &state.money_format,
+                ),
+            ]
+            .spacing(10)
+        ]
+        .spacing(30),
     ])
-    .width(600)
+    .width(850)
     .padding([10, 30])
     .style(container::rounded_box)
     .into()
@@ -362,27 +2959,80 @@ fn create_basic_info_section(state: &StudentManagerState) -> Element<'_, Msg> {
                 ),
             ]
             .spacing(20),
+            row![
+                create_validated_input(
+                    "Phone",
+                    "e.g., 024 123 4567",
+                    &state.modal_state.modal_input.phone,
+                    state.modal_state.validation_errors.as_ref().map(|v| &v.phone),
+                    Msg::PhoneInputChanged
+                ),
+                create_validated_input(
+                    "Email",
+                    "e.g., parent@example.com",
+                    &state.modal_state.modal_input.email,
+                    state.modal_state.validation_errors.as_ref().map(|v| &v.email),
+                    Msg::EmailInputChanged
+                ),
+            ]
+            .spacing(20),
             column![
                 text("Subject").size(13).font(Font {
                     weight: font::Weight::Medium,
                     ..Default::default()
                 }),
-                pick_list(
-                    state.tutor.as_ref().unwrap().subjects.clone(),
-                    state.modal_state.selected_subject,
+                searchable_pick_list(
+                    &state.subject_options,
+                    "Pick tutor subject",
+                    state.modal_state.selected_subject.as_ref(),
                     Msg::SubjectSelected
-                )
-                .placeholder("Pick tutor subject")
-                .menu_height(100),
+                ),
             ]
             .padding([10, 0])
             .spacing(5),
+            column![
+                text("Tags").size(13).font(Font {
+                    weight: font::Weight::Medium,
+                    ..Default::default()
+                }),
+                tag_input(
+                    &state.modal_state.tags,
+                    &state.modal_state.tag_draft,
+                    &state.known_tags,
+                    "e.g. exam year",
+                    Msg::TagDraftChanged,
+                    Msg::TagAdded,
+                    Msg::TagRemoved,
+                ),
+            ]
+            .spacing(5),
+            row![
+                create_validated_input(
+                    "Rate per session (GHS)",
+                    "e.g., 150",
+                    &state.modal_state.modal_input.pay_rate,
+                    state.modal_state.validation_errors.as_ref().map(|v| &v.rate),
+                    Msg::RateInputChanged
+                ),
+                create_validated_input(
+                    "Tuition start date",
+                    "YYYY-MM-DD",
+                    &state.modal_state.modal_input.start_date,
+                    state.modal_state.validation_errors.as_ref().map(|v| &v.start_date),
+                    Msg::StartDateChanged
+                ),
+            ]
+            .spacing(20),
             create_validated_input(
-                "Rate per session (GHS)",
-                "e.g., 150",
-                &state.modal_state.modal_input.pay_rate,
-                state.modal_state.validation_errors.as_ref().map(|v| &v.rate),
-                Msg::RateInputChanged
+                "Minimum sessions a month (monthly fee only)",
+                "Blank to charge for every session",
+                &state.modal_state.modal_input.min_sessions,
+                state
+                    .modal_state
+                    .validation_errors
+                    .as_ref()
+                    .map(|v| &v.min_sessions),
+                Msg::MinSessionsChanged
             ),
         ]
         .spacing(20),
@@ -418,7 +3068,7 @@ where
                     ..Default::default()
                 })
                 .style(|_theme: &Theme| text::Style {
-                    color: Some(Color::from_rgb(1.0, 0.0, 0.0)),
+                    color: Some(theme::DANGER),
                     ..Default::default()
                 })
         );
@@ -452,7 +3102,7 @@ fn create_schedule_section(state: &StudentManagerState) -> Element<'_, Msg> {
                     icons::plus(),
                     16.0,
                     18.0,
-                    |_| Color::from_rgba(0.0, 0.2, 0.9, 0.7),
+                    |_| theme::ACCENT,
                     |theme| theme.extended_palette().background.weak.color,
                 )
                 .padding(5)
@@ -464,9 +3114,15 @@ fn create_schedule_section(state: &StudentManagerState) -> Element<'_, Msg> {
     ]
     .spacing(10);
 
+    let mut slots = Column::new();
     for slot in &state.modal_state.time_slots {
-        schedule_column = schedule_column.push(create_time_slot_row(slot, days.clone(), state));
+        slots = slots.push(create_time_slot_row(slot, days.clone(), state));
     }
+    schedule_column = schedule_column.push(
+        mouse_area(slots)
+            .on_release(Msg::TimeSlotDragEnded)
+            .on_exit(Msg::TimeSlotDragEnded),
+    );
 
     // Add validation error message if present
     if let Some(validated) = &state.modal_state.validation_errors {
@@ -480,7 +3136,7 @@ fn create_schedule_section(state: &StudentManagerState) -> Element<'_, Msg> {
                             ..Default::default()
                         })
                         .style(|_theme: &Theme| text::Style {
-                            color: Some(Color::from_rgb(1.0, 0.0, 0.0)),
+                            color: Some(theme::DANGER),
                             ..Default::default()
                         })
                 )
@@ -492,6 +3148,152 @@ fn create_schedule_section(state: &StudentManagerState) -> Element<'_, Msg> {
     schedule_column.into()
 }
 
+/// Lists the sessions the slots give between a past start date and today,
+/// so the tutor can tick the ones that happened before saving.
+fn create_backfill_section(state: &StudentManagerState) -> Element<'_, Msg> {
+    let today = state.clock.today();
+    let expected = state.modal_state.expected_backfill(today);
+    if expected.is_empty() {
+        return space().into();
+    }
+
+    let held = &state.modal_state.backfill_held;
+    let all_held = expected.iter().all(|at| held.contains(at));
+    let confirmed = state.modal_state.backfill_confirmed.as_ref() == Some(&expected);
+
+    let sessions = Column::new()
+        .extend(expected.iter().map(|at| {
+            let at = *at;
+            checkbox(held.contains(&at))
+                .label(at.format("%a %-d %b %Y, %-I:%M %p").to_string())
+                .text_size(12)
+                .on_toggle(move |is_held| Msg::BackfillSessionToggled(at, is_held))
+                .into()
+        }))
+        .spacing(4);
+
+    column![
+        text("Past Sessions").size(18).font(Font {
+            weight: font::Weight::Semibold,
+            ..Default::default()
+        }),
+        text(format!(
+            "The start date is in the past, so {} sessions may already have happened. Tick the ones that did.",
+            expected.len()
+        ))
+        .size(12),
+        checkbox(all_held)
+            .label("All of them")
+            .text_size(12)
+            .on_toggle(Msg::BackfillAllToggled),
+        scrollable(sessions).height(Length::Fixed(150.0)),
+        checkbox(confirmed)
+            .label(format!(
+                "{} of {} sessions happened",
+                expected.iter().filter(|at| held.contains(at)).count(),
+                expected.len()
+            ))
+            .text_size(12)
+            .on_toggle(Msg::BackfillConfirmed),
+    ]
+    .spacing(8)
+    .padding([20, 0])
+    .into()
+}
+
+/// Further subjects, each with its own rate and weekly slot.
+fn create_other_subjects_section(state: &StudentManagerState) -> Element<'_, Msg> {
+    let Some(tutor) = &state.tutor else {
+        return space().into();
+    };
+    let modal = &state.modal_state;
+    let days: Vec<DaySelection> = tutor
+        .tutoring_days
+        .iter()
+        .copied()
+        .map(DaySelection::Day)
+        .collect();
+    let can_add = modal.other_subjects.len() + 1 < tutor.subjects.len();
+
+    let header = row![
+        text("Other Subjects").size(18).font(Font {
+            weight: font::Weight::Semibold,
+            ..Default::default()
+        }),
+        space().width(Length::Fill),
+        button(text("Add subject").size(12))
+            .style(button::text)
+            .padding(5)
+            .on_press_maybe(can_add.then_some(Msg::AddOtherSubject)),
+    ]
+    .align_y(Center);
+
+    let rows = Column::new()
+        .extend(
+            modal
+                .other_subjects
+                .iter()
+                .enumerate()
+                .map(|(index, draft)| {
+                    let subjects: Vec<TutorSubject> = tutor
+                        .subjects
+                        .iter()
+                        .filter(|subject| modal.selected_subject.as_ref() != Some(*subject))
+                        .cloned()
+                        .collect();
+                    let times: Vec<TimeSelection> = match &draft.slot.selected_day {
+                        Some(DaySelection::Day(day)) => tutor
+                            .available_times
+                            .get(day)
+                            .cloned()
+                            .unwrap_or_default()
+                            .into_iter()
+                            .map(TimeSelection::Time)
+                            .collect(),
+                        None => Vec::new(),
+                    };
+
+                    row![
+                        pick_list(subjects, draft.subject.clone(), move |subject| {
+                            Msg::OtherSubjectSelected(index, subject)
+                        })
+                        .placeholder("Subject")
+                        .width(Length::FillPortion(2)),
+                        text_input("Rate", &draft.rate)
+                            .on_input(move |rate| Msg::OtherSubjectRateChanged(index, rate))
+                            .width(Length::Fixed(70.0)),
+                        pick_list(days.clone(), draft.slot.selected_day.clone(), move |day| {
+                            Msg::OtherSubjectDaySelected(index, day)
+                        })
+                        .placeholder("Day")
+                        .width(Length::FillPortion(1)),
+                        pick_list(times, draft.slot.selected_time.clone(), move |time| {
+                            Msg::OtherSubjectTimeSelected(index, time)
+                        })
+                        .placeholder("Time")
+                        .width(Length::FillPortion(1)),
+                        button(text("Remove").size(12))
+                            .style(button::text)
+                            .padding(5)
+                            .on_press(Msg::RemoveOtherSubject(index)),
+                    ]
+                    .spacing(10)
+                    .align_y(Center)
+                    .into()
+                }),
+        )
+        .spacing(8);
+
+    let mut section = column![header, rows].spacing(10).padding([10, 0]);
+    if let Some(error) = &modal.other_subjects_error {
+        section = section.push(text(error.clone()).size(13).style(|_: &Theme| text::Style {
+            color: Some(theme::DANGER),
+        }));
+    }
+
+    section.into()
+}
+
 fn create_time_slot_row<'a>(
     slot: &'a TimeSlot,
     days: Vec<DaySelection>,
@@ -499,11 +3301,25 @@ fn create_time_slot_row<'a>(
 ) -> Element<'a, Msg> {
     let slot_id = slot.id;
     let can_remove = state.modal_state.time_slots.len() > 1;
+    let can_duplicate = state.modal_state.time_slots.len() < MAX_TIME_SLOTS;
+    let is_dragged = state.modal_state.dragged_slot == Some(slot_id);
 
     let time_picker = create_time_picker(slot, state);
     let remove_button = create_remove_button(can_remove, slot_id);
+    let drag_handle = mouse_area(text("⠿").size(18))
+        .on_press(Msg::TimeSlotDragStarted(slot_id))
+        .interaction(if is_dragged {
+            Interaction::Grabbing
+        } else {
+            Interaction::Grab
+        });
+    let duplicate_button = button(text("Duplicate").size(12))
+        .style(button::text)
+        .padding(5)
+        .on_press_maybe(can_duplicate.then_some(Msg::DuplicateTimeSlot(slot_id)));
 
-    row![
+    let slot_row = row![
+        drag_handle,
         pick_list(days, slot.selected_day.clone(), move |day| {
             Msg::TutoringDaySelected(slot_id, day)
         })
@@ -512,14 +3328,143 @@ fn create_time_slot_row<'a>(
         .menu_height(155),
         space().width(Length::Fixed(20.0)),
         time_picker,
+        number_stepper(
+            slot.duration_minutes,
+            SESSION_MINUTES,
+            SESSION_MINUTES_STEP,
+            move |minutes| Msg::TimeSlotDurationChanged(slot_id, minutes)
+        ),
+        text("min").size(12),
         space().width(Length::Fixed(10.0)),
+        duplicate_button,
         remove_button,
     ]
     .spacing(10)
     .padding([10, 0])
+    .align_y(Center);
+
+    let slot_row = container(slot_row).style(move |theme: &Theme| {
+        if is_dragged {
+            container::Style {
+                background: Some(theme.extended_palette().background.weak.color.into()),
+                ..Default::default()
+            }
+        } else {
+            container::Style::default()
+        }
+    });
+
+    if state.modal_state.dragged_slot.is_some() {
+        mouse_area(slot_row)
+            .on_enter(Msg::TimeSlotDraggedOver(slot_id))
+            .into()
+    } else {
+        slot_row.into()
+    }
+}
+
+/// The week as the chosen slots lay it out, one column per day.
+fn view_schedule_preview(slots: &[TimeSlot]) -> Element<'_, Msg> {
+    let days = Row::new()
+        .extend(weekly_preview(slots).into_iter().map(|(day, times)| {
+            column![text(day.to_string()).size(12).font(Font {
+                weight: font::Weight::Semibold,
+                ..Default::default()
+            })]
+            .extend(times.into_iter().map(|time| text(time).size(11).into()))
+            .spacing(4)
+            .width(Length::Fixed(70.0))
+            .into()
+        }))
+        .spacing(4);
+
+    column![
+        text("This week").size(13),
+        container(days).padding(10).style(container::rounded_box)
+    ]
+    .spacing(10)
+    .padding([20, 0])
+    .into()
+}
+
+/// What the student would be billed this month, for quoting before saving.
+#[derive(Debug, Clone, PartialEq)]
+struct MonthlyQuote {
+    month: String,
+    sessions: i32,
+    /// Missing until a valid rate is entered.
+    cost: Option<f32>,
+}
+
+fn monthly_quote(modal: &AddStudentModal, today: NaiveDate) -> MonthlyQuote {
+    let days: Vec<Weekday> = modal
+        .time_slots
+        .iter()
+        .filter_map(|slot| {
+            let DaySelection::Day(day) = slot.selected_day.clone()?;
+            Some(day)
+        })
+        .collect();
+    let sessions = sessions_in_month(&days, today.year(), today.month());
+    let rate = modal
+        .modal_input
+        .pay_rate
+        .trim()
+        .parse::<f32>()
+        .ok()
+        .filter(|rate| *rate >= 0.0);
+
+    MonthlyQuote {
+        month: month_label(today.year(), today.month()),
+        sessions,
+        cost: rate.map(|rate| rate * sessions as f32),
+    }
+}
+
+fn view_monthly_quote<'a>(quote: MonthlyQuote, money: &MoneyFormat) -> Element<'a, Msg> {
+    let cost = match quote.cost {
+        Some(cost) => money.format(cost),
+        None => "Enter a rate".to_string(),
+    };
+
+    column![
+        text(format!("Quote for {}", quote.month)).size(13),
+        text(format!("{} sessions", quote.sessions)).size(12),
+        text(cost).size(16).font(Font {
+            weight: font::Weight::Semibold,
+            ..Default::default()
+        }),
+    ]
+    .spacing(4)
     .into()
 }
 
+/// Times booked on each day of the week, Monday first, in slot order.
+fn weekly_preview(slots: &[TimeSlot]) -> Vec<(Weekday, Vec<String>)> {
+    [
+        Weekday::Mon,
+        Weekday::Tue,
+        Weekday::Wed,
+        Weekday::Thu,
+        Weekday::Fri,
+        Weekday::Sat,
+        Weekday::Sun,
+    ]
+    .into_iter()
+    .map(|day| {
+        let times = slots
+            .iter()
+            .filter(|slot| slot.selected_day == Some(DaySelection::Day(day)))
+            .map(|slot| match &slot.selected_time {
+                Some(TimeSelection::Time(time)) => time.clone(),
+                None => "Time not set".to_string(),
+            })
+            .collect();
+        (day, times)
+    })
+    .collect()
+}
+
 fn create_time_picker<'a>(
     slot: &'a TimeSlot,
     state: &'a StudentManagerState,
@@ -582,7 +3527,13 @@ fn create_remove_button<'a>(can_remove: bool, slot_id: usize) -> Element<'a, Msg
     }
 }
 
-fn create_action_section<'a>() -> Element<'a, Msg> {
+fn create_action_section<'a>(is_editing: bool) -> Element<'a, Msg> {
+    let (save_label, save_icon, on_save) = if is_editing {
+        ("Save Changes", icons::edit(), Msg::UpdateStudent)
+    } else {
+        ("Add Student", icons::plus(), Msg::AddStudent)
+    };
+
     container(
         row![
             mouse_area(
@@ -599,7 +3550,7 @@ fn create_action_section<'a>() -> Element<'a, Msg> {
                     border: Border {
                         color: Color::BLACK,
                         width: 1.0,
-                        radius: 10.0.into(),
+                        radius: theme::RADIUS.into(),
                     },
                     ..Default::default()
                 })
@@ -611,9 +3562,9 @@ fn create_action_section<'a>() -> Element<'a, Msg> {
             .interaction(Interaction::Pointer),
             mouse_area(
                 ui_button(
-                    "Add Student",
+                    save_label,
                     12.0,
-                    icons::plus(),
+                    save_icon,
                     16.0,
                     18.0,
                     |_| Color::WHITE,
@@ -622,7 +3573,7 @@ fn create_action_section<'a>() -> Element<'a, Msg> {
                 .padding(10)
                 .width(Length::FillPortion(1))
                 .height(Length::Fixed(40.0))
-                .on_press(Msg::AddStudent),
+                .on_press(on_save),
             )
             .interaction(Interaction::Pointer),
         ]
@@ -641,7 +3592,7 @@ fn create_action_section<'a>() -> Element<'a, Msg> {
 }
 
 fn view_search_bar<'a>(placeholder: &'a str, query: &'a str) -> Element<'a, Msg> {
-    container(text_input(placeholder, query)).into()
+    container(text_input(placeholder, query).on_input(Msg::SearchQueryChanged)).into()
 }
 
 fn view_student_manager_card_list(state: &StudentManagerState) -> Vec<Element<'_, Msg>> {
@@ -655,27 +3606,470 @@ fn render_student_cards<'a>(
     state: &'a StudentManagerState,
     students: &'a [Student],
 ) -> Vec<Element<'a, Msg>> {
-    let today = Local::now().naive_local().date();
+    let cards = student_card_models(state, students);
+    if cards.is_empty() {
+        return vec![view_no_students(state)];
+    }
 
-    students
-        .iter()
+    cards
+        .into_iter()
         .enumerate()
-        .map(|(index, student)| create_student_card(state, student, index, today))
+        .map(|(index, card)| create_student_card(state, card, index))
+        .collect()
+}
+
+/// Says why no cards are shown, with a way out of an empty search.
+fn view_no_students(state: &StudentManagerState) -> Element<'_, Msg> {
+    let query = state.search_query.trim();
+    let content = if !query.is_empty() {
+        column![
+            text(format!("No students match \"{}\"", query)).size(14),
+            button(text("Clear search").size(12))
+                .style(button::text)
+                .on_press(Msg::SearchQueryChanged(String::new())),
+        ]
+    } else if !state.tag_filter.is_empty() {
+        column![
+            text("No students have all of these tags").size(14),
+            button(text("Clear tags").size(12))
+                .style(button::text)
+                .on_press(Msg::TagFilterCleared),
+        ]
+    } else {
+        column![text("No students yet. Add one to get started.").size(14)]
+    };
+
+    container(content.spacing(5)).padding(20).into()
+}
+
+/// What a student card shows, already formatted, so card contents can be
+/// checked without building widgets.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StudentCardModel {
+    pub student_id: String,
+    pub name: String,
+    pub subject: String,
+    pub risk_level: RiskLevel,
+    pub risk_score: String,
+    pub risk_factors: Vec<String>,
+    /// Weekly slots in week order.
+    pub schedule: Vec<String>,
+    /// The slot in `schedule` the next session falls in.
+    pub next_slot: Option<usize>,
+    pub next_session: String,
+    /// How long until the next session starts, e.g. "in 2h 15m".
+    pub countdown: Option<String>,
+    /// Whether a session is running now.
+    pub in_session: bool,
+    pub completed_sessions: String,
+    /// Share of this month's sessions so far that were held.
+    pub attendance: Option<f32>,
+    pub accrued: String,
+    /// The latest engagement ratings, oldest first.
+    pub engagement: Vec<u8>,
+    pub todays_session: Option<TodaysSession>,
+    /// Ways to reach the student the card offers, given their details.
+    pub contact_actions: Vec<ContactAction>,
+}
+
+/// A session tabled for today, to mark from the card.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TodaysSession {
+    pub at: DateTime<Local>,
+    pub time: String,
+    pub status: Option<SessionStatus>,
+}
+
+impl StudentCardModel {
+    fn from_row(row: &StudentRow<'_>, money: &MoneyFormat, now: DateTime<Local>) -> Self {
+        let student = row.student;
+        let schedule = weekly_schedule(student);
+        let upcoming = upcoming_session(student, now);
+        let timing = upcoming.map(|at| SessionTiming::of(student, at, now));
+
+        Self {
+            student_id: student.id.clone(),
+            name: student.name.full_name(),
+            subject: student.subject_names(),
+            risk_level: row.risk.level,
+            risk_score: format!("Risk score {:.0}/100", row.risk.score),
+            risk_factors: row
+                .risk
+                .factors
+                .iter()
+                .map(|factor| {
+                    format!(
+                        "{}: +{:.0} ({})",
+                        factor.label, factor.points, factor.detail
+                    )
+                })
+                .collect(),
+            next_slot: row
+                .next_session
+                .and_then(|next| schedule.iter().position(|slot| slot.day == next.weekday())),
+            schedule: schedule.iter().map(WeeklySlot::to_string).collect(),
+            next_session: upcoming
+                .map(|at| at.date_naive())
+                .or(row.next_session)
+                .map_or_else(
+                    || NO_NEXT_SESSION.to_string(),
+                    |date| date.format("%A, %d %B %Y").to_string(),
+                ),
+            countdown: timing.and_then(|timing| timing.countdown()),
+            in_session: timing == Some(SessionTiming::InSession),
+            completed_sessions: row.completed_sessions.to_string(),
+            attendance: row.attendance,
+            accrued: money.format(row.accrued),
+            engagement: recent_engagement(student, ENGAGEMENT_TREND_SESSIONS),
+            todays_session: row.todays_session.map(|at| TodaysSession {
+                at,
+                time: at.format("%-I:%M %p").to_string(),
+                status: student.session_status(at),
+            }),
+            contact_actions: ContactAction::ALL
+                .into_iter()
+                .filter(|action| action.link(student).is_some())
+                .collect(),
+        }
+    }
+}
+
+pub fn student_card_models(
+    state: &StudentManagerState,
+    students: &[Student],
+) -> Vec<StudentCardModel> {
+    student_rows(state, students)
+        .iter()
+        .map(|row| StudentCardModel::from_row(row, &state.money_format, state.now))
         .collect()
 }
 
+/// Per-student figures shared by the card grid and the table view.
+#[derive(Clone)]
+struct StudentRow<'a> {
+    student: &'a Student,
+    risk: ChurnRisk,
+    next_session: Option<NaiveDate>,
+    completed_sessions: i32,
+    /// Share of the shown month's sessions so far that were held.
+    attendance: Option<f32>,
+    accrued: f32,
+    /// Owed as of today, carried over months; negative is credit.
+    balance: f32,
+    /// The first of today's sessions still unmarked, or else the last.
+    todays_session: Option<DateTime<Local>>,
+}
+
+fn todays_session(student: &Student, today: NaiveDate) -> Option<DateTime<Local>> {
+    if !is_active_on(student, today) {
+        return None;
+    }
+
+    let sessions = scheduled_sessions_on(student, today);
+    sessions
+        .iter()
+        .find(|at| student.session_status(**at).is_none())
+        .or(sessions.last())
+        .copied()
+}
+
+/// Rows for the students whose name or subject loosely match the search
+/// and who have every filtered tag, sorted as chosen, with figures for the
+/// shown month. Archived students are left out unless shown.
+fn student_rows<'a>(state: &StudentManagerState, students: &'a [Student]) -> Vec<StudentRow<'a>> {
+    let today = state.clock.today();
+    let (year, month) = state.shown_month();
+    let (month_start, month_end) = get_month_date_range(year, month);
+
+    let mut rows: Vec<StudentRow<'a>> = students
+        .iter()
+        .filter(|student| state.show_archived || !has_left(student, today))
+        .filter(|student| {
+            state
+                .tag_filter
+                .iter()
+                .all(|tag| student.tags.iter().any(|own| own.eq_ignore_ascii_case(tag)))
+        })
+        .filter(|student| {
+            loosely_matches(
+                &format!("{} {}", student.name.full_name(), student.subject_names()),
+                &state.search_query,
+            )
+        })
+        .map(|student| StudentRow {
+            student,
+            risk: compute_churn_risk(
+                student,
+                state.ledger.days_overdue(&student.id, today),
+                today,
+            ),
+            next_session: get_next_session(student, today),
+            completed_sessions: compute_monthly_completed_sessions(student, month, year),
+            attendance: attendance_rate(student, month_start, month_end.min(today)),
+            accrued: compute_monthly_sum(student, month, year, compute_monthly_completed_sessions),
+            balance: state
+                .ledger
+                .account_for(student, &state.locations, today)
+                .closing(),
+            todays_session: todays_session(student, today),
+        })
+        .collect();
+
+    rows.sort_by(|a, b| {
+        let ordering = match state.sort_by {
+            StudentSort::Name => a
+                .student
+                .name
+                .full_name()
+                .to_lowercase()
+                .cmp(&b.student.name.full_name().to_lowercase()),
+            StudentSort::Subject => a
+                .student
+                .subject_names()
+                .cmp(&b.student.subject_names()),
+            StudentSort::Tags => a
+                .student
+                .tags
+                .join(", ")
+                .to_lowercase()
+                .cmp(&b.student.tags.join(", ").to_lowercase()),
+            StudentSort::Rate => a
+                .student
+                .payment_data
+                .amount
+                .total_cmp(&b.student.payment_data.amount),
+            // Students with nothing coming up go last.
+            StudentSort::NextSession => a
+                .next_session
+                .is_none()
+                .cmp(&b.next_session.is_none())
+                .then(a.next_session.cmp(&b.next_session)),
+            StudentSort::CompletedSessions => a.completed_sessions.cmp(&b.completed_sessions),
+            StudentSort::Accrued => a.accrued.total_cmp(&b.accrued),
+            StudentSort::Balance => a.balance.total_cmp(&b.balance),
+            StudentSort::ChurnRisk => a.risk.score.total_cmp(&b.risk.score),
+        };
+
+        if state.sort_descending {
+            ordering.reverse()
+        } else {
+            ordering
+        }
+    });
+
+    rows
+}
+
+fn view_student_table(state: &StudentManagerState) -> Element<'_, Msg> {
+    let Some(students) = &state.students else {
+        return container(text!("Loading students…")).padding(20).into();
+    };
+
+    let rows = student_rows(state, students);
+
+    let column_chooser = Row::new()
+        .extend(StudentSort::ALL.iter().map(|column| {
+            let column = *column;
+            checkbox(state.visible_columns.contains(&column))
+                .label(column.label())
+                .text_size(13)
+                .on_toggle_maybe(
+                    (column != StudentSort::Name)
+                        .then_some(move |is_visible| Msg::ColumnToggled(column, is_visible)),
+                )
+                .into()
+        }))
+        .spacing(15);
+
+    let header = |sort: StudentSort| {
+        let marker = if state.sort_by != sort {
+            ""
+        } else if state.sort_descending {
+            " ▼"
+        } else {
+            " ▲"
+        };
+
+        button(
+            text(format!("{}{}", sort.label(), marker))
+                .size(13)
+                .font(Font {
+                    weight: font::Weight::Semibold,
+                    ..Default::default()
+                }),
+        )
+        .padding(0)
+        .style(|_theme, _status| button::Style {
+            background: None,
+            ..Default::default()
+        })
+        .on_press(Msg::SortColumnClicked(sort))
+    };
+
+    let columns = state.visible_columns.iter().map(|column| {
+        let column = *column;
+        table::column(header(column), move |row: StudentRow<'_>| {
+            view_table_cell(state, column, &row)
+        })
+    });
+
+    let mut content = column![
+        column_chooser,
+        scrollable(table(columns, rows).padding_x(15).padding_y(8)).height(Length::Fill),
+    ]
+    .spacing(15);
+
+    if let Some(message) = &state.status_message {
+        content = content.push(text(message).size(13));
+    }
+
+    content.into()
+}
+
+fn view_table_cell<'a>(
+    state: &'a StudentManagerState,
+    column: StudentSort,
+    row: &StudentRow<'a>,
+) -> Element<'a, Msg> {
+    let inline_field = match column {
+        StudentSort::Rate => InlineField::Rate,
+        StudentSort::Subject => InlineField::Subject,
+        StudentSort::Tags => InlineField::Tags,
+        StudentSort::Name => {
+            let open = Route::Students(Some(row.student.id.clone()));
+            return button(text(row.student.name.full_name()))
+                .padding(0)
+                .style(button::text)
+                .on_press(Msg::Effect(Effect::Navigate(open)))
+                .into();
+        }
+        _ => return text(table_cell(column, row, &state.money_format)).into(),
+    };
+
+    let student_id = row.student.id.clone();
+
+    match &state.inline_edit {
+        Some(edit) if edit.student_id == student_id && edit.field == inline_field => {
+            let editor: Element<'a, Msg> = match inline_field {
+                InlineField::Rate => text_input("e.g., 150", &edit.value)
+                    .on_input(Msg::InlineEditChanged)
+                    .on_submit(Msg::CommitInlineEdit)
+                    .width(Length::Fixed(100.0))
+                    .into(),
+                InlineField::Tags => text_input("e.g., exam year, online", &edit.value)
+                    .on_input(Msg::InlineEditChanged)
+                    .on_submit(Msg::CommitInlineEdit)
+                    .width(Length::Fixed(180.0))
+                    .into(),
+                InlineField::Subject => pick_list(
+                    state.tutor.as_ref().map(|tutor| tutor.subjects.clone()).unwrap_or_default(),
+                    Some(row.student.subject.clone()),
+                    Msg::InlineSubjectSelected,
+                )
+                .into(),
+            };
+
+            let mut cell = column![
+                row![
+                    editor,
+                    button(text("×").size(14))
+                        .padding([0, 6])
+                        .style(button::text)
+                        .on_press(Msg::CancelInlineEdit),
+                ]
+                .align_y(Center)
+                .spacing(5)
+            ];
+
+            if let Some(error) = &edit.error {
+                cell = cell.push(text(error).size(11).style(|_theme: &Theme| text::Style {
+                    color: Some(theme::DANGER),
+                }));
+            }
+
+            cell.into()
+        }
+        _ => mouse_area(text(table_cell(column, row, &state.money_format)))
+            .interaction(Interaction::Text)
+            .on_double_click(Msg::StartInlineEdit(student_id, inline_field))
+            .into(),
+    }
+}
+
+/// Saves an edited copy of the student. The list itself changes once
+/// the app applies the saved student to the domain.
+fn save_inline_change(
+    state: &mut StudentManagerState,
+    student_id: &str,
+    apply: impl FnOnce(&mut Student),
+) -> Task<Msg> {
+    let Some(mut student) = state
+        .students
+        .iter()
+        .flatten()
+        .find(|student| student.id == student_id)
+        .cloned()
+    else {
+        return Task::none();
+    };
+
+    apply(&mut student);
+
+    Task::perform(update_student(student), Msg::StudentUpdated)
+}
+
+fn table_cell(column: StudentSort, row: &StudentRow<'_>, money: &MoneyFormat) -> String {
+    match column {
+        StudentSort::Name => row.student.name.full_name(),
+        StudentSort::Subject => row.student.subject_names(),
+        StudentSort::Tags => row.student.tags.join(", "),
+        StudentSort::Rate => money.format(row.student.payment_data.amount),
+        StudentSort::NextSession => row.next_session.map_or_else(
+            || NO_NEXT_SESSION.to_string(),
+            |date| date.format("%a %d %b").to_string(),
+        ),
+        StudentSort::CompletedSessions => row.completed_sessions.to_string(),
+        StudentSort::Accrued => money.format(row.accrued),
+        StudentSort::Balance => money.format(row.balance),
+        StudentSort::ChurnRisk => format!("{} ({:.0})", row.risk.level, row.risk.score),
+    }
+}
+
+fn students_to_csv(
+    rows: &[StudentRow<'_>],
+    columns: &[StudentSort],
+    money: &MoneyFormat,
+) -> String {
+    let mut lines = vec![csv_line(columns.iter().map(|column| column.label()))];
+
+    lines.extend(rows.iter().map(|row| {
+        let cells: Vec<String> = columns
+            .iter()
+            .map(|column| table_cell(*column, row, money))
+            .collect();
+        csv_line(cells.iter().map(String::as_str))
+    }));
+
+    lines.join("\n") + "\n"
+}
+
 fn create_student_card<'a>(
     state: &'a StudentManagerState,
-    student: &'a Student,
+    card: StudentCardModel,
     index: usize,
-    today: chrono::NaiveDate,
 ) -> Element<'a, Msg> {
-    let next_session = get_next_session(student);
     let is_hovered = state.hovered_student_card == Some(index);
+    let open_detail = Msg::Effect(Effect::Navigate(Route::StudentDetail(
+        card.student_id.clone(),
+    )));
 
-    let title_section = create_card_title(student);
-    let main_section = create_card_main_section(student, next_session, today);
-    let action_section = create_card_actions();
+    let density = state.card_density;
+    let (card_width, card_height) = density.card_size();
+
+    let title_section = create_card_title(&card);
+    let action_section = create_card_actions(card.student_id.clone(), card.todays_session.clone());
+    let ring_fill = state.rings_filled.animate_bool(0.0, 1.0, Instant::now());
+    let main_section = create_card_main_section(card, density, ring_fill);
 
     let card = container(
         column![
@@ -684,8 +4078,8 @@ fn create_student_card<'a>(
         ]
         .spacing(20),
     )
-    .width(Length::Fixed(300.0))
-    .height(Length::Fixed(500.0))
+    .width(Length::Fixed(card_width))
+    .height(Length::Fixed(card_height))
     .padding([10, 20])
     .style(move |theme: &Theme| {
         let palette = theme.extended_palette();
@@ -693,106 +4087,226 @@ fn create_student_card<'a>(
             border: Border {
                 color: palette.background.strong.color,
                 width: 1.5,
-                radius: 10.0.into(),
+                radius: theme::RADIUS.into(),
                 ..Default::default()
             },
-            shadow: if is_hovered {
-                Shadow {
-                    color: Color::from_rgba(0.0, 0.0, 0.0, 0.25),
-                    offset: Vector::new(0.4, 0.0),
-                    blur_radius: 12.0,
-                }
-            } else {
-                Shadow::default()
-            },
+            shadow: theme::card_shadow(is_hovered),
             ..Default::default()
         }
     });
 
     mouse_area(card)
         .interaction(Interaction::Pointer)
+        .on_press(open_detail)
         .on_enter(Msg::StudentCardHovered(Some(index)))
         .on_exit(Msg::StudentCardHovered(None))
         .into()
 }
 
-fn create_card_title<'a>(student: &'a Student) -> Element<'a, Msg> {
-    let full_name = if let Some(other) = &student.name.other {
-        format!("{} {} {}", student.name.first, other, student.name.last)
-    } else {
-        format!("{} {}", student.name.first, student.name.last)
-    };
+fn create_card_title<'a>(card: &StudentCardModel) -> Element<'a, Msg> {
+    let schedule = format!(
+        "{}, {}: {}. Next session {}.",
+        card.name,
+        card.subject,
+        card.schedule.join(", "),
+        card.next_session
+    );
+
+    row![
+        column![
+            text(card.name.clone())
+                .font(Font {
+                    weight: font::Weight::Bold,
+                    ..Default::default()
+                })
+                .size(20),
+            text(card.subject.clone())
+                .font(Font {
+                    weight: font::Weight::Light,
+                    ..Default::default()
+                })
+                .size(15),
+        ]
+        .align_x(Alignment::Start)
+        .width(Length::Fill)
+        .spacing(5),
+        Row::new()
+            .extend(card.contact_actions.iter().map(|&action| {
+                let icon = match action {
+                    ContactAction::Call => icons::call(),
+                    ContactAction::WhatsApp => icons::chat(),
+                    ContactAction::Email => icons::mail(),
+                };
 
-    row![column![
-        text(full_name)
-            .font(Font {
-                weight: font::Weight::Bold,
-                ..Default::default()
-            })
-            .size(20),
-        text(student.subject.to_string())
-            .font(Font {
-                weight: font::Weight::Light,
-                ..Default::default()
-            })
-            .size(15),
+                tooltip(
+                    icon_button(icon).on_press(Msg::OpenContact(card.student_id.clone(), action)),
+                    container(text(action.to_string()).size(12))
+                        .padding(6)
+                        .style(container::rounded_box),
+                    tooltip::Position::Bottom,
+                )
+                .into()
+            }))
+            .spacing(2),
+        copy_button().on_press(Msg::Effect(Effect::Copy(CopyRequest::new(
+            schedule, "schedule"
+        )))),
+        create_risk_badge(card.risk_level, &card.risk_score, &card.risk_factors),
     ]
-    .align_x(Alignment::Start)
-    .width(Length::Fill)
-    .spacing(5)]
+    .spacing(6)
     .height(Length::Fixed(50.0))
     .into()
 }
 
+fn create_risk_badge<'a>(level: RiskLevel, score: &str, factors: &[String]) -> Element<'a, Msg> {
+    let badge = container(text(level.to_string()).size(11).font(Font {
+        weight: font::Weight::Medium,
+        ..Default::default()
+    }))
+    .padding([2, 8])
+    .style(move |_theme: &Theme| container::Style {
+        background: Some(Background::Color(risk_badge_color(level))),
+        border: theme::rounded(),
+        ..Default::default()
+    });
+
+    let breakdown = Column::new()
+        .push(text(score.to_string()).size(12))
+        .extend(
+            factors
+                .iter()
+                .map(|factor| text(factor.clone()).size(11).into()),
+        )
+        .spacing(4);
+
+    tooltip(
+        badge,
+        container(breakdown).padding(10).style(container::rounded_box),
+        tooltip::Position::Bottom,
+    )
+    .into()
+}
+
+fn risk_badge_color(level: RiskLevel) -> Color {
+    match level {
+        RiskLevel::Low => Color::from_rgba(0.4, 1.0, 0.5, 0.6),
+        RiskLevel::Medium => Color::from_rgba(1.0, 0.8, 0.2, 0.6),
+        RiskLevel::High => Color::from_rgba(1.0, 0.5, 0.2, 0.6),
+    }
+}
+
+/// `ring_fill` scales the attendance ring while it animates in.
 fn create_card_main_section<'a>(
-    student: &'a Student,
-    next_session: chrono::NaiveDate,
-    today: chrono::NaiveDate,
+    card: StudentCardModel,
+    density: CardDensity,
+    ring_fill: f32,
 ) -> Element<'a, Msg> {
-    let day = next_session.format("%A").to_string();
-    let date = next_session.format("%d %B %Y").to_string();
+    let show_all_rows = density != CardDensity::Compact;
 
-    column![
-        create_info_row(
+    let mut section = Column::new().spacing(density.info_spacing());
+
+    if show_all_rows {
+        let next_slot = card.next_slot;
+        let schedule = card
+            .schedule
+            .into_iter()
+            .enumerate()
+            .map(|(index, session)| {
+                if next_slot == Some(index) {
+                    text(session)
+                        .font(Font {
+                            weight: font::Weight::Semibold,
+                            ..Default::default()
+                        })
+                        .color(theme::ACCENT)
+                        .into()
+                } else {
+                    text(session).into()
+                }
+            });
+        section = section.push(create_info_row(
             icons::calendar(),
             "Schedule",
-            Column::new()
-                .extend(student.tabled_sessions.iter().map(|session| {
-                    text(format!("{} {}", session.day, session.time)).into()
-                }))
-                .spacing(2)
-        ),
-        create_info_row(
-            icons::schedule(),
-            "Next session",
-            column![text(format!("{}, {}", day, date))].spacing(5)
-        ),
-        create_info_row(
+            Column::new().extend(schedule).spacing(2),
+        ));
+    }
+
+    section = section.push(create_info_row(
+        icons::schedule(),
+        "Next session",
+        row![text(card.next_session)]
+            .push(session_countdown(card.countdown, card.in_session))
+            .spacing(10)
+            .align_y(Center),
+    ));
+
+    if show_all_rows {
+        let mut completed = row![text(card.completed_sessions)]
+            .spacing(10)
+            .align_y(Center);
+        if let Some(attendance) = card.attendance {
+            completed = completed.push(progress_ring(
+                attendance * ring_fill,
+                ATTENDANCE_RING_SIZE,
+                theme::SUCCESS,
+                format!("{:.0}%", attendance * 100.0),
+            ));
+        }
+        section = section.push(create_info_row(
             icons::check_circle(),
             "Completed sessions",
-            column![text(format!(
-                "{}",
-                compute_monthly_completed_sessions(student, today.month(), today.year())
-            ))]
-            .spacing(5)
-        ),
-        create_info_row(
-            icons::payments(),
-            "Amount accrued",
-            column![text(format!(
-                "GHS {}",
-                compute_monthly_sum(
-                    student,
-                    today.month(),
-                    today.year(),
-                    compute_monthly_completed_sessions,
-                )
-            ))]
-            .spacing(5)
-        ),
-    ]
-    .spacing(40)
-    .into()
+            completed,
+        ));
+    }
+
+    section = section.push(create_info_row(
+        icons::payments(),
+        "Amount accrued",
+        column![text(card.accrued)].spacing(5),
+    ));
+
+    if density == CardDensity::Detailed && !card.engagement.is_empty() {
+        section = section.push(create_info_row(
+            icons::arrow_up(),
+            "Engagement",
+            engagement_sparkline(&card.engagement),
+        ));
+    }
+
+    section.into()
+}
+
+const ATTENDANCE_RING_SIZE: f32 = 32.0;
+/// Ratings shown in the engagement trend on a detailed card.
+const ENGAGEMENT_TREND_SESSIONS: usize = 10;
+const SPARKLINE_HEIGHT: f32 = 20.0;
+
+/// A bar per rated session, with the average beside them.
+fn engagement_sparkline<'a>(ratings: &[u8]) -> Element<'a, Msg> {
+    let average = ratings.iter().map(|&rating| rating as f32).sum::<f32>() / ratings.len() as f32;
+    let bars = Row::new()
+        .extend(ratings.iter().map(|&rating| {
+            container(space())
+                .width(Length::Fixed(5.0))
+                .height(Length::Fixed(
+                    rating as f32 / MAX_ENGAGEMENT as f32 * SPARKLINE_HEIGHT,
+                ))
+                .style(|theme: &Theme| container::Style {
+                    background: Some(Background::Color(
+                        theme.extended_palette().primary.base.color,
+                    )),
+                    ..Default::default()
+                })
+                .into()
+        }))
+        .spacing(2)
+        .height(Length::Fixed(SPARKLINE_HEIGHT))
+        .align_y(Alignment::End);
+
+    row![bars, text(format!("{:.1} avg", average)).size(12)]
+        .spacing(10)
+        .align_y(Center)
+        .into()
 }
 
 fn create_info_row<'a, C>(icon: svg::Handle, label: &'a str, content: C) -> Element<'a, Msg>
@@ -818,40 +4332,126 @@ where
     .into()
 }
 
-fn create_card_actions<'a>() -> Element<'a, Msg> {
-    container(
-        row![
-            ui_button(
-                "Add Session",
-                12.0,
-                icons::edit(),
-                16.0,
-                18.0,
-                |_| Color::WHITE,
-                |_| Color::BLACK,
-            )
-            .padding(10)
-            .width(Length::FillPortion(2))
-            .height(Length::Fixed(40.0)),
-            ui_button(
-                "Edit",
-                12.0,
-                icons::edit(),
-                16.0,
-                18.0,
-                |theme| theme.extended_palette().background.weak.text,
-                |theme| theme.extended_palette().background.weak.color,
-            )
-            .padding(10)
-            .width(Length::FillPortion(1))
-            .height(Length::Fixed(40.0)),
+fn create_card_actions<'a>(
+    student_id: String,
+    todays_session: Option<TodaysSession>,
+) -> Element<'a, Msg> {
+    let marking = todays_session.map(|session| view_session_marking(&student_id, session));
+
+    let actions = row![
+        ui_button(
+            "Add Session",
+            12.0,
+            icons::edit(),
+            16.0,
+            18.0,
+            |_| Color::WHITE,
+            |_| Color::BLACK,
+        )
+        .on_press(Msg::StartSessionLog(student_id.clone()))
+        .padding(10)
+        .width(Length::FillPortion(2))
+        .height(Length::Fixed(40.0)),
+        ui_button(
+            "Payment",
+            12.0,
+            icons::payments(),
+            16.0,
+            18.0,
+            |theme| theme.extended_palette().background.weak.text,
+            |theme| theme.extended_palette().background.weak.color,
+        )
+        .on_press(Msg::StartPaymentEntry(student_id.clone()))
+        .padding(10)
+        .width(Length::FillPortion(1))
+        .height(Length::Fixed(40.0)),
+        ui_button(
+            "Edit",
+            12.0,
+            icons::edit(),
+            16.0,
+            18.0,
+            |theme| theme.extended_palette().background.weak.text,
+            |theme| theme.extended_palette().background.weak.color,
+        )
+        .on_press(Msg::StartStudentEdit(student_id.clone()))
+        .padding(10)
+        .width(Length::FillPortion(1))
+        .height(Length::Fixed(40.0)),
+        ui_button(
+            "Remove",
+            12.0,
+            icons::delete(),
+            16.0,
+            18.0,
+            |_| theme::DANGER,
+            |theme| theme.extended_palette().background.weak.color,
+        )
+        .on_press(Msg::StartStudentRemoval(student_id.clone()))
+        .padding(10)
+        .width(Length::FillPortion(1))
+        .height(Length::Fixed(40.0)),
+    ]
+    .spacing(10);
+
+    container(column![actions].push(marking).spacing(12))
+        .height(Length::Fixed(150.0))
+        .width(Length::Fill)
+        .align_y(Alignment::Start)
+        .into()
+}
+
+/// Today's session with a button for each way it can go, or how it went
+/// once marked.
+fn view_session_marking<'a>(student_id: &str, session: TodaysSession) -> Element<'a, Msg> {
+    let mark = |status| Msg::SessionMarked {
+        student_id: student_id.to_string(),
+        at: session.at,
+        status,
+    };
+    let label = text(format!("Today {}", session.time)).size(12);
+
+    match session.status {
+        Some(status) => row![
+            label,
+            text(status.to_string())
+                .size(12)
+                .style(|_theme: &Theme| text::Style {
+                    color: Some(theme::MUTED),
+                }),
+            button(text("Undo").size(12))
+                .style(button::text)
+                .padding([2, 6])
+                .on_press(mark(None)),
         ]
-        .spacing(10),
-    )
-    .height(Length::Fixed(150.0))
-    .width(Length::Fill)
-    .align_y(Alignment::Start)
-    .into()
+        .spacing(8)
+        .align_y(Center)
+        .into(),
+        None => column![
+            label,
+            Row::new()
+                .extend(SessionStatus::ALL.map(|status| {
+                    button(text(mark_label(status)).size(11))
+                        .style(button::secondary)
+                        .padding([2, 6])
+                        .on_press(mark(Some(status)))
+                        .into()
+                }))
+                .spacing(4),
+        ]
+        .spacing(6)
+        .into(),
+    }
+}
+
+/// Short enough for four to fit across a card.
+fn mark_label(status: SessionStatus) -> &'static str {
+    match status {
+        SessionStatus::Completed => "Held",
+        SessionStatus::MissedByStudent => "Missed",
+        SessionStatus::CancelledByTutor => "Cancelled",
+        SessionStatus::Rescheduled => "Moved",
+    }
 }
 
 fn modal<'a, Message>(
@@ -886,13 +4486,21 @@ pub enum ValidityError {
     TooLong,
     TooShort,
     ContainsNonLetters,
+    NotADate,
+    NotAPhoneNumber,
+    NotAnEmail,
+    Unconfirmed,
 }
 
 pub struct ValidatedStudent {
     first: (String, ValidityTag),
     last: (String, ValidityTag),
     other: (String, ValidityTag),
+    phone: (String, ValidityTag),
+    email: (String, ValidityTag),
     rate: (String, ValidityTag),
+    min_sessions: (String, ValidityTag),
+    start_date: (String, ValidityTag),
     time_slots: ValidityTag,
 }
 
@@ -901,21 +4509,66 @@ impl ValidatedStudent {
         matches!(self.first.1, ValidityTag::Safe)
             && matches!(self.last.1, ValidityTag::Safe)
             && matches!(self.other.1, ValidityTag::Safe)
+            && matches!(self.phone.1, ValidityTag::Safe)
+            && matches!(self.email.1, ValidityTag::Safe)
             && matches!(self.rate.1, ValidityTag::Safe)
+            && matches!(self.min_sessions.1, ValidityTag::Safe)
+            && matches!(self.start_date.1, ValidityTag::Safe)
             && matches!(self.time_slots, ValidityTag::Safe)
     }
 }
 
-fn validate_student(modal_input: ModalInput, time_slots: &[TimeSlot]) -> ValidatedStudent {
+fn validate_student(
+    modal_input: ModalInput,
+    time_slots: &[TimeSlot],
+    backfill_pending: bool,
+) -> ValidatedStudent {
     ValidatedStudent {
         first: validate_name(modal_input.first_name),
         last: validate_name(modal_input.last_name),
         other: validate_optional_field(modal_input.other_names, 100),
+        phone: validate_phone(modal_input.phone),
+        email: validate_email(modal_input.email),
         rate: validate_number(modal_input.pay_rate),
+        min_sessions: validate_min_sessions(modal_input.min_sessions),
+        start_date: validate_start_date(modal_input.start_date, backfill_pending),
         time_slots: validate_time_slots(time_slots),
     }
 }
 
+fn parse_start_date(date: &str) -> Option<NaiveDate> {
+    NaiveDate::parse_from_str(date.trim(), "%Y-%m-%d").ok()
+}
+
+fn validate_start_date(date: String, backfill_pending: bool) -> (String, ValidityTag) {
+    let (date, tag) = validate_empty(date);
+    if !matches!(tag, ValidityTag::Safe) {
+        return (date, tag);
+    }
+
+    if parse_start_date(&date).is_none() {
+        return (
+            date,
+            ValidityTag::Problematic {
+                error_type: ValidityError::NotADate,
+                message: "Must be a date like 2026-01-31".to_string(),
+            },
+        );
+    }
+
+    if backfill_pending {
+        return (
+            date,
+            ValidityTag::Problematic {
+                error_type: ValidityError::Unconfirmed,
+                message: "Tick the past sessions that happened, then confirm them".to_string(),
+            },
+        );
+    }
+
+    (date, ValidityTag::Safe)
+}
+
 fn validate_name(name: String) -> (String, ValidityTag) {
     let (name, tag) = validate_length(name, 2, 50);
     if !matches!(tag, ValidityTag::Safe) {
@@ -1004,6 +4657,25 @@ fn validate_number(rate: String) -> (String, ValidityTag) {
     }
 }
 
+/// Blank, or a whole number of sessions no more than a month can hold.
+fn validate_min_sessions(sessions: String) -> (String, ValidityTag) {
+    let sessions = sessions.trim().to_string();
+    let in_range = sessions
+        .parse::<u32>()
+        .is_ok_and(|min| (1..=MAX_MIN_SESSIONS).contains(&min));
+    if sessions.is_empty() || in_range {
+        return (sessions, ValidityTag::Safe);
+    }
+
+    (
+        sessions,
+        ValidityTag::Problematic {
+            error_type: ValidityError::NotANumber,
+            message: format!("Must be a whole number from 1 to {}", MAX_MIN_SESSIONS),
+        },
+    )
+}
+
 fn validate_optional_field(input: String, max: usize) -> (String, ValidityTag) {
     let input = input.trim().to_string();
 
@@ -1024,6 +4696,36 @@ fn validate_optional_field(input: String, max: usize) -> (String, ValidityTag) {
     (input, ValidityTag::Safe)
 }
 
+fn validate_phone(phone: String) -> (String, ValidityTag) {
+    let phone = phone.trim().to_string();
+    if phone.is_empty() || international_number(&phone).is_some() {
+        return (phone, ValidityTag::Safe);
+    }
+
+    (
+        phone,
+        ValidityTag::Problematic {
+            error_type: ValidityError::NotAPhoneNumber,
+            message: "Must be a phone number like 024 123 4567".to_string(),
+        },
+    )
+}
+
+fn validate_email(email: String) -> (String, ValidityTag) {
+    let email = email.trim().to_string();
+    if email.is_empty() || is_email(&email) {
+        return (email, ValidityTag::Safe);
+    }
+
+    (
+        email,
+        ValidityTag::Problematic {
+            error_type: ValidityError::NotAnEmail,
+            message: "Must be an email like parent@example.com".to_string(),
+        },
+    )
+}
+
 fn validate_time_slots(time_slots: &[TimeSlot]) -> ValidityTag {
     let has_complete_slot = time_slots.iter().any(|slot| {
         slot.selected_day.is_some() && slot.selected_time.is_some()
@@ -1039,6 +4741,542 @@ fn validate_time_slots(time_slots: &[TimeSlot]) -> ValidityTag {
     }
 }
 
-async fn add_student(_modal_input: ModalInput) -> Result<(), StudentError> {
-    Ok(())
+/// An id made from the student's name, numbered on if already taken.
+fn new_student_id<'a>(
+    input: &ModalInput,
+    students: impl Iterator<Item = &'a Student> + Clone,
+) -> String {
+    let name = format!("{} {}", input.first_name, input.last_name).to_lowercase();
+    let base = name.split_whitespace().collect::<Vec<_>>().join("-");
+    let taken = |id: &str| students.clone().any(|student| student.id == id);
+
+    std::iter::once(base.clone())
+        .chain((2..).map(|n| format!("{}-{}", base, n)))
+        .find(|id| !taken(id))
+        .unwrap_or(base)
+}
+
+async fn add_student(student: Student) -> Result<Box<Student>, StudentError> {
+    Ok(Box::new(student))
+}
+
+async fn update_student(student: Student) -> Result<Box<Student>, StudentError> {
+    Ok(Box::new(student))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::fixtures::{date, sample_domain};
+    use crate::store::{DomainEdit, DomainStore};
+
+    fn sample_student_manager() -> StudentManagerState {
+        let mut state = StudentManagerState::empty();
+        state.attach_domain(Rc::new(sample_domain()));
+        state
+    }
+
+    #[test]
+    fn student_cards() {
+        let state = sample_student_manager();
+        let students = state.students.as_deref().unwrap();
+
+        insta::assert_debug_snapshot!(student_card_models(&state, students));
+    }
+
+    #[test]
+    fn slots_can_be_dragged_into_place_and_duplicated_up_to_the_limit() {
+        let mut state = sample_student_manager();
+        let _ = update(&mut state, Msg::ShowAddStudentModal);
+        let _ = update(&mut state, Msg::AddTimeSlot);
+        let _ = update(
+            &mut state,
+            Msg::TutoringDaySelected(1, DaySelection::Day(Weekday::Thu)),
+        );
+        let ids = |state: &StudentManagerState| {
+            state
+                .modal_state
+                .time_slots
+                .iter()
+                .map(|slot| slot.id)
+                .collect::<Vec<_>>()
+        };
+
+        let _ = update(&mut state, Msg::TimeSlotDragStarted(1));
+        let _ = update(&mut state, Msg::TimeSlotDraggedOver(0));
+        let _ = update(&mut state, Msg::TimeSlotDragEnded);
+        assert_eq!(ids(&state), vec![1, 0]);
+
+        let _ = update(&mut state, Msg::DuplicateTimeSlot(1));
+        let _ = update(&mut state, Msg::DuplicateTimeSlot(1));
+        assert_eq!(ids(&state), vec![1, 2, 0]);
+        assert_eq!(
+            weekly_preview(&state.modal_state.time_slots)[3],
+            (Weekday::Thu, vec!["Time not set".to_string(); 2])
+        );
+    }
+
+    #[test]
+    fn quote_counts_each_slot_in_the_current_month() {
+        let mut state = sample_student_manager();
+        let _ = update(&mut state, Msg::ShowAddStudentModal);
+        let _ = update(
+            &mut state,
+            Msg::TutoringDaySelected(0, DaySelection::Day(Weekday::Tue)),
+        );
+        let _ = update(&mut state, Msg::DuplicateTimeSlot(0));
+        let today = state.clock.today();
+
+        let quote = monthly_quote(&state.modal_state, today);
+        assert_eq!((quote.month.as_str(), quote.sessions), ("Feb '26", 8));
+        assert_eq!(quote.cost, None);
+
+        let _ = update(&mut state, Msg::RateInputChanged("150".to_string()));
+        assert_eq!(monthly_quote(&state.modal_state, today).cost, Some(1200.0));
+    }
+
+    #[test]
+    fn past_start_dates_hold_the_student_until_past_sessions_are_confirmed() {
+        let mut state = sample_student_manager();
+        let _ = update(&mut state, Msg::ShowAddStudentModal);
+        for msg in [
+            Msg::FirstNameInputChanged("Gwen".to_string()),
+            Msg::LastNameInputChanged("Stacy".to_string()),
+            Msg::RateInputChanged("150".to_string()),
+            Msg::TutoringDaySelected(0, DaySelection::Day(Weekday::Tue)),
+            Msg::TutoringTimeSelected(0, TimeSelection::Time("5:00 PM".to_string())),
+            // Seen from Thursday 12 February: Tuesdays 3 and 10 have passed.
+            Msg::StartDateChanged("2026-02-01".to_string()),
+        ] {
+            let _ = update(&mut state, msg);
+        }
+        let today = state.clock.today();
+        let expected = state.modal_state.expected_backfill(today);
+        assert_eq!(expected.len(), 2);
+
+        let _ = update(&mut state, Msg::AddStudent);
+        let errors = state.modal_state.validation_errors.as_ref().unwrap();
+        assert!(matches!(
+            errors.start_date.1,
+            ValidityTag::Problematic {
+                error_type: ValidityError::Unconfirmed,
+                ..
+            }
+        ));
+
+        let _ = update(&mut state, Msg::BackfillSessionToggled(expected[1], true));
+        let _ = update(&mut state, Msg::BackfillConfirmed(true));
+        assert!(!state.modal_state.backfill_pending(today));
+
+        let _ = update(&mut state, Msg::StartDateChanged("2026-01-20".to_string()));
+        assert!(state.modal_state.backfill_pending(today));
+    }
+
+    #[test]
+    fn an_added_student_reaches_the_domain_with_their_past_sessions() {
+        let mut state = sample_student_manager();
+        let mut store = DomainStore::new(sample_domain());
+        let _ = update(&mut state, Msg::ShowAddStudentModal);
+        for msg in [
+            Msg::FirstNameInputChanged("Gwen".to_string()),
+            Msg::LastNameInputChanged("Stacy".to_string()),
+            Msg::RateInputChanged("150".to_string()),
+            Msg::MinSessionsChanged("4".to_string()),
+            Msg::TutoringDaySelected(0, DaySelection::Day(Weekday::Tue)),
+            Msg::TutoringTimeSelected(0, TimeSelection::Time("5:00 PM".to_string())),
+            Msg::StartDateChanged("2026-02-01".to_string()),
+        ] {
+            let _ = update(&mut state, msg);
+        }
+        let today = state.clock.today();
+        let expected = state.modal_state.expected_backfill(today);
+        let _ = update(&mut state, Msg::BackfillSessionToggled(expected[1], true));
+        let _ = update(&mut state, Msg::BackfillConfirmed(true));
+
+        let input = state.modal_state.modal_input.clone();
+        let id = new_student_id(&input, state.students.iter().flatten());
+        let student = state.modal_state.added(id, today);
+        assert!(store.apply(DomainEdit::StudentAdded(student)));
+
+        let domain = store.domain();
+        let added = domain.students.last().unwrap();
+        assert_eq!(added.id, "gwen-stacy");
+        assert_eq!(added.name.full_name(), "Gwen Stacy");
+        assert_eq!(added.tution_start_date.date_naive(), date(2026, 2, 1));
+        assert_eq!(added.payment_data.min_sessions, Some(4));
+        assert_eq!(
+            added
+                .sessions
+                .iter()
+                .map(|record| (record.at, record.status))
+                .collect::<Vec<_>>(),
+            [(expected[1], SessionStatus::Completed)]
+        );
+
+        state.attach_domain(domain);
+        let taken = new_student_id(&input, state.students.iter().flatten());
+        assert_eq!(taken, "gwen-stacy-2");
+    }
+
+    #[test]
+    fn student_cards_sorted_by_accrued() {
+        let mut state = sample_student_manager();
+        let _ = update(&mut state, Msg::SortSelected(StudentSort::Accrued));
+        let students = state.students.as_deref().unwrap();
+
+        let names: Vec<String> = student_card_models(&state, students)
+            .into_iter()
+            .map(|card| format!("{} {}", card.name, card.accrued))
+            .collect();
+        insta::assert_debug_snapshot!(names);
+    }
+
+    #[test]
+    fn students_sort_by_what_they_owe_in_the_ledger() {
+        let mut state = sample_student_manager();
+        let students = state.students.clone().unwrap();
+        let mut ledger = Ledger::default();
+        ledger.record_payment(Payment {
+            student_id: students[0].id.clone(),
+            paid_on: date(2026, 2, 1),
+            amount: 100_000.0,
+            invoice_number: None,
+        });
+        state.attach_ledger(&ledger);
+
+        let _ = update(&mut state, Msg::SortSelected(StudentSort::Balance));
+        let rows = student_rows(&state, &students);
+        assert_eq!(rows[0].student.id, students[1].id);
+        assert!(rows[1].balance < 0.0);
+        assert_eq!(
+            table_cell(StudentSort::Balance, &rows[0], &state.money_format),
+            state.money_format.format(rows[0].balance)
+        );
+
+        let _ = update(&mut state, Msg::SortColumnClicked(StudentSort::Balance));
+        assert_eq!(
+            student_rows(&state, &students)[0].student.id,
+            students[0].id
+        );
+    }
+
+    #[test]
+    fn cards_can_show_an_earlier_months_figures() {
+        let mut state = sample_student_manager();
+        let students = state.students.clone().unwrap();
+        let completed = |state: &StudentManagerState| {
+            student_card_models(state, &students)
+                .into_iter()
+                .map(|card| card.completed_sessions)
+                .collect::<Vec<_>>()
+        };
+        let this_month = completed(&state);
+
+        let _ = update(&mut state, Msg::MetricsPreviousMonth);
+        assert_eq!(state.shown_month(), (2026, 1));
+        assert_ne!(completed(&state), this_month);
+        assert_eq!(
+            completed(&state)[0],
+            compute_monthly_completed_sessions(&students[0], 1, 2026).to_string()
+        );
+
+        let _ = update(&mut state, Msg::MetricsNextMonth);
+        assert_eq!(state.metrics_month, None);
+        assert_eq!(completed(&state), this_month);
+    }
+
+    #[test]
+    fn logging_a_session_checks_the_date_and_time() {
+        let mut state = sample_student_manager();
+        let student_id = state.students.as_deref().unwrap()[0].id.clone();
+        let _ = update(&mut state, Msg::StartSessionLog(student_id));
+        let _ = update(
+            &mut state,
+            Msg::SessionLogDateChanged("2026-02-10".to_string()),
+        );
+        let _ = update(
+            &mut state,
+            Msg::SessionLogTimeChanged("teatime".to_string()),
+        );
+
+        let _ = update(&mut state, Msg::LogSession);
+        let log = state.session_log.as_mut().unwrap();
+        assert!(log.error.is_some());
+
+        log.time = "5:30 PM".to_string();
+        assert_eq!(
+            log.start_time(),
+            Ok(Local.with_ymd_and_hms(2026, 2, 10, 17, 30, 0).unwrap())
+        );
+    }
+
+    #[test]
+    fn payments_need_a_positive_amount() {
+        let mut state = sample_student_manager();
+        let student_id = state.students.as_deref().unwrap()[0].id.clone();
+        let _ = update(&mut state, Msg::StartPaymentEntry(student_id));
+
+        for amount in ["", "abc", "-20"] {
+            let _ = update(&mut state, Msg::PaymentAmountChanged(amount.to_string()));
+            let _ = update(&mut state, Msg::RecordPayment);
+            assert!(state.payment_entry.as_ref().unwrap().error.is_some());
+        }
+
+        let _ = update(&mut state, Msg::PaymentAmountChanged("150".to_string()));
+        let _ = update(&mut state, Msg::RecordPayment);
+        assert!(state.payment_entry.as_ref().unwrap().error.is_none());
+    }
+
+    #[test]
+    fn quotes_need_a_rate_for_the_subject() {
+        let mut state = sample_student_manager();
+        let _ = update(&mut state, Msg::StartQuote);
+        let subject = state.quote_form.as_ref().unwrap().subject.clone().unwrap();
+
+        let form = state.quote_form.as_ref().unwrap();
+        assert!(form.quote(&state.rates, &state.locations).is_err());
+
+        state.rates.push(SubjectRate {
+            subject,
+            per_hour: 150.0,
+        });
+        let _ = update(&mut state, Msg::QuoteSessionsChanged(2));
+        let form = state.quote_form.as_ref().unwrap();
+        let quote = form.quote(&state.rates, &state.locations).unwrap();
+        assert_eq!(quote.per_session, 150.0);
+        assert_eq!(quote.monthly(), 1300.0);
+    }
+
+    #[test]
+    fn rate_wizard_raises_the_chosen_students_from_a_month_start() {
+        let mut state = sample_student_manager();
+        let _ = update(&mut state, Msg::StartRateWizard);
+        let wizard = state.rate_wizard.as_ref().unwrap();
+        assert_eq!(wizard.student_ids.len(), 2);
+        assert_eq!(wizard.from, "2026-03-01");
+
+        let peter = state.students.as_ref().unwrap()[1].id.clone();
+        let _ = update(&mut state, Msg::RateWizardStudentToggled(peter, false));
+        let _ = update(
+            &mut state,
+            Msg::RateWizardFromChanged("2026-03-15".to_string()),
+        );
+        let _ = update(&mut state, Msg::RateWizardNext);
+        let wizard = state.rate_wizard.as_ref().unwrap();
+        assert_eq!(wizard.step, RateWizardStep::Choose);
+        assert!(wizard.error.is_some());
+
+        let _ = update(
+            &mut state,
+            Msg::RateWizardFromChanged("2026-03-01".to_string()),
+        );
+        let _ = update(&mut state, Msg::RateWizardNext);
+        let wizard = state.rate_wizard.as_ref().unwrap();
+        assert_eq!(wizard.step, RateWizardStep::Notify);
+        let rates: Vec<(String, f32)> = wizard
+            .new_rates(state.students.as_ref().unwrap(), &state.money_format)
+            .into_iter()
+            .map(|(student, rate)| (student.name.first.clone(), rate))
+            .collect();
+        assert_eq!(rates, [("Mary".to_string(), 165.0)]);
+    }
+
+    #[test]
+    fn editing_a_student_changes_their_details_and_keeps_their_history() {
+        let mut state = sample_student_manager();
+        let mary = state.students.as_ref().unwrap()[0].clone();
+        let _ = update(&mut state, Msg::StartStudentEdit(mary.id.clone()));
+        assert!(state.show_add_student_modal);
+        assert_eq!(state.modal_state.modal_input.first_name, "Mary");
+        assert_eq!(state.modal_state.modal_input.pay_rate, "150");
+        assert_eq!(state.modal_state.time_slots.len(), 2);
+
+        for msg in [
+            Msg::FirstNameInputChanged("Marianne".to_string()),
+            Msg::RateInputChanged("175".to_string()),
+            Msg::RemoveTimeSlot(0),
+            Msg::AddTimeSlot,
+            Msg::TutoringDaySelected(2, DaySelection::Day(Weekday::Fri)),
+            Msg::TutoringTimeSelected(2, TimeSelection::Time("4:00 PM".to_string())),
+            Msg::TimeSlotDurationChanged(2, 90),
+        ] {
+            let _ = update(&mut state, msg);
+        }
+
+        let edited = state.modal_state.edited(&mary);
+        assert_eq!(edited.name.first, "Marianne");
+        assert_eq!(edited.payment_data.amount, 175.0);
+        assert_eq!(
+            edited
+                .tabled_sessions
+                .iter()
+                .map(|session| (session.day, session.time.as_str(), session.duration_minutes))
+                .collect::<Vec<_>>(),
+            [
+                (Weekday::Thu, "5:30 PM", DEFAULT_SESSION_MINUTES),
+                (Weekday::Fri, "4:00 PM", 90)
+            ]
+        );
+        assert_eq!(edited.tution_start_date, mary.tution_start_date);
+        assert_eq!(edited.sessions, mary.sessions);
+        assert_eq!(edited.payment_data.min_sessions, None);
+
+        let _ = update(&mut state, Msg::MinSessionsChanged("40".to_string()));
+        let _ = update(&mut state, Msg::UpdateStudent);
+        assert!(state.show_add_student_modal);
+        let _ = update(&mut state, Msg::MinSessionsChanged(" 4 ".to_string()));
+        assert_eq!(
+            state.modal_state.edited(&mary).payment_data.min_sessions,
+            Some(4)
+        );
+
+        let _ = update(&mut state, Msg::UpdateStudent);
+        assert!(!state.show_add_student_modal);
+        assert_eq!(state.modal_state.editing, None);
+    }
+
+    #[test]
+    fn contact_details_are_checked_and_offered_on_the_card() {
+        let mut state = sample_student_manager();
+        let mary = state.students.as_ref().unwrap()[0].clone();
+        let _ = update(&mut state, Msg::StartStudentEdit(mary.id.clone()));
+        let _ = update(&mut state, Msg::PhoneInputChanged("call me".to_string()));
+        let _ = update(&mut state, Msg::EmailInputChanged("parent@".to_string()));
+        let _ = update(&mut state, Msg::UpdateStudent);
+        let errors = state.modal_state.validation_errors.as_ref().unwrap();
+        assert_eq!(
+            (&errors.phone.1, &errors.email.1),
+            (
+                &ValidityTag::Problematic {
+                    error_type: ValidityError::NotAPhoneNumber,
+                    message: "Must be a phone number like 024 123 4567".to_string(),
+                },
+                &ValidityTag::Problematic {
+                    error_type: ValidityError::NotAnEmail,
+                    message: "Must be an email like parent@example.com".to_string(),
+                }
+            )
+        );
+
+        let _ = update(
+            &mut state,
+            Msg::PhoneInputChanged(" 024 123 4567 ".to_string()),
+        );
+        let _ = update(&mut state, Msg::EmailInputChanged(String::new()));
+        let edited = state.modal_state.edited(&mary);
+        assert_eq!(
+            (edited.phone.as_deref(), edited.email.as_deref()),
+            (Some("024 123 4567"), None)
+        );
+
+        let mut domain = sample_domain();
+        domain.students[0] = edited;
+        state.refresh(Rc::new(domain));
+        let students = state.students.as_deref().unwrap();
+        let actions: Vec<Vec<ContactAction>> = student_card_models(&state, students)
+            .into_iter()
+            .map(|card| card.contact_actions)
+            .collect();
+        assert_eq!(
+            actions,
+            [
+                vec![ContactAction::Call, ContactAction::WhatsApp],
+                Vec::new()
+            ]
+        );
+    }
+
+    #[test]
+    fn tags_are_added_once_and_narrow_the_list() {
+        let mut state = sample_student_manager();
+        let mary = state.students.as_ref().unwrap()[0].clone();
+        let _ = update(&mut state, Msg::StartStudentEdit(mary.id.clone()));
+        for tag in [" Exam year ", "exam YEAR", "  ", "Online"] {
+            let _ = update(&mut state, Msg::TagAdded(tag.to_string()));
+        }
+        let _ = update(&mut state, Msg::TagRemoved("Online".to_string()));
+        assert_eq!(state.modal_state.tags, ["Exam year"]);
+
+        let mut domain = sample_domain();
+        domain.students[0] = state.modal_state.edited(&mary);
+        state.refresh(Rc::new(domain));
+        assert_eq!(state.known_tags, ["Exam year"]);
+
+        let _ = update(&mut state, Msg::TagFilterAdded("exam year".to_string()));
+        let students = state.students.as_deref().unwrap();
+        let names: Vec<String> = student_card_models(&state, students)
+            .into_iter()
+            .map(|card| card.name)
+            .collect();
+        assert_eq!(names, ["Mary Jane"]);
+    }
+
+    #[test]
+    fn archived_students_are_hidden_until_asked_for() {
+        let mut state = sample_student_manager();
+        let mary = state.students.as_ref().unwrap()[0].id.clone();
+        let _ = update(&mut state, Msg::StartStudentRemoval(mary.clone()));
+        assert!(!state.removal.as_ref().unwrap().archived);
+        let _ = update(&mut state, Msg::CloseStudentRemoval);
+
+        // What archiving saves, once the app has applied it.
+        let today = state.clock.today();
+        state.students.as_mut().unwrap()[0].tution_end_date = Local
+            .from_local_datetime(&today.and_time(NaiveTime::MIN))
+            .earliest();
+        let card_ids = |state: &StudentManagerState| {
+            student_card_models(state, state.students.as_deref().unwrap())
+                .into_iter()
+                .map(|card| card.student_id)
+                .collect::<Vec<_>>()
+        };
+        assert!(!card_ids(&state).contains(&mary));
+
+        let _ = update(&mut state, Msg::ShowArchivedToggled(true));
+        assert!(card_ids(&state).contains(&mary));
+        let _ = update(&mut state, Msg::StartStudentRemoval(mary));
+        assert!(state.removal.as_ref().unwrap().archived);
+    }
+
+    #[test]
+    fn quick_log_offers_only_students_still_taught() {
+        let mut state = sample_student_manager();
+        let _ = update(&mut state, Msg::StartQuickLog);
+        assert!(state.session_log.is_some());
+
+        let today = state.clock.today();
+        let mut students = state.students.clone().unwrap();
+        students[0].tution_end_date = Local
+            .from_local_datetime(&today.and_time(NaiveTime::MIN))
+            .earliest();
+        let names: Vec<String> = student_choices(&students, today)
+            .into_iter()
+            .map(|choice| choice.name)
+            .collect();
+        assert_eq!(names, [students[1].name.full_name()]);
+    }
+
+    #[test]
+    fn tags_are_edited_in_the_table_as_a_list() {
+        let mut state = sample_student_manager();
+        let mary = state.students.as_ref().unwrap()[0].clone();
+        state.students.as_mut().unwrap()[0].tags = vec!["Exam year".to_string()];
+
+        let _ = update(
+            &mut state,
+            Msg::StartInlineEdit(mary.id.clone(), InlineField::Tags),
+        );
+        assert_eq!(state.inline_edit.as_ref().unwrap().value, "Exam year");
+
+        let _ = update(
+            &mut state,
+            Msg::InlineEditChanged("Exam year, online ,, exam YEAR, Weekends".to_string()),
+        );
+        assert_eq!(
+            parse_tags(&state.inline_edit.as_ref().unwrap().value),
+            ["Exam year", "online", "Weekends"]
+        );
+        let _ = update(&mut state, Msg::CommitInlineEdit);
+        assert!(state.inline_edit.is_none());
+    }
 }
@@ -0,0 +1,34 @@
+use iced::{Border, Color, Shadow, Vector};
+
+/// Buttons and icons that start something, like "Add Student".
+pub const ACCENT: Color = Color::from_rgba(0.0, 0.2, 0.9, 0.7);
+/// Errors and destructive actions.
+pub const DANGER: Color = Color::from_rgb(1.0, 0.0, 0.0);
+/// Something needs a look but is not wrong yet.
+pub const WARNING: Color = Color::from_rgb(0.85, 0.45, 0.0);
+pub const SUCCESS: Color = Color::from_rgb(0.0, 0.5, 0.2);
+/// Axis labels and other text that should stay out of the way.
+pub const MUTED: Color = Color::from_rgb(0.5, 0.5, 0.5);
+
+/// Corner radius of cards, badges and buttons.
+pub const RADIUS: f32 = 10.0;
+
+pub fn rounded() -> Border {
+    Border {
+        radius: RADIUS.into(),
+        ..Default::default()
+    }
+}
+
+/// The lift a card gets while the pointer is over it.
+pub fn card_shadow(is_hovered: bool) -> Shadow {
+    if is_hovered {
+        Shadow {
+            color: Color::from_rgba(0.0, 0.0, 0.0, 0.25),
+            offset: Vector::new(0.4, 0.0),
+            blur_radius: 12.0,
+        }
+    } else {
+        Shadow::default()
+    }
+}
@@ -1,7 +1,24 @@
 use iced::advanced::graphics::core::font;
-use iced::widget::{Button, Container, button, container, svg};
-use iced::widget::{Row, row, text};
-use iced::{Background, Border, Center, Color, Element, Font, Theme};
+use iced::widget::canvas::{self, Canvas, Path, Stroke, Text};
+use iced::widget::{
+    Button, ComboBox, Container, button, combo_box, container, mouse_area, svg, text_input, tooltip,
+};
+use iced::widget::{Column, Row, column, row, text};
+use iced::{
+    Background, Center, Color, Element, Font, Length, Point, Radians, Rectangle, Renderer, Theme,
+    mouse,
+};
+use std::f32::consts::{FRAC_PI_2, TAU};
+use std::ops::RangeInclusive;
+use std::time::Duration;
+
+use crate::icons;
+use crate::theme;
+
+/// Most suggestions offered under a [`tag_input`].
+const TAG_SUGGESTIONS: usize = 5;
+/// How long the pointer rests on something before its [`popover`] opens.
+const POPOVER_DELAY: Duration = Duration::from_millis(300);
 
 pub fn page_header<'a, Message: 'a>(header_text: &'a str) -> Row<'a, Message> {
     let page_title_text = text(header_text)
@@ -56,15 +73,395 @@ pub fn ui_button<'a, Message: 'a>(
     .style(
         move |theme: &Theme, _status: button::Status| button::Style {
             background: Some(Background::Color(bg_color_fn(theme))),
-            border: Border {
-                radius: 10.0.into(),
-                ..Default::default()
-            },
+            border: theme::rounded(),
             ..Default::default()
         },
     )
 }
 
+/// A pick list that narrows its options to those containing what is typed,
+/// for lists too long to scroll through. `state` holds the options and
+/// lives as long as the screen does.
+pub fn searchable_pick_list<'a, T, Message>(
+    state: &'a combo_box::State<T>,
+    placeholder: &str,
+    selected: Option<&T>,
+    on_selected: impl Fn(T) -> Message + 'static,
+) -> ComboBox<'a, T, Message>
+where
+    T: std::fmt::Display + Clone + 'static,
+    Message: Clone + 'a,
+{
+    combo_box(state, placeholder, selected, on_selected)
+        .size(14.0)
+        .menu_height(160)
+}
+
+/// A whole number between − and + buttons, for counts and durations that
+/// are easier to nudge than type. Each press moves `step` and stays
+/// within `range`.
+pub fn number_stepper<'a, Message: Clone + 'a>(
+    value: u32,
+    range: RangeInclusive<u32>,
+    step: u32,
+    on_change: impl Fn(u32) -> Message,
+) -> Row<'a, Message> {
+    let (min, max) = (*range.start(), *range.end());
+    let less = (value > min).then(|| value.saturating_sub(step).max(min));
+    let more = (value < max).then(|| value.saturating_add(step).min(max));
+    let nudge = |label: &'a str, to: Option<u32>| {
+        button(text(label).size(14).align_x(Center))
+            .width(28)
+            .padding([2, 0])
+            .style(button::secondary)
+            .on_press_maybe(to.map(&on_change))
+    };
+
+    row![
+        nudge("−", less),
+        text(value.to_string())
+            .size(14)
+            .width(Length::Fixed(36.0))
+            .align_x(Center),
+        nudge("+", more),
+    ]
+    .spacing(4)
+    .align_y(Center)
+}
+
+/// Chosen `tags` as chips, each removed with its ×, then a box that adds
+/// what is typed on Enter. Tags from `known` that match what is typed and
+/// are not chosen yet are offered underneath.
+pub fn tag_input<'a, Message: Clone + 'a>(
+    tags: &'a [String],
+    draft: &'a str,
+    known: &'a [String],
+    placeholder: &'a str,
+    on_draft: impl Fn(String) -> Message + 'a,
+    on_add: impl Fn(String) -> Message,
+    on_remove: impl Fn(String) -> Message,
+) -> Column<'a, Message> {
+    let chips = Row::new()
+        .extend(tags.iter().map(|tag| {
+            container(
+                row![
+                    text(tag.as_str()).size(12),
+                    button(text("×").size(12))
+                        .style(button::text)
+                        .padding(0)
+                        .on_press(on_remove(tag.clone())),
+                ]
+                .spacing(4)
+                .align_y(Center),
+            )
+            .padding([2, 8])
+            .style(|theme: &Theme| container::Style {
+                background: Some(theme.extended_palette().background.weak.color.into()),
+                border: theme::rounded(),
+                ..Default::default()
+            })
+            .into()
+        }))
+        .push(
+            text_input(placeholder, draft)
+                .on_input(on_draft)
+                .on_submit(on_add(draft.to_string()))
+                .size(12)
+                .width(Length::Fixed(140.0)),
+        )
+        .spacing(6)
+        .align_y(Center)
+        .wrap();
+
+    let typed = draft.trim().to_lowercase();
+    let suggestions = Row::new()
+        .extend(
+            known
+                .iter()
+                .filter(|tag| !typed.is_empty() && tag.to_lowercase().contains(&typed))
+                .filter(|tag| !tags.iter().any(|chosen| chosen.eq_ignore_ascii_case(tag)))
+                .take(TAG_SUGGESTIONS)
+                .map(|tag| {
+                    button(text(tag.as_str()).size(11))
+                        .style(button::text)
+                        .padding([2, 6])
+                        .on_press(on_add(tag.clone()))
+                        .into()
+                }),
+        )
+        .spacing(4);
+
+    column![chips, suggestions].spacing(4)
+}
+
+/// A circle filled clockwise from the top to `progress`, between 0 and 1,
+/// with `label` in the middle. Callers animate the fill by passing a
+/// growing `progress`.
+pub fn progress_ring<'a, Message: 'a>(
+    progress: f32,
+    size: f32,
+    color: Color,
+    label: String,
+) -> Element<'a, Message> {
+    Canvas::new(ProgressRing {
+        progress: progress.clamp(0.0, 1.0),
+        color,
+        label,
+    })
+    .width(size)
+    .height(size)
+    .into()
+}
+
+struct ProgressRing {
+    progress: f32,
+    color: Color,
+    label: String,
+}
+
+impl<Message> canvas::Program<Message> for ProgressRing {
+    type State = ();
+
+    fn draw(
+        &self,
+        _state: &Self::State,
+        renderer: &Renderer,
+        theme: &Theme,
+        bounds: Rectangle,
+        _cursor: mouse::Cursor,
+    ) -> Vec<canvas::Geometry> {
+        let mut frame = canvas::Frame::new(renderer, bounds.size());
+        let width = (frame.width().min(frame.height()) / 8.0).max(2.0);
+        let radius = frame.width().min(frame.height()) / 2.0 - width / 2.0;
+        let center = frame.center();
+
+        frame.stroke(
+            &Path::circle(center, radius),
+            Stroke::default()
+                .with_color(theme.extended_palette().background.strong.color)
+                .with_width(width),
+        );
+        if self.progress > 0.0 {
+            let fill = Path::new(|builder| {
+                builder.arc(canvas::path::Arc {
+                    center,
+                    radius,
+                    start_angle: Radians(-FRAC_PI_2),
+                    end_angle: Radians(-FRAC_PI_2 + TAU * self.progress),
+                })
+            });
+            frame.stroke(
+                &fill,
+                Stroke::default()
+                    .with_color(self.color)
+                    .with_width(width)
+                    .with_line_cap(canvas::LineCap::Round),
+            );
+        }
+        frame.fill_text(Text {
+            content: self.label.clone(),
+            position: Point::new(center.x, center.y),
+            color: theme.palette().text,
+            size: (radius * 0.6).max(8.0).into(),
+            align_x: iced::advanced::text::Alignment::Center,
+            align_y: iced::alignment::Vertical::Center,
+            ..Default::default()
+        });
+
+        vec![frame.into_geometry()]
+    }
+}
+
+/// A live "In session" badge while a session runs, or else the countdown
+/// to the next one in muted text. `None` when there is neither.
+pub fn session_countdown<'a, Message: 'a>(
+    countdown: Option<String>,
+    in_session: bool,
+) -> Option<Element<'a, Message>> {
+    if in_session {
+        return Some(
+            container(text("In session").size(11).font(Font {
+                weight: font::Weight::Medium,
+                ..Default::default()
+            }))
+            .padding([2, 8])
+            .style(|_: &Theme| container::Style {
+                background: Some(Background::Color(theme::SUCCESS)),
+                text_color: Some(Color::WHITE),
+                border: theme::rounded(),
+                ..Default::default()
+            })
+            .into(),
+        );
+    }
+
+    countdown.map(|countdown| {
+        text(countdown)
+            .size(12)
+            .style(|_: &Theme| text::Style {
+                color: Some(theme::MUTED),
+            })
+            .into()
+    })
+}
+
+/// Floats `content` in a card beside `base` while the pointer rests on
+/// it, for details that do not fit on `base` itself.
+pub fn popover<'a, Message: 'a>(
+    base: impl Into<Element<'a, Message>>,
+    content: impl Into<Element<'a, Message>>,
+) -> Element<'a, Message> {
+    let card = container(content)
+        .padding(10)
+        .max_width(260)
+        .style(|theme: &Theme| container::Style {
+            shadow: theme::card_shadow(true),
+            ..container::rounded_box(theme)
+        });
+
+    tooltip(base, card, tooltip::Position::Right)
+        .gap(6)
+        .delay(POPOVER_DELAY)
+        .into()
+}
+
+/// A small icon button for copying what is next to it.
+pub fn copy_button<'a, Message: 'a>() -> Button<'a, Message> {
+    icon_button(icons::copy())
+}
+
+/// A small borderless button showing just `icon`.
+pub fn icon_button<'a, Message: 'a>(icon: svg::Handle) -> Button<'a, Message> {
+    button(
+        svg::Svg::new(icon)
+            .width(14)
+            .height(14)
+            .style(|theme: &Theme, _status: svg::Status| svg::Style {
+                color: Some(theme.extended_palette().background.strong.text),
+            }),
+    )
+    .padding(4)
+    .style(button::text)
+}
+
+/// The change shown under a metric, with what it compares against in a
+/// tooltip. `is_positive` picks the arrow, if any.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TrendChip {
+    pub label: String,
+    pub is_positive: Option<bool>,
+    pub detail: String,
+}
+
+/// A small card with a headline number. Pressing the trend sends
+/// `on_trend_press`; wrap the card in a `mouse_area` to track hovering.
+pub fn metric_card<'a, Message: Clone + 'a>(
+    title: String,
+    value: String,
+    trend: Option<TrendChip>,
+    is_hovered: bool,
+    background_fn: impl Fn(&Theme) -> Option<Color> + 'a,
+    on_trend_press: Message,
+) -> Container<'a, Message> {
+    let mut content = column![
+        text(title).size(15).font(Font {
+            weight: font::Weight::Medium,
+            ..Default::default()
+        }),
+        text(value).size(25).font(Font {
+            weight: font::Weight::Medium,
+            ..Default::default()
+        }),
+    ]
+    .align_x(Center)
+    .spacing(5);
+
+    if let Some(TrendChip {
+        label: trend_text,
+        is_positive,
+        detail,
+    }) = trend
+    {
+        let trend_icon: Option<svg::Handle> = match is_positive {
+            None => None,
+            Some(true) => Some(icons::arrow_up()),
+            Some(false) => Some(icons::arrow_down()),
+        };
+
+        let trend_row = match trend_icon {
+            None => container(text(trend_text).size(12).font(Font {
+                weight: font::Weight::Medium,
+                ..Default::default()
+            })),
+            Some(icon) => container(row![
+                svg::Svg::new(icon).width(14).height(14),
+                text(trend_text).size(12).font(Font {
+                    weight: font::Weight::Medium,
+                    ..Default::default()
+                }),
+            ]),
+        };
+
+        let trend_chip = tooltip(
+            mouse_area(trend_row)
+                .interaction(iced::mouse::Interaction::Pointer)
+                .on_press(on_trend_press),
+            container(text(format!("{}\nClick to switch between % and totals", detail)).size(12))
+                .padding(8)
+                .style(container::rounded_box),
+            tooltip::Position::Bottom,
+        );
+
+        content = content.push(container(trend_chip).align_bottom(Length::Fill));
+    }
+
+    container(content)
+        .height(Length::Fixed(100.0))
+        .padding([10, 20])
+        .center_x(Length::Fixed(180.0))
+        .style(move |theme: &Theme| container::Style {
+            background: background_fn(theme).map(Background::Color),
+            border: theme::rounded(),
+            shadow: theme::card_shadow(is_hovered),
+            ..Default::default()
+        })
+}
+
+/// A titled panel around a chart, with any controls to the right of the
+/// title.
+pub fn chart_panel<'a, Message: 'a>(
+    title: &'a str,
+    controls: Option<Element<'a, Message>>,
+    chart: impl Into<Element<'a, Message>>,
+) -> Container<'a, Message> {
+    container(column![
+        row![container(text(title).size(20)).center_x(Length::Fill)]
+            .push(controls)
+            .align_y(Center),
+        chart.into(),
+    ])
+    .padding(20)
+    .style(|theme: &Theme| container::Style {
+        background: Some(theme.extended_palette().background.weak.color.into()),
+        ..Default::default()
+    })
+}
+
+/// Stands in for a screen that has not been built yet.
+pub fn not_implemented<'a, Message: 'a>(screen: &'a str) -> Element<'a, Message> {
+    column![
+        page_header(screen),
+        global_content_container(
+            text(format!("{} is not available yet.", screen))
+                .size(14)
+                .style(|theme: &Theme| text::Style {
+                    color: Some(theme.extended_palette().background.strong.text),
+                }),
+        ),
+    ]
+    .into()
+}
+
 pub fn global_content_container<'a, Message: 'a>(
     content: impl Into<Element<'a, Message>>,
 ) -> Container<'a, Message> {
@@ -0,0 +1,157 @@
+//! How the app is used, for the tutor's own reflection. Kept in a file in
+//! the data folder and never sent anywhere.
+
+use chrono::{Duration, NaiveDate};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use crate::domain::week_start;
+
+const USAGE_FILE: &str = "usage.json";
+
+#[derive(Debug, Clone)]
+pub enum UsageError {
+    SaveFailed(String),
+}
+
+impl std::fmt::Display for UsageError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            UsageError::SaveFailed(reason) => {
+                write!(f, "Could not save usage statistics: {}", reason)
+            }
+        }
+    }
+}
+
+/// A session marked as held or missed, and the day it was marked.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SessionLog {
+    pub held_on: NaiveDate,
+    pub logged_on: NaiveDate,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct UsageStats {
+    /// Times each action was taken, by its name.
+    #[serde(default)]
+    actions: BTreeMap<String, u32>,
+    #[serde(default)]
+    sessions_logged: Vec<SessionLog>,
+}
+
+/// Sessions logged in the week starting `week`, and how many of them on
+/// the day they were held.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct WeekLog {
+    pub week: NaiveDate,
+    pub logged: usize,
+    pub same_day: usize,
+}
+
+impl UsageStats {
+    pub fn record(&mut self, action: &str) {
+        *self.actions.entry(action.to_string()).or_default() += 1;
+    }
+
+    pub fn session_logged(&mut self, held_on: NaiveDate, logged_on: NaiveDate) {
+        self.sessions_logged.push(SessionLog { held_on, logged_on });
+    }
+
+    /// Everything recorded so far, which goes up with every change.
+    pub fn total(&self) -> usize {
+        self.actions
+            .values()
+            .map(|count| *count as usize)
+            .sum::<usize>()
+            + self.sessions_logged.len()
+    }
+
+    /// The `count` actions taken most, most first.
+    pub fn most_used(&self, count: usize) -> Vec<(&str, u32)> {
+        let mut actions: Vec<(&str, u32)> = self
+            .actions
+            .iter()
+            .map(|(action, times)| (action.as_str(), *times))
+            .collect();
+        actions.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+        actions.truncate(count);
+        actions
+    }
+
+    /// Each of the last `weeks` weeks up to the one holding `today`,
+    /// oldest first, by the day sessions were logged on.
+    pub fn logged_per_week(&self, today: NaiveDate, weeks: usize) -> Vec<WeekLog> {
+        let this_week = week_start(today);
+        (0..weeks as i64)
+            .rev()
+            .map(|ago| {
+                let week = this_week - Duration::weeks(ago);
+                let logs = self
+                    .sessions_logged
+                    .iter()
+                    .filter(|log| week_start(log.logged_on) == week);
+                WeekLog {
+                    week,
+                    logged: logs.clone().count(),
+                    same_day: logs.filter(|log| log.held_on == log.logged_on).count(),
+                }
+            })
+            .collect()
+    }
+}
+
+/// Starts afresh when there is no file yet or it cannot be read.
+pub fn load_usage(dir: &Path) -> UsageStats {
+    std::fs::read_to_string(dir.join(USAGE_FILE))
+        .ok()
+        .and_then(|json| serde_json::from_str(&json).ok())
+        .unwrap_or_default()
+}
+
+pub async fn save_usage(dir: PathBuf, usage: UsageStats) -> Result<(), UsageError> {
+    let json =
+        serde_json::to_string_pretty(&usage).map_err(|e| UsageError::SaveFailed(e.to_string()))?;
+    std::fs::write(dir.join(USAGE_FILE), json).map_err(|e| UsageError::SaveFailed(e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::fixtures::date;
+
+    #[test]
+    fn weeks_count_sessions_by_the_day_they_were_logged() {
+        let mut usage = UsageStats::default();
+        usage.session_logged(date(2026, 2, 2), date(2026, 2, 2));
+        usage.session_logged(date(2026, 2, 3), date(2026, 2, 9));
+        usage.session_logged(date(2026, 2, 10), date(2026, 2, 10));
+        usage.record("Logged a session");
+        usage.record("Recorded a payment");
+        usage.record("Logged a session");
+
+        assert_eq!(
+            usage.logged_per_week(date(2026, 2, 12), 3),
+            vec![
+                WeekLog {
+                    week: date(2026, 1, 26),
+                    logged: 0,
+                    same_day: 0,
+                },
+                WeekLog {
+                    week: date(2026, 2, 2),
+                    logged: 1,
+                    same_day: 1,
+                },
+                WeekLog {
+                    week: date(2026, 2, 9),
+                    logged: 2,
+                    same_day: 1,
+                },
+            ]
+        );
+        assert_eq!(usage.most_used(1), vec![("Logged a session", 2)]);
+        assert_eq!(usage.total(), 6);
+    }
+}